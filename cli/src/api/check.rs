@@ -1,4 +1,6 @@
-pub use super::graphql::mutations::{SchemaCheck, SchemaCheckErrorSeverity, SchemaCheckGitCommitInput};
+pub use super::graphql::mutations::{
+    SchemaCheck, SchemaCheckDiagnostic, SchemaCheckErrorSeverity, SchemaCheckGitCommitInput, SchemaCheckStep,
+};
 
 use super::{
     client::create_client,
@@ -1,5 +1,12 @@
+mod local;
+
 use crate::api::check;
-use crate::{cli_input::CheckCommand, errors::CliError, report};
+use crate::{
+    cli_input::{CheckCommand, OutputFormat},
+    errors::CliError,
+    output::diagnostics::{self, Diagnostic},
+    report,
+};
 use std::{
     fs,
     io::{IsTerminal, Read},
@@ -10,15 +17,7 @@ const FAILED_CHECK_EXIT_STATUS: i32 = 1;
 
 #[tokio::main]
 pub(crate) async fn check(command: CheckCommand) -> Result<(), CliError> {
-    let CheckCommand {
-        graph_ref,
-        subgraph_name,
-        schema,
-    } = command;
-
-    let git_commit = find_git_commit();
-
-    let schema = match schema {
+    let schema = match &command.schema {
         Some(schema) => fs::read_to_string(schema).map_err(CliError::SchemaReadError)?,
         None if std::io::stdin().is_terminal() => {
             return Err(CliError::MissingArgument("--schema or a schema piped through stdin"));
@@ -34,13 +33,23 @@ pub(crate) async fn check(command: CheckCommand) -> Result<(), CliError> {
         }
     };
 
-    report::checking();
+    let Some(graph_ref) = &command.graph_ref else {
+        let config = command.config()?;
+        return local::check(&config, &command, &schema).await;
+    };
+
+    let subgraph_name = &command.subgraph_name;
+    let git_commit = find_git_commit();
+
+    if command.format == OutputFormat::Text {
+        report::checking();
+    }
 
     let result = check::check(
         graph_ref.account(),
         graph_ref.graph(),
         graph_ref.branch(),
-        &subgraph_name,
+        subgraph_name,
         &schema,
         git_commit,
     )
@@ -58,17 +67,23 @@ pub(crate) async fn check(command: CheckCommand) -> Result<(), CliError> {
         }
     };
 
-    if diagnostics.is_empty() {
-        report::check_success();
-    } else {
-        report::check_errors(error_count != 0, &diagnostics);
+    let diagnostics = diagnostics.iter().map(Diagnostic::from).collect::<Vec<_>>();
+    report_diagnostics(error_count != 0, &diagnostics, command.format);
 
-        if error_count > 0 {
-            std::process::exit(FAILED_CHECK_EXIT_STATUS);
-        }
+    Ok(())
+}
+
+fn report_diagnostics(has_errors: bool, diagnostics: &[Diagnostic], format: OutputFormat) {
+    match format {
+        OutputFormat::Text if diagnostics.is_empty() => report::check_success(),
+        OutputFormat::Text => report::check_errors(has_errors, diagnostics),
+        OutputFormat::Json => diagnostics::print_json(diagnostics, []),
+        OutputFormat::Sarif => diagnostics::print_sarif(diagnostics),
     }
 
-    Ok(())
+    if has_errors {
+        std::process::exit(FAILED_CHECK_EXIT_STATUS);
+    }
 }
 
 fn find_git_commit() -> Option<check::SchemaCheckGitCommitInput> {
@@ -0,0 +1,231 @@
+//! Schema checks without the Grafbase platform: composition runs against the subgraphs of the
+//! gateway configuration, and operation checks use the field usage collected by the gateway.
+
+use gateway_config::Config;
+use runtime_local::{
+    field_usage::FieldUsageStorage,
+    redis::{RedisPoolFactory, RedisTlsConfig},
+};
+
+use super::report_diagnostics;
+use crate::{
+    api::check::{SchemaCheckErrorSeverity, SchemaCheckStep},
+    cli_input::{CheckCommand, OutputFormat},
+    dev::SubgraphCache,
+    errors::CliError,
+    output::diagnostics::{Diagnostic, Location},
+    report,
+};
+
+pub(super) async fn check(config: &Config, command: &CheckCommand, schema: &str) -> Result<(), CliError> {
+    let subgraph_name = command.subgraph_name.as_str();
+
+    if command.format == OutputFormat::Text {
+        report::checking();
+    }
+
+    let (warnings_sender, _warnings_receiver) = tokio::sync::mpsc::channel(1);
+    let subgraph_cache = SubgraphCache::new(None, config, warnings_sender)
+        .await
+        .map_err(|err| CliError::GenericError(err.into()))?;
+
+    let mut subgraphs = Vec::new();
+    subgraph_cache
+        .for_each_subgraph(|subgraph| subgraphs.push(subgraph.clone()))
+        .await;
+
+    let validation = graphql_schema_validation::validate(schema);
+
+    if validation.has_errors() {
+        let diagnostics = validation
+            .iter()
+            .map(|diagnostic| error(SchemaCheckStep::Validation, diagnostic.to_string()))
+            .collect::<Vec<_>>();
+
+        report_diagnostics(true, &diagnostics, command.format);
+        return Ok(());
+    }
+
+    let source = compose_api_schema(
+        subgraphs
+            .iter()
+            .map(|subgraph| (subgraph.name.as_str(), subgraph.url.as_deref(), subgraph.sdl.as_str())),
+    )
+    .map_err(|errors| anyhow::anyhow!("The current subgraphs do not compose:\n{}", errors.join("\n")))?;
+
+    let url = subgraphs
+        .iter()
+        .find(|subgraph| subgraph.name == subgraph_name)
+        .and_then(|subgraph| subgraph.url.as_deref());
+
+    let target = compose_api_schema(
+        subgraphs
+            .iter()
+            .filter(|subgraph| subgraph.name != subgraph_name)
+            .map(|subgraph| (subgraph.name.as_str(), subgraph.url.as_deref(), subgraph.sdl.as_str()))
+            .chain(std::iter::once((subgraph_name, url, schema))),
+    );
+
+    let target = match target {
+        Ok(target) => target,
+        Err(errors) => {
+            let diagnostics = errors
+                .into_iter()
+                .map(|message| error(SchemaCheckStep::Composition, message))
+                .collect::<Vec<_>>();
+
+            report_diagnostics(true, &diagnostics, command.format);
+            return Ok(());
+        }
+    };
+
+    let usage = load_field_usage(config, command.format).await?;
+    let mut diagnostics = operation_check(&source, &target, &usage)?;
+
+    // The changes between the previous and the checked subgraph schema point to the checked schema file.
+    let previous_sdl = subgraphs
+        .iter()
+        .find(|subgraph| subgraph.name == subgraph_name)
+        .map(|subgraph| subgraph.sdl.as_str())
+        .unwrap_or_default();
+
+    if let Ok(subgraph_changes) = graphql_schema_diff::diff(previous_sdl, schema) {
+        for diagnostic in &mut diagnostics {
+            diagnostic.inner.location = subgraph_changes
+                .iter()
+                .find(|change| change.path == diagnostic.path && change.span.start < change.span.end)
+                .map(|change| Location::new(command.schema.clone(), schema, change.span.start..change.span.end));
+        }
+    }
+
+    let diagnostics = diagnostics
+        .into_iter()
+        .map(|diagnostic| diagnostic.inner)
+        .collect::<Vec<_>>();
+    let has_errors = diagnostics.iter().any(Diagnostic::is_error);
+
+    report_diagnostics(has_errors, &diagnostics, command.format);
+
+    Ok(())
+}
+
+/// An operation check diagnostic, with the schema diff path of the change that caused it.
+struct OperationDiagnostic {
+    inner: Diagnostic,
+    path: String,
+}
+
+/// Composes the subgraphs, given as (name, url, sdl), and renders the resulting API schema.
+fn compose_api_schema<'a>(
+    subgraphs: impl Iterator<Item = (&'a str, Option<&'a str>, &'a str)>,
+) -> Result<String, Vec<String>> {
+    let mut composed = graphql_composition::Subgraphs::default();
+
+    for (name, url, sdl) in subgraphs {
+        let parsed = cynic_parser::parse_type_system_document(sdl)
+            .map_err(|err| vec![format!("[{name}] Failed to parse subgraph SDL: {err}")])?;
+
+        composed.ingest(&parsed, name, url);
+    }
+
+    graphql_composition::compose(&mut composed)
+        .into_result()
+        .map(|graph| graphql_composition::render_api_sdl(&graph))
+        .map_err(|diagnostics| diagnostics.iter_errors().map(ToOwned::to_owned).collect())
+}
+
+async fn load_field_usage(
+    config: &Config,
+    format: OutputFormat,
+) -> anyhow::Result<operation_checks::SchemaCoordinateUsage> {
+    let field_usage = &config.field_usage;
+
+    let storage = match field_usage.storage {
+        gateway_config::FieldUsageStorage::File => FieldUsageStorage::File(field_usage.path.clone()),
+        gateway_config::FieldUsageStorage::Redis => {
+            let redis = &field_usage.redis;
+            let tls = redis.tls.as_ref().map(|tls| RedisTlsConfig {
+                cert: tls.cert.as_deref(),
+                key: tls.key.as_deref(),
+                ca: tls.ca.as_deref(),
+            });
+
+            FieldUsageStorage::Redis {
+                pool: RedisPoolFactory::default().pool(redis.url.as_str(), tls)?,
+                key_prefix: redis.key_prefix.clone(),
+            }
+        }
+    };
+
+    let usage = storage.load().await?;
+
+    if usage.is_empty() && format == OutputFormat::Text {
+        report::check_no_field_usage();
+    }
+
+    Ok(usage)
+}
+
+fn operation_check(
+    source: &str,
+    target: &str,
+    usage: &operation_checks::SchemaCoordinateUsage,
+) -> anyhow::Result<Vec<OperationDiagnostic>> {
+    let parse = |sdl: &str| -> anyhow::Result<operation_checks::Schema> {
+        let document = async_graphql_parser::parse_schema(sdl)
+            .map_err(|err| anyhow::anyhow!("Could not parse the composed API schema: {err}"))?;
+        Ok(document.into())
+    };
+
+    let (source_schema, target_schema) = (parse(source)?, parse(target)?);
+
+    let diff = graphql_schema_diff::diff(source, target)
+        .map_err(|err| anyhow::anyhow!("Could not diff the API schemas: {err}"))?;
+
+    let field_usage = usage.to_field_usage(&source_schema);
+
+    let diagnostics = operation_checks::check(&operation_checks::CheckParams {
+        source: &source_schema,
+        target: &target_schema,
+        diff: &diff,
+        field_usage: &field_usage,
+    });
+
+    Ok(diagnostics
+        .into_iter()
+        .map(|diagnostic| {
+            let severity = match diagnostic.severity {
+                operation_checks::Severity::Error => SchemaCheckErrorSeverity::Error,
+                operation_checks::Severity::Warning => SchemaCheckErrorSeverity::Warning,
+            };
+
+            let mut inner = Diagnostic::new(SchemaCheckStep::Operation, severity, diagnostic.message);
+            inner.rule = Some(diagnostic.change_kind.as_str().to_owned());
+            inner.coordinate = Some(schema_coordinate(&diagnostic.path));
+
+            OperationDiagnostic {
+                inner,
+                path: diagnostic.path,
+            }
+        })
+        .collect())
+}
+
+/// Turns a schema diff path into a schema coordinate, e.g. `Query.user.id` into `Query.user(id:)`.
+fn schema_coordinate(path: &str) -> String {
+    use graphql_schema_diff::path::{Path, PathInField, PathInType};
+
+    match Path::parse(path) {
+        Ok(Path::TypeDefinition(
+            type_name,
+            Some(PathInType::InField(field_name, Some(PathInField::InArgument(argument)))),
+        )) => {
+            format!("{type_name}.{field_name}({argument}:)")
+        }
+        _ => path.to_owned(),
+    }
+}
+
+fn error(step: SchemaCheckStep, message: String) -> Diagnostic {
+    Diagnostic::new(step, SchemaCheckErrorSeverity::Error, message)
+}
@@ -12,6 +12,8 @@ mod introspect;
 mod lint;
 mod login;
 mod mcp;
+mod mock;
+mod output_format;
 mod publish;
 mod schema;
 mod schema_proposal;
@@ -29,6 +31,8 @@ pub(crate) use graph_ref::FullGraphRef;
 pub(crate) use introspect::IntrospectCommand;
 pub(crate) use lint::LintCommand;
 pub(crate) use login::LoginCommand;
+pub(crate) use mock::MockCommand;
+pub(crate) use output_format::OutputFormat;
 pub(crate) use publish::PublishCommand;
 pub(crate) use schema::SchemaCommand;
 pub(crate) use sub_command::RequiresLogin;
@@ -1,9 +1,13 @@
-use super::FullGraphRef;
+use std::path::PathBuf;
+
+use gateway_config::Config;
+
+use super::{FullGraphRef, OutputFormat};
 
 #[derive(Debug, clap::Args)]
 pub struct CheckCommand {
     #[arg(help = FullGraphRef::ARG_DESCRIPTION)]
-    pub graph_ref: FullGraphRef,
+    pub graph_ref: Option<FullGraphRef>,
     /// The name of the subgraph to check
     #[arg(long("name"))]
     pub(crate) subgraph_name: String,
@@ -12,4 +16,22 @@ pub struct CheckCommand {
     /// from stdin.
     #[arg(long)]
     pub schema: Option<String>,
+
+    /// The path of the gateway configuration file. Without a graph ref, the check runs locally
+    /// against the subgraphs and the field usage storage defined in this configuration.
+    #[arg(short('c'), long("config"), conflicts_with("graph_ref"))]
+    pub(crate) config_path: Option<PathBuf>,
+
+    /// The format of the check diagnostics
+    #[arg(long, value_enum, default_value_t)]
+    pub(crate) format: OutputFormat,
+}
+
+impl CheckCommand {
+    pub fn config(&self) -> anyhow::Result<Config> {
+        Config::loader()
+            .load(self.config_path.as_ref())
+            .map_err(|err| anyhow::anyhow!(err))?
+            .ok_or_else(|| anyhow::anyhow!("Could not read the configuration file."))
+    }
 }
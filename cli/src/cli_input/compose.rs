@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use clap::Parser;
 use gateway_config::Config;
 
-use super::FullGraphRef;
+use super::{FullGraphRef, OutputFormat};
 
 /// Compose a federated schema.
 #[derive(Debug, Parser)]
@@ -13,6 +13,10 @@ pub(crate) struct ComposeCommand {
     /// The path of the gateway configuration file
     #[arg(short('c'), long("config"))]
     config_path: Option<PathBuf>,
+    /// The format of the composition diagnostics. With `json`, the composed schema is included in the
+    /// document. With `sarif`, only the diagnostics are printed.
+    #[arg(long, value_enum, default_value_t)]
+    pub(crate) format: OutputFormat,
 }
 
 impl ComposeCommand {
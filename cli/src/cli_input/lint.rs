@@ -1,9 +1,14 @@
 use clap::Parser;
 use std::path::PathBuf;
 
+use super::OutputFormat;
+
 /// Lint a GraphQL schema
 #[derive(Debug, Parser)]
 pub struct LintCommand {
     /// The path of the schema to lint
     pub schema: Option<PathBuf>,
+    /// The format of the lint diagnostics
+    #[arg(long, value_enum, default_value_t)]
+    pub(crate) format: OutputFormat,
 }
@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use gateway_config::Config;
+
+use super::FullGraphRef;
+
+/// Serve mocked subgraphs with generated data, built from the subgraph schemas.
+#[derive(Debug, Parser)]
+pub(crate) struct MockCommand {
+    #[arg(short('r'), long("graph-ref"), help = FullGraphRef::ARG_DESCRIPTION)]
+    pub(crate) graph_ref: Option<FullGraphRef>,
+    /// The path of the gateway configuration file
+    #[arg(short('c'), long("config"))]
+    config_path: Option<PathBuf>,
+    /// The port of the first mocked subgraph, the following subgraphs use the next ports
+    #[arg(short('p'), long("port"), default_value_t = 4001)]
+    pub(crate) port: u16,
+    /// The seed used to generate values. The same seed always produces the same responses
+    #[arg(long, default_value_t = 0)]
+    pub(crate) seed: u64,
+    /// The maximum length of generated lists
+    #[arg(long, default_value_t = 3)]
+    pub(crate) max_list_length: u64,
+    /// A directory with a `<subgraph name>.json` fixture file per subgraph, mapping type names to
+    /// field names to the value to return for that field
+    #[arg(long)]
+    pub(crate) fixtures: Option<PathBuf>,
+}
+
+impl MockCommand {
+    pub fn config(&self) -> anyhow::Result<Config> {
+        Config::loader()
+            .load(self.config_path.as_ref())
+            .map(Option::unwrap_or_default)
+            .map_err(|err| anyhow::anyhow!(err))
+    }
+}
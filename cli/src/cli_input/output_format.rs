@@ -0,0 +1,12 @@
+/// How a command prints its results and diagnostics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum, strum::AsRefStr, strum::Display)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum OutputFormat {
+    /// Colored text, for humans
+    #[default]
+    Text,
+    /// A JSON document on stdout
+    Json,
+    /// A SARIF 2.1.0 log on stdout, to annotate pull requests with the diagnostics
+    Sarif,
+}
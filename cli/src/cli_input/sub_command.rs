@@ -4,8 +4,8 @@ use crate::{cli_input::ExtensionSubCommand, is_not_direct_install};
 
 use super::{
     CheckCommand, CompletionsCommand, CreateCommand, DevCommand, ExtensionCommand, IntrospectCommand, LintCommand,
-    LoginCommand, PublishCommand, SchemaCommand, SchemaProposalCommand, SubgraphCommand, branch::BranchCommand,
-    compose::ComposeCommand, mcp::McpCommand, trust::TrustCommand,
+    LoginCommand, MockCommand, PublishCommand, SchemaCommand, SchemaProposalCommand, SubgraphCommand,
+    branch::BranchCommand, compose::ComposeCommand, mcp::McpCommand, trust::TrustCommand,
 };
 
 #[derive(Debug, Parser, strum::AsRefStr, strum::Display)]
@@ -46,6 +46,8 @@ pub(crate) enum SubCommand {
     Lint(LintCommand),
     /// Start the development server
     Dev(DevCommand),
+    /// Serve mocked subgraphs generated from their schemas
+    Mock(MockCommand),
     /// Start the MCP server
     Mcp(McpCommand),
     /// Manage extensions
@@ -67,11 +69,12 @@ impl RequiresLogin for SubCommand {
                 | SubCommand::Trust(_)
                 | SubCommand::Subgraph(_)
                 | SubCommand::SchemaProposal(_)
-                | SubCommand::Check(_)
+                | SubCommand::Check(CheckCommand { graph_ref: Some(_), .. })
                 | SubCommand::Branch(_)
                 | SubCommand::Schema(_)
                 | SubCommand::Compose(ComposeCommand { graph_ref: Some(_), .. })
                 | SubCommand::Dev(DevCommand { graph_ref: Some(_), .. })
+                | SubCommand::Mock(MockCommand { graph_ref: Some(_), .. })
                 | SubCommand::Extension(ExtensionCommand {
                     command: ExtensionSubCommand::Publish(_)
                 })
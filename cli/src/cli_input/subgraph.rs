@@ -1,4 +1,4 @@
-use super::FullGraphRef;
+use super::{FullGraphRef, OutputFormat};
 use clap::Parser;
 
 /// Manage subgraphs
@@ -23,6 +23,9 @@ pub struct SubgraphListCommand {
     /// Graph ref
     #[arg(help = FullGraphRef::ARG_DESCRIPTION)]
     pub graph_ref: FullGraphRef,
+    /// The format of the subgraph list. SARIF is not supported, there are no diagnostics to report.
+    #[arg(long, value_enum, default_value_t)]
+    pub(crate) format: OutputFormat,
 }
 
 #[derive(Debug, Parser)]
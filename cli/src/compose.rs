@@ -1,4 +1,14 @@
-use crate::{cli_input::ComposeCommand, dev::SubgraphCache, output::report};
+use serde_json::json;
+
+use crate::{
+    api::check::{SchemaCheckErrorSeverity, SchemaCheckStep},
+    cli_input::{ComposeCommand, OutputFormat},
+    dev::SubgraphCache,
+    output::{
+        diagnostics::{self, Diagnostic},
+        report,
+    },
+};
 
 #[tokio::main]
 pub(crate) async fn compose(args: ComposeCommand) -> anyhow::Result<()> {
@@ -8,21 +18,69 @@ pub(crate) async fn compose(args: ComposeCommand) -> anyhow::Result<()> {
         return Err(anyhow::anyhow!("No subgraphs found"));
     }
 
-    let (warnings_sender, _warnings_receiver) = tokio::sync::mpsc::channel(1);
+    let (warnings_sender, mut warnings_receiver) = tokio::sync::mpsc::channel(1);
 
     let subgraph_cache = SubgraphCache::new(args.graph_ref.as_ref(), &config, warnings_sender).await?;
 
     let result = subgraph_cache.compose().await?;
 
-    match result {
-        Ok(schema) => {
+    match (result, args.format) {
+        (Ok(schema), OutputFormat::Text) => {
             println!("{schema}");
 
             Ok(())
         }
-        Err(diagnostics) => {
+        (Ok(schema), format) => {
+            // On success, the composition warnings are only reported through the warnings channel.
+            let warnings = warnings_receiver
+                .try_recv()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|message| {
+                    Diagnostic::new(SchemaCheckStep::Composition, SchemaCheckErrorSeverity::Warning, message)
+                })
+                .collect::<Vec<_>>();
+
+            if format == OutputFormat::Json {
+                diagnostics::print_json(&warnings, [("schema", json!(schema))]);
+            } else {
+                diagnostics::print_sarif(&warnings);
+            }
+
+            Ok(())
+        }
+        (Err(diagnostics), OutputFormat::Text) => {
             report::composition_diagnostics(&diagnostics);
             std::process::exit(1)
         }
+        (Err(composition_diagnostics), format) => {
+            let composition_diagnostics = composition_diagnostics
+                .iter()
+                .map(composition_diagnostic)
+                .collect::<Vec<_>>();
+
+            if format == OutputFormat::Json {
+                diagnostics::print_json(&composition_diagnostics, [("schema", serde_json::Value::Null)]);
+            } else {
+                diagnostics::print_sarif(&composition_diagnostics);
+            }
+
+            std::process::exit(1)
+        }
     }
 }
+
+fn composition_diagnostic(diagnostic: &graphql_composition::diagnostics::Diagnostic) -> Diagnostic {
+    let severity = if diagnostic.severity().is_error() {
+        SchemaCheckErrorSeverity::Error
+    } else {
+        SchemaCheckErrorSeverity::Warning
+    };
+
+    let mut result = Diagnostic::new(SchemaCheckStep::Composition, severity, diagnostic.message().to_owned());
+    result.rule = diagnostic
+        .composite_schemas_error_code()
+        .map(|code| code.as_str().to_owned());
+
+    result
+}
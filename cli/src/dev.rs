@@ -129,7 +129,11 @@ async fn start(args: DevCommand, logging_filter: String) -> anyhow::Result<()> {
     let server_config = ServeConfig {
         listen_address,
         config_path: None,
-        config_hot_reload: false,
+        // `config_path` is unset, so the server doesn't watch the configuration file itself. The
+        // configuration is watched by `hot_reload` above and sent through `config_receiver`, which
+        // hot reload forwards to the engine reloader. It also watches the Wasm modules of the
+        // extensions, reloading them when they're rebuilt during development.
+        config_hot_reload: true,
         config_receiver,
         graph_loader: GraphLoader::FromChannel { sdl_receiver },
         grafbase_access_token: None,
@@ -128,7 +128,7 @@ impl SubgraphCache {
     }
 
     /// Execute a closure for each cached subgraph.
-    pub(super) async fn for_each_subgraph(&self, mut f: impl FnMut(&Arc<CachedSubgraph>)) {
+    pub(crate) async fn for_each_subgraph(&self, mut f: impl FnMut(&Arc<CachedSubgraph>)) {
         let local_from_introspection = self.local_from_introspection.lock().await;
         let local_from_file = self.local_from_file.lock().await;
 
@@ -1,4 +1,12 @@
-use crate::{errors::CliError, output::report};
+use crate::{
+    api::check::{SchemaCheckErrorSeverity, SchemaCheckStep},
+    cli_input::OutputFormat,
+    errors::CliError,
+    output::{
+        diagnostics::{self, Diagnostic, Location},
+        report,
+    },
+};
 use graphql_lint::Severity;
 use std::{
     borrow::Borrow,
@@ -9,8 +17,8 @@ use std::{
 
 const ALLOWED_EXTENSIONS: [&str; 4] = ["gql", "graphql", "graphqls", "sdl"];
 
-pub fn lint(schema_path: Option<PathBuf>) -> Result<(), CliError> {
-    let schema = match schema_path {
+pub fn lint(schema_path: Option<PathBuf>, format: OutputFormat) -> Result<(), CliError> {
+    let schema = match &schema_path {
         Some(schema_path) => {
             let extension = schema_path
                 .extension()
@@ -21,7 +29,7 @@ pub fn lint(schema_path: Option<PathBuf>) -> Result<(), CliError> {
                 return Err(CliError::LintUnsupportedFileExtension(extension.into_owned()));
             }
 
-            fs::read_to_string(&schema_path).map_err(|error| CliError::ReadLintSchema(schema_path, error))?
+            fs::read_to_string(schema_path).map_err(|error| CliError::ReadLintSchema(schema_path.clone(), error))?
         }
         None if std::io::stdin().is_terminal() => {
             return Err(CliError::MissingArgument("[schema] or a schema piped through stdin"));
@@ -37,16 +45,43 @@ pub fn lint(schema_path: Option<PathBuf>) -> Result<(), CliError> {
         }
     };
 
-    let diagnostics = graphql_lint::lint(&schema)?;
+    let lint_diagnostics = graphql_lint::lint_diagnostics(&schema)?;
+
+    if format != OutputFormat::Text {
+        let file = schema_path.map(|path| path.display().to_string());
+
+        let lint_diagnostics = lint_diagnostics
+            .into_iter()
+            .map(|diagnostic| {
+                let severity = match diagnostic.severity {
+                    Severity::Warning => SchemaCheckErrorSeverity::Warning,
+                };
+
+                let mut result = Diagnostic::new(SchemaCheckStep::Lint, severity, diagnostic.message);
+                result.rule = Some(diagnostic.rule.as_str().to_owned());
+                result.coordinate = Some(diagnostic.coordinate);
+                result.location = Some(Location::new(file.clone(), &schema, diagnostic.span));
+                result
+            })
+            .collect::<Vec<_>>();
+
+        if format == OutputFormat::Json {
+            diagnostics::print_json(&lint_diagnostics, []);
+        } else {
+            diagnostics::print_sarif(&lint_diagnostics);
+        }
+
+        return Ok(());
+    }
 
-    if diagnostics.is_empty() {
+    if lint_diagnostics.is_empty() {
         report::lint_success();
         return Ok(());
     }
 
-    for (message, severity) in diagnostics {
-        match severity {
-            Severity::Warning => report::lint_warning(message),
+    for diagnostic in lint_diagnostics {
+        match diagnostic.severity {
+            Severity::Warning => report::lint_warning(diagnostic.message),
         }
     }
 
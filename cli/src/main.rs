@@ -15,6 +15,7 @@ mod lint;
 mod login;
 mod logout;
 mod mcp;
+mod mock;
 mod output;
 mod panic_hook;
 mod plugins;
@@ -153,7 +154,7 @@ fn try_main(args: Args) -> Result<(), CliError> {
         SubCommand::Create(cmd) => create(&cmd.create_arguments()),
         SubCommand::Compose(cmd) => Ok(compose::compose(cmd)?),
         SubCommand::Subgraph(cmd) => match cmd.command {
-            SubgraphSubCommand::List(cmd) => subgraph::list(cmd.graph_ref),
+            SubgraphSubCommand::List(cmd) => subgraph::list(cmd.graph_ref, cmd.format),
             SubgraphSubCommand::Delete(cmd) => subgraph::delete(cmd.graph_ref, cmd.name),
         },
         SubCommand::Schema(cmd) => schema::schema(cmd),
@@ -170,13 +171,14 @@ fn try_main(args: Args) -> Result<(), CliError> {
             }
             upgrade::install_grafbase().map_err(Into::into)
         }
-        SubCommand::Lint(cmd) => lint::lint(cmd.schema),
+        SubCommand::Lint(cmd) => lint::lint(cmd.schema, cmd.format),
         SubCommand::Plugins => Ok(plugins::list()?),
         SubCommand::Branch(cmd) => match cmd.command {
             BranchSubCommand::Delete(cmd) => branch::delete(cmd.branch_ref),
             BranchSubCommand::Create(cmd) => branch::create(cmd.branch_ref),
         },
         SubCommand::Dev(cmd) => dev::dev(cmd, logging_filter),
+        SubCommand::Mock(cmd) => Ok(mock::mock(cmd)?),
         SubCommand::Extension(cmd) => Ok(extension::execute(cmd)?),
         SubCommand::Mcp(cmd) => Ok(mcp::run(cmd)?),
 
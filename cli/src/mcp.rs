@@ -13,7 +13,9 @@ use engine::{CachedOperation, RequestExtensions};
 use gateway_config::{Config, HeaderForward, HeaderInsert, HeaderRule, NameOrPattern};
 use grafbase_telemetry::metrics::{EngineMetrics, meter_from_global_provider};
 use regex::Regex;
-use runtime::{entity_cache::EntityCache, rate_limiting::RateLimiter, trusted_documents_client};
+use runtime::{
+    entity_cache::EntityCache, field_usage::FieldUsageCollector, rate_limiting::RateLimiter, trusted_documents_client,
+};
 use runtime_local::{InMemoryEntityCache, InMemoryOperationCache, NativeFetcher};
 use std::io::stdout;
 use wasi_component_loader::extension::EngineWasmExtensions;
@@ -49,6 +51,7 @@ pub(crate) async fn run(args: McpCommand) -> anyhow::Result<()> {
             value: value
                 .parse()
                 .map_err(|err| anyhow::anyhow!("Invalid header value '{value}': {err}"))?,
+            template: false,
         }));
     }
 
@@ -170,6 +173,10 @@ impl engine::Runtime for MinimalRuntime {
         &self.entity_cache
     }
 
+    fn field_usage_collector(&self) -> &dyn FieldUsageCollector {
+        &()
+    }
+
     fn metrics(&self) -> &EngineMetrics {
         &self.metrics
     }
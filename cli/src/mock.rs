@@ -0,0 +1,101 @@
+use std::{collections::BTreeMap, path::Path};
+
+use crossterm::style::Stylize;
+use graphql_mocks::{MockGraphQlServer, dynamic::DynamicSchema};
+
+use crate::{cli_input::MockCommand, dev::SubgraphCache};
+
+/// Fixtures of a single subgraph: type name -> field name -> value.
+type Fixtures = BTreeMap<String, BTreeMap<String, serde_json::Value>>;
+
+#[tokio::main(flavor = "multi_thread")]
+pub(crate) async fn mock(args: MockCommand) -> anyhow::Result<()> {
+    let config = args.config()?;
+
+    if args.graph_ref.is_none() && config.subgraphs.is_empty() {
+        return Err(anyhow::anyhow!("No subgraphs found"));
+    }
+
+    let (warnings_sender, _warnings_receiver) = tokio::sync::mpsc::channel(1);
+    let subgraph_cache = SubgraphCache::new(args.graph_ref.as_ref(), &config, warnings_sender).await?;
+
+    let mut subgraphs = Vec::new();
+    subgraph_cache
+        .for_each_subgraph(|subgraph| subgraphs.push(subgraph.clone()))
+        .await;
+
+    // Each subgraph gets the next port, so they must all fit within the valid port range.
+    let last_port = u16::try_from(subgraphs.len().saturating_sub(1))
+        .ok()
+        .and_then(|offset| args.port.checked_add(offset))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Cannot mock {} subgraphs on consecutive ports starting at {}",
+                subgraphs.len(),
+                args.port
+            )
+        })?;
+
+    let mut servers = Vec::with_capacity(subgraphs.len());
+
+    for (subgraph, port) in subgraphs.iter().zip(args.port..=last_port) {
+        let diagnostics = graphql_schema_validation::validate(&subgraph.sdl);
+
+        if diagnostics.has_errors() {
+            let errors = diagnostics.iter().map(|diagnostic| format!("- {diagnostic}\n"));
+            return Err(anyhow::anyhow!(
+                "Invalid schema for subgraph '{}':\n{}",
+                subgraph.name,
+                errors.collect::<String>()
+            ));
+        }
+
+        let mut builder = DynamicSchema::builder(&subgraph.sdl)
+            .with_generated_values(args.seed)
+            .with_max_generated_list_length(args.max_list_length);
+
+        if let Some(directory) = &args.fixtures {
+            for (type_name, fields) in load_fixtures(directory, &subgraph.name)? {
+                for (field_name, value) in fields {
+                    builder = builder.with_resolver(&type_name, &field_name, value);
+                }
+            }
+        }
+
+        let schema = builder
+            .try_finish()
+            .map_err(|err| anyhow::anyhow!("Could not mock subgraph '{}': {err}", subgraph.name))?;
+
+        let server = MockGraphQlServer::builder(schema).with_port(port).await;
+        servers.push((subgraph.name.clone(), server));
+    }
+
+    println!("{} {} subgraphs\n", "Mocking".green().bold(), servers.len());
+
+    for (name, server) in &servers {
+        println!("{} {}: {}", "➜".green(), name.as_str().bold(), server.url());
+    }
+
+    println!("\nPoint the gateway configuration at the mocks with:\n");
+
+    for (name, server) in &servers {
+        println!("[subgraphs.{name}]\nurl = \"{}\"\n", server.url());
+    }
+
+    tokio::signal::ctrl_c().await?;
+
+    Ok(())
+}
+
+fn load_fixtures(directory: &Path, subgraph_name: &str) -> anyhow::Result<Fixtures> {
+    let path = directory.join(format!("{subgraph_name}.json"));
+
+    if !path.exists() {
+        return Ok(Fixtures::default());
+    }
+
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|err| anyhow::anyhow!("Could not read fixtures from {}: {err}", path.display()))?;
+
+    serde_json::from_str(&contents).map_err(|err| anyhow::anyhow!("Invalid fixtures in {}: {err}", path.display()))
+}
@@ -0,0 +1,248 @@
+//! Machine readable diagnostics, printed as JSON or SARIF for `--format json|sarif`.
+
+use serde_json::{Value, json};
+
+use crate::api::check::{SchemaCheckErrorSeverity, SchemaCheckStep};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// A composition, lint or check diagnostic with everything we know about where it applies.
+#[derive(Debug)]
+pub(crate) struct Diagnostic {
+    pub(crate) step: SchemaCheckStep,
+    pub(crate) severity: SchemaCheckErrorSeverity,
+    pub(crate) message: String,
+    /// The lint rule, composition error code or schema change kind behind the diagnostic.
+    pub(crate) rule: Option<String>,
+    /// The schema coordinate the diagnostic applies to, e.g. `User.name`.
+    pub(crate) coordinate: Option<String>,
+    pub(crate) location: Option<Location>,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(step: SchemaCheckStep, severity: SchemaCheckErrorSeverity, message: String) -> Self {
+        Diagnostic {
+            step,
+            severity,
+            message,
+            rule: None,
+            coordinate: None,
+            location: None,
+        }
+    }
+
+    pub(crate) fn is_error(&self) -> bool {
+        matches!(self.severity, SchemaCheckErrorSeverity::Error)
+    }
+
+    fn to_json(&self) -> Value {
+        let mut diagnostic = json!({
+            "step": step_str(self.step),
+            "severity": severity_str(self.severity),
+            "message": self.message,
+        });
+
+        if let Some(rule) = &self.rule {
+            diagnostic["rule"] = json!(rule);
+        }
+
+        if let Some(coordinate) = &self.coordinate {
+            diagnostic["coordinate"] = json!(coordinate);
+        }
+
+        if let Some(location) = &self.location {
+            diagnostic["location"] = location.to_json();
+        }
+
+        diagnostic
+    }
+
+    fn to_sarif_result(&self) -> Value {
+        let mut result = json!({
+            "level": severity_str(self.severity),
+            "message": { "text": self.message },
+        });
+
+        if let Some(rule) = &self.rule {
+            result["ruleId"] = json!(rule);
+        }
+
+        let mut location = json!({});
+
+        if let Some(Location {
+            file: Some(file),
+            start,
+            end,
+            byte_offset,
+            byte_length,
+        }) = &self.location
+        {
+            location["physicalLocation"] = json!({
+                "artifactLocation": { "uri": file },
+                "region": {
+                    "startLine": start.line,
+                    "startColumn": start.column,
+                    "endLine": end.line,
+                    "endColumn": end.column,
+                    "byteOffset": byte_offset,
+                    "byteLength": byte_length,
+                },
+            });
+        }
+
+        if let Some(coordinate) = &self.coordinate {
+            location["logicalLocations"] = json!([{ "fullyQualifiedName": coordinate }]);
+        }
+
+        if location.as_object().is_some_and(|location| !location.is_empty()) {
+            result["locations"] = json!([location]);
+        }
+
+        result
+    }
+}
+
+impl From<&crate::api::check::SchemaCheckDiagnostic> for Diagnostic {
+    fn from(diagnostic: &crate::api::check::SchemaCheckDiagnostic) -> Self {
+        Diagnostic::new(diagnostic.step, diagnostic.severity, diagnostic.message.clone())
+    }
+}
+
+/// Where a diagnostic applies in a schema file.
+#[derive(Debug)]
+pub(crate) struct Location {
+    /// The path of the schema file, absent when the schema was read from stdin.
+    pub(crate) file: Option<String>,
+    pub(crate) start: Position,
+    pub(crate) end: Position,
+    pub(crate) byte_offset: usize,
+    pub(crate) byte_length: usize,
+}
+
+impl Location {
+    /// Resolves the byte offsets of a span in the schema to lines and columns.
+    pub(crate) fn new(file: Option<String>, schema: &str, span: std::ops::Range<usize>) -> Self {
+        Location {
+            file,
+            start: Position::new(schema, span.start),
+            end: Position::new(schema, span.end),
+            byte_offset: span.start,
+            byte_length: span.end.saturating_sub(span.start),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let mut location = json!({
+            "start": { "line": self.start.line, "column": self.start.column },
+            "end": { "line": self.end.line, "column": self.end.column },
+            "span": { "start": self.byte_offset, "end": self.byte_offset + self.byte_length },
+        });
+
+        if let Some(file) = &self.file {
+            location["file"] = json!(file);
+        }
+
+        location
+    }
+}
+
+/// A one-based line and column. Columns count characters.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Position {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+impl Position {
+    fn new(schema: &str, offset: usize) -> Self {
+        let before = schema.get(..offset).unwrap_or(schema);
+        let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+
+        Position {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+/// Prints the diagnostics as a JSON document. The extra fields are added at the top level, next
+/// to the diagnostics.
+pub(crate) fn print_json(diagnostics: &[Diagnostic], extra: impl IntoIterator<Item = (&'static str, Value)>) {
+    let mut document = json!({
+        "diagnostics": diagnostics.iter().map(Diagnostic::to_json).collect::<Vec<_>>(),
+    });
+
+    for (key, value) in extra {
+        document[key] = value;
+    }
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&document).expect("a JSON value always serializes")
+    );
+}
+
+/// Prints the diagnostics as a SARIF 2.1.0 log with a single run.
+pub(crate) fn print_sarif(diagnostics: &[Diagnostic]) {
+    let mut rules = diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.rule.as_deref())
+        .collect::<Vec<_>>();
+
+    rules.sort_unstable();
+    rules.dedup();
+
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "grafbase",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://grafbase.com",
+                    "rules": rules.into_iter().map(|rule| json!({ "id": rule })).collect::<Vec<_>>(),
+                },
+            },
+            "results": diagnostics.iter().map(Diagnostic::to_sarif_result).collect::<Vec<_>>(),
+        }],
+    });
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&log).expect("a JSON value always serializes")
+    );
+}
+
+fn step_str(step: SchemaCheckStep) -> &'static str {
+    match step {
+        SchemaCheckStep::Validation => "validation",
+        SchemaCheckStep::Composition => "composition",
+        SchemaCheckStep::Operation => "operation",
+        SchemaCheckStep::Lint => "lint",
+        SchemaCheckStep::Custom => "custom",
+        SchemaCheckStep::Proposal => "proposal",
+    }
+}
+
+fn severity_str(severity: SchemaCheckErrorSeverity) -> &'static str {
+    match severity {
+        SchemaCheckErrorSeverity::Error => "error",
+        SchemaCheckErrorSeverity::Warning => "warning",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_counts_lines_and_characters() {
+        let schema = "type Query {\n  héllo: String\n}\n";
+        let offset = schema.find("String").unwrap();
+
+        let position = Position::new(schema, offset);
+
+        assert_eq!((position.line, position.column), (2, 10));
+    }
+}
@@ -1 +1,2 @@
+pub(crate) mod diagnostics;
 pub mod report;
@@ -1,11 +1,7 @@
 use std::collections::BTreeMap;
 
 use crate::{
-    api::{
-        self,
-        errors::SchemaProposalParserError,
-        graphql::mutations::{SchemaCheckDiagnostic, SchemaCheckStep},
-    },
+    api::{self, errors::SchemaProposalParserError, graphql::mutations::SchemaCheckStep},
     common::{
         environment::{PlatformData, Warning},
         trusted_documents::TrustedDocumentsManifest,
@@ -13,6 +9,7 @@ use crate::{
 };
 use crate::{
     errors::CliError,
+    output::diagnostics::Diagnostic,
     watercolor::{self, watercolor},
 };
 use crossterm::style::Stylize;
@@ -119,14 +116,18 @@ pub(crate) fn check_success() {
     watercolor::output!("\n✨ Successful check!", @BrightBlue);
 }
 
-pub(crate) fn check_errors(has_errors: bool, diagnostics: &[SchemaCheckDiagnostic]) {
+pub(crate) fn check_no_field_usage() {
+    watercolor::output!("⚠️ No field usage was found, the gateway may not be collecting it yet. Operation checks will not report any breaking change.", @BrightYellow);
+}
+
+pub(crate) fn check_errors(has_errors: bool, diagnostics: &[Diagnostic]) {
     if has_errors {
         watercolor::output!("\nErrors were found in your schema check:", @BrightRed);
     } else {
         watercolor::output!("\nWarnings were found in your schema check:", @BrightYellow);
     }
 
-    let mut sections: BTreeMap<SchemaCheckStep, Vec<&SchemaCheckDiagnostic>> = BTreeMap::new();
+    let mut sections: BTreeMap<SchemaCheckStep, Vec<&Diagnostic>> = BTreeMap::new();
 
     for diagnostic in diagnostics {
         sections.entry(diagnostic.step).or_default().push(diagnostic);
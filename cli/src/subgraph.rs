@@ -1,12 +1,33 @@
-use crate::{api, cli_input::FullGraphRef, errors::CliError, output::report};
+use crate::{
+    api,
+    cli_input::{FullGraphRef, OutputFormat},
+    errors::CliError,
+    output::report,
+};
 
 #[tokio::main]
-pub(super) async fn list(graph_ref: FullGraphRef) -> Result<(), CliError> {
+pub(super) async fn list(graph_ref: FullGraphRef, format: OutputFormat) -> Result<(), CliError> {
+    if format == OutputFormat::Sarif {
+        return Err(CliError::GenericError(anyhow::anyhow!(
+            "SARIF output is only available for commands reporting diagnostics"
+        )));
+    }
+
     let (branch, subgraphs) = api::subgraph::list(graph_ref.account(), graph_ref.graph(), graph_ref.branch())
         .await
         .map_err(CliError::BackendApiError)?;
 
-    report::subgraph_list_command_success(&branch, subgraphs.iter().map(|subgraph| subgraph.name.as_str()));
+    let names = subgraphs.iter().map(|subgraph| subgraph.name.as_str());
+
+    if format == OutputFormat::Json {
+        let document = serde_json::json!({ "branch": branch, "subgraphs": names.collect::<Vec<_>>() });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&document).expect("a JSON value always serializes")
+        );
+    } else {
+        report::subgraph_list_command_success(&branch, names);
+    }
 
     Ok(())
 }
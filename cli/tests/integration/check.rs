@@ -0,0 +1,116 @@
+use std::fs;
+
+use duct::cmd;
+
+use crate::cargo_bin;
+
+const SUBGRAPH_SDL: &str = r#"
+    type Query {
+        pets(filter: PetFilter): [Pet!]!
+    }
+
+    input PetFilter {
+        kind: PetKind
+    }
+
+    enum PetKind {
+        CAT
+        DOG
+    }
+
+    type Pet {
+        name: String!
+        age: Int
+    }
+"#;
+
+/// Runs `grafbase check` without a graph ref, against a single `pets` subgraph and the given
+/// field usage. Returns the messages of the diagnostics and whether the check passed.
+fn check_locally(field_usage: serde_json::Value, checked_sdl: &str) -> (Vec<String>, bool) {
+    let working_directory = tempfile::tempdir().unwrap();
+    let schema_path = working_directory.path().join("pets.graphql");
+    let checked_schema_path = working_directory.path().join("pets-checked.graphql");
+    let field_usage_path = working_directory.path().join("field-usage.json");
+    let config_path = working_directory.path().join("grafbase.toml");
+
+    fs::write(&schema_path, SUBGRAPH_SDL).unwrap();
+    fs::write(&checked_schema_path, checked_sdl).unwrap();
+    fs::write(&field_usage_path, serde_json::to_vec(&field_usage).unwrap()).unwrap();
+    fs::write(
+        &config_path,
+        format!(
+            r#"
+            [subgraphs.pets]
+            schema_path = '{}'
+
+            [field_usage]
+            enabled = true
+            path = '{}'
+            "#,
+            schema_path.display(),
+            field_usage_path.display(),
+        ),
+    )
+    .unwrap();
+
+    let output = cmd(
+        cargo_bin("grafbase"),
+        &[
+            "check",
+            "--name",
+            "pets",
+            "--schema",
+            checked_schema_path.to_str().unwrap(),
+            "--config",
+            config_path.to_str().unwrap(),
+            "--format",
+            "json",
+        ],
+    )
+    .dir(working_directory.path())
+    .stdout_capture()
+    .unchecked()
+    .run()
+    .unwrap();
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let messages = report["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|diagnostic| diagnostic["message"].as_str().unwrap().to_owned())
+        .collect();
+
+    (messages, output.status.success())
+}
+
+#[test]
+fn local_check_fails_on_removed_used_enum_value() {
+    let field_usage = serde_json::json!({
+        "fields": { "Query.pets": 3, "Pet.name": 3, "PetFilter.kind": 1 },
+        "arguments": { "Query.pets.filter": 1 },
+        "enum_values": { "PetKind.CAT": 1 },
+    });
+
+    let checked_sdl = SUBGRAPH_SDL.replace("CAT\n", "");
+    let (messages, passed) = check_locally(field_usage, &checked_sdl);
+
+    assert!(!passed);
+    assert_eq!(
+        messages,
+        ["The enum value `PetKind.CAT` was removed but it is still used by clients."]
+    );
+}
+
+#[test]
+fn local_check_passes_on_removed_unused_field() {
+    let field_usage = serde_json::json!({
+        "fields": { "Query.pets": 3, "Pet.name": 3 },
+    });
+
+    let checked_sdl = SUBGRAPH_SDL.replace("age: Int\n", "");
+    let (messages, passed) = check_locally(field_usage, &checked_sdl);
+
+    assert!(passed, "{messages:?}");
+    assert!(messages.is_empty(), "{messages:?}");
+}
@@ -1,3 +1,4 @@
+mod check;
 mod dev;
 mod mcp;
 mod setup;
@@ -43,6 +43,8 @@ pub enum ErrorCode {
     RateLimited,
     // Timeouts
     GatewayTimeout,
+    // Overload
+    ServiceUnavailable,
 }
 
 impl From<ErrorCode> for http::StatusCode {
@@ -65,6 +67,7 @@ impl ErrorCode {
             ErrorCode::Unauthenticated => (http::StatusCode::UNAUTHORIZED, 600),
             ErrorCode::Unauthorized => (http::StatusCode::FORBIDDEN, 600),
             ErrorCode::RateLimited => (http::StatusCode::TOO_MANY_REQUESTS, 500),
+            ErrorCode::ServiceUnavailable => (http::StatusCode::SERVICE_UNAVAILABLE, 400),
             ErrorCode::SubgraphError | ErrorCode::SubgraphInvalidResponseError | ErrorCode::SubgraphRequestError => {
                 (http::StatusCode::BAD_GATEWAY, 300)
             }
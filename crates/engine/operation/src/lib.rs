@@ -15,7 +15,7 @@ mod validation;
 pub use error::*;
 pub use model::*;
 pub use request::*;
-use schema::Schema;
+use schema::{FieldDefinitionId, Schema};
 pub use validation::complexity::{ComplexityCost, ComplexityError};
 
 impl Operation {
@@ -74,6 +74,22 @@ impl Operation {
             variables,
         )
     }
+
+    pub fn compute_actual_complexity(
+        &self,
+        schema: &Schema,
+        variables: &Variables,
+        occurrences: impl Fn(ResponseKey, FieldDefinitionId) -> usize,
+    ) -> ComplexityCost {
+        validation::complexity::calculate_actual_complexity(
+            OperationContext {
+                schema,
+                operation: self,
+            },
+            variables,
+            occurrences,
+        )
+    }
 }
 
 impl Variables {
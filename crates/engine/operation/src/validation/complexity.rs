@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+
 use grafbase_telemetry::graphql::OperationType;
-use schema::{ComplexityControl, InputObjectDefinition, StringId};
+use schema::{ComplexityControl, FieldDefinitionId, InputObjectDefinition, StringId};
 use serde::Deserialize;
+use walker::Walk as _;
 
-use crate::{DataField, Field, FieldArgument, OperationContext, SelectionSet, Variables};
+use crate::{DataField, DataFieldId, Field, FieldArgument, OperationContext, ResponseKey, SelectionSet, Variables};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ComplexityError {
@@ -33,10 +36,7 @@ pub fn calculate_complexity(
     ctx: OperationContext<'_>,
     variables: &Variables,
 ) -> Result<ComplexityCost, ComplexityError> {
-    let base_cost = match ctx.operation.attributes.ty {
-        OperationType::Query | OperationType::Subscription => 0,
-        OperationType::Mutation => 10,
-    };
+    let base_cost = base_cost(ctx);
 
     let selection_set = ctx.root_selection_set();
 
@@ -57,6 +57,47 @@ pub fn calculate_complexity(
     Ok(ComplexityCost(cost))
 }
 
+/// Computes the cost of an executed operation from what was actually returned. Every field
+/// costs the same as in the estimation, its type weight and arguments, but it's multiplied by
+/// the number of times it appeared in the response instead of the assumed list sizes.
+///
+/// `occurrences` provides that number for a field, identified by its response key and definition.
+pub fn calculate_actual_complexity(
+    ctx: OperationContext<'_>,
+    variables: &Variables,
+    occurrences: impl Fn(ResponseKey, FieldDefinitionId) -> usize,
+) -> ComplexityCost {
+    // The same field may be present multiple times in the operation, through fragments for
+    // example, but it's only present once in the response.
+    let mut unit_costs =
+        HashMap::<(ResponseKey, FieldDefinitionId), usize>::with_capacity(ctx.operation.data_fields.len());
+
+    for id in (0..ctx.operation.data_fields.len()).map(DataFieldId::from) {
+        let field = id.walk(ctx);
+        let cost = field_unit_cost(variables, field);
+
+        let unit_cost = unit_costs.entry((field.response_key, field.definition_id)).or_default();
+        *unit_cost = (*unit_cost).max(cost);
+    }
+
+    let cost = base_cost(ctx)
+        + unit_costs
+            .into_iter()
+            .map(|((key, definition_id), unit_cost)| occurrences(key, definition_id) * unit_cost)
+            .sum::<usize>();
+
+    tracing::debug!("Actual complexity was {cost}");
+
+    ComplexityCost(cost)
+}
+
+fn base_cost(ctx: OperationContext<'_>) -> usize {
+    match ctx.operation.attributes.ty {
+        OperationType::Query | OperationType::Subscription => 0,
+        OperationType::Mutation => 10,
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct ComplexityCost(pub usize);
 
@@ -97,27 +138,34 @@ fn field_complexity(
     field: DataField<'_>,
     preset_list_size: Option<usize>,
 ) -> Result<usize, ComplexityError> {
-    let type_cost = field
-        .definition()
-        .cost()
-        .map(|cost| cost.weight)
-        .unwrap_or_else(|| cost_for_type(field.definition().ty().definition())) as usize;
-
     let list_size_directive = field.definition().list_size();
 
     let child_count = calculate_child_count(context, field, list_size_directive, preset_list_size)?;
 
-    let argument_cost = field
-        .sorted_arguments()
-        .map(|argument| cost_for_argument(argument, context.variables))
-        .sum::<usize>();
+    let unit_cost = field_unit_cost(context.variables, field);
 
     let this_field_count = child_count.this_field_count();
     let child_field_count = child_count.child_field_count();
 
     let child_cost = selection_set_complexity(context, field.selection_set(), child_field_count)?;
 
-    Ok(this_field_count * (type_cost + argument_cost + child_cost))
+    Ok(this_field_count * (unit_cost + child_cost))
+}
+
+/// Cost of a single occurrence of a field: its type weight and its arguments.
+fn field_unit_cost(variables: &Variables, field: DataField<'_>) -> usize {
+    let type_cost = field
+        .definition()
+        .cost()
+        .map(|cost| cost.weight)
+        .unwrap_or_else(|| cost_for_type(field.definition().ty().definition())) as usize;
+
+    let argument_cost = field
+        .sorted_arguments()
+        .map(|argument| cost_for_argument(argument, variables))
+        .sum::<usize>();
+
+    type_cost + argument_cost
 }
 
 fn cost_for_argument(argument: FieldArgument<'_>, variables: &Variables) -> usize {
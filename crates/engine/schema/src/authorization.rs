@@ -0,0 +1,28 @@
+use crate::StringId;
+
+/// Arguments of `@requiresScopes` and `@policy`: a list of groups of scopes or policies. Access is
+/// granted if every element of at least one of the groups is granted. `@authenticated` is a
+/// single empty group, granted to any authenticated request.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AuthorizationGroups(Vec<Vec<StringId>>);
+
+impl AuthorizationGroups {
+    pub(crate) fn new(groups: Vec<Vec<StringId>>) -> Self {
+        Self(groups)
+    }
+
+    pub fn groups(&self) -> impl ExactSizeIterator<Item = &[StringId]> + '_ {
+        self.0.iter().map(Vec::as_slice)
+    }
+
+    /// All the scopes or policies of the groups, including duplicates.
+    pub fn ids(&self) -> impl Iterator<Item = StringId> + '_ {
+        self.0.iter().flatten().copied()
+    }
+
+    pub fn is_granted(&self, mut is_element_granted: impl FnMut(StringId) -> bool) -> bool {
+        self.0
+            .iter()
+            .any(|group| group.iter().all(|id| is_element_granted(*id)))
+    }
+}
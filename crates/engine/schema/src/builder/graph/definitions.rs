@@ -75,6 +75,8 @@ pub(crate) fn ingest_definitions(
         input_values: Default::default(),
         cost_directives: Vec::new(),
         list_size_directives: Vec::new(),
+        requires_scopes_directives: Vec::new(),
+        policy_directives: Vec::new(),
         extension_directives: Vec::new(),
         extension_directive_arguments: Vec::new(),
         templates: Vec::new(),
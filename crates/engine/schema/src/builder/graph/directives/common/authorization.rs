@@ -0,0 +1,63 @@
+use cynic_parser_deser::ConstDeserializer as _;
+
+use crate::{
+    AuthorizationGroups, PolicyDirectiveRecord, RequiresScopesDirectiveRecord, TypeSystemDirectiveId,
+    builder::{Error, graph::directives::DirectivesIngester, sdl},
+};
+
+impl<'sdl> DirectivesIngester<'_, 'sdl> {
+    /// @authenticated is a @requiresScopes with a single empty group, granted to any
+    /// authenticated request.
+    pub fn create_authenticated_directive(&mut self) -> TypeSystemDirectiveId {
+        self.graph
+            .requires_scopes_directives
+            .push(RequiresScopesDirectiveRecord {
+                scopes: AuthorizationGroups::new(vec![Vec::new()]),
+            });
+        TypeSystemDirectiveId::RequiresScopes((self.graph.requires_scopes_directives.len() - 1).into())
+    }
+
+    pub fn create_requires_scopes_directive(
+        &mut self,
+        _def: sdl::SdlDefinition<'sdl>,
+        directive: sdl::Directive<'sdl>,
+    ) -> Result<TypeSystemDirectiveId, Error> {
+        let dir = directive.deserialize::<sdl::RequiresScopesDirective>().map_err(|err| {
+            (
+                format!("Invalid @requiresScopes directive: {err}"),
+                directive.arguments_span(),
+            )
+        })?;
+        let scopes = self.ingest_authorization_groups(dir.scopes);
+        self.graph
+            .requires_scopes_directives
+            .push(RequiresScopesDirectiveRecord { scopes });
+        Ok(TypeSystemDirectiveId::RequiresScopes(
+            (self.graph.requires_scopes_directives.len() - 1).into(),
+        ))
+    }
+
+    pub fn create_policy_directive(
+        &mut self,
+        _def: sdl::SdlDefinition<'sdl>,
+        directive: sdl::Directive<'sdl>,
+    ) -> Result<TypeSystemDirectiveId, Error> {
+        let dir = directive
+            .deserialize::<sdl::PolicyDirective>()
+            .map_err(|err| (format!("Invalid @policy directive: {err}"), directive.arguments_span()))?;
+        let policies = self.ingest_authorization_groups(dir.policies);
+        self.graph.policy_directives.push(PolicyDirectiveRecord { policies });
+        Ok(TypeSystemDirectiveId::Policy(
+            (self.graph.policy_directives.len() - 1).into(),
+        ))
+    }
+
+    fn ingest_authorization_groups(&mut self, groups: Vec<Vec<&str>>) -> AuthorizationGroups {
+        AuthorizationGroups::new(
+            groups
+                .into_iter()
+                .map(|group| group.into_iter().map(|element| self.ingest_str(element)).collect())
+                .collect(),
+        )
+    }
+}
@@ -1,3 +1,4 @@
+mod authorization;
 mod cost;
 mod deprecated;
 mod list_size;
@@ -37,6 +38,22 @@ impl<'sdl> DirectivesIngester<'_, 'sdl> {
                     Ok(id) => directive_ids.push(id),
                     Err(err) => self.errors.push(err),
                 },
+                // Unless imported from an extension, in which case they're handled by it.
+                "authenticated" if !self.sdl.directive_imports.contains_key(directive.name()) => {
+                    directive_ids.push(self.create_authenticated_directive())
+                }
+                "requiresScopes" if !self.sdl.directive_imports.contains_key(directive.name()) => {
+                    match self.create_requires_scopes_directive(def, directive) {
+                        Ok(id) => directive_ids.push(id),
+                        Err(err) => self.errors.push(err),
+                    }
+                }
+                "policy" if !self.sdl.directive_imports.contains_key(directive.name()) => {
+                    match self.create_policy_directive(def, directive) {
+                        Ok(id) => directive_ids.push(id),
+                        Err(err) => self.errors.push(err),
+                    }
+                }
                 "oneOf" => {
                     let sdl::SdlDefinition::InputObject(_) = def else {
                         self.errors
@@ -101,7 +101,7 @@ impl<'a> Builder<'a> {
                 ExtensionsContext::load(&sdl, &extension_catalog).await?
             };
 
-            BuildContext::new(&sdl, &extensions, &config).build(for_operation_analytics_only)
+            BuildContext::new(&sdl, &extensions, &config)?.build(for_operation_analytics_only)
         } else {
             let sdl = Default::default();
             let extensions = if for_operation_analytics_only {
@@ -110,7 +110,7 @@ impl<'a> Builder<'a> {
                 ExtensionsContext::load(&sdl, &extension_catalog).await?
             };
 
-            BuildContext::new(&sdl, &extensions, &config).build(for_operation_analytics_only)
+            BuildContext::new(&sdl, &extensions, &config)?.build(for_operation_analytics_only)
         }
     }
 }
@@ -176,7 +176,8 @@ fn build_settings(config: &Config) -> PartialConfig {
         timeout: config.gateway.timeout,
         operation_limits: config.operation_limits.unwrap_or_default(),
         disable_introspection: !config.graph.introspection.unwrap_or_default(),
-        retry: config.gateway.retry.enabled.then_some(config.gateway.retry.into()),
+        retry: config.gateway.retry.enabled.then(|| config.gateway.retry.clone().into()),
+        load_shedding: config.traffic_shaping.load_shedding.map(Into::into),
         batching: config.gateway.batching.clone(),
         complexity_control: (&config.complexity_control).into(),
         response_extension: config
@@ -196,5 +197,6 @@ fn build_settings(config: &Config) -> PartialConfig {
         websocket_forward_connection_init_payload: config.websockets.forward_connection_init_payload,
         contract_cache_max_size: config.graph.contracts.cache.max_size,
         error_code_mapping: config.graph.error_code_mapping.clone(),
+        response_header_rules: config.response_headers.iter().map(Into::into).collect(),
     }
 }
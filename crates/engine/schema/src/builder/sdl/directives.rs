@@ -103,6 +103,22 @@ pub struct DeprecatedDirective<'a> {
     pub reason: Option<&'a str>,
 }
 
+/// ```ignore,graphql
+/// directive @requiresScopes(scopes: [[String!]!]!) on FIELD_DEFINITION | OBJECT | INTERFACE | SCALAR | ENUM
+/// ```
+#[derive(ValueDeserialize)]
+pub struct RequiresScopesDirective<'a> {
+    pub scopes: Vec<Vec<&'a str>>,
+}
+
+/// ```ignore,graphql
+/// directive @policy(policies: [[String!]!]!) on FIELD_DEFINITION | OBJECT | INTERFACE | SCALAR | ENUM
+/// ```
+#[derive(ValueDeserialize)]
+pub struct PolicyDirective<'a> {
+    pub policies: Vec<Vec<&'a str>>,
+}
+
 pub type DerivedDirective<'a> = LookupDirective<'a>;
 
 ///```ignore,graphql
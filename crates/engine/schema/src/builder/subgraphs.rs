@@ -3,8 +3,9 @@ use id_newtypes::IdRange;
 use rapidhash::fast::RapidHashMap;
 
 use crate::{
-    ForwardHeaderRuleRecord, HeaderRuleId, HeaderRuleRecord, InsertHeaderRuleRecord, NameOrPatternId,
-    RemoveHeaderRuleRecord, RenameDuplicateHeaderRuleRecord, SubGraphs, introspection::IntrospectionSubgraph,
+    ForwardHeaderRuleRecord, HeaderRuleId, HeaderRuleRecord, HeaderTemplateRecord, InsertHeaderRuleRecord,
+    NameOrPatternId, RemoveHeaderRuleRecord, RenameDuplicateHeaderRuleRecord, SubGraphs,
+    introspection::IntrospectionSubgraph,
 };
 
 use super::{
@@ -24,10 +25,15 @@ pub(crate) struct SubgraphsBuilder<'sdl> {
     pub virtual_subgraphs: Vec<VirtualSubgraphRecord>,
     pub default_header_rules: IdRange<HeaderRuleId>,
     pub header_rules: Vec<HeaderRuleRecord>,
+    pub header_templates: Vec<HeaderTemplateRecord>,
 }
 
 impl<'sdl> SubgraphsBuilder<'sdl> {
-    pub(super) fn new(sdl: &'sdl Sdl<'sdl>, config: &gateway_config::Config, interners: &mut Interners) -> Self {
+    pub(super) fn new(
+        sdl: &'sdl Sdl<'sdl>,
+        config: &gateway_config::Config,
+        interners: &mut Interners,
+    ) -> Result<Self, Error> {
         let mut subgraphs = SubgraphsBuilder {
             all: Vec::with_capacity(sdl.subgraphs.len()),
             mapping: RapidHashMap::with_capacity_and_hasher(sdl.subgraphs.len(), Default::default()),
@@ -35,9 +41,10 @@ impl<'sdl> SubgraphsBuilder<'sdl> {
             virtual_subgraphs: Vec::new(),
             header_rules: Vec::new(),
             default_header_rules: IdRange::default(),
+            header_templates: Vec::new(),
         };
 
-        subgraphs.default_header_rules = ingest_header_rules(&mut subgraphs.header_rules, &config.headers, interners);
+        subgraphs.default_header_rules = subgraphs.ingest_header_rules(&config.headers, interners)?;
 
         let default_cache_ttl = if config.entity_caching.enabled {
             Some(config.entity_caching.ttl)
@@ -58,16 +65,19 @@ impl<'sdl> SubgraphsBuilder<'sdl> {
             let SubgraphConfig {
                 url,
                 headers,
+                response_headers,
                 websocket_url,
                 timeout,
                 retry,
+                response_limits,
+                batching,
                 entity_caching,
                 subscription_protocol,
                 ..
             } = config.subgraphs.get(name).cloned().unwrap_or_default();
             let url = url.or(subgraph.url.clone());
 
-            let header_rule_ids = ingest_header_rules(&mut subgraphs.header_rules, &headers, interners);
+            let header_rule_ids = subgraphs.ingest_header_rules(&headers, interners)?;
             let subgraph_id = if let Some(url) = url {
                 subgraphs.graphql_endpoints.push(GraphqlSubgraphRecord {
                     name_id: subgraph_name_id,
@@ -91,6 +101,9 @@ impl<'sdl> SubgraphsBuilder<'sdl> {
                                     .flatten()
                             })
                             .or(default_cache_ttl),
+                        response_header_rules: response_headers.iter().map(Into::into).collect(),
+                        response_limits: response_limits.map(Into::into).unwrap_or_default(),
+                        batching: batching.filter(|batching| batching.enabled).map(Into::into),
                     },
                     schema_directive_ids: Vec::new(),
                 });
@@ -107,7 +120,77 @@ impl<'sdl> SubgraphsBuilder<'sdl> {
             subgraphs.mapping.insert(graph_enum_name, subgraph_id);
         }
 
-        subgraphs
+        Ok(subgraphs)
+    }
+
+    fn ingest_header_rules(
+        &mut self,
+        rules: &[gateway_config::HeaderRule],
+        interners: &mut Interners,
+    ) -> Result<IdRange<HeaderRuleId>, Error> {
+        use gateway_config::*;
+        let start = self.header_rules.len();
+        for rule in rules {
+            let record = match rule {
+                HeaderRule::Forward(rule) => {
+                    let name_id = match &rule.name {
+                        NameOrPattern::Pattern(pattern) => {
+                            NameOrPatternId::Pattern(interners.regexps.get_or_insert(pattern.0.clone()))
+                        }
+                        NameOrPattern::Name(name) => NameOrPatternId::Name(interners.strings.get_or_new(name.as_str())),
+                    };
+
+                    let default_id = rule.default.as_ref().map(|s| interners.strings.get_or_new(s.as_str()));
+                    let rename_id = rule.rename.as_ref().map(|s| interners.strings.get_or_new(s.as_str()));
+
+                    HeaderRuleRecord::Forward(ForwardHeaderRuleRecord {
+                        name_id,
+                        default_id,
+                        rename_id,
+                    })
+                }
+                HeaderRule::Insert(rule) => {
+                    let name_id = interners.strings.get_or_new(rule.name.as_str());
+                    let value_id = interners.strings.get_or_new(rule.value.as_str());
+                    let template_id = if rule.template {
+                        let template = HeaderTemplateRecord::new(rule.value.to_string())
+                            .map_err(|err| format!("Invalid template for the header '{}': {err}", rule.name))?;
+                        self.header_templates.push(template);
+                        Some((self.header_templates.len() - 1).into())
+                    } else {
+                        None
+                    };
+
+                    HeaderRuleRecord::Insert(InsertHeaderRuleRecord {
+                        name_id,
+                        value_id,
+                        template_id,
+                    })
+                }
+                HeaderRule::Remove(rule) => {
+                    let name_id = match &rule.name {
+                        NameOrPattern::Pattern(pattern) => {
+                            NameOrPatternId::Pattern(interners.regexps.get_or_insert(pattern.0.clone()))
+                        }
+                        NameOrPattern::Name(name) => NameOrPatternId::Name(interners.strings.get_or_new(name.as_str())),
+                    };
+
+                    HeaderRuleRecord::Remove(RemoveHeaderRuleRecord { name_id })
+                }
+                HeaderRule::RenameDuplicate(rule) => {
+                    HeaderRuleRecord::RenameDuplicate(RenameDuplicateHeaderRuleRecord {
+                        name_id: interners.strings.get_or_new(rule.name.as_str()),
+                        default_id: rule
+                            .default
+                            .as_ref()
+                            .map(|default| interners.strings.get_or_new(default.as_str())),
+                        rename_id: interners.strings.get_or_new(rule.rename.as_str()),
+                    })
+                }
+            };
+            self.header_rules.push(record);
+        }
+        Ok((start..self.header_rules.len()).into())
     }
 
     pub(super) fn try_get(&self, name: GraphName<'_>, span: sdl::Span) -> Result<SubgraphId, Error> {
@@ -123,6 +206,7 @@ impl<'sdl> SubgraphsBuilder<'sdl> {
             virtual_subgraphs,
             default_header_rules,
             header_rules,
+            header_templates,
             ..
         } = self;
         SubGraphs {
@@ -131,61 +215,7 @@ impl<'sdl> SubgraphsBuilder<'sdl> {
             introspection,
             default_header_rules,
             header_rules,
+            header_templates,
         }
     }
 }
-
-fn ingest_header_rules(
-    header_rules: &mut Vec<HeaderRuleRecord>,
-    rules: &[gateway_config::HeaderRule],
-    interners: &mut Interners,
-) -> IdRange<HeaderRuleId> {
-    use gateway_config::*;
-    let start = header_rules.len();
-    header_rules.extend(rules.iter().map(|rule| -> HeaderRuleRecord {
-        match rule {
-            HeaderRule::Forward(rule) => {
-                let name_id = match &rule.name {
-                    NameOrPattern::Pattern(pattern) => {
-                        NameOrPatternId::Pattern(interners.regexps.get_or_insert(pattern.0.clone()))
-                    }
-                    NameOrPattern::Name(name) => NameOrPatternId::Name(interners.strings.get_or_new(name.as_str())),
-                };
-
-                let default_id = rule.default.as_ref().map(|s| interners.strings.get_or_new(s.as_str()));
-                let rename_id = rule.rename.as_ref().map(|s| interners.strings.get_or_new(s.as_str()));
-
-                HeaderRuleRecord::Forward(ForwardHeaderRuleRecord {
-                    name_id,
-                    default_id,
-                    rename_id,
-                })
-            }
-            HeaderRule::Insert(rule) => {
-                let name_id = interners.strings.get_or_new(rule.name.as_str());
-                let value_id = interners.strings.get_or_new(rule.value.as_str());
-
-                HeaderRuleRecord::Insert(InsertHeaderRuleRecord { name_id, value_id })
-            }
-            HeaderRule::Remove(rule) => {
-                let name_id = match &rule.name {
-                    NameOrPattern::Pattern(pattern) => {
-                        NameOrPatternId::Pattern(interners.regexps.get_or_insert(pattern.0.clone()))
-                    }
-                    NameOrPattern::Name(name) => NameOrPatternId::Name(interners.strings.get_or_new(name.as_str())),
-                };
-
-                HeaderRuleRecord::Remove(RemoveHeaderRuleRecord { name_id })
-            }
-            HeaderRule::RenameDuplicate(rule) => HeaderRuleRecord::RenameDuplicate(RenameDuplicateHeaderRuleRecord {
-                name_id: interners.strings.get_or_new(rule.name.as_str()),
-                default_id: rule
-                    .default
-                    .as_ref()
-                    .map(|default| interners.strings.get_or_new(default.as_str())),
-                rename_id: interners.strings.get_or_new(rule.rename.as_str()),
-            }),
-        }
-    }));
-    (start..header_rules.len()).into()
-}
@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+/// Some requests must always go through to keep measuring the latency, otherwise the gateway would
+/// never recover from a latency spike.
+const MAX_REJECTION_RATIO: f64 = 0.99;
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct LoadSheddingConfig {
+    /// Average operation latency above which incoming requests start to be rejected.
+    pub latency_threshold: Duration,
+    /// Maximum fraction of the incoming requests that can be rejected, always below 1.
+    pub max_rejection_ratio: f64,
+    /// Value of the Retry-After header sent with rejected requests.
+    pub retry_after: Duration,
+}
+
+impl From<gateway_config::LoadSheddingConfig> for LoadSheddingConfig {
+    fn from(config: gateway_config::LoadSheddingConfig) -> Self {
+        LoadSheddingConfig {
+            latency_threshold: config.latency_threshold,
+            max_rejection_ratio: config.max_rejection_ratio.clamp(0.0, MAX_REJECTION_RATIO),
+            retry_after: config.retry_after,
+        }
+    }
+}
@@ -1,10 +1,14 @@
 mod complexity_control;
+mod load_shedding;
 mod response_extensions;
+mod response_headers;
 mod retry;
 mod trusted_documents;
 
 pub use complexity_control::*;
+pub use load_shedding::*;
 pub use response_extensions::*;
+pub use response_headers::*;
 pub use retry::*;
 pub use trusted_documents::*;
 
@@ -14,6 +18,7 @@ pub struct PartialConfig {
     pub operation_limits: gateway_config::OperationLimitsConfig,
     pub disable_introspection: bool,
     pub retry: Option<RetryConfig>,
+    pub load_shedding: Option<LoadSheddingConfig>,
     pub batching: gateway_config::BatchingConfig,
     pub complexity_control: ComplexityControl,
     pub response_extension: ResponseExtensionConfig,
@@ -23,4 +28,5 @@ pub struct PartialConfig {
     pub websocket_forward_connection_init_payload: bool,
     pub contract_cache_max_size: usize,
     pub error_code_mapping: gateway_config::ErrorCodeMapping,
+    pub response_header_rules: Vec<ResponseHeaderRule>,
 }
@@ -8,6 +8,8 @@ pub struct ResponseExtensionConfig {
     pub include_trace_id: bool,
     /// Whether the query plan is exposed in the grafbase response extension. Defaults to true.
     pub include_query_plan: bool,
+    /// Whether the operation cost is exposed in the grafbase response extension. Defaults to true.
+    pub include_cost: bool,
     /// Defines under which conditions the grafbase response extension will be added.
     /// Defaults to a simple header rule, the presence of `x-grafbase-telemetry` is enough.
     pub access_control: Vec<AccessControl>,
@@ -24,6 +26,7 @@ impl From<ResponseExtensionExporterConfig> for ResponseExtensionConfig {
         ResponseExtensionConfig {
             include_trace_id: config.trace_id,
             include_query_plan: config.query_plan,
+            include_cost: config.cost,
             access_control: config
                 .access_control
                 .into_iter()
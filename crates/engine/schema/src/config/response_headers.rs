@@ -0,0 +1,42 @@
+use gateway_config::ResponseHeaderMerge;
+use regex::Regex;
+
+/// Subgraph response header propagated to the client response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ResponseHeaderRule {
+    pub name: ResponseHeaderName,
+    /// Value used if none of the subgraphs returned the header.
+    pub default: Option<String>,
+    /// Name of the header in the client response, if different from the subgraph one.
+    pub rename: Option<String>,
+    pub merge: ResponseHeaderMerge,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum ResponseHeaderName {
+    Name(String),
+    Pattern(#[serde(with = "serde_regex")] Regex),
+}
+
+impl ResponseHeaderName {
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            ResponseHeaderName::Name(expected) => expected.eq_ignore_ascii_case(name),
+            ResponseHeaderName::Pattern(regex) => regex.is_match(name),
+        }
+    }
+}
+
+impl From<&gateway_config::ResponseHeaderRule> for ResponseHeaderRule {
+    fn from(rule: &gateway_config::ResponseHeaderRule) -> Self {
+        ResponseHeaderRule {
+            name: match &rule.name {
+                gateway_config::NameOrPattern::Name(name) => ResponseHeaderName::Name(name.to_string()),
+                gateway_config::NameOrPattern::Pattern(pattern) => ResponseHeaderName::Pattern(pattern.0.clone()),
+            },
+            default: rule.default.as_ref().map(ToString::to_string),
+            rename: rule.rename.as_ref().map(ToString::to_string),
+            merge: rule.merge,
+        }
+    }
+}
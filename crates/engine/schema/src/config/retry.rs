@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct RetryConfig {
     /// How many retries are available per second, at a minimum.
     pub min_per_second: Option<u32>,
@@ -10,6 +10,61 @@ pub struct RetryConfig {
     pub retry_percent: Option<f32>,
     /// Whether mutations should be retried at all. False by default.
     pub retry_mutations: bool,
+    /// Maximum number of attempts of a single request, including the initial one. Only limited by
+    /// the budget if absent.
+    pub max_attempts: Option<u32>,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between two retries.
+    pub max_backoff: Option<Duration>,
+    /// Factor applied to the delay after each retry.
+    pub backoff_multiplier: f64,
+    /// Whether the delay is randomized, between zero and twice its value.
+    pub jitter: bool,
+    /// Which failures are retried, any failed request if absent.
+    pub retry_on: Option<RetryOn>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RetryOn {
+    pub connection_errors: bool,
+    pub timeouts: bool,
+    /// Any 5xx and 429 if absent.
+    pub status_codes: Option<Vec<u16>>,
+    pub graphql_error_codes: Vec<String>,
+}
+
+/// Delay used instead of one that overflows when no `max_backoff` is configured.
+const OVERFLOWING_BACKOFF_FALLBACK: Duration = Duration::from_secs(60);
+
+impl RetryConfig {
+    /// Delay before the given retry, starting at zero for the first one.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        if self.initial_backoff.is_zero() {
+            return Duration::ZERO;
+        }
+
+        let exponent = i32::try_from(retry).unwrap_or(i32::MAX);
+        let backoff = self.initial_backoff.as_secs_f64() * self.backoff_multiplier.powi(exponent);
+
+        let Ok(backoff) = Duration::try_from_secs_f64(backoff) else {
+            return self.max_backoff.unwrap_or(OVERFLOWING_BACKOFF_FALLBACK);
+        };
+
+        match self.max_backoff {
+            Some(max_backoff) => backoff.min(max_backoff),
+            None => backoff,
+        }
+    }
+}
+
+impl RetryOn {
+    pub fn status_code(&self, status: http::StatusCode) -> bool {
+        match &self.status_codes {
+            Some(codes) => codes.contains(&status.as_u16()),
+            None => status.is_server_error() || status == http::StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
 }
 
 impl From<gateway_config::RetryConfig> for RetryConfig {
@@ -19,6 +74,54 @@ impl From<gateway_config::RetryConfig> for RetryConfig {
             ttl: config.ttl,
             retry_percent: config.retry_percent,
             retry_mutations: config.retry_mutations,
+            max_attempts: config.max_attempts,
+            initial_backoff: config.initial_backoff.unwrap_or(Duration::from_millis(100)),
+            max_backoff: config.max_backoff,
+            backoff_multiplier: config.backoff_multiplier.unwrap_or(2.0),
+            jitter: config.jitter.unwrap_or(true),
+            retry_on: config.retry_on.map(Into::into),
         }
     }
 }
+
+impl From<gateway_config::RetryOnConfig> for RetryOn {
+    fn from(config: gateway_config::RetryOnConfig) -> Self {
+        RetryOn {
+            connection_errors: config.connection_errors,
+            timeouts: config.timeouts,
+            status_codes: config.status_codes,
+            graphql_error_codes: config.graphql_error_codes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(max_backoff: Option<Duration>) -> RetryConfig {
+        RetryConfig::from(gateway_config::RetryConfig {
+            initial_backoff: Some(Duration::from_millis(100)),
+            max_backoff,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn backoff_grows_up_to_the_max_backoff() {
+        let config = config(Some(Duration::from_secs(1)));
+
+        assert_eq!(config.backoff(0), Duration::from_millis(100));
+        assert_eq!(config.backoff(2), Duration::from_millis(400));
+        assert_eq!(config.backoff(10), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn overflowing_backoff_falls_back_to_the_max_backoff() {
+        assert_eq!(
+            config(Some(Duration::from_secs(1))).backoff(u32::MAX),
+            Duration::from_secs(1)
+        );
+        assert_eq!(config(None).backoff(u32::MAX), OVERFLOWING_BACKOFF_FALLBACK);
+    }
+}
@@ -3,11 +3,13 @@
 //! ===================
 //! Generated with: `cargo run -p engine-codegen`
 //! Source file: <engine-codegen dir>/domain/schema.graphql
+mod authorization;
 mod complexity_control;
 mod deprecated;
 mod extension;
 
 use crate::prelude::*;
+pub use authorization::*;
 pub use complexity_control::*;
 pub use deprecated::*;
 pub use extension::*;
@@ -21,6 +23,8 @@ use walker::{Iter, Walk};
 ///   | DeprecatedDirective
 ///   | CostDirective
 ///   | ListSizeDirective
+///   | RequiresScopesDirective
+///   | PolicyDirective
 ///   | ExtensionDirective
 /// ```
 #[derive(serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -29,6 +33,8 @@ pub enum TypeSystemDirectiveId {
     Deprecated(DeprecatedDirectiveRecord),
     Extension(ExtensionDirectiveId),
     ListSize(ListSizeDirectiveId),
+    Policy(PolicyDirectiveId),
+    RequiresScopes(RequiresScopesDirectiveId),
 }
 
 impl std::fmt::Debug for TypeSystemDirectiveId {
@@ -38,6 +44,8 @@ impl std::fmt::Debug for TypeSystemDirectiveId {
             TypeSystemDirectiveId::Deprecated(variant) => variant.fmt(f),
             TypeSystemDirectiveId::Extension(variant) => variant.fmt(f),
             TypeSystemDirectiveId::ListSize(variant) => variant.fmt(f),
+            TypeSystemDirectiveId::Policy(variant) => variant.fmt(f),
+            TypeSystemDirectiveId::RequiresScopes(variant) => variant.fmt(f),
         }
     }
 }
@@ -62,6 +70,16 @@ impl From<ListSizeDirectiveId> for TypeSystemDirectiveId {
         TypeSystemDirectiveId::ListSize(value)
     }
 }
+impl From<PolicyDirectiveId> for TypeSystemDirectiveId {
+    fn from(value: PolicyDirectiveId) -> Self {
+        TypeSystemDirectiveId::Policy(value)
+    }
+}
+impl From<RequiresScopesDirectiveId> for TypeSystemDirectiveId {
+    fn from(value: RequiresScopesDirectiveId) -> Self {
+        TypeSystemDirectiveId::RequiresScopes(value)
+    }
+}
 
 impl TypeSystemDirectiveId {
     pub fn is_cost(&self) -> bool {
@@ -100,6 +118,24 @@ impl TypeSystemDirectiveId {
             _ => None,
         }
     }
+    pub fn is_policy(&self) -> bool {
+        matches!(self, TypeSystemDirectiveId::Policy(_))
+    }
+    pub fn as_policy(&self) -> Option<PolicyDirectiveId> {
+        match self {
+            TypeSystemDirectiveId::Policy(id) => Some(*id),
+            _ => None,
+        }
+    }
+    pub fn is_requires_scopes(&self) -> bool {
+        matches!(self, TypeSystemDirectiveId::RequiresScopes(_))
+    }
+    pub fn as_requires_scopes(&self) -> Option<RequiresScopesDirectiveId> {
+        match self {
+            TypeSystemDirectiveId::RequiresScopes(id) => Some(*id),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -108,6 +144,8 @@ pub enum TypeSystemDirective<'a> {
     Deprecated(DeprecatedDirective<'a>),
     Extension(ExtensionDirective<'a>),
     ListSize(ListSizeDirective<'a>),
+    Policy(PolicyDirective<'a>),
+    RequiresScopes(RequiresScopesDirective<'a>),
 }
 
 impl std::fmt::Debug for TypeSystemDirective<'_> {
@@ -117,6 +155,8 @@ impl std::fmt::Debug for TypeSystemDirective<'_> {
             TypeSystemDirective::Deprecated(variant) => variant.fmt(f),
             TypeSystemDirective::Extension(variant) => variant.fmt(f),
             TypeSystemDirective::ListSize(variant) => variant.fmt(f),
+            TypeSystemDirective::Policy(variant) => variant.fmt(f),
+            TypeSystemDirective::RequiresScopes(variant) => variant.fmt(f),
         }
     }
 }
@@ -141,6 +181,16 @@ impl<'a> From<ListSizeDirective<'a>> for TypeSystemDirective<'a> {
         TypeSystemDirective::ListSize(item)
     }
 }
+impl<'a> From<PolicyDirective<'a>> for TypeSystemDirective<'a> {
+    fn from(item: PolicyDirective<'a>) -> Self {
+        TypeSystemDirective::Policy(item)
+    }
+}
+impl<'a> From<RequiresScopesDirective<'a>> for TypeSystemDirective<'a> {
+    fn from(item: RequiresScopesDirective<'a>) -> Self {
+        TypeSystemDirective::RequiresScopes(item)
+    }
+}
 
 impl<'a> Walk<&'a Schema> for TypeSystemDirectiveId {
     type Walker<'w>
@@ -158,6 +208,8 @@ impl<'a> Walk<&'a Schema> for TypeSystemDirectiveId {
             TypeSystemDirectiveId::Deprecated(item) => TypeSystemDirective::Deprecated(item.walk(schema)),
             TypeSystemDirectiveId::Extension(id) => TypeSystemDirective::Extension(id.walk(schema)),
             TypeSystemDirectiveId::ListSize(id) => TypeSystemDirective::ListSize(id.walk(schema)),
+            TypeSystemDirectiveId::Policy(id) => TypeSystemDirective::Policy(id.walk(schema)),
+            TypeSystemDirectiveId::RequiresScopes(id) => TypeSystemDirective::RequiresScopes(id.walk(schema)),
         }
     }
 }
@@ -169,6 +221,8 @@ impl<'a> TypeSystemDirective<'a> {
             TypeSystemDirective::Deprecated(walker) => TypeSystemDirectiveId::Deprecated(walker.item),
             TypeSystemDirective::Extension(walker) => TypeSystemDirectiveId::Extension(walker.id),
             TypeSystemDirective::ListSize(walker) => TypeSystemDirectiveId::ListSize(walker.id),
+            TypeSystemDirective::Policy(walker) => TypeSystemDirectiveId::Policy(walker.id),
+            TypeSystemDirective::RequiresScopes(walker) => TypeSystemDirectiveId::RequiresScopes(walker.id),
         }
     }
     pub fn is_cost(&self) -> bool {
@@ -207,4 +261,22 @@ impl<'a> TypeSystemDirective<'a> {
             _ => None,
         }
     }
+    pub fn is_policy(&self) -> bool {
+        matches!(self, TypeSystemDirective::Policy(_))
+    }
+    pub fn as_policy(&self) -> Option<PolicyDirective<'a>> {
+        match self {
+            TypeSystemDirective::Policy(item) => Some(*item),
+            _ => None,
+        }
+    }
+    pub fn is_requires_scopes(&self) -> bool {
+        matches!(self, TypeSystemDirective::RequiresScopes(_))
+    }
+    pub fn as_requires_scopes(&self) -> Option<RequiresScopesDirective<'a>> {
+        match self {
+            TypeSystemDirective::RequiresScopes(item) => Some(*item),
+            _ => None,
+        }
+    }
 }
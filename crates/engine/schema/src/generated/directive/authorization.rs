@@ -0,0 +1,130 @@
+//! ===================
+//! !!! DO NOT EDIT !!!
+//! ===================
+//! Generated with: `cargo run -p engine-codegen`
+//! Source file: <engine-codegen dir>/domain/schema.graphql
+use crate::{AuthorizationGroups, prelude::*};
+#[allow(unused_imports)]
+use walker::{Iter, Walk};
+
+/// Generated from:
+///
+/// ```custom,{.language-graphql}
+/// type RequiresScopesDirective @meta(module: "directive/authorization") @indexed(id_size: "u32") {
+///   scopes: AuthorizationGroups!
+/// }
+/// ```
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct RequiresScopesDirectiveRecord {
+    pub scopes: AuthorizationGroups,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, serde::Serialize, serde::Deserialize, id_derives::Id)]
+pub struct RequiresScopesDirectiveId(std::num::NonZero<u32>);
+
+#[derive(Clone, Copy)]
+pub struct RequiresScopesDirective<'a> {
+    pub(crate) schema: &'a Schema,
+    pub id: RequiresScopesDirectiveId,
+}
+
+impl std::ops::Deref for RequiresScopesDirective<'_> {
+    type Target = RequiresScopesDirectiveRecord;
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+impl<'a> RequiresScopesDirective<'a> {
+    /// Prefer using Deref unless you need the 'a lifetime.
+    #[allow(clippy::should_implement_trait)]
+    pub fn as_ref(&self) -> &'a RequiresScopesDirectiveRecord {
+        &self.schema[self.id]
+    }
+}
+
+impl<'a> Walk<&'a Schema> for RequiresScopesDirectiveId {
+    type Walker<'w>
+        = RequiresScopesDirective<'w>
+    where
+        'a: 'w;
+    fn walk<'w>(self, schema: impl Into<&'a Schema>) -> Self::Walker<'w>
+    where
+        Self: 'w,
+        'a: 'w,
+    {
+        RequiresScopesDirective {
+            schema: schema.into(),
+            id: self,
+        }
+    }
+}
+
+impl std::fmt::Debug for RequiresScopesDirective<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequiresScopesDirective")
+            .field("scopes", &self.scopes)
+            .finish()
+    }
+}
+
+/// Generated from:
+///
+/// ```custom,{.language-graphql}
+/// type PolicyDirective @meta(module: "directive/authorization") @indexed(id_size: "u32") {
+///   policies: AuthorizationGroups!
+/// }
+/// ```
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct PolicyDirectiveRecord {
+    pub policies: AuthorizationGroups,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, serde::Serialize, serde::Deserialize, id_derives::Id)]
+pub struct PolicyDirectiveId(std::num::NonZero<u32>);
+
+#[derive(Clone, Copy)]
+pub struct PolicyDirective<'a> {
+    pub(crate) schema: &'a Schema,
+    pub id: PolicyDirectiveId,
+}
+
+impl std::ops::Deref for PolicyDirective<'_> {
+    type Target = PolicyDirectiveRecord;
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+impl<'a> PolicyDirective<'a> {
+    /// Prefer using Deref unless you need the 'a lifetime.
+    #[allow(clippy::should_implement_trait)]
+    pub fn as_ref(&self) -> &'a PolicyDirectiveRecord {
+        &self.schema[self.id]
+    }
+}
+
+impl<'a> Walk<&'a Schema> for PolicyDirectiveId {
+    type Walker<'w>
+        = PolicyDirective<'w>
+    where
+        'a: 'w;
+    fn walk<'w>(self, schema: impl Into<&'a Schema>) -> Self::Walker<'w>
+    where
+        Self: 'w,
+        'a: 'w,
+    {
+        PolicyDirective {
+            schema: schema.into(),
+            id: self,
+        }
+    }
+}
+
+impl std::fmt::Debug for PolicyDirective<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PolicyDirective")
+            .field("policies", &self.policies)
+            .finish()
+    }
+}
@@ -3,7 +3,7 @@
 //! ===================
 //! Generated with: `cargo run -p engine-codegen`
 //! Source file: <engine-codegen dir>/domain/schema.graphql
-use crate::{StringId, prelude::*};
+use crate::{HeaderTemplate, HeaderTemplateId, StringId, prelude::*};
 #[allow(unused_imports)]
 use walker::{Iter, Walk};
 
@@ -13,12 +13,14 @@ use walker::{Iter, Walk};
 /// type InsertHeaderRule @meta(module: "header_rule/insert") @copy {
 ///   name: String!
 ///   value: String!
+///   template: HeaderTemplate
 /// }
 /// ```
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy)]
 pub struct InsertHeaderRuleRecord {
     pub name_id: StringId,
     pub value_id: StringId,
+    pub template_id: Option<HeaderTemplateId>,
 }
 
 #[derive(Clone, Copy)]
@@ -45,6 +47,9 @@ impl<'a> InsertHeaderRule<'a> {
     pub fn value(&self) -> &'a str {
         self.value_id.walk(self.schema)
     }
+    pub fn template(&self) -> Option<HeaderTemplate<'a>> {
+        self.template_id.walk(self.schema)
+    }
 }
 
 impl<'a> Walk<&'a Schema> for InsertHeaderRuleRecord {
@@ -69,6 +74,7 @@ impl std::fmt::Debug for InsertHeaderRule<'_> {
         f.debug_struct("InsertHeaderRule")
             .field("name", &self.name())
             .field("value", &self.value())
+            .field("template", &self.template())
             .finish()
     }
 }
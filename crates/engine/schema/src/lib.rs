@@ -3,6 +3,7 @@
 use builder::Builder;
 use grafbase_workspace_hack as _;
 
+mod authorization;
 mod builder;
 mod composite_type;
 mod config;
@@ -36,6 +37,7 @@ mod union;
 #[cfg(test)]
 mod tests;
 
+pub use authorization::*;
 pub use builder::mutable::MutableSchema;
 pub use config::*;
 pub use extension::*;
@@ -129,6 +131,7 @@ id_newtypes::forward_with_range! {
     impl Index<GraphqlSubgraphId, Output = GraphqlSubgraphRecord> for Schema.subgraphs,
     impl Index<VirtualSubgraphId, Output = VirtualSubgraphRecord> for Schema.subgraphs,
     impl Index<HeaderRuleId, Output = HeaderRuleRecord> for Schema.subgraphs,
+    impl Index<HeaderTemplateId, Output = HeaderTemplateRecord> for Schema.subgraphs,
     impl Index<SchemaFieldId, Output = SchemaFieldRecord> for Schema.selections,
     impl Index<SchemaFieldArgumentId, Output = SchemaFieldArgumentRecord> for Schema.selections,
     impl Index<KeyValueInjectionId, Output = KeyValueInjectionRecord> for Schema.selections,
@@ -183,6 +186,11 @@ pub struct Graph {
     #[indexed_by(ListSizeDirectiveId)]
     list_size_directives: Vec<ListSizeDirectiveRecord>,
 
+    #[indexed_by(RequiresScopesDirectiveId)]
+    requires_scopes_directives: Vec<RequiresScopesDirectiveRecord>,
+    #[indexed_by(PolicyDirectiveId)]
+    policy_directives: Vec<PolicyDirectiveRecord>,
+
     #[indexed_by(ExtensionDirectiveId)]
     extension_directives: Vec<ExtensionDirectiveRecord>,
     #[indexed_by(ExtensionDirectiveArgumentId)]
@@ -217,6 +225,8 @@ pub struct SubGraphs {
     /// Headers we might want to send to a subgraph
     #[indexed_by(HeaderRuleId)]
     header_rules: Vec<HeaderRuleRecord>,
+    #[indexed_by(HeaderTemplateId)]
+    header_templates: Vec<HeaderTemplateRecord>,
 }
 
 impl Schema {
@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use walker::{Iter, Walk};
 
-use crate::{ExtensionDirective, ExtensionDirectiveId, HeaderRule, RetryConfig, Subgraph};
+use crate::{ExtensionDirective, ExtensionDirectiveId, HeaderRule, ResponseHeaderRule, RetryConfig, Subgraph};
 
 impl<'a> Subgraph<'a> {
     pub fn name(&self) -> &'a str {
@@ -43,4 +43,45 @@ pub struct SubgraphConfig {
     // The ttl to use for caching for this subgraph.
     // If None then caching is disabled for this subgraph
     pub cache_ttl: Option<Duration>,
+    pub response_header_rules: Vec<ResponseHeaderRule>,
+    pub response_limits: SubgraphResponseLimits,
+    // If None then concurrent requests are sent individually.
+    pub batching: Option<SubgraphBatching>,
+}
+
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub struct SubgraphBatching {
+    /// Maximum number of requests in a single batch.
+    pub max_size: usize,
+    /// How long the first request of a batch waits for others to join it.
+    pub max_wait: Duration,
+}
+
+impl From<gateway_config::SubgraphBatchingConfig> for SubgraphBatching {
+    fn from(config: gateway_config::SubgraphBatchingConfig) -> Self {
+        SubgraphBatching {
+            max_size: config.max_size.max(1),
+            max_wait: config.max_wait,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct SubgraphResponseLimits {
+    /// Maximum size of the response body in bytes.
+    pub max_size: Option<usize>,
+    /// Maximum number of items of a single list in the response data.
+    pub max_list_length: Option<usize>,
+    /// Maximum nesting of objects and lists in the response data.
+    pub max_depth: Option<usize>,
+}
+
+impl From<gateway_config::SubgraphResponseLimitsConfig> for SubgraphResponseLimits {
+    fn from(config: gateway_config::SubgraphResponseLimitsConfig) -> Self {
+        SubgraphResponseLimits {
+            max_size: config.max_size.map(|size| size.bytes().max(0) as usize),
+            max_list_length: config.max_list_length,
+            max_depth: config.max_depth,
+        }
+    }
 }
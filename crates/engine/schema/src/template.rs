@@ -97,3 +97,85 @@ impl<'a> Walk<&'a Schema> for TemplateId {
         }
     }
 }
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, serde::Serialize, serde::Deserialize, id_derives::Id)]
+pub struct HeaderTemplateId(u32);
+
+/// Template of an inserted header value. Rendered values are never escaped, the result must be a
+/// valid header value.
+pub struct HeaderTemplateRecord {
+    pub inner: ramhorns::Template<'static>,
+}
+
+impl HeaderTemplateRecord {
+    pub(crate) fn new(source: String) -> Result<Self, ramhorns::Error> {
+        Ok(Self {
+            inner: ramhorns::Template::new(source)?,
+        })
+    }
+}
+
+impl Clone for HeaderTemplateRecord {
+    fn clone(&self) -> Self {
+        Self {
+            inner: ramhorns::Template::new(self.inner.source().to_owned()).unwrap(),
+        }
+    }
+}
+
+impl serde::Serialize for HeaderTemplateRecord {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.inner.source().serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for HeaderTemplateRecord {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let source = String::deserialize(deserializer)?;
+        Self::new(source).map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct HeaderTemplate<'a> {
+    pub(crate) schema: &'a Schema,
+    pub id: HeaderTemplateId,
+}
+
+impl std::ops::Deref for HeaderTemplate<'_> {
+    type Target = HeaderTemplateRecord;
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+impl<'a> HeaderTemplate<'a> {
+    /// Prefer using Deref unless you need the 'a lifetime.
+    #[allow(clippy::should_implement_trait)]
+    pub fn as_ref(&self) -> &'a HeaderTemplateRecord {
+        &self.schema[self.id]
+    }
+}
+
+impl std::fmt::Debug for HeaderTemplate<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("HeaderTemplate").field(&self.inner.source()).finish()
+    }
+}
+
+impl<'a> Walk<&'a Schema> for HeaderTemplateId {
+    type Walker<'w>
+        = HeaderTemplate<'w>
+    where
+        'a: 'w;
+    fn walk<'w>(self, schema: impl Into<&'a Schema>) -> Self::Walker<'w>
+    where
+        Self: 'w,
+        'a: 'w,
+    {
+        HeaderTemplate {
+            schema: schema.into(),
+            id: self,
+        }
+    }
+}
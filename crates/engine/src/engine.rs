@@ -1,6 +1,8 @@
 pub(crate) mod cache;
+mod load_shedding;
 pub mod mcp;
 mod retry_budget;
+mod revalidation;
 mod runtime;
 
 use ::runtime::{
@@ -15,7 +17,9 @@ use futures::{StreamExt, TryFutureExt};
 use futures_util::Stream;
 use graphql_tools::{parser::parse_schema, static_graphql::schema::Document};
 use hive_console_sdk::agent::usage_agent::{UsageAgent, UsageAgentExt};
+use load_shedding::LoadShedder;
 use retry_budget::RetryBudgets;
+use revalidation::EntityCacheRevalidations;
 use schema::Schema;
 use std::{borrow::Cow, env, future::Future, sync::Arc};
 use tokio_util::sync::CancellationToken;
@@ -28,6 +32,7 @@ use crate::{
     response::Response,
     websocket::{self, InitPayload},
 };
+pub(crate) use revalidation::EntityCacheRevalidation;
 pub(crate) use runtime::*;
 
 pub use runtime::Runtime;
@@ -161,7 +166,9 @@ impl<R: Runtime> ContractAwareEngine<R> {
                         ErrorResponse::new(http::StatusCode::INTERNAL_SERVER_ERROR)
                             .with_error(GraphqlError::new(err, ErrorCode::ExtensionError))
                     })?;
-                let engine = Arc::new(Engine::new(schema, runtime));
+                let mut engine = Engine::new(schema, runtime);
+                engine.load_shedder = self.no_contract.load_shedder.clone();
+                let engine = Arc::new(engine);
                 let _ = guard.insert(engine.clone());
                 Ok(engine)
             }
@@ -175,6 +182,9 @@ pub struct Engine<R: Runtime> {
     pub schema: Arc<Schema>,
     pub runtime: R,
     pub(crate) retry_budgets: RetryBudgets,
+    // Shared with the contract engines as the latency is measured gateway-wide.
+    pub(crate) load_shedder: Option<Arc<LoadShedder>>,
+    pub(crate) entity_cache_revalidations: EntityCacheRevalidations,
     pub hive_usage_reporter: Option<HiveUsageReporter>,
 }
 
@@ -218,6 +228,11 @@ impl<R: Runtime> Engine<R> {
         }
         Self {
             retry_budgets: RetryBudgets::build(&schema),
+            load_shedder: schema
+                .config
+                .load_shedding
+                .map(|config| Arc::new(LoadShedder::new(config))),
+            entity_cache_revalidations: Default::default(),
             schema,
             runtime,
             hive_usage_reporter,
@@ -284,7 +299,13 @@ impl<R: Runtime> Engine<R> {
                     self.schema.config.error_code_mapping.clone(),
                 ))
             }) {
-            Ok((request_context, request)) => self.execute_well_formed_graphql_request(request_context, request).await,
+            Ok((request_context, request)) => {
+                let mut response = self
+                    .execute_well_formed_graphql_request(request_context.clone(), request)
+                    .await;
+                request_context.response_headers.apply(response.headers_mut());
+                response
+            }
             Err(response) => Http::error(ctx.response_format, response),
         }
     }
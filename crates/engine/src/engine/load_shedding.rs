@@ -0,0 +1,161 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use schema::LoadSheddingConfig;
+
+use super::Runtime;
+
+/// Weight of the latest sample in the moving average of the operation latency.
+const EWMA_ALPHA: f64 = 0.1;
+
+/// Without any new sample for longer than this, the average latency starts decaying. Otherwise
+/// the gateway could keep rejecting requests based on an old latency spike.
+const DECAY_DELAY: Duration = Duration::from_secs(1);
+
+/// Half-life of the average latency once it decays.
+const DECAY_HALF_LIFE: Duration = Duration::from_secs(1);
+
+/// Rejects a growing fraction of the incoming requests once the average operation latency goes
+/// above the configured threshold.
+pub(crate) struct LoadShedder {
+    config: LoadSheddingConfig,
+    started_at: Instant,
+    // Exponentially weighted moving average of the operation latency, in microseconds.
+    average_latency_micros: AtomicU64,
+    // Time of the latest sample, in microseconds since `started_at`.
+    last_sample_micros: AtomicU64,
+}
+
+impl LoadShedder {
+    pub fn new(config: LoadSheddingConfig) -> Self {
+        Self {
+            config,
+            started_at: Instant::now(),
+            average_latency_micros: AtomicU64::new(0),
+            last_sample_micros: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record_latency(&self, latency: Duration) {
+        self.record_latency_at(latency, Instant::now())
+    }
+
+    fn record_latency_at(&self, latency: Duration, now: Instant) {
+        let sample = latency.as_micros().min(u64::MAX as u128) as u64;
+        let now = self.micros_since_start(now);
+        let last_sample = self.last_sample_micros.swap(now, Ordering::Relaxed);
+        let _ = self
+            .average_latency_micros
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |average| {
+                let average = decayed(average, now.saturating_sub(last_sample));
+                if average == 0 {
+                    return Some(sample);
+                }
+                Some((EWMA_ALPHA * sample as f64 + (1.0 - EWMA_ALPHA) * average as f64) as u64)
+            });
+    }
+
+    /// Probability with which an incoming request should be rejected, growing with how far the
+    /// average latency is above the threshold.
+    pub fn rejection_ratio(&self) -> f64 {
+        self.rejection_ratio_at(Instant::now())
+    }
+
+    fn rejection_ratio_at(&self, now: Instant) -> f64 {
+        let elapsed = self
+            .micros_since_start(now)
+            .saturating_sub(self.last_sample_micros.load(Ordering::Relaxed));
+        let average = decayed(self.average_latency_micros.load(Ordering::Relaxed), elapsed) as f64;
+        let threshold = self.config.latency_threshold.as_micros() as f64;
+        if average <= threshold {
+            return 0.0;
+        }
+        (1.0 - threshold / average).min(self.config.max_rejection_ratio)
+    }
+
+    pub fn should_shed(&self) -> bool {
+        let ratio = self.rejection_ratio();
+        ratio > 0.0 && rand::random::<f64>() < ratio
+    }
+
+    pub fn retry_after(&self) -> Duration {
+        self.config.retry_after
+    }
+
+    fn micros_since_start(&self, now: Instant) -> u64 {
+        now.saturating_duration_since(self.started_at)
+            .as_micros()
+            .min(u64::MAX as u128) as u64
+    }
+}
+
+/// Average latency after `elapsed_micros` without any new sample.
+fn decayed(average: u64, elapsed_micros: u64) -> u64 {
+    let delay = DECAY_DELAY.as_micros() as u64;
+    if elapsed_micros <= delay {
+        return average;
+    }
+    let half_lives = (elapsed_micros - delay) as f64 / DECAY_HALF_LIFE.as_micros() as f64;
+    (average as f64 * 0.5f64.powf(half_lives)) as u64
+}
+
+impl<R: Runtime> super::Engine<R> {
+    pub(crate) fn load_shedder(&self) -> Option<&LoadShedder> {
+        self.load_shedder.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shedder() -> LoadShedder {
+        LoadShedder::new(LoadSheddingConfig {
+            latency_threshold: Duration::from_millis(100),
+            max_rejection_ratio: 0.5,
+            retry_after: Duration::from_secs(2),
+        })
+    }
+
+    #[test]
+    fn no_rejection_below_threshold() {
+        let shedder = shedder();
+        shedder.record_latency(Duration::from_millis(50));
+        assert_eq!(shedder.rejection_ratio(), 0.0);
+        assert!(!shedder.should_shed());
+    }
+
+    #[test]
+    fn rejection_grows_with_latency_up_to_max_ratio() {
+        let shedder = shedder();
+        shedder.record_latency(Duration::from_millis(125));
+        let ratio = shedder.rejection_ratio();
+        assert!((ratio - 0.2).abs() < 1e-9, "{ratio}");
+
+        for _ in 0..100 {
+            shedder.record_latency(Duration::from_secs(10));
+        }
+        assert_eq!(shedder.rejection_ratio(), 0.5);
+    }
+
+    #[test]
+    fn recovers_after_latency_spike() {
+        let shedder = shedder();
+        let now = Instant::now();
+        for _ in 0..100 {
+            shedder.record_latency_at(Duration::from_secs(10), now);
+        }
+        assert_eq!(shedder.rejection_ratio_at(now), 0.5);
+        assert_eq!(shedder.rejection_ratio_at(now + DECAY_DELAY), 0.5);
+
+        // Even if no request went through, the spike is eventually forgotten.
+        let later = now + Duration::from_secs(20);
+        assert_eq!(shedder.rejection_ratio_at(later), 0.0);
+
+        // And new samples start from the decayed average rather than the spike.
+        shedder.record_latency_at(Duration::from_millis(50), later);
+        assert_eq!(shedder.rejection_ratio_at(later), 0.0);
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+use super::{Engine, Runtime};
+
+/// Keys of the entity cache entries currently refreshed in the background, so that a stale entry
+/// is only refreshed by a single subgraph request at a time.
+#[derive(Default)]
+pub(crate) struct EntityCacheRevalidations {
+    in_flight: Mutex<HashSet<String>>,
+}
+
+/// Entries reserved for a background refresh, released when dropped.
+pub(crate) struct EntityCacheRevalidation<R: Runtime> {
+    engine: Arc<Engine<R>>,
+    keys: Vec<String>,
+}
+
+impl<R: Runtime> EntityCacheRevalidation<R> {
+    pub(crate) fn new(engine: &Arc<Engine<R>>) -> Self {
+        Self {
+            engine: engine.clone(),
+            keys: Vec::new(),
+        }
+    }
+
+    /// Returns false if the entry is already being refreshed.
+    pub(crate) fn reserve(&mut self, key: &str) -> bool {
+        let reserved = self
+            .engine
+            .entity_cache_revalidations
+            .in_flight
+            .lock()
+            .unwrap()
+            .insert(key.to_owned());
+
+        if reserved {
+            self.keys.push(key.to_owned());
+        }
+
+        reserved
+    }
+
+    pub(crate) fn engine(&self) -> &Arc<Engine<R>> {
+        &self.engine
+    }
+
+    /// Reserved keys, in the order they were reserved.
+    pub(crate) fn keys(&self) -> &[String] {
+        &self.keys
+    }
+}
+
+impl<R: Runtime> Drop for EntityCacheRevalidation<R> {
+    fn drop(&mut self) {
+        let mut in_flight = self.engine.entity_cache_revalidations.in_flight.lock().unwrap();
+        for key in &self.keys {
+            in_flight.remove(key);
+        }
+    }
+}
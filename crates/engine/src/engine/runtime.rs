@@ -1,7 +1,10 @@
 use std::{future::Future, sync::Arc};
 
 use grafbase_telemetry::metrics::EngineMetrics;
-use runtime::{entity_cache::EntityCache, extension::EngineExtensions, rate_limiting::RateLimiter};
+use runtime::{
+    entity_cache::EntityCache, extension::EngineExtensions, field_usage::FieldUsageCollector,
+    rate_limiting::RateLimiter,
+};
 use schema::Schema;
 
 use crate::{CachedOperation, EngineOperationContext, EngineRequestContext};
@@ -18,6 +21,7 @@ pub trait Runtime: Send + Sync + Sized + 'static {
     fn rate_limiter(&self) -> &RateLimiter;
     fn sleep(&self, duration: std::time::Duration) -> impl Future<Output = ()> + Send;
     fn entity_cache(&self) -> &dyn EntityCache;
+    fn field_usage_collector(&self) -> &dyn FieldUsageCollector;
     fn extensions(&self) -> &Self::Extensions;
 
     fn clone_and_adjust_for_contract(&self, schema: &Arc<Schema>) -> impl Future<Output = Result<Self, String>> + Send;
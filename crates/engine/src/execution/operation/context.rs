@@ -7,7 +7,7 @@ use schema::{HeaderRule, Schema};
 
 use crate::{
     Engine, Runtime,
-    execution::{RequestContext, apply_header_rules},
+    execution::{HeaderRuleContext, RequestContext, apply_header_rules},
     prepare::{CachedOperationContext, OperationPlanContext, PreparedOperation, Shapes},
 };
 
@@ -41,7 +41,13 @@ impl<'ctx, R: Runtime> ExecutionContext<'ctx, R> {
             .as_ref()
             .unwrap_or(&self.request_context.subgraph_default_headers)
             .clone();
-        apply_header_rules(&self.request_context.headers, rules, &mut subgraph_headers);
+        let ctx = HeaderRuleContext::new(
+            &self.request_context.headers,
+            &self.request_context.token,
+            self.request_context.client.as_ref(),
+            self.operation.cached.operation.attributes.name.original(),
+        );
+        apply_header_rules(&ctx, rules, &mut subgraph_headers);
         subgraph_headers
     }
 
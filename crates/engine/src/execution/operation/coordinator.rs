@@ -3,7 +3,10 @@ use std::{collections::VecDeque, sync::Arc};
 use event_queue::{ExecutedOperation, ExecutedOperationBuilder};
 use futures::{Future, FutureExt, Stream, stream::FuturesOrdered};
 use futures_util::{StreamExt, future::BoxFuture, stream::FuturesUnordered};
-use grafbase_telemetry::graphql::{GraphqlResponseStatus, OperationType};
+use grafbase_telemetry::{
+    graphql::{GraphqlResponseStatus, OperationType},
+    metrics::OperationCostAttributes,
+};
 use tracing::Instrument;
 use walker::Walk;
 
@@ -335,9 +338,40 @@ impl<'ctx, R: Runtime> OperationExecution<'ctx, R> {
             this.executed_operation_builder.complexity(complexity.0 as u64);
         }
 
+        let actual_complexity = this.response.actual_complexity_cost();
+        if let Some(actual) = actual_complexity {
+            this.executed_operation_builder.actual_complexity(actual.0 as u64);
+        }
+
         event_queue.push_operation(this.executed_operation_builder);
 
-        this.response.build(operation.attributes())
+        let mut response = this.response.build(operation.attributes());
+
+        if let Some((estimated, actual)) = operation.complexity_cost.zip(actual_complexity) {
+            this.ctx.metrics().record_operation_cost(
+                OperationCostAttributes {
+                    operation: operation.attributes(),
+                    client: this.ctx.request_context.client.clone(),
+                },
+                estimated.0,
+                actual.0,
+            );
+
+            if this.ctx.request_context.include_grafbase_response_extension
+                && this.ctx.schema().config.response_extension.include_cost
+            {
+                let extensions = response.extensions_mut();
+                extensions.grafbase = Some(
+                    extensions
+                        .grafbase
+                        .take()
+                        .unwrap_or_default()
+                        .with_cost(estimated, actual),
+                );
+            }
+        }
+
+        response
     }
 
     async fn ingest_execution_result<'exec>(
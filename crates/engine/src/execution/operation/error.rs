@@ -38,6 +38,10 @@ impl From<ExecutionError> for GraphqlError {
         let message = err.to_string();
         let code = match &err {
             ExecutionError::Internal(_) => ErrorCode::InternalServerError,
+            ExecutionError::Fetch {
+                error: FetchError::Overloaded,
+                ..
+            } => ErrorCode::ServiceUnavailable,
             ExecutionError::Fetch { .. } => ErrorCode::SubgraphRequestError,
             ExecutionError::RateLimit(_) => ErrorCode::RateLimited,
             ExecutionError::Graphql(err) => err.code,
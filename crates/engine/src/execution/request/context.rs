@@ -4,7 +4,12 @@ use event_queue::EventQueue;
 use grafbase_telemetry::grafbase_client::Client;
 use runtime::extension::Token;
 
-use crate::graphql_over_http::{ContentType, ResponseFormat};
+use crate::{
+    graphql_over_http::{ContentType, ResponseFormat},
+    resolver::SubgraphRequestBatches,
+};
+
+use super::PropagatedResponseHeaders;
 
 /// Context only used early in the request processing before generating the RequestContext used
 /// everywhere else. Contrary to the RequestContext this one never fails to be created.
@@ -32,4 +37,6 @@ pub(crate) struct RequestContext {
     pub include_mcp_response_extension: bool,
     pub event_queue: Arc<EventQueue>,
     pub hooks_context: Arc<[u8]>,
+    pub response_headers: PropagatedResponseHeaders,
+    pub subgraph_batches: SubgraphRequestBatches,
 }
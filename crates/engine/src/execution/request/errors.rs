@@ -100,6 +100,23 @@ pub(crate) mod response {
         )
     }
 
+    pub(crate) fn gateway_overloaded(
+        error_code_mapping: ErrorCodeMapping,
+        retry_after: std::time::Duration,
+    ) -> Response {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(
+            http::header::RETRY_AFTER,
+            http::HeaderValue::from(retry_after.as_secs().max(1)),
+        );
+        Response::refused_request(
+            error_code_mapping,
+            http::StatusCode::SERVICE_UNAVAILABLE,
+            [GraphqlError::new("Service unavailable", ErrorCode::ServiceUnavailable)],
+            headers,
+        )
+    }
+
     // We assume any invalid request error would be raised before the timeout expires. So if we do
     // end up sending this error it means operation was valid and the query was just slow.
     pub(crate) fn gateway_timeout(error_code_mapping: ErrorCodeMapping) -> Response {
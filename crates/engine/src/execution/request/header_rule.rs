@@ -1,23 +1,123 @@
-use std::{borrow::Cow, str::FromStr, sync::OnceLock};
+use std::{borrow::Cow, cell::OnceCell, str::FromStr, sync::OnceLock};
 
+use grafbase_telemetry::{
+    grafbase_client::Client,
+    otel::{opentelemetry::trace::TraceContextExt, tracing_opentelemetry::OpenTelemetrySpanExt},
+};
 use http::{HeaderName, header};
+use runtime::extension::Token;
 use schema::{
     ForwardHeaderRule, HeaderRule, HeaderRuleVariant, InsertHeaderRule, NameOrPattern, RemoveHeaderRule,
     RenameDuplicateHeaderRule,
 };
 
+/// Request data available to the header rules. Inserted header values with `template = true` are
+/// templates which can reference:
+/// - `token.<claim path>`: claims of the authenticated token, e.g. `{{ token.sub }}`,
+/// - `client.name` and `client.version`,
+/// - `operation.name`, only available for subgraph header rules,
+/// - `trace_id`,
+/// - `headers.<name>`: a header of the incoming request.
+pub(crate) struct HeaderRuleContext<'a> {
+    pub headers: &'a http::HeaderMap,
+    pub token: &'a Token,
+    pub client: Option<&'a Client>,
+    pub operation_name: Option<&'a str>,
+    token_claims: OnceCell<Option<serde_json::Value>>,
+}
+
+impl<'a> HeaderRuleContext<'a> {
+    pub fn new(
+        headers: &'a http::HeaderMap,
+        token: &'a Token,
+        client: Option<&'a Client>,
+        operation_name: Option<&'a str>,
+    ) -> Self {
+        Self {
+            headers,
+            token,
+            client,
+            operation_name,
+            token_claims: OnceCell::new(),
+        }
+    }
+
+    fn token_claims(&self) -> Option<&serde_json::Value> {
+        self.token_claims
+            .get_or_init(|| {
+                self.token
+                    .as_bytes()
+                    .and_then(|bytes| serde_json::from_slice(bytes).ok())
+            })
+            .as_ref()
+    }
+
+    fn get(&self, name: &str) -> Option<Cow<'_, str>> {
+        let (root, path) = name.split_once('.').unwrap_or((name, ""));
+        match (root, path) {
+            ("token", path) => {
+                let value = path
+                    .split('.')
+                    .filter(|key| !key.is_empty())
+                    .try_fold(self.token_claims()?, |parent, key| parent.as_object()?.get(key))?;
+
+                match value {
+                    serde_json::Value::String(s) => Some(Cow::Borrowed(s)),
+                    serde_json::Value::Null => None,
+                    value => Some(Cow::Owned(value.to_string())),
+                }
+            }
+            ("client", "name") => self.client.map(|client| Cow::Borrowed(client.name.as_str())),
+            ("client", "version") => self
+                .client
+                .and_then(|client| client.version.as_deref())
+                .map(Cow::Borrowed),
+            ("operation", "name") => self.operation_name.map(Cow::Borrowed),
+            ("trace_id", "") => {
+                let span_context = tracing::Span::current().context().span().span_context().clone();
+                span_context
+                    .is_valid()
+                    .then(|| Cow::Owned(format!("{:x}", span_context.trace_id())))
+            }
+            ("headers", name) => self.headers.get(name)?.to_str().ok().map(Cow::Borrowed),
+            _ => None,
+        }
+    }
+}
+
+impl ramhorns::Content for HeaderRuleContext<'_> {
+    fn render_field_escaped<E>(&self, hash: u64, name: &str, encoder: &mut E) -> Result<bool, E::Error>
+    where
+        E: ramhorns::encoding::Encoder,
+    {
+        // Header values are never escaped, invalid values are rejected when building the header.
+        self.render_field_unescaped(hash, name, encoder)
+    }
+
+    fn render_field_unescaped<E>(&self, _: u64, name: &str, encoder: &mut E) -> Result<bool, E::Error>
+    where
+        E: ramhorns::encoding::Encoder,
+    {
+        match self.get(name) {
+            Some(value) => encoder.write_unescaped(&value).map(|_| true),
+            None => Ok(false),
+        }
+    }
+}
+
 pub(crate) fn apply_header_rules<'ctx>(
-    gateway_headers: &http::HeaderMap,
+    ctx: &HeaderRuleContext<'_>,
     rules: impl Iterator<Item = HeaderRule<'ctx>>,
     subgraph_headers: &mut http::HeaderMap,
 ) {
+    let gateway_headers = ctx.headers;
     for rule in rules {
         match rule.variant() {
             HeaderRuleVariant::Forward(rule) => {
                 handle_forward(gateway_headers, rule, subgraph_headers);
             }
             HeaderRuleVariant::Insert(rule) => {
-                handle_insert(rule, subgraph_headers);
+                handle_insert(ctx, rule, subgraph_headers);
             }
             HeaderRuleVariant::Remove(rule) => handle_remove(rule, subgraph_headers),
             HeaderRuleVariant::RenameDuplicate(rule) => {
@@ -76,9 +176,22 @@ fn handle_remove(rule: RemoveHeaderRule<'_>, subgraph_headers: &mut http::Header
     }
 }
 
-fn handle_insert(rule: InsertHeaderRule<'_>, subgraph_headers: &mut http::HeaderMap) {
+fn handle_insert(ctx: &HeaderRuleContext<'_>, rule: InsertHeaderRule<'_>, subgraph_headers: &mut http::HeaderMap) {
     let name = http::HeaderName::from_bytes(rule.name().as_bytes()).ok();
-    let value = http::HeaderValue::from_str(rule.value()).ok();
+    let value = match rule.template() {
+        Some(template) => {
+            let value = template.inner.render(ctx);
+
+            // The header is not sent at all when the template renders to nothing, for example
+            // `{{ token.sub }}` for an anonymous request.
+            if value.is_empty() {
+                return;
+            }
+
+            http::HeaderValue::from_str(&value).ok()
+        }
+        None => http::HeaderValue::from_str(rule.value()).ok(),
+    };
 
     if let Some((name, value)) = name.zip(value) {
         if is_header_denied(&name) {
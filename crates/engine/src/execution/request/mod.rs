@@ -2,6 +2,7 @@ mod context;
 pub(crate) mod errors;
 mod header_rule;
 mod response_extension;
+mod response_header_rule;
 mod single;
 mod stream;
 mod well_formed_graphql_request;
@@ -10,6 +11,7 @@ pub(crate) use context::*;
 pub(crate) use header_rule::*;
 use response_extension::should_include_grafbase_response_extension;
 pub(crate) use response_extension::*;
+pub(crate) use response_header_rule::*;
 pub(crate) use stream::*;
 
 use ::runtime::rate_limiting::RateLimitKey;
@@ -115,9 +117,18 @@ impl<R: Runtime> Engine<R> {
             ));
         }
 
+        if let Some(load_shedder) = self.load_shedder()
+            && load_shedder.should_shed()
+        {
+            return Err(errors::response::gateway_overloaded(
+                self.schema.config.error_code_mapping.clone(),
+                load_shedder.retry_after(),
+            ));
+        }
+
         let mut subgraph_default_headers = http::HeaderMap::new();
         apply_header_rules(
-            &headers,
+            &HeaderRuleContext::new(&headers, &extensions.token, client.as_ref(), None),
             self.schema.default_header_rules(),
             &mut subgraph_default_headers,
         );
@@ -134,6 +145,8 @@ impl<R: Runtime> Engine<R> {
             include_mcp_response_extension: ctx.include_mcp_response_extension,
             event_queue: extensions.event_queue,
             hooks_context: extensions.hooks_context,
+            response_headers: Default::default(),
+            subgraph_batches: Default::default(),
         };
 
         Ok(Arc::new(request_context))
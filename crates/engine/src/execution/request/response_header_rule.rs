@@ -0,0 +1,362 @@
+use std::{str::FromStr, sync::Mutex};
+
+use gateway_config::ResponseHeaderMerge;
+use http::{HeaderName, HeaderValue};
+use schema::{ResponseHeaderName, ResponseHeaderRule};
+
+/// Subgraph response headers matching the response header rules, collected during the execution
+/// and merged into the client response at the end. Entity cache hits don't go through a subgraph
+/// response and thus contribute no headers.
+#[derive(Default)]
+pub(crate) struct PropagatedResponseHeaders {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    // One entry per rule and subgraph response, in the order the responses were received.
+    values: Vec<PropagatedHeader>,
+    defaults: Vec<(HeaderName, HeaderValue)>,
+}
+
+struct PropagatedHeader {
+    name: HeaderName,
+    merge: ResponseHeaderMerge,
+    values: Vec<HeaderValue>,
+}
+
+impl PropagatedResponseHeaders {
+    pub(crate) fn record<'a>(
+        &self,
+        rules: impl IntoIterator<Item = &'a ResponseHeaderRule>,
+        subgraph_headers: &http::HeaderMap,
+    ) {
+        let mut inner = self.inner.lock().unwrap();
+
+        for rule in rules {
+            let rename = match rule.rename.as_deref().map(HeaderName::from_str) {
+                Some(Ok(rename)) => Some(rename),
+                Some(Err(_)) => continue,
+                None => None,
+            };
+
+            match &rule.name {
+                ResponseHeaderName::Name(name) => {
+                    let Ok(name) = HeaderName::from_str(name) else {
+                        continue;
+                    };
+                    let values = subgraph_headers.get_all(&name).iter().cloned().collect::<Vec<_>>();
+                    let name = rename.unwrap_or(name);
+
+                    if is_response_header_denied(&name) {
+                        continue;
+                    }
+
+                    if !values.is_empty() {
+                        inner.values.push(PropagatedHeader {
+                            name,
+                            merge: rule.merge,
+                            values,
+                        });
+                    } else if let Some(value) = rule.default.as_deref().and_then(|d| HeaderValue::from_str(d).ok()) {
+                        inner.defaults.push((name, value));
+                    }
+                }
+                ResponseHeaderName::Pattern(regex) => {
+                    for name in subgraph_headers.keys() {
+                        if is_response_header_denied(name) || !regex.is_match(name.as_str()) {
+                            continue;
+                        }
+
+                        inner.values.push(PropagatedHeader {
+                            name: rename.clone().unwrap_or_else(|| name.clone()),
+                            merge: rule.merge,
+                            values: subgraph_headers.get_all(name).iter().cloned().collect(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    pub(crate) fn apply(&self, response_headers: &mut http::HeaderMap) {
+        let inner = self.inner.lock().unwrap();
+
+        let mut names = Vec::<&HeaderName>::new();
+        for header in &inner.values {
+            if !names.contains(&&header.name) {
+                names.push(&header.name);
+            }
+        }
+
+        for name in names {
+            let mut headers = inner.values.iter().filter(|header| &header.name == name).peekable();
+            // The first rule matching a header decides how it's merged.
+            let Some(merge) = headers.peek().map(|header| header.merge) else {
+                continue;
+            };
+
+            let values: Vec<HeaderValue> = match merge {
+                ResponseHeaderMerge::First => headers.next().map(|header| header.values.clone()).unwrap_or_default(),
+                ResponseHeaderMerge::Last => headers.last().map(|header| header.values.clone()).unwrap_or_default(),
+                ResponseHeaderMerge::Append => headers.flat_map(|header| header.values.iter().cloned()).collect(),
+                ResponseHeaderMerge::MostRestrictive => {
+                    most_restrictive_cache_control(headers.flat_map(|header| header.values.iter()))
+                        .into_iter()
+                        .collect()
+                }
+            };
+
+            response_headers.remove(name);
+            for value in values {
+                response_headers.append(name.clone(), value);
+            }
+        }
+
+        for (name, value) in &inner.defaults {
+            if !response_headers.contains_key(name) {
+                response_headers.insert(name.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Merges Cache-Control values into the most restrictive policy: no-store wins over everything,
+/// no-cache, private and must-revalidate are kept if any value has them, public only if all values
+/// have it, and the smallest max-age and s-maxage are used.
+fn most_restrictive_cache_control<'a>(values: impl Iterator<Item = &'a HeaderValue>) -> Option<HeaderValue> {
+    let mut no_store = false;
+    let mut no_cache = false;
+    let mut private = false;
+    let mut public = true;
+    let mut must_revalidate = false;
+    let mut max_age: Option<u64> = None;
+    let mut s_maxage: Option<u64> = None;
+    let mut any = false;
+
+    for value in values {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+
+        any = true;
+        let mut has_public = false;
+
+        for directive in value.split(',') {
+            let (key, arg) = match directive.split_once('=') {
+                Some((key, arg)) => (key.trim(), Some(arg.trim().trim_matches('"'))),
+                None => (directive.trim(), None),
+            };
+
+            match key.to_ascii_lowercase().as_str() {
+                "no-store" => no_store = true,
+                "no-cache" => no_cache = true,
+                "private" => private = true,
+                "public" => has_public = true,
+                "must-revalidate" => must_revalidate = true,
+                "max-age" => {
+                    if let Some(seconds) = arg.and_then(|arg| arg.parse::<u64>().ok()) {
+                        max_age = Some(max_age.map_or(seconds, |current| current.min(seconds)));
+                    }
+                }
+                "s-maxage" => {
+                    if let Some(seconds) = arg.and_then(|arg| arg.parse::<u64>().ok()) {
+                        s_maxage = Some(s_maxage.map_or(seconds, |current| current.min(seconds)));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        public &= has_public;
+    }
+
+    if !any {
+        return None;
+    }
+
+    if no_store {
+        return Some(HeaderValue::from_static("no-store"));
+    }
+
+    let mut directives = Vec::new();
+
+    if no_cache {
+        directives.push("no-cache".to_string());
+    }
+
+    if private {
+        directives.push("private".to_string());
+    } else if public {
+        directives.push("public".to_string());
+    }
+
+    if must_revalidate {
+        directives.push("must-revalidate".to_string());
+    }
+
+    if let Some(max_age) = max_age {
+        directives.push(format!("max-age={max_age}"));
+    }
+
+    if let Some(s_maxage) = s_maxage.filter(|_| !private) {
+        directives.push(format!("s-maxage={s_maxage}"));
+    }
+
+    if directives.is_empty() {
+        return None;
+    }
+
+    HeaderValue::from_str(&directives.join(", ")).ok()
+}
+
+fn is_response_header_denied(name: &HeaderName) -> bool {
+    matches!(
+        name.as_str(),
+        "content-length"
+            | "content-type"
+            | "content-encoding"
+            // hop-by-hop headers
+            | "connection"
+            | "keep-alive"
+            | "proxy-authenticate"
+            | "proxy-authorization"
+            | "te"
+            | "trailer"
+            | "transfer-encoding"
+            | "upgrade"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merged(values: &[&'static str]) -> Option<HeaderValue> {
+        let values = values
+            .iter()
+            .map(|value| HeaderValue::from_static(value))
+            .collect::<Vec<_>>();
+        most_restrictive_cache_control(values.iter())
+    }
+
+    fn rule(name: &str, merge: ResponseHeaderMerge) -> ResponseHeaderRule {
+        ResponseHeaderRule {
+            name: ResponseHeaderName::Name(name.to_string()),
+            default: None,
+            rename: None,
+            merge,
+        }
+    }
+
+    fn subgraph_headers(headers: &[(&'static str, &'static str)]) -> http::HeaderMap {
+        let mut map = http::HeaderMap::new();
+        for (name, value) in headers {
+            map.append(*name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    fn values(headers: &http::HeaderMap, name: &str) -> Vec<String> {
+        headers
+            .get_all(name)
+            .iter()
+            .map(|value| value.to_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn most_restrictive_no_store_wins() {
+        assert_eq!(
+            merged(&["public, max-age=60", "no-store", "private, max-age=10"]),
+            Some(HeaderValue::from_static("no-store"))
+        );
+    }
+
+    #[test]
+    fn most_restrictive_smallest_max_age() {
+        assert_eq!(
+            merged(&["public, max-age=60, s-maxage=120", "public, max-age=30, s-maxage=300"]),
+            Some(HeaderValue::from_static("public, max-age=30, s-maxage=120"))
+        );
+    }
+
+    #[test]
+    fn most_restrictive_private_wins() {
+        assert_eq!(
+            merged(&["public, max-age=60, s-maxage=120", "private, max-age=90"]),
+            Some(HeaderValue::from_static("private, max-age=60"))
+        );
+        assert_eq!(
+            merged(&["public, max-age=60", "max-age=90, must-revalidate"]),
+            Some(HeaderValue::from_static("must-revalidate, max-age=60"))
+        );
+    }
+
+    #[test]
+    fn most_restrictive_without_values() {
+        assert_eq!(merged(&[]), None);
+    }
+
+    #[test]
+    fn merge_strategies() {
+        let rules = [
+            rule("x-first", ResponseHeaderMerge::First),
+            rule("x-last", ResponseHeaderMerge::Last),
+            rule("set-cookie", ResponseHeaderMerge::Append),
+            rule("content-type", ResponseHeaderMerge::Append),
+        ];
+
+        let propagated = PropagatedResponseHeaders::default();
+        propagated.record(
+            &rules,
+            &subgraph_headers(&[
+                ("x-first", "1"),
+                ("x-last", "1"),
+                ("set-cookie", "a=1"),
+                ("content-type", "text/plain"),
+            ]),
+        );
+        propagated.record(
+            &rules,
+            &subgraph_headers(&[
+                ("x-first", "2"),
+                ("x-last", "2"),
+                ("set-cookie", "b=2"),
+                ("set-cookie", "c=3"),
+            ]),
+        );
+
+        let mut response_headers = subgraph_headers(&[("content-type", "application/json")]);
+        propagated.apply(&mut response_headers);
+
+        assert_eq!(values(&response_headers, "x-first"), ["1"]);
+        assert_eq!(values(&response_headers, "x-last"), ["2"]);
+        assert_eq!(values(&response_headers, "set-cookie"), ["a=1", "b=2", "c=3"]);
+        assert_eq!(values(&response_headers, "content-type"), ["application/json"]);
+    }
+
+    #[test]
+    fn rename_and_default() {
+        let rules = [
+            ResponseHeaderRule {
+                rename: Some("x-upstream-version".into()),
+                ..rule("x-version", ResponseHeaderMerge::First)
+            },
+            ResponseHeaderRule {
+                default: Some("none".into()),
+                ..rule("x-missing", ResponseHeaderMerge::First)
+            },
+        ];
+
+        let propagated = PropagatedResponseHeaders::default();
+        propagated.record(&rules, &subgraph_headers(&[("x-version", "3")]));
+
+        let mut response_headers = http::HeaderMap::new();
+        propagated.apply(&mut response_headers);
+
+        assert_eq!(values(&response_headers, "x-upstream-version"), ["3"]);
+        assert!(response_headers.get("x-version").is_none());
+        assert_eq!(values(&response_headers, "x-missing"), ["none"]);
+    }
+}
@@ -54,6 +54,10 @@ impl<R: Runtime> Engine<R> {
                 );
             }
 
+            if let Some(load_shedder) = self.load_shedder() {
+                load_shedder.record_latency(start.elapsed());
+            }
+
             // After recording all operation metadata
             tracing::debug!("Executed operation");
 
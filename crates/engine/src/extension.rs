@@ -7,6 +7,7 @@ use runtime::extension::Token;
 use crate::{
     Runtime,
     execution::{ExecutionContext, RequestContext},
+    graphql_over_http::{CompleteResponseFormat, ResponseFormat},
     prepare::PreparedOperation,
 };
 
@@ -20,6 +21,26 @@ impl From<&Arc<RequestContext>> for EngineRequestContext {
 }
 
 impl EngineRequestContext {
+    /// Context not tied to any HTTP request, used to call extensions outside of the engine such
+    /// as in the extension test harness.
+    pub fn detached(headers: http::HeaderMap, token: Token, hooks_context: Arc<[u8]>) -> Self {
+        Self(Arc::new(RequestContext {
+            can_mutate: true,
+            headers,
+            websocket_init_payload: None,
+            response_format: ResponseFormat::Complete(CompleteResponseFormat::Json),
+            client: None,
+            token,
+            subgraph_default_headers: Default::default(),
+            include_grafbase_response_extension: false,
+            include_mcp_response_extension: false,
+            event_queue: Default::default(),
+            hooks_context,
+            response_headers: Default::default(),
+            subgraph_batches: Default::default(),
+        }))
+    }
+
     pub fn event_queue(&self) -> &Arc<EventQueue> {
         &self.0.event_queue
     }
@@ -36,19 +57,29 @@ impl EngineRequestContext {
 #[derive(Clone)]
 pub struct EngineOperationContext {
     request: Arc<RequestContext>,
-    operation: Arc<PreparedOperation>,
+    // None for a detached context, which has no prepared operation.
+    operation: Option<Arc<PreparedOperation>>,
 }
 
 impl<R: Runtime> From<&ExecutionContext<'_, R>> for EngineOperationContext {
     fn from(ctx: &ExecutionContext<'_, R>) -> Self {
         Self {
             request: ctx.request_context.clone(),
-            operation: ctx.operation.clone(),
+            operation: Some(ctx.operation.clone()),
         }
     }
 }
 
 impl EngineOperationContext {
+    /// Context not tied to any operation, used to call extensions outside of the engine such as
+    /// in the extension test harness. It has no authorization context nor state.
+    pub fn detached(request: EngineRequestContext) -> Self {
+        Self {
+            request: request.0,
+            operation: None,
+        }
+    }
+
     pub fn event_queue(&self) -> &Arc<EventQueue> {
         &self.request.event_queue
     }
@@ -62,10 +93,30 @@ impl EngineOperationContext {
     }
 
     pub fn authorization_context(&self) -> &[(ExtensionId, Arc<[u8]>)] {
-        &self.operation.plan.query_modifications.extension.authorization_context
+        self.operation
+            .as_ref()
+            .map(|operation| {
+                operation
+                    .plan
+                    .query_modifications
+                    .extension
+                    .authorization_context
+                    .as_slice()
+            })
+            .unwrap_or_default()
     }
 
     pub fn authorization_state(&self) -> &[(ExtensionId, Vec<u8>)] {
-        &self.operation.plan.query_modifications.extension.authorization_state
+        self.operation
+            .as_ref()
+            .map(|operation| {
+                operation
+                    .plan
+                    .query_modifications
+                    .extension
+                    .authorization_state
+                    .as_slice()
+            })
+            .unwrap_or_default()
     }
 }
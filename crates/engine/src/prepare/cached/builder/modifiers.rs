@@ -60,6 +60,10 @@ impl Solver<'_> {
             };
             let field_definition = self.output.query_plan[field_id].definition_id.walk(self.schema);
             for directive in field_definition.directives() {
+                if let Some(rule) = native_authorization_rule(directive) {
+                    accumulator.insert(Rule::Query(rule), Some(field_id));
+                    continue;
+                }
                 let TypeSystemDirective::Extension(directive) = directive else {
                     continue;
                 };
@@ -143,6 +147,10 @@ impl Solver<'_> {
             {
                 let definition_id = TypeDefinitionId::from(field_definition.parent_entity_id);
                 for directive in field_definition.parent_entity().directives() {
+                    if let Some(rule) = native_authorization_rule(directive) {
+                        accumulator.insert(Rule::Query(rule), Some(field_id));
+                        continue;
+                    }
                     let TypeSystemDirective::Extension(directive) = directive else {
                         continue;
                     };
@@ -188,6 +196,10 @@ impl Solver<'_> {
 
             let output_definition = field_definition.ty().definition();
             for directive in output_definition.directives() {
+                if let Some(rule) = native_authorization_rule(directive) {
+                    accumulator.insert(Rule::Query(rule), Some(field_id));
+                    continue;
+                }
                 let TypeSystemDirective::Extension(directive) = directive else {
                     continue;
                 };
@@ -233,6 +245,10 @@ impl Solver<'_> {
         }
 
         for directive in self.output.operation.root_object_id.walk(self.schema).directives() {
+            if let Some(rule) = native_authorization_rule(directive) {
+                accumulator.insert(Rule::Query(rule), None);
+                continue;
+            }
             let TypeSystemDirective::Extension(directive) = directive else {
                 continue;
             };
@@ -278,6 +294,16 @@ impl Solver<'_> {
     }
 }
 
+/// @authenticated, @requiresScopes and @policy are enforced by the engine itself rather than by an
+/// extension.
+fn native_authorization_rule(directive: TypeSystemDirective<'_>) -> Option<QueryModifierRule> {
+    match directive {
+        TypeSystemDirective::RequiresScopes(directive) => Some(QueryModifierRule::RequiresScopes(directive.id)),
+        TypeSystemDirective::Policy(directive) => Some(QueryModifierRule::Policy(directive.id)),
+        _ => None,
+    }
+}
+
 enum Rule {
     Query(QueryModifierRule),
     Resp(ResponseModifierRule),
@@ -1,6 +1,7 @@
 use id_newtypes::IdRange;
 use schema::{
-    DirectiveSiteId, EntityDefinitionId, ExtensionDirectiveId, FieldDefinitionId, SubgraphId, TypeDefinitionId,
+    DirectiveSiteId, EntityDefinitionId, ExtensionDirectiveId, FieldDefinitionId, PolicyDirectiveId,
+    RequiresScopesDirectiveId, SubgraphId, TypeDefinitionId,
 };
 
 use super::PartitionFieldArgumentId;
@@ -11,6 +12,9 @@ pub(crate) enum QueryModifierRule {
         // sorted
         directives: Vec<operation::ExecutableDirectiveId>,
     },
+    // @authenticated & @requiresScopes
+    RequiresScopes(RequiresScopesDirectiveId),
+    Policy(PolicyDirectiveId),
     Extension {
         directive_id: ExtensionDirectiveId,
         target: QueryModifierTarget,
@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+
+use operation::{
+    DataField, Field, Operation, OperationContext, QueryInputValueRecord, Selection, SelectionSet,
+    VariableInputValueRecord, VariableValueRecord, Variables,
+};
+use runtime::field_usage::OperationFieldUsage;
+use schema::{EntityDefinitionId, EnumValueId, InputValueDefinitionId, InputValueParentDefinition, Schema};
+use walker::Walk;
+
+/// Computes the schema coordinates used by a bound operation. Argument values are resolved
+/// against the request variables, so enum values and input object fields provided through
+/// variables are counted like inline ones.
+pub(super) fn compute(schema: &Schema, operation: &Operation, variables: &Variables) -> OperationFieldUsage {
+    let ctx = OperationContext { schema, operation };
+    let mut collector = UsageCollector {
+        schema,
+        operation,
+        variables,
+        usage: OperationFieldUsage::default(),
+    };
+
+    collector.selection_set(ctx.root_object().name(), ctx.root_selection_set());
+    collector.usage
+}
+
+struct UsageCollector<'a> {
+    schema: &'a Schema,
+    operation: &'a Operation,
+    variables: &'a Variables,
+    usage: OperationFieldUsage,
+}
+
+impl<'a> UsageCollector<'a> {
+    fn selection_set(&mut self, parent_type_name: &str, selection_set: SelectionSet<'a>) {
+        for selection in selection_set {
+            match selection {
+                Selection::Field(Field::Data(field)) => self.data_field(field),
+                Selection::Field(Field::Typename(_)) => {}
+                Selection::FragmentSpread(spread) => {
+                    let fragment = spread.fragment();
+                    let type_name = fragment.type_condition().name();
+
+                    self.type_condition(parent_type_name, type_name);
+                    self.selection_set(type_name, fragment.selection_set());
+                }
+                Selection::InlineFragment(fragment) => match fragment.type_condition() {
+                    Some(type_condition) => {
+                        self.type_condition(parent_type_name, type_condition.name());
+                        self.selection_set(type_condition.name(), fragment.selection_set());
+                    }
+                    None => self.selection_set(parent_type_name, fragment.selection_set()),
+                },
+            }
+        }
+    }
+
+    fn type_condition(&mut self, parent_type_name: &str, type_name: &str) {
+        if parent_type_name != type_name {
+            increment(
+                &mut self.usage.type_conditions,
+                format!("{parent_type_name}.{type_name}"),
+            );
+        }
+    }
+
+    fn data_field(&mut self, field: DataField<'a>) {
+        let definition = field.definition();
+        let introspection = &self.schema.subgraphs.introspection;
+
+        // Introspection fields are not part of the API schema the usage is checked against.
+        if introspection.meta_fields.contains(&definition.id) {
+            return;
+        }
+
+        if let EntityDefinitionId::Object(object_id) = definition.parent_entity_id
+            && introspection.meta_objects.contains(&object_id)
+        {
+            return;
+        }
+
+        let coordinate = format!("{}.{}", definition.parent_entity().name(), definition.name());
+
+        for argument in field.sorted_arguments() {
+            let argument_definition = argument.definition();
+            let argument_coordinate = format!("{coordinate}.{}", argument_definition.name());
+            let operation = self.operation;
+
+            match &operation.query_input_values[argument.value_id] {
+                // Added by the binder for arguments that were not provided.
+                QueryInputValueRecord::DefaultValue(_) => {
+                    if argument_definition.ty().is_required() {
+                        increment(&mut self.usage.arguments_with_defaults_left_out, argument_coordinate);
+                    }
+                }
+                value => {
+                    increment(&mut self.usage.arguments, argument_coordinate);
+                    self.query_value(value);
+                }
+            }
+        }
+
+        increment(&mut self.usage.fields, coordinate);
+        self.selection_set(definition.ty().definition().name(), field.selection_set());
+    }
+
+    fn query_value(&mut self, value: &'a QueryInputValueRecord) {
+        let (operation, variables) = (self.operation, self.variables);
+
+        match value {
+            QueryInputValueRecord::EnumValue(id) => self.enum_value(*id),
+            QueryInputValueRecord::InputObject(ids) => {
+                for id in *ids {
+                    let (definition_id, value) = &operation.query_input_values[id];
+
+                    if !matches!(value, QueryInputValueRecord::DefaultValue(_)) {
+                        self.input_field(*definition_id);
+                        self.query_value(value);
+                    }
+                }
+            }
+            QueryInputValueRecord::List(ids) => {
+                for id in *ids {
+                    self.query_value(&operation.query_input_values[id]);
+                }
+            }
+            QueryInputValueRecord::Variable(id) => match variables[*id] {
+                VariableValueRecord::Undefined => {}
+                VariableValueRecord::Provided(id) => self.variable_value(&variables[id]),
+                VariableValueRecord::DefaultValue(id) => self.query_value(&operation.query_input_values[id]),
+            },
+            _ => {}
+        }
+    }
+
+    fn variable_value(&mut self, value: &'a VariableInputValueRecord) {
+        let variables = self.variables;
+
+        match value {
+            VariableInputValueRecord::EnumValue(id) => self.enum_value(*id),
+            VariableInputValueRecord::InputObject(ids) => {
+                for id in *ids {
+                    let (definition_id, value) = &variables[id];
+
+                    if !matches!(value, VariableInputValueRecord::DefaultValue(_)) {
+                        self.input_field(*definition_id);
+                        self.variable_value(value);
+                    }
+                }
+            }
+            VariableInputValueRecord::List(ids) => {
+                for id in *ids {
+                    self.variable_value(&variables[id]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn enum_value(&mut self, id: EnumValueId) {
+        let value = id.walk(self.schema);
+
+        increment(
+            &mut self.usage.enum_values,
+            format!("{}.{}", value.parent_enum().name(), value.name()),
+        );
+    }
+
+    fn input_field(&mut self, id: InputValueDefinitionId) {
+        let definition = id.walk(self.schema);
+
+        if let InputValueParentDefinition::InputObject(input_object) = definition.parent() {
+            increment(
+                &mut self.usage.fields,
+                format!("{}.{}", input_object.name(), definition.name()),
+            );
+        }
+    }
+}
+
+fn increment(counts: &mut BTreeMap<String, u64>, coordinate: String) {
+    *counts.entry(coordinate).or_default() += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use operation::RawVariables;
+
+    use super::*;
+
+    const SDL: &str = r#"
+        type Query {
+            pets(filter: PetFilter, first: Int! = 10): [Pet!]!
+        }
+
+        input PetFilter {
+            kind: PetKind
+            names: [String!]
+        }
+
+        enum PetKind {
+            CAT
+            DOG
+        }
+
+        interface Pet {
+            name: String!
+        }
+
+        type Cat implements Pet {
+            name: String!
+            lives: Int!
+        }
+
+        type Dog implements Pet {
+            name: String!
+        }
+    "#;
+
+    async fn usage(query: &str, variables: serde_json::Value) -> OperationFieldUsage {
+        let schema = Schema::from_sdl_or_panic(SDL).await;
+        let operation = Operation::parse(&schema, None, query).unwrap();
+        let variables = Variables::bind(&schema, &operation, RawVariables::from_value(variables)).unwrap();
+
+        compute(&schema, &operation, &variables)
+    }
+
+    fn coordinates(counts: BTreeMap<String, u64>) -> Vec<(String, u64)> {
+        counts.into_iter().collect()
+    }
+
+    #[tokio::test]
+    async fn counts_values_provided_through_variables() {
+        let usage = usage(
+            "query($filter: PetFilter) { pets(filter: $filter) { name ... on Cat { lives } } }",
+            serde_json::json!({ "filter": { "kind": "DOG" } }),
+        )
+        .await;
+
+        assert_eq!(
+            coordinates(usage.fields),
+            [
+                ("Cat.lives".to_string(), 1),
+                ("Pet.name".to_string(), 1),
+                ("PetFilter.kind".to_string(), 1),
+                ("Query.pets".to_string(), 1)
+            ]
+        );
+        assert_eq!(coordinates(usage.arguments), [("Query.pets.filter".to_string(), 1)]);
+        assert_eq!(coordinates(usage.enum_values), [("PetKind.DOG".to_string(), 1)]);
+        assert_eq!(
+            coordinates(usage.arguments_with_defaults_left_out),
+            [("Query.pets.first".to_string(), 1)]
+        );
+        assert_eq!(coordinates(usage.type_conditions), [("Pet.Cat".to_string(), 1)]);
+    }
+
+    #[tokio::test]
+    async fn counts_inline_values() {
+        let usage = usage(
+            "{ pets(filter: { kind: CAT, names: [\"Tom\"] }, first: 1) { name } }",
+            serde_json::json!({}),
+        )
+        .await;
+
+        assert_eq!(
+            coordinates(usage.fields),
+            [
+                ("Pet.name".to_string(), 1),
+                ("PetFilter.kind".to_string(), 1),
+                ("PetFilter.names".to_string(), 1),
+                ("Query.pets".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            coordinates(usage.arguments),
+            [
+                ("Query.pets.filter".to_string(), 1),
+                ("Query.pets.first".to_string(), 1)
+            ]
+        );
+        assert_eq!(coordinates(usage.enum_values), [("PetKind.CAT".to_string(), 1)]);
+        assert!(usage.arguments_with_defaults_left_out.is_empty());
+    }
+}
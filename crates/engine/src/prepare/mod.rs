@@ -1,5 +1,6 @@
 pub(crate) mod cached;
 mod context;
+mod field_usage;
 mod operation_plan;
 mod trusted_documents;
 mod with_cache;
@@ -44,6 +45,15 @@ impl<R: Runtime> PrepareContext<'_, R> {
                 self.metrics()
                     .record_successful_preparation_duration(operation.attributes(), duration);
 
+                let collector = self.runtime().field_usage_collector();
+                if collector.is_enabled() {
+                    collector.record(field_usage::compute(
+                        self.schema(),
+                        &operation.cached.operation,
+                        &operation.variables,
+                    ));
+                }
+
                 Ok(operation)
             }
             Err(response) => {
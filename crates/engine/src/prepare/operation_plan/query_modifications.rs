@@ -5,14 +5,15 @@ use futures::future::FutureExt;
 use id_newtypes::{BitSet, IdRange, IdToMany};
 use operation::{InputValueContext, Variables};
 use runtime::extension::{
-    AuthorizationDecisions, AuthorizationExtension as _, AuthorizeQuery, QueryAuthorizationDecisions, QueryElement,
+    AuthorizationDecisions, AuthorizationExtension as _, AuthorizeQuery, EngineHooksExtension as _,
+    QueryAuthorizationDecisions, QueryElement, Token,
 };
 use schema::DirectiveSiteId;
 use serde::Deserialize;
 use walker::Walk;
 
 use crate::{
-    EngineRequestContext, Runtime,
+    EngineRequestContext, ErrorCode, Runtime,
     execution::find_matching_denied_header,
     prepare::{
         CachedOperation, CachedOperationContext, ConcreteShapeId, DataFieldId, Derive, FieldShapeId, GraphqlError,
@@ -97,9 +98,9 @@ where
     'ctx: 'op,
 {
     pub(super) async fn build(mut self) -> PlanResult<QueryModifications> {
-        // Hooks authorization will go away and @authenticiated &
-        // @requiresScopes will be an extension. So we're left with skip/include in the host part
-        // which don't need I/O. So no need to parallelize that today.
+        // Native modifiers are skip/include and @authenticated/@requiresScopes which don't need
+        // any I/O, and @policy which is evaluated in a single hook call. So no need to parallelize
+        // them with extensions today.
         let modifiers = &self.operation_ctx.cached.query_plan.query_modifiers;
         self.handle_native_modifiers(&modifiers[modifiers.native_ids]).await?;
 
@@ -238,32 +239,126 @@ where
     }
 
     async fn handle_native_modifiers(&mut self, query_modifiers: &'op [QueryModifierRecord]) -> PlanResult<()> {
+        let schema = self.operation_ctx.schema;
+        let request_context = self.ctx.request_context;
+        let token = &request_context.token;
+        let mut scopes = None;
+        let mut unauthenticated_error_id = None;
+        let mut insufficient_scopes_error_id = None;
+        let mut policy_modifiers = Vec::new();
+
         for modifier in query_modifiers {
-            if let QueryModifierRule::Executable { directives } = &modifier.rule {
-                // GraphQL spec:
-                //   Stated conversely, the field or fragment must not be queried if either the @skip condition is true or the @include condition is false.
-                let is_skipped = directives.iter().any(|directive| match directive {
-                    operation::ExecutableDirectiveId::Include(directive) => {
-                        !bool::deserialize(directive.condition.walk(self.input_value_ctx))
-                            .expect("at this point we've already checked the argument type")
+            match &modifier.rule {
+                QueryModifierRule::Executable { directives } => {
+                    // GraphQL spec:
+                    //   Stated conversely, the field or fragment must not be queried if either the @skip condition is true or the @include condition is false.
+                    let is_skipped = directives.iter().any(|directive| match directive {
+                        operation::ExecutableDirectiveId::Include(directive) => {
+                            !bool::deserialize(directive.condition.walk(self.input_value_ctx))
+                                .expect("at this point we've already checked the argument type")
+                        }
+                        operation::ExecutableDirectiveId::Skip(directive) => {
+                            bool::deserialize(directive.condition.walk(self.input_value_ctx))
+                                .expect("at this point we've already checked the argument type")
+                        }
+                    });
+
+                    if is_skipped {
+                        self.skip_field(modifier)
                     }
-                    operation::ExecutableDirectiveId::Skip(directive) => {
-                        bool::deserialize(directive.condition.walk(self.input_value_ctx))
-                            .expect("at this point we've already checked the argument type")
+                }
+                QueryModifierRule::RequiresScopes(id) => {
+                    if token.as_bytes().is_none() {
+                        let error_id = *unauthenticated_error_id
+                            .get_or_insert_with(|| self.push_error(GraphqlError::unauthenticated()));
+                        self.deny_field(modifier, error_id);
+                        continue;
                     }
-                });
 
-                if is_skipped {
-                    self.skip_field(modifier)
+                    let scopes = scopes.get_or_insert_with(|| token_scopes(token));
+                    if !schema[*id]
+                        .scopes
+                        .is_granted(|scope| scopes.iter().any(|granted| granted == &schema[scope]))
+                    {
+                        let error_id = *insufficient_scopes_error_id.get_or_insert_with(|| {
+                            self.push_error(GraphqlError::new(
+                                "Not authorized: insufficient scopes",
+                                ErrorCode::Unauthorized,
+                            ))
+                        });
+                        self.deny_field(modifier, error_id);
+                    }
                 }
-            } else {
-                unreachable!("Not a native modifier")
+                QueryModifierRule::Policy(_) => policy_modifiers.push(modifier),
+                QueryModifierRule::Extension { .. } => unreachable!("Not a native modifier"),
             }
         }
 
+        if !policy_modifiers.is_empty() {
+            self.handle_policies(policy_modifiers).await;
+        }
+
         Ok(())
     }
 
+    /// Evaluates all the policies of the operation at once with the hooks and denies the fields
+    /// whose policies aren't granted.
+    async fn handle_policies(&mut self, modifiers: Vec<&'op QueryModifierRecord>) {
+        let schema = self.operation_ctx.schema;
+        let policy_groups = |modifier: &QueryModifierRecord| match modifier.rule {
+            QueryModifierRule::Policy(id) => &schema[id].policies,
+            _ => unreachable!("Not a policy modifier"),
+        };
+
+        let mut policy_ids = modifiers
+            .iter()
+            .flat_map(|modifier| policy_groups(modifier).ids())
+            .collect::<Vec<_>>();
+        policy_ids.sort_unstable();
+        policy_ids.dedup();
+
+        let policies = policy_ids.iter().map(|id| schema[*id].as_str()).collect::<Vec<_>>();
+        let result = self
+            .ctx
+            .extensions()
+            .authorize_policies(EngineRequestContext::from(self.ctx.request_context), &policies)
+            .boxed()
+            .await
+            .and_then(|granted| {
+                if granted.len() == policies.len() {
+                    Ok(granted)
+                } else {
+                    tracing::error!(
+                        "Policy hook returned {} decisions for {} policies",
+                        granted.len(),
+                        policies.len()
+                    );
+                    Err(GraphqlError::internal_extension_error())
+                }
+            });
+
+        let granted = match result {
+            Ok(granted) => granted,
+            Err(error) => {
+                let error_id = self.push_error(error);
+                for modifier in modifiers {
+                    self.deny_field(modifier, error_id);
+                }
+                return;
+            }
+        };
+
+        let mut error_id = None;
+        for modifier in modifiers {
+            let is_granted = policy_groups(modifier)
+                .is_granted(|policy| policy_ids.binary_search(&policy).is_ok_and(|ix| granted[ix]));
+            if !is_granted {
+                let error_id = *error_id.get_or_insert_with(|| self.push_error(GraphqlError::unauthorized()));
+                self.deny_field(modifier, error_id);
+            }
+        }
+    }
+
     fn finalize(self) -> QueryModifications {
         let Self {
             mut modifications,
@@ -410,3 +505,29 @@ where
         id
     }
 }
+
+/// Scopes granted by the authentication token, read from the `scope` claim of its JSON claims. The
+/// claim is either a space separated string, as defined by RFC 8693, or a list of strings.
+fn token_scopes(token: &Token) -> Vec<String> {
+    #[derive(serde::Deserialize)]
+    struct Claims {
+        scope: Option<Scopes>,
+    }
+
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum Scopes {
+        Joined(String),
+        List(Vec<String>),
+    }
+
+    let claims = token
+        .as_bytes()
+        .and_then(|bytes| serde_json::from_slice::<Claims>(bytes).ok());
+
+    match claims.and_then(|claims| claims.scope) {
+        Some(Scopes::Joined(scopes)) => scopes.split_whitespace().map(str::to_owned).collect(),
+        Some(Scopes::List(scopes)) => scopes,
+        None => Vec::new(),
+    }
+}
@@ -1,22 +1,53 @@
 use bytes::Bytes;
 use futures::future::join_all;
-use grafbase_telemetry::graphql::GraphqlResponseStatus;
+use grafbase_telemetry::{graphql::GraphqlResponseStatus, span::subgraph::SubgraphHttpRequestSpan};
 use headers::HeaderMapExt;
 use http::HeaderMap;
-use itertools::Itertools;
-use runtime::entity_cache::EntityCache;
+use runtime::{
+    entity_cache::EntityCache,
+    extension::{EngineHooksExtension as _, ReqwestParts},
+    fetch::{FetchRequest, Fetcher as _},
+};
+use serde::de::IgnoredAny;
 use serde_json::value::RawValue;
-use std::time::Duration;
+use std::{
+    borrow::Cow,
+    time::{Duration, SystemTime},
+};
+use tracing::Instrument as _;
 
-use crate::{Runtime, response::ParentObjectId};
+use crate::{
+    EngineOperationContext, Runtime,
+    engine::EntityCacheRevalidation,
+    resolver::graphql::request::{inject_trace_context, insert_graphql_request_headers},
+    response::ParentObjectId,
+};
 
 use super::{EntityToFetch, SubgraphContext};
 
+/// Lifetimes of a cache entry, derived from the `Cache-Control` header of the subgraph response.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct CacheTtl {
+    /// How long the entry is fresh.
+    pub max_age: Duration,
+    /// How long after expiring the entry may still be served while it's refreshed in the background.
+    pub stale_while_revalidate: Duration,
+    /// How long after expiring the entry may still be served if the subgraph request fails.
+    pub stale_if_error: Duration,
+}
+
+impl CacheTtl {
+    /// How long the entry must be kept by the cache.
+    fn storage_ttl(&self) -> Duration {
+        self.max_age + self.stale_while_revalidate.max(self.stale_if_error)
+    }
+}
+
 pub(super) fn calculate_cache_ttl(
     status: GraphqlResponseStatus,
     headers: &HeaderMap,
     subgraph_default_ttl: Option<Duration>,
-) -> Option<Duration> {
+) -> Option<CacheTtl> {
     let Some(subgraph_default_ttl) = subgraph_default_ttl else {
         // The subgraph_default_ttl is set to None if entity caching is disabled for a subgraph, so
         // we always return None here in that case.
@@ -28,7 +59,11 @@ pub(super) fn calculate_cache_ttl(
     }
 
     let Some(cache_control) = headers.typed_get::<headers::CacheControl>() else {
-        return Some(subgraph_default_ttl);
+        return Some(CacheTtl {
+            max_age: subgraph_default_ttl,
+            stale_while_revalidate: Duration::ZERO,
+            stale_if_error: Duration::ZERO,
+        });
     };
 
     if cache_control.private() || cache_control.no_store() {
@@ -37,19 +72,108 @@ pub(super) fn calculate_cache_ttl(
 
     let age = headers.typed_get::<headers::Age>().map(|age| age.as_secs());
 
-    let cache_ttl = cache_control
+    let max_age = cache_control
         .max_age()
-        .map(|max_age| max_age - Duration::from_secs(age.unwrap_or_default()))
+        .map(|max_age| max_age.saturating_sub(Duration::from_secs(age.unwrap_or_default())))
         .unwrap_or(subgraph_default_ttl);
 
-    Some(cache_ttl)
+    Some(CacheTtl {
+        max_age,
+        stale_while_revalidate: stale_directive(headers, "stale-while-revalidate"),
+        stale_if_error: stale_directive(headers, "stale-if-error"),
+    })
+}
+
+/// The typed `Cache-Control` header doesn't support the RFC 5861 directives.
+fn stale_directive(headers: &HeaderMap, name: &str) -> Duration {
+    headers
+        .get_all(http::header::CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|directive| directive.trim().split_once('='))
+        .find(|(directive, _)| directive.trim().eq_ignore_ascii_case(name))
+        .and_then(|(_, seconds)| seconds.trim().trim_matches('"').parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or_default()
+}
+
+/// Cached values are prefixed with three timestamps, in milliseconds since the UNIX epoch: until
+/// when the entry is fresh, may be served while revalidating and may be served on errors.
+const ENTRY_HEADER_LEN: usize = 3 * size_of::<u64>();
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn encode_entry(data: &[u8], ttl: CacheTtl) -> Vec<u8> {
+    let fresh_until = now_millis().saturating_add(ttl.max_age.as_millis() as u64);
+
+    let mut entry = Vec::with_capacity(ENTRY_HEADER_LEN + data.len());
+    entry.extend_from_slice(&fresh_until.to_le_bytes());
+    for stale in [ttl.stale_while_revalidate, ttl.stale_if_error] {
+        entry.extend_from_slice(&fresh_until.saturating_add(stale.as_millis() as u64).to_le_bytes());
+    }
+    entry.extend_from_slice(data);
+    entry
+}
+
+fn decode_entry(mut entry: Bytes) -> CacheLookup {
+    if entry.len() < ENTRY_HEADER_LEN {
+        return CacheLookup::Miss;
+    }
+
+    let header = entry.split_to(ENTRY_HEADER_LEN);
+    let timestamp = |i: usize| u64::from_le_bytes(header[i * 8..(i + 1) * 8].try_into().unwrap());
+    let now = now_millis();
+
+    if now < timestamp(0) {
+        CacheLookup::Fresh(entry)
+    } else if now < timestamp(1) {
+        CacheLookup::Revalidate(entry)
+    } else if now < timestamp(2) {
+        CacheLookup::StaleIfError(entry)
+    } else {
+        CacheLookup::Miss
+    }
+}
+
+pub(super) enum CacheLookup {
+    Fresh(Bytes),
+    /// Expired entry which may be served while it's refreshed in the background.
+    Revalidate(Bytes),
+    /// Expired entry which may only be served if the subgraph request fails.
+    StaleIfError(Bytes),
+    Miss,
+}
+
+async fn get_entry(entity_cache: &dyn EntityCache, key: &str) -> CacheLookup {
+    entity_cache
+        .get(key)
+        .await
+        .inspect_err(|err| tracing::warn!("Failed to read the cache key {key}: {err}"))
+        .ok()
+        .flatten()
+        .map(decode_entry)
+        .unwrap_or(CacheLookup::Miss)
+}
+
+pub(super) async fn put_entry(entity_cache: &dyn EntityCache, key: &str, data: &[u8], ttl: CacheTtl) {
+    entity_cache
+        .put(key, Cow::Owned(encode_entry(data, ttl)), ttl.storage_ttl())
+        .await
+        .inspect_err(|err| tracing::warn!("Failed to write the cache key {key}: {err}"))
+        .ok();
 }
 
 pub(super) async fn fetch_response<R: Runtime>(
     ctx: &mut SubgraphContext<'_, R>,
     subgraph_headers: &http::HeaderMap,
     subgraph_request_body: &[u8],
-) -> Result<ResponseCacheHit, ResponseCacheMiss> {
+) -> ResponseCacheLookup {
     // FIXME: handle cache scopes
     let additional_scopes = Vec::new();
 
@@ -58,24 +182,13 @@ pub(super) async fn fetch_response<R: Runtime>(
         .finalize()
         .to_string();
 
-    ctx.engine()
-        .runtime
-        .entity_cache()
-        .get(&key)
-        .await
-        .inspect_err(|err| tracing::warn!("Failed to read the cache key {key}: {err}"))
-        .ok()
-        .flatten()
-        .map(|data| Ok(ResponseCacheHit { data }))
-        .unwrap_or(Err(ResponseCacheMiss { key }))
-}
-
-pub(super) struct ResponseCacheHit {
-    pub data: Bytes,
+    let entry = get_entry(ctx.engine().runtime.entity_cache(), &key).await;
+    ResponseCacheLookup { key, entry }
 }
 
-pub(super) struct ResponseCacheMiss {
+pub(super) struct ResponseCacheLookup {
     pub key: String,
+    pub entry: CacheLookup,
 }
 
 pub(super) async fn fetch_entities<R: Runtime>(
@@ -116,13 +229,30 @@ pub(super) async fn fetch_entities<R: Runtime>(
             fetch_entity(entity_cache, id, key, representation)
         });
 
-    let (hits, misses) = join_all(fetches).await.into_iter().partition_result();
-    CacheFetchEntitiesOutcome { hits, misses }
+    let mut outcome = CacheFetchEntitiesOutcome {
+        hits: Vec::new(),
+        misses: Vec::new(),
+        revalidations: Vec::new(),
+    };
+
+    for lookup in join_all(fetches).await {
+        match lookup {
+            EntityLookup::Hit { hit, revalidation } => {
+                outcome.hits.push(hit);
+                outcome.revalidations.extend(revalidation);
+            }
+            EntityLookup::Miss(miss) => outcome.misses.push(miss),
+        }
+    }
+
+    outcome
 }
 
 pub(super) struct CacheFetchEntitiesOutcome {
     pub hits: Vec<EntityCacheHit>,
     pub misses: Vec<EntityCacheMiss>,
+    /// Expired hits which should be refreshed in the background.
+    pub revalidations: Vec<EntityRevalidation>,
 }
 
 pub(super) struct EntityCacheHit {
@@ -134,6 +264,21 @@ pub(super) struct EntityCacheMiss {
     pub id: ParentObjectId,
     pub key: String,
     pub representation: Box<RawValue>,
+    /// Expired entry which may be served if the subgraph request fails.
+    pub stale: Option<Bytes>,
+}
+
+pub(super) struct EntityRevalidation {
+    pub key: String,
+    pub representation: Box<RawValue>,
+}
+
+enum EntityLookup {
+    Hit {
+        hit: EntityCacheHit,
+        revalidation: Option<EntityRevalidation>,
+    },
+    Miss(EntityCacheMiss),
 }
 
 async fn fetch_entity(
@@ -141,27 +286,187 @@ async fn fetch_entity(
     id: ParentObjectId,
     key: String,
     representation: Box<RawValue>,
-) -> Result<EntityCacheHit, EntityCacheMiss> {
-    let data = entity_cache
-        .get(&key)
-        .await
-        .inspect_err(|err| tracing::warn!("Failed to read the cache key {key}: {err}"))
-        .ok()
-        .flatten();
-
-    match data {
-        Some(data) => Ok(EntityCacheHit { id, data }),
-        None => Err(EntityCacheMiss {
+) -> EntityLookup {
+    match get_entry(entity_cache, &key).await {
+        CacheLookup::Fresh(data) => EntityLookup::Hit {
+            hit: EntityCacheHit { id, data },
+            revalidation: None,
+        },
+        CacheLookup::Revalidate(data) => EntityLookup::Hit {
+            hit: EntityCacheHit { id, data },
+            revalidation: Some(EntityRevalidation { key, representation }),
+        },
+        CacheLookup::StaleIfError(data) => EntityLookup::Miss(EntityCacheMiss {
+            id,
+            key,
+            representation,
+            stale: Some(data),
+        }),
+        CacheLookup::Miss => EntityLookup::Miss(EntityCacheMiss {
             id,
             key,
             representation,
+            stale: None,
         }),
     }
 }
 
+/// What the subgraph request of a background refresh retrieves.
+pub(super) enum RevalidationTarget {
+    /// A root fields response, cached as a whole under the single reserved key.
+    Response,
+    /// The `_entities` list, each entity cached under the reserved key at the same position.
+    Entities,
+}
+
+/// Refreshes stale entries with a new subgraph request in the background, the current request
+/// doesn't wait for it. Failures are only logged, the stale entries being kept as is.
+pub(super) async fn revalidate_in_background<R: Runtime>(
+    ctx: &SubgraphContext<'_, R>,
+    subgraph_headers: http::HeaderMap,
+    body: Vec<u8>,
+    revalidation: EntityCacheRevalidation<R>,
+    target: RevalidationTarget,
+) {
+    let subgraph = ctx.endpoint();
+
+    // Same hooks as for any other subgraph request, they may add authentication headers.
+    let parts = ctx
+        .extensions()
+        .on_graphql_subgraph_request(
+            EngineOperationContext::from(&ctx.ctx),
+            ctx.subgraph,
+            ReqwestParts {
+                url: Cow::Borrowed(subgraph.url()),
+                method: http::Method::POST,
+                headers: subgraph_headers,
+            },
+        )
+        .await;
+
+    let ReqwestParts {
+        url,
+        method,
+        mut headers,
+    } = match parts {
+        Ok(parts) => parts,
+        Err(err) => {
+            tracing::warn!(
+                "Could not refresh stale cache entries of subgraph {}: {err}",
+                subgraph.name()
+            );
+            return;
+        }
+    };
+
+    let body = Bytes::from(body);
+    insert_graphql_request_headers(&mut headers, &body);
+
+    let http_span = SubgraphHttpRequestSpan::new(url.as_ref(), &method);
+    inject_trace_context(&http_span, &mut headers);
+
+    let request = FetchRequest {
+        subgraph_id: subgraph.id,
+        url: Cow::Owned(url.into_owned()),
+        is_mutation: false,
+        method,
+        headers,
+        body,
+        timeout: subgraph.config.timeout,
+        max_response_size: subgraph.config.response_limits.max_size,
+    };
+    let subgraph_name = subgraph.name().to_string();
+    let subgraph_default_cache_ttl = subgraph.config.cache_ttl;
+
+    tokio::spawn(
+        async move {
+            let engine = revalidation.engine();
+            let response = match engine.runtime.fetcher().fetch(request).await {
+                (Ok(response), _) if response.status().is_success() => response,
+                (Ok(response), _) => {
+                    tracing::warn!(
+                        "Could not refresh stale cache entries of subgraph {subgraph_name}: status {}",
+                        response.status()
+                    );
+                    return;
+                }
+                (Err(err), _) => {
+                    tracing::warn!("Could not refresh stale cache entries of subgraph {subgraph_name}: {err}");
+                    return;
+                }
+            };
+
+            let Some((data, ttl)) = revalidated_data(&response, subgraph_default_cache_ttl) else {
+                return;
+            };
+
+            let entity_cache = engine.runtime.entity_cache();
+            let keys = revalidation.keys();
+
+            match target {
+                RevalidationTarget::Response => {
+                    if let Some(key) = keys.first() {
+                        put_entry(entity_cache, key, response.body(), ttl).await;
+                    }
+                }
+                RevalidationTarget::Entities => {
+                    #[derive(serde::Deserialize)]
+                    struct EntitiesData<'a> {
+                        #[serde(borrow, rename = "_entities")]
+                        entities: Vec<&'a RawValue>,
+                    }
+
+                    match serde_json::from_str::<EntitiesData<'_>>(data.get()) {
+                        Ok(EntitiesData { entities }) if entities.len() == keys.len() => {
+                            join_all(
+                                keys.iter()
+                                    .zip(entities)
+                                    .map(|(key, entity)| put_entry(entity_cache, key, entity.get().as_bytes(), ttl)),
+                            )
+                            .await;
+                        }
+                        _ => tracing::warn!(
+                            "Could not refresh stale cache entries of subgraph {subgraph_name}: unexpected entities"
+                        ),
+                    }
+                }
+            }
+        }
+        .instrument(http_span.span()),
+    );
+}
+
+/// Data of the refreshed response if it can be cached, only responses without any errors are.
+fn revalidated_data<'a>(
+    response: &'a http::Response<Bytes>,
+    subgraph_default_cache_ttl: Option<Duration>,
+) -> Option<(&'a RawValue, CacheTtl)> {
+    #[derive(serde::Deserialize)]
+    struct Response<'a> {
+        #[serde(borrow, default)]
+        data: Option<&'a RawValue>,
+        #[serde(default)]
+        errors: Option<Vec<IgnoredAny>>,
+    }
+
+    let Response { data, errors } = serde_json::from_slice(response.body()).ok()?;
+    if errors.is_some_and(|errors| !errors.is_empty()) {
+        return None;
+    }
+
+    let ttl = calculate_cache_ttl(
+        GraphqlResponseStatus::Success,
+        response.headers(),
+        subgraph_default_cache_ttl,
+    )?;
+
+    Some((data?, ttl))
+}
+
 fn prepare_key_hasher(subgraph_name: &str, headers: &HeaderMap, additional_scopes: &[String]) -> blake3::Hasher {
     let mut hasher = blake3::Hasher::new();
-    hasher.update(b"v1");
+    // v2 entries are prefixed with their freshness timestamps.
+    hasher.update(b"v2");
     hasher.update(subgraph_name.as_bytes());
     hasher.update(&headers.len().to_le_bytes());
     for (name, value) in headers {
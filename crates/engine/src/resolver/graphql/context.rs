@@ -4,7 +4,7 @@ use grafbase_telemetry::{
     graphql::GraphqlResponseStatus,
     span::subgraph::{SubgraphGraphqlRequestSpan, SubgraphHttpRequestSpan, SubgraphRequestSpanBuilder},
 };
-use schema::GraphqlSubgraph;
+use schema::{GraphqlSubgraph, ResponseHeaderRule, RetryConfig};
 use std::{ops::Deref, time::Instant};
 use tower::retry::budget::TpsBudget;
 use tracing::Span;
@@ -26,6 +26,7 @@ pub(crate) struct SubgraphContext<'ctx, R: Runtime> {
     pub(super) ctx: ExecutionContext<'ctx, R>,
     pub(super) subgraph: GraphqlSubgraph<'ctx>,
     pub(super) retry_budget: Option<&'ctx TpsBudget>,
+    retry_config: Option<&'ctx RetryConfig>,
     span: SubgraphGraphqlRequestSpan,
     start: Instant,
     executed_request_builder: ExecutedSubgraphRequestBuilder<'ctx>,
@@ -56,6 +57,11 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
             _ => ctx.engine.get_retry_budget_for_non_mutation(subgraph.id),
         };
 
+        let retry_config = retry_budget.and_then(|_| {
+            let schema = ctx.schema();
+            subgraph.as_ref().config.retry.as_ref().or(schema.config.retry.as_ref())
+        });
+
         let span = span.build();
 
         Self {
@@ -65,6 +71,7 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
             span,
             start: Instant::now(),
             retry_budget,
+            retry_config,
             status: None,
             http_status_code: None,
             send_count: 0,
@@ -87,12 +94,17 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
         self.subgraph
     }
 
-    pub fn retry_budget(&self) -> Option<&TpsBudget> {
+    pub fn retry_budget(&self) -> Option<&'ctx TpsBudget> {
         self.retry_budget
     }
 
+    pub fn retry_config(&self) -> Option<&'ctx RetryConfig> {
+        self.retry_config
+    }
+
     pub async fn finalize(self, response_part: ResponsePartBuilder<'ctx>) -> ResolverResult<'ctx> {
         let duration = self.start.elapsed();
+        self.span.record_retry_count(self.send_count.saturating_sub(1));
 
         if let Some(status) = self.status {
             self.span.record_graphql_response_status(status);
@@ -142,6 +154,12 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
             .record_subgraph_cache_partial_hit(self.subgraph.name().to_string());
     }
 
+    pub(super) fn record_cache_stale_hit(&mut self) {
+        self.executed_request_builder.cache_status(CacheStatus::Stale);
+        self.metrics()
+            .record_subgraph_cache_stale_hit(self.subgraph.name().to_string());
+    }
+
     pub(super) fn record_cache_miss(&mut self) {
         self.executed_request_builder.cache_status(CacheStatus::Miss);
         self.metrics().record_subgraph_cache_miss(SubgraphCacheMissAttributes {
@@ -180,8 +198,23 @@ impl<'ctx, R: Runtime> SubgraphContext<'ctx, R> {
         self.executed_request_builder.push_execution(kind);
     }
 
+    pub(super) fn response_header_rules(
+        &self,
+    ) -> impl Iterator<Item = &'ctx ResponseHeaderRule> + Clone + use<'ctx, R> {
+        let schema = self.ctx.schema();
+        schema
+            .config
+            .response_header_rules
+            .iter()
+            .chain(self.subgraph.as_ref().config.response_header_rules.iter())
+    }
+
     pub(super) fn record_http_response(&mut self, response: &http::Response<Bytes>) {
         self.http_status_code = Some(response.status());
+        self.ctx
+            .request_context
+            .response_headers
+            .record(self.response_header_rules(), response.headers());
         self.metrics().record_subgraph_response_size(
             SubgraphResponseBodySizeAttributes {
                 name: self.subgraph.name().to_string(),
@@ -7,11 +7,13 @@ use itertools::Itertools as _;
 use operation::{OperationContext, ResponseKeys};
 use schema::{GraphqlFederationEntityResolverDefinition, GraphqlSubgraphId};
 use serde_json::value::RawValue;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tracing::Instrument;
 use walker::Walk;
 
 use crate::{
     Runtime,
+    engine::EntityCacheRevalidation,
     execution::ExecutionContext,
     prepare::{Plan, PlanError, PlanQueryPartition, PlanResult, RootFieldsShapeId},
     resolver::graphql::request::{SubgraphGraphqlRequest, SubgraphVariables},
@@ -20,6 +22,7 @@ use crate::{
 
 use super::{
     SubgraphContext,
+    cache::{EntityRevalidation, RevalidationTarget},
     request::{PreparedFederationEntityOperation, execute_subgraph_request},
 };
 
@@ -259,6 +262,7 @@ pub(super) async fn fetch_entities_without_cache<'ctx, R: Runtime>(
         shape_id,
         parent_objects,
         fetched_entities: entities_to_fetch,
+        limits: ctx.endpoint().config.response_limits,
     };
 
     execute_subgraph_request(ctx, subgraph_headers, false, body, response_part, ingester).await
@@ -273,18 +277,29 @@ pub(super) async fn fetch_entities_with_cache<'ctx, R: Runtime>(
     shape_id: RootFieldsShapeId,
     mut response_part: ResponsePartBuilder<'ctx>,
 ) -> ResponsePartBuilder<'ctx> {
-    let cache_fetch_outcome = super::cache::fetch_entities(ctx, &subgraph_headers, entities_to_fetch).await;
+    let mut cache_fetch_outcome = super::cache::fetch_entities(ctx, &subgraph_headers, entities_to_fetch).await;
+
+    let revalidations = std::mem::take(&mut cache_fetch_outcome.revalidations);
+    let has_stale_hits = !revalidations.is_empty();
+    if has_stale_hits {
+        revalidate_entities(ctx, &subgraph_headers, subgraph_operation, revalidations).await;
+    }
+
     if cache_fetch_outcome.misses.is_empty() {
-        ctx.record_cache_hit();
-        let state = response_part.into_seed_state(shape_id);
+        if has_stale_hits {
+            ctx.record_cache_stale_hit();
+        } else {
+            ctx.record_cache_hit();
+        }
+        let state = response_part
+            .into_seed_state(shape_id)
+            .with_limits(ctx.endpoint().config.response_limits);
         with_cache::ingest_hits(&state, &parent_objects, cache_fetch_outcome.hits);
         return state.into_response_part();
-    } else if cache_fetch_outcome.hits.is_empty() {
-        ctx.record_cache_miss();
-    } else {
-        ctx.record_cache_partial_hit();
     }
 
+    let has_hits = !cache_fetch_outcome.hits.is_empty();
+
     let variables = SubgraphVariables {
         ctx: ctx.input_value_context(),
         variables: &subgraph_operation.variables,
@@ -320,13 +335,74 @@ pub(super) async fn fetch_entities_with_cache<'ctx, R: Runtime>(
         }
     };
 
+    let served_stale = AtomicBool::new(false);
     let ingester = with_cache::PartiallyCachedEntitiesIngester {
         ctx: ctx.execution_context(),
         parent_objects,
         cache_fetch_outcome,
         shape_id,
         subgraph_default_cache_ttl: ctx.endpoint().config.cache_ttl,
+        limits: ctx.endpoint().config.response_limits,
+        served_stale: &served_stale,
     };
 
-    execute_subgraph_request(ctx, subgraph_headers, false, body, response_part, ingester).await
+    let response_part = execute_subgraph_request(ctx, subgraph_headers, false, body, response_part, ingester).await;
+
+    if has_stale_hits || served_stale.load(Ordering::Relaxed) {
+        ctx.record_cache_stale_hit();
+    } else if has_hits {
+        ctx.record_cache_partial_hit();
+    } else {
+        ctx.record_cache_miss();
+    }
+
+    response_part
+}
+
+/// Refreshes the expired entities served from the cache in the background, skipping those
+/// already being refreshed.
+async fn revalidate_entities<R: Runtime>(
+    ctx: &SubgraphContext<'_, R>,
+    subgraph_headers: &http::HeaderMap,
+    subgraph_operation: &PreparedFederationEntityOperation,
+    revalidations: Vec<EntityRevalidation>,
+) {
+    let mut revalidation = EntityCacheRevalidation::new(ctx.engine);
+    let revalidations = revalidations
+        .into_iter()
+        .filter(|entity| revalidation.reserve(&entity.key))
+        .collect::<Vec<_>>();
+
+    if revalidations.is_empty() {
+        return;
+    }
+
+    let variables = SubgraphVariables {
+        ctx: ctx.input_value_context(),
+        variables: &subgraph_operation.variables,
+        extra_variables: vec![(
+            &subgraph_operation.entities_variable_name,
+            RepresentationListView(revalidations.iter().map(|entity| entity.representation.as_ref())),
+        )],
+    };
+
+    let body = match serde_json::to_vec(&SubgraphGraphqlRequest {
+        query: &subgraph_operation.query,
+        variables,
+    }) {
+        Ok(body) => body,
+        Err(err) => {
+            tracing::error!("Failed to serialize query: {err}");
+            return;
+        }
+    };
+
+    super::cache::revalidate_in_background(
+        ctx,
+        subgraph_headers.clone(),
+        body,
+        revalidation,
+        RevalidationTarget::Entities,
+    )
+    .await;
 }
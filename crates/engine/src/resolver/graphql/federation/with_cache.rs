@@ -1,8 +1,12 @@
-use std::{borrow::Cow, time::Duration};
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
 
 use bytes::Bytes;
 use futures::future::join_all;
 use grafbase_telemetry::graphql::GraphqlResponseStatus;
+use schema::SubgraphResponseLimits;
 use serde::{
     Deserializer,
     de::{DeserializeSeed, IgnoredAny, SeqAccess, Visitor},
@@ -14,11 +18,11 @@ use crate::{
     execution::ExecutionContext,
     prepare::RootFieldsShapeId,
     resolver::graphql::{
-        cache::{CacheFetchEntitiesOutcome, EntityCacheHit, EntityCacheMiss, calculate_cache_ttl},
+        cache::{CacheFetchEntitiesOutcome, EntityCacheHit, EntityCacheMiss, calculate_cache_ttl, put_entry},
         deserialize::{EntitiesDataSeed, EntityErrorPathConverter, GraphqlErrorsSeed, GraphqlResponseSeed},
         request::ResponseIngester,
     },
-    response::{Deserializable, GraphqlError, ParentObjectSet, ResponsePartBuilder, SeedState},
+    response::{Deserializable, ErrorCode, GraphqlError, ParentObjectSet, ResponsePartBuilder, SeedState},
 };
 
 pub(super) fn ingest_hits<'parent>(
@@ -37,15 +41,17 @@ pub(super) fn ingest_hits<'parent>(
     }
 }
 
-pub(super) struct PartiallyCachedEntitiesIngester<'ctx, R: Runtime> {
+pub(super) struct PartiallyCachedEntitiesIngester<'ctx, 'a, R: Runtime> {
     pub ctx: ExecutionContext<'ctx, R>,
     pub parent_objects: ParentObjectSet,
     pub cache_fetch_outcome: CacheFetchEntitiesOutcome,
     pub shape_id: RootFieldsShapeId,
     pub subgraph_default_cache_ttl: Option<Duration>,
+    pub limits: SubgraphResponseLimits,
+    pub served_stale: &'a AtomicBool,
 }
 
-impl<R> ResponseIngester for PartiallyCachedEntitiesIngester<'_, R>
+impl<R> ResponseIngester for PartiallyCachedEntitiesIngester<'_, '_, R>
 where
     R: Runtime,
 {
@@ -57,11 +63,33 @@ where
         let Self {
             ctx,
             parent_objects,
-            cache_fetch_outcome: CacheFetchEntitiesOutcome { hits, misses },
+            cache_fetch_outcome: CacheFetchEntitiesOutcome { hits, misses, .. },
             shape_id,
             subgraph_default_cache_ttl,
+            limits,
+            served_stale,
         } = self;
 
+        let failed = match &result {
+            Ok(http_response) => http_response.status().is_server_error(),
+            Err(_) => true,
+        };
+
+        if failed && misses.iter().any(|miss| miss.stale.is_some()) {
+            served_stale.store(true, Ordering::Relaxed);
+            let error = match result {
+                Ok(http_response) => GraphqlError::new(
+                    format!(
+                        "Subgraph request failed with status {}",
+                        http_response.status().as_u16()
+                    ),
+                    ErrorCode::SubgraphRequestError,
+                ),
+                Err(err) => err,
+            };
+            return ingest_stale_entities(response_part, &parent_objects, shape_id, limits, hits, misses, error);
+        }
+
         let http_response = match result {
             Ok(http_response) => http_response,
             Err(err) => {
@@ -74,7 +102,7 @@ where
         // while deserializing.
         let mut cache_updates = Vec::with_capacity(misses.len());
         let (status, response_part) = {
-            let state = response_part.into_seed_state(shape_id);
+            let state = response_part.into_seed_state(shape_id).with_limits(limits);
 
             ingest_hits(&state, &parent_objects, hits);
 
@@ -121,19 +149,50 @@ where
             && let Some(cache_ttl) = calculate_cache_ttl(status, http_response.headers(), subgraph_default_cache_ttl)
         {
             let cache = ctx.runtime().entity_cache();
-            join_all(cache_updates.into_iter().map(|(key, value)| async move {
-                cache
-                    .put(&key, Cow::Borrowed(value.get().as_bytes()), cache_ttl)
-                    .await
-                    .inspect_err(|err| tracing::warn!("Failed to write the cache key {key}: {err}"))
-                    .ok();
-            }))
+            join_all(
+                cache_updates
+                    .iter()
+                    .map(|(key, value)| put_entry(cache, key, value.get().as_bytes(), cache_ttl)),
+            )
             .await;
         }
         (status, response_part)
     }
 }
 
+/// Serves the expired entries instead of the failed subgraph response, entities without any are
+/// given the error.
+fn ingest_stale_entities<'ctx>(
+    response_part: ResponsePartBuilder<'ctx>,
+    parent_objects: &ParentObjectSet,
+    shape_id: RootFieldsShapeId,
+    limits: SubgraphResponseLimits,
+    hits: Vec<EntityCacheHit>,
+    misses: Vec<EntityCacheMiss>,
+    error: GraphqlError,
+) -> (Option<GraphqlResponseStatus>, ResponsePartBuilder<'ctx>) {
+    let state = response_part.into_seed_state(shape_id).with_limits(limits);
+
+    ingest_hits(&state, parent_objects, hits);
+
+    let mut stale = Vec::new();
+    let mut failed = Vec::new();
+    for EntityCacheMiss { id, stale: data, .. } in misses {
+        match data {
+            Some(data) => stale.push(EntityCacheHit { id, data }),
+            None => failed.push(id),
+        }
+    }
+    ingest_hits(&state, parent_objects, stale);
+
+    if failed.is_empty() {
+        return (Some(GraphqlResponseStatus::Success), state.into_response_part());
+    }
+
+    state.insert_error_updates(failed.iter().map(|id| &parent_objects[*id]), [error]);
+    (None, state.into_response_part())
+}
+
 struct PartiallyCachedEntitiesSeed<'ctx, 'parent, 'state, 'de> {
     state: &'state SeedState<'ctx, 'parent>,
     parent_objects: &'parent ParentObjectSet,
@@ -1,5 +1,6 @@
 use bytes::Bytes;
 use grafbase_telemetry::graphql::GraphqlResponseStatus;
+use schema::SubgraphResponseLimits;
 
 use crate::{
     prepare::RootFieldsShapeId,
@@ -16,6 +17,7 @@ pub(super) struct EntityIngester {
     pub shape_id: RootFieldsShapeId,
     pub parent_objects: ParentObjectSet,
     pub fetched_entities: Vec<EntityToFetch>,
+    pub limits: SubgraphResponseLimits,
 }
 
 impl ResponseIngester for EntityIngester {
@@ -28,6 +30,7 @@ impl ResponseIngester for EntityIngester {
             shape_id,
             parent_objects,
             fetched_entities,
+            limits,
         } = self;
 
         let http_response = match result {
@@ -38,7 +41,7 @@ impl ResponseIngester for EntityIngester {
             }
         };
 
-        let state = response_part.into_seed_state(shape_id);
+        let state = response_part.into_seed_state(shape_id).with_limits(limits);
         let seed = GraphqlResponseSeed::new(
             EntitiesDataSeed::new(
                 state.parent_list_seed(fetched_entities.iter().map(|entity| &parent_objects[entity.id])),
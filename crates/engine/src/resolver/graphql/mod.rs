@@ -8,4 +8,5 @@ mod subscription;
 
 pub(super) use context::*;
 pub(crate) use federation::*;
+pub(crate) use request::SubgraphRequestBatches;
 pub(crate) use root_fields::*;
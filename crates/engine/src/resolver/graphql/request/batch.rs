@@ -0,0 +1,308 @@
+use std::sync::{
+    Mutex,
+    atomic::{AtomicU64, Ordering},
+};
+
+use bytes::{BufMut as _, Bytes, BytesMut};
+use event_queue::SubgraphResponseBuilder;
+use futures::{channel::oneshot, future::select};
+use runtime::fetch::{FetchError, FetchRequest, FetchResult, Fetcher as _};
+use schema::{GraphqlSubgraphId, SubgraphBatching};
+use serde_json::value::RawValue;
+use url::Url;
+
+use crate::Runtime;
+
+type BatchResult = FetchResult<http::Response<Bytes>>;
+type BatchOutput = (BatchResult, Option<SubgraphResponseBuilder>);
+
+/// Groups concurrent requests to the same subgraph into a single GraphQL batch request, a JSON
+/// array of requests answered by a JSON array of responses in the same order.
+///
+/// The first request of a batch leads it: it waits for others to join, sends the batch request
+/// and distributes the individual responses. Only requests sharing the same URL, method and
+/// headers, apart from `Content-Length`, are grouped together.
+#[derive(Default)]
+pub(crate) struct SubgraphRequestBatches {
+    next_id: AtomicU64,
+    pending: Mutex<Vec<PendingBatch>>,
+}
+
+struct PendingBatch {
+    id: u64,
+    subgraph_id: GraphqlSubgraphId,
+    url: Url,
+    method: http::Method,
+    // Without the Content-Length header, which depends on the individual bodies.
+    headers: http::HeaderMap,
+    // Bodies of the requests which joined the batch, the leader's own body isn't included.
+    bodies: Vec<Bytes>,
+    senders: Vec<oneshot::Sender<BatchOutput>>,
+    is_full: bool,
+    flush: Option<oneshot::Sender<()>>,
+}
+
+impl PendingBatch {
+    fn accepts(&self, request: &FetchRequest<'_>) -> bool {
+        !self.is_full
+            && self.subgraph_id == request.subgraph_id
+            && self.method == request.method
+            && self.url == *request.url
+            && same_headers_ignoring_content_length(&self.headers, &request.headers)
+    }
+}
+
+fn same_headers_ignoring_content_length(batch: &http::HeaderMap, request: &http::HeaderMap) -> bool {
+    let request_len = request.len() - usize::from(request.contains_key(http::header::CONTENT_LENGTH));
+    batch.len() == request_len
+        && request
+            .iter()
+            .filter(|(name, _)| *name != http::header::CONTENT_LENGTH)
+            .all(|(name, value)| batch.get_all(name).iter().any(|batch_value| batch_value == value))
+}
+
+fn without_content_length(headers: &http::HeaderMap) -> http::HeaderMap {
+    let mut headers = headers.clone();
+    headers.remove(http::header::CONTENT_LENGTH);
+    headers
+}
+
+impl SubgraphRequestBatches {
+    /// Sends the request as part of a batch. `prepare_batch_request` is called on the request
+    /// actually sent, only by the leader of the batch.
+    pub(crate) async fn fetch<R: Runtime>(
+        &self,
+        runtime: &R,
+        config: &SubgraphBatching,
+        request: FetchRequest<'_>,
+        prepare_batch_request: impl FnOnce(&mut FetchRequest<'_>) + Send,
+    ) -> BatchOutput {
+        let mut request = request;
+
+        if config.max_size <= 1 {
+            prepare_batch_request(&mut request);
+            return runtime.fetcher().fetch(request).await;
+        }
+
+        let guard = match self.join_or_lead(config, &request) {
+            Role::Follower(receiver) => {
+                // Followers share the response info of the batch request.
+                return receiver.await.unwrap_or_else(|_| {
+                    (
+                        Err(FetchError::Message(
+                            "The batched subgraph request was cancelled".to_string(),
+                        )),
+                        None,
+                    )
+                });
+            }
+            Role::Leader { id, flush } => {
+                let guard = LeaderGuard { batches: self, id };
+                // Either the batch is full or we waited long enough for others to join.
+                let sleep = std::pin::pin!(runtime.sleep(config.max_wait));
+                select(flush, sleep).await;
+                guard
+            }
+        };
+
+        let batch = guard.take_batch().expect("Only the leader removes the batch");
+        let count = batch.bodies.len() + 1;
+
+        if count > 1 {
+            request.body = batch_body(&request.body, &batch.bodies);
+            request.max_response_size = request.max_response_size.map(|size| size.saturating_mul(count));
+            request
+                .headers
+                .insert(http::header::CONTENT_LENGTH, request.body.len().into());
+        }
+
+        prepare_batch_request(&mut request);
+
+        let (result, info) = runtime.fetcher().fetch(request).await;
+        if count == 1 {
+            return (result, info);
+        }
+
+        let mut results = split_batch_response(result, count).into_iter();
+        let own = results.next().expect("At least one response");
+
+        for (sender, result) in batch.senders.into_iter().zip(results) {
+            // The request may have been cancelled in the meantime.
+            let _ = sender.send((result, info.clone()));
+        }
+
+        (own, info)
+    }
+
+    fn join_or_lead(&self, config: &SubgraphBatching, request: &FetchRequest<'_>) -> Role {
+        let mut pending = self.pending.lock().unwrap();
+
+        if let Some(batch) = pending.iter_mut().find(|batch| batch.accepts(request)) {
+            let (sender, receiver) = oneshot::channel();
+            batch.bodies.push(request.body.clone());
+            batch.senders.push(sender);
+
+            if batch.bodies.len() + 1 >= config.max_size {
+                batch.is_full = true;
+                if let Some(flush) = batch.flush.take() {
+                    let _ = flush.send(());
+                }
+            }
+
+            return Role::Follower(receiver);
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (flush, receiver) = oneshot::channel();
+
+        pending.push(PendingBatch {
+            id,
+            subgraph_id: request.subgraph_id,
+            url: request.url.clone().into_owned(),
+            method: request.method.clone(),
+            headers: without_content_length(&request.headers),
+            bodies: Vec::new(),
+            senders: Vec::new(),
+            is_full: false,
+            flush: Some(flush),
+        });
+
+        Role::Leader { id, flush: receiver }
+    }
+}
+
+enum Role {
+    Leader { id: u64, flush: oneshot::Receiver<()> },
+    Follower(oneshot::Receiver<BatchOutput>),
+}
+
+/// Ensures the batch doesn't stay pending if the leader is cancelled while waiting, the followers
+/// are then notified by their dropped sender.
+struct LeaderGuard<'a> {
+    batches: &'a SubgraphRequestBatches,
+    id: u64,
+}
+
+impl LeaderGuard<'_> {
+    fn take_batch(&self) -> Option<PendingBatch> {
+        let mut pending = self.batches.pending.lock().unwrap();
+        let position = pending.iter().position(|batch| batch.id == self.id)?;
+        Some(pending.swap_remove(position))
+    }
+}
+
+impl Drop for LeaderGuard<'_> {
+    fn drop(&mut self) {
+        self.take_batch();
+    }
+}
+
+fn batch_body(first: &Bytes, others: &[Bytes]) -> Bytes {
+    let len = 2 + first.len() + others.iter().map(|body| body.len() + 1).sum::<usize>();
+    let mut body = BytesMut::with_capacity(len);
+
+    body.put_u8(b'[');
+    body.put_slice(first);
+    for other in others {
+        body.put_u8(b',');
+        body.put_slice(other);
+    }
+    body.put_u8(b']');
+
+    body.freeze()
+}
+
+/// Splits the batch response into the individual responses. Errors and unsuccessful responses are
+/// shared by all requests of the batch.
+fn split_batch_response(result: BatchResult, count: usize) -> Vec<BatchResult> {
+    let response = match result {
+        Ok(response) => response,
+        Err(err) => return (0..count).map(|_| Err(err.clone())).collect(),
+    };
+
+    if !response.status().is_success() {
+        return (0..count)
+            .map(|_| Ok(with_body(&response, response.body().clone())))
+            .collect();
+    }
+
+    let body = response.body();
+    match serde_json::from_slice::<Vec<&RawValue>>(body) {
+        Ok(items) if items.len() == count => items
+            .into_iter()
+            .map(|item| Ok(with_body(&response, body.slice_ref(item.get().as_bytes()))))
+            .collect(),
+        _ => {
+            let err = FetchError::Message(format!("Subgraph did not return a batch response with {count} items"));
+            (0..count).map(|_| Err(err.clone())).collect()
+        }
+    }
+}
+
+fn with_body(response: &http::Response<Bytes>, body: Bytes) -> http::Response<Bytes> {
+    let mut headers = response.headers().clone();
+    headers.remove(http::header::CONTENT_LENGTH);
+
+    let mut individual = http::Response::new(body);
+    *individual.status_mut() = response.status();
+    *individual.version_mut() = response.version();
+    *individual.headers_mut() = headers;
+    individual
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: http::StatusCode, body: &'static str) -> BatchResult {
+        let mut response = http::Response::new(Bytes::from_static(body.as_bytes()));
+        *response.status_mut() = status;
+        Ok(response)
+    }
+
+    fn bodies(results: Vec<BatchResult>) -> Vec<String> {
+        results
+            .into_iter()
+            .map(|result| match result {
+                Ok(response) => String::from_utf8(response.into_body().to_vec()).unwrap(),
+                Err(err) => err.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn batch_body_is_a_json_array() {
+        let body = batch_body(
+            &Bytes::from_static(br#"{"query":"{a}"}"#),
+            &[Bytes::from_static(br#"{"query":"{b}"}"#)],
+        );
+        assert_eq!(body, Bytes::from_static(br#"[{"query":"{a}"},{"query":"{b}"}]"#));
+    }
+
+    #[test]
+    fn split_successful_batch_response() {
+        let results = split_batch_response(
+            response(http::StatusCode::OK, r#"[{"data":{"a":1}}, {"data":{"b":2}}]"#),
+            2,
+        );
+        assert_eq!(bodies(results), [r#"{"data":{"a":1}}"#, r#"{"data":{"b":2}}"#]);
+    }
+
+    #[test]
+    fn split_unexpected_batch_response() {
+        let results = split_batch_response(response(http::StatusCode::OK, r#"{"data":{"a":1}}"#), 2);
+        assert_eq!(
+            bodies(results),
+            [
+                "Subgraph did not return a batch response with 2 items",
+                "Subgraph did not return a batch response with 2 items"
+            ]
+        );
+    }
+
+    #[test]
+    fn unsuccessful_batch_response_is_shared() {
+        let results = split_batch_response(response(http::StatusCode::BAD_REQUEST, "oops"), 2);
+        assert_eq!(bodies(results), ["oops", "oops"]);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, time::Duration};
+use std::borrow::Cow;
 
 use bytes::Bytes;
 use event_queue::{RequestExecution, SubgraphResponseBuilder};
@@ -13,6 +13,7 @@ use runtime::{
     fetch::{FetchError, FetchRequest, FetchResult, Fetcher},
     rate_limiting::RateLimitKey,
 };
+use schema::RetryOn;
 use tower::retry::budget::Budget;
 use tracing::{Instrument, Span};
 
@@ -63,17 +64,7 @@ pub(crate) async fn execute_subgraph_request<'ctx, R: Runtime>(
             .await?;
 
         let body: Bytes = body.into();
-
-        headers.typed_insert(headers::ContentType::json());
-        headers.typed_insert(headers::ContentLength(body.len() as u64));
-
-        headers.insert(
-            http::header::ACCEPT,
-            http::HeaderValue::from_static(
-                "application/graphql-response+json; charset=utf-8, application/json; charset=utf-8",
-            ),
-        );
-        headers.insert(http::header::CONNECTION, http::HeaderValue::from_static("keep-alive"));
+        insert_graphql_request_headers(&mut headers, &body);
 
         let request = FetchRequest {
             subgraph_id: subgraph.id,
@@ -83,28 +74,42 @@ pub(crate) async fn execute_subgraph_request<'ctx, R: Runtime>(
             method,
             body,
             timeout: subgraph.config.timeout,
+            max_response_size: subgraph.config.response_limits.max_size,
         };
 
         ctx.record_request_size(request.body.len());
 
-        let fetcher = ctx.runtime().fetcher();
+        let runtime = ctx.runtime();
+        let fetcher = runtime.fetcher();
+        let response_header_rules = ctx.response_header_rules();
+        // Mutations are executed sequentially, there is nothing to batch.
+        let batching = subgraph.config.batching.filter(|_| !is_mutation);
+        let request_context = ctx.request_context;
 
-        let fetch_result = retrying_fetch(ctx, || {
+        let fetch = |attempt| {
             let mut request = request.clone();
             let subgraph_name = subgraph.name().to_string();
+            let response_header_rules = response_header_rules.clone();
 
             async move {
                 let http_span = SubgraphHttpRequestSpan::new(request.url.as_ref(), &http::Method::POST);
-
-                grafbase_telemetry::otel::opentelemetry::global::get_text_map_propagator(|propagator| {
-                    let context = http_span.context();
-                    propagator.inject_context(
-                        &context,
-                        &mut grafbase_telemetry::http::HeaderInjector(&mut request.headers),
-                    );
-                });
-
-                let (fetch_result, mut info) = fetcher.fetch(request).instrument(http_span.span()).await;
+                http_span.record_resend_count(attempt);
+
+                let (fetch_result, mut info) = match batching {
+                    Some(batching) => {
+                        request_context
+                            .subgraph_batches
+                            .fetch(runtime, &batching, request, |request| {
+                                inject_trace_context(&http_span, &mut request.headers)
+                            })
+                            .instrument(http_span.span())
+                            .await
+                    }
+                    None => {
+                        inject_trace_context(&http_span, &mut request.headers);
+                        fetcher.fetch(request).instrument(http_span.span()).await
+                    }
+                };
 
                 let result = fetch_result.and_then(|mut response| {
                     tracing::debug!("Received response:\n{}", String::from_utf8_lossy(response.body()));
@@ -117,23 +122,38 @@ pub(crate) async fn execute_subgraph_request<'ctx, R: Runtime>(
 
                         // Performance optimization: Instead of cloning the entire HeaderMap,
                         // we extract only the cache-related headers (Cache-Control and Age)
-                        // that are needed by the caching logic. This avoids an expensive clone
-                        // of all headers while still allowing telemetry/hooks to receive the
-                        // complete header information.
-                        let cache_control = response.headers().typed_get::<headers::CacheControl>();
-                        let age = response.headers().typed_get::<headers::Age>();
-
-                        // Move all headers to the hooks
-                        info.headers(std::mem::take(response.headers_mut()));
+                        // that are needed by the caching logic and the ones propagated to the
+                        // client. This avoids an expensive clone of all headers while still
+                        // allowing telemetry/hooks to receive the complete header information.
+                        let mut retained = http::HeaderMap::new();
+
+                        // Copied as is, the typed header would drop the stale-while-revalidate
+                        // and stale-if-error directives.
+                        for cache_control in response.headers().get_all(http::header::CACHE_CONTROL) {
+                            retained.append(http::header::CACHE_CONTROL, cache_control.clone());
+                        }
 
-                        // Put back cache-related headers for cache control logic
-                        if let Some(cache_control) = cache_control {
-                            response.headers_mut().typed_insert(cache_control);
+                        if let Some(age) = response.headers().typed_get::<headers::Age>() {
+                            retained.typed_insert(age);
                         }
 
-                        if let Some(age) = age {
-                            response.headers_mut().typed_insert(age);
+                        for name in response.headers().keys() {
+                            if !retained.contains_key(name)
+                                && response_header_rules
+                                    .clone()
+                                    .any(|rule| rule.name.matches(name.as_str()))
+                            {
+                                for value in response.headers().get_all(name) {
+                                    retained.append(name.clone(), value.clone());
+                                }
+                            }
                         }
+
+                        // Move all headers to the hooks
+                        info.headers(std::mem::take(response.headers_mut()));
+
+                        // Put back the headers we still need
+                        *response.headers_mut() = retained;
                     }
 
                     if status.is_server_error() {
@@ -162,8 +182,9 @@ pub(crate) async fn execute_subgraph_request<'ctx, R: Runtime>(
 
                 (result, info)
             }
-        })
-        .await;
+        };
+
+        let fetch_result = retrying_fetch(ctx, fetch, is_retryable_response).await;
 
         match fetch_result {
             Ok(http_response) => {
@@ -224,57 +245,154 @@ pub(crate) async fn execute_subgraph_request<'ctx, R: Runtime>(
     }
 }
 
+pub(crate) fn insert_graphql_request_headers(headers: &mut http::HeaderMap, body: &Bytes) {
+    headers.typed_insert(headers::ContentType::json());
+    headers.typed_insert(headers::ContentLength(body.len() as u64));
+
+    headers.insert(
+        http::header::ACCEPT,
+        http::HeaderValue::from_static(
+            "application/graphql-response+json; charset=utf-8, application/json; charset=utf-8",
+        ),
+    );
+    headers.insert(http::header::CONNECTION, http::HeaderValue::from_static("keep-alive"));
+}
+
+pub(crate) fn inject_trace_context(http_span: &SubgraphHttpRequestSpan, headers: &mut http::HeaderMap) {
+    grafbase_telemetry::otel::opentelemetry::global::get_text_map_propagator(|propagator| {
+        let context = http_span.context();
+        propagator.inject_context(&context, &mut grafbase_telemetry::http::HeaderInjector(headers));
+    });
+}
+
+/// Fetches with the retry policy of the subgraph, if any. Without explicit `retry_on` rules any
+/// failed request is retried, except for oversized responses and requests shed by the gateway
+/// itself. Otherwise failures are classified and `retry_response` decides
+/// whether a successfully received response should be retried. The fetch function receives the
+/// number of previous attempts.
 pub(crate) async fn retrying_fetch<R: Runtime, F, T>(
     ctx: &mut SubgraphContext<'_, R>,
-    fetch: impl Fn() -> F + Send + Sync,
+    fetch: impl Fn(usize) -> F + Send + Sync,
+    retry_response: impl Fn(&RetryOn, &T) -> bool + Send,
 ) -> ExecutionResult<T>
 where
     F: Future<Output = (FetchResult<T>, Option<SubgraphResponseBuilder>)> + Send,
     T: Send,
 {
-    let mut fetch_result = rate_limited_fetch(ctx, &fetch).instrument(Span::current()).await;
+    let mut fetch_result = rate_limited_fetch(ctx, &fetch, 0).instrument(Span::current()).await;
 
-    if ctx.retry_budget().is_none() {
+    let (Some(budget), Some(config)) = (ctx.retry_budget(), ctx.retry_config()) else {
         return fetch_result;
     };
 
-    let mut counter = 0;
+    let mut retries: u32 = 0;
 
     loop {
-        match fetch_result {
-            Ok(response) => {
-                if let Some(b) = ctx.retry_budget() {
-                    b.deposit()
-                }
-                return Ok(response);
+        let should_retry = match (&fetch_result, &config.retry_on) {
+            (Ok(response), Some(retry_on)) => retry_response(retry_on, response),
+            (Ok(_), None) => false,
+            (Err(err), retry_on) => is_retryable_error(retry_on.as_ref(), err),
+        };
+
+        if !should_retry {
+            if fetch_result.is_ok() {
+                budget.deposit();
             }
-            Err(err) => {
-                let withdraw = ctx.retry_budget().map(|b| b.withdraw()).unwrap_or_default();
+            return fetch_result;
+        }
 
-                if withdraw {
-                    let jitter = rand::random::<f64>() * 2.0;
-                    let exp_backoff = (100 * 2u64.pow(counter)) as f64;
-                    let backoff_ms = (exp_backoff * jitter).round() as u64;
+        let can_retry = config.max_attempts.is_none_or(|max| retries + 1 < max) && budget.withdraw();
 
-                    ctx.engine().runtime.sleep(Duration::from_millis(backoff_ms)).await;
-                    ctx.record_request_retry();
+        if !can_retry {
+            ctx.record_aborted_request_retry();
+            return fetch_result;
+        }
 
-                    counter += 1;
+        let mut backoff = config.backoff(retries);
+        if config.jitter {
+            backoff = backoff.mul_f64(rand::random::<f64>() * 2.0);
+        }
 
-                    fetch_result = rate_limited_fetch(ctx, &fetch).await;
-                } else {
-                    ctx.record_aborted_request_retry();
+        ctx.engine().runtime.sleep(backoff).await;
+        ctx.record_request_retry();
 
-                    return Err(err);
-                }
+        retries += 1;
+
+        fetch_result = rate_limited_fetch(ctx, &fetch, retries as usize).await;
+    }
+}
+
+fn is_retryable_error(retry_on: Option<&RetryOn>, err: &ExecutionError) -> bool {
+    let Some(retry_on) = retry_on else {
+        return !matches!(
+            err,
+            ExecutionError::Fetch {
+                error: FetchError::ResponseTooLarge(_) | FetchError::Overloaded,
+                ..
             }
-        }
+        );
+    };
+
+    match err {
+        ExecutionError::Fetch { error, .. } => match error {
+            FetchError::InvalidStatusCode(status, _) => retry_on.status_code(*status),
+            // Only retried if explicitly asked for, the gateway sheds those requests itself.
+            FetchError::Overloaded => retry_on
+                .status_codes
+                .as_ref()
+                .is_some_and(|codes| codes.contains(&http::StatusCode::SERVICE_UNAVAILABLE.as_u16())),
+            FetchError::Timeout(_) => retry_on.timeouts,
+            FetchError::Connection(_) => retry_on.connection_errors,
+            _ => false,
+        },
+        _ => false,
     }
 }
 
+/// Whether the response should be retried according to its status code or the codes of the
+/// GraphQL errors it contains.
+fn is_retryable_response(retry_on: &RetryOn, response: &http::Response<Bytes>) -> bool {
+    let status = response.status();
+    if !status.is_success() && retry_on.status_code(status) {
+        return true;
+    }
+
+    if retry_on.graphql_error_codes.is_empty() {
+        return false;
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Response<'a> {
+        #[serde(borrow, default)]
+        errors: Option<Vec<Error<'a>>>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Error<'a> {
+        #[serde(borrow, default)]
+        extensions: Option<Extensions<'a>>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Extensions<'a> {
+        #[serde(borrow, default)]
+        code: Option<Cow<'a, str>>,
+    }
+
+    let Ok(Response { errors: Some(errors) }) = serde_json::from_slice::<Response<'_>>(response.body()) else {
+        return false;
+    };
+
+    errors
+        .iter()
+        .filter_map(|error| error.extensions.as_ref()?.code.as_deref())
+        .any(|code| retry_on.graphql_error_codes.iter().any(|retryable| retryable == code))
+}
+
 async fn rate_limited_fetch<R: Runtime, F, T>(
     ctx: &mut SubgraphContext<'_, R>,
-    fetch: impl Fn() -> F + Send,
+    fetch: impl Fn(usize) -> F + Send,
+    attempt: usize,
 ) -> ExecutionResult<T>
 where
     F: Future<Output = (FetchResult<T>, Option<SubgraphResponseBuilder>)> + Send,
@@ -290,7 +408,7 @@ where
         })?;
 
     ctx.increment_inflight_requests();
-    let (result, info) = fetch().await;
+    let (result, info) = fetch(attempt).await;
     ctx.decrement_inflight_requests();
 
     match info {
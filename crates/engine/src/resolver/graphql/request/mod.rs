@@ -1,7 +1,9 @@
+mod batch;
 mod execute;
 mod prepare;
 mod types;
 
+pub(crate) use batch::SubgraphRequestBatches;
 pub(super) use execute::*;
 pub(super) use prepare::*;
 pub(super) use types::*;
@@ -1,21 +1,25 @@
-use std::{borrow::Cow, time::Duration};
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
 
 use bytes::Bytes;
 use grafbase_telemetry::graphql::OperationType;
 use grafbase_telemetry::{graphql::GraphqlResponseStatus, span::subgraph::SubgraphRequestSpanBuilder};
 use operation::OperationContext;
-use schema::{GraphqlRootFieldResolverDefinition, GraphqlSubgraphId};
+use schema::{GraphqlRootFieldResolverDefinition, GraphqlSubgraphId, SubgraphResponseLimits};
 use tracing::Instrument;
 use walker::Walk;
 
 use super::{
     SubgraphContext,
-    cache::{ResponseCacheHit, ResponseCacheMiss},
+    cache::{CacheLookup, ResponseCacheLookup, RevalidationTarget},
     deserialize::{GraphqlErrorsSeed, GraphqlResponseSeed},
     request::{PreparedGraphqlOperation, ResponseIngester, SubgraphVariables, execute_subgraph_request},
 };
 use crate::{
     Runtime,
+    engine::EntityCacheRevalidation,
     execution::ExecutionContext,
     prepare::{Plan, PlanError, PlanResult, RootFieldsShapeId, SubgraphSelectionSet},
     resolver::graphql::request::SubgraphGraphqlRequest,
@@ -163,6 +167,7 @@ async fn fetch_response_without_cache<'ctx, R: Runtime>(
     struct Ingester {
         parent_objects: ParentObjectSet,
         shape_id: RootFieldsShapeId,
+        limits: SubgraphResponseLimits,
     }
 
     impl ResponseIngester for Ingester {
@@ -174,6 +179,7 @@ async fn fetch_response_without_cache<'ctx, R: Runtime>(
             let Self {
                 shape_id,
                 parent_objects,
+                limits,
             } = self;
 
             match result {
@@ -181,6 +187,7 @@ async fn fetch_response_without_cache<'ctx, R: Runtime>(
                     response_part,
                     &parent_objects,
                     shape_id,
+                    limits,
                     Deserializable::Json(http_response.body()),
                 ),
                 Err(error) => {
@@ -200,6 +207,7 @@ async fn fetch_response_without_cache<'ctx, R: Runtime>(
         Ingester {
             parent_objects,
             shape_id,
+            limits: ctx.endpoint().config.response_limits,
         },
     )
     .await
@@ -214,37 +222,83 @@ async fn fetch_response_with_cache<'ctx, R: Runtime>(
     shape_id: RootFieldsShapeId,
     response_part: ResponsePartBuilder<'ctx>,
 ) -> ResponsePartBuilder<'ctx> {
-    match super::cache::fetch_response(ctx, &subgraph_headers, &body).await {
-        Ok(ResponseCacheHit { data }) => {
+    let ResponseCacheLookup { key, entry } = super::cache::fetch_response(ctx, &subgraph_headers, &body).await;
+
+    let stale = match entry {
+        CacheLookup::Fresh(data) => {
             ctx.record_cache_hit();
-            let (_, response_part) =
-                ingest_graphql_data(response_part, &parent_objects, shape_id, Deserializable::Json(&data));
-            response_part
+            let (_, response_part) = ingest_graphql_data(
+                response_part,
+                &parent_objects,
+                shape_id,
+                ctx.endpoint().config.response_limits,
+                Deserializable::Json(&data),
+            );
+            return response_part;
         }
-        Err(ResponseCacheMiss { key }) => {
-            ctx.record_cache_miss();
-            let ingester = GraphqlWithCachePutIngester {
-                ctx: ctx.execution_context(),
-                parent_objects,
-                subgraph_default_cache_ttl: ctx.endpoint().config.cache_ttl,
-                cache_key: key,
+        CacheLookup::Revalidate(data) => {
+            ctx.record_cache_stale_hit();
+            let mut revalidation = EntityCacheRevalidation::new(ctx.engine);
+            if revalidation.reserve(&key) {
+                super::cache::revalidate_in_background(
+                    ctx,
+                    subgraph_headers,
+                    body,
+                    revalidation,
+                    RevalidationTarget::Response,
+                )
+                .await;
+            }
+            let (_, response_part) = ingest_graphql_data(
+                response_part,
+                &parent_objects,
                 shape_id,
-            };
-
-            execute_subgraph_request(ctx, subgraph_headers, is_mutation, body, response_part, ingester).await
+                ctx.endpoint().config.response_limits,
+                Deserializable::Json(&data),
+            );
+            return response_part;
         }
+        CacheLookup::StaleIfError(data) => Some(data),
+        CacheLookup::Miss => None,
+    };
+
+    let served_stale = AtomicBool::new(false);
+    let ingester = GraphqlWithCachePutIngester {
+        ctx: ctx.execution_context(),
+        parent_objects,
+        subgraph_default_cache_ttl: ctx.endpoint().config.cache_ttl,
+        cache_key: key,
+        shape_id,
+        limits: ctx.endpoint().config.response_limits,
+        stale,
+        served_stale: &served_stale,
+    };
+
+    let response_part =
+        execute_subgraph_request(ctx, subgraph_headers, is_mutation, body, response_part, ingester).await;
+
+    if served_stale.load(Ordering::Relaxed) {
+        ctx.record_cache_stale_hit();
+    } else {
+        ctx.record_cache_miss();
     }
+
+    response_part
 }
 
-struct GraphqlWithCachePutIngester<'ctx, R: Runtime> {
+struct GraphqlWithCachePutIngester<'ctx, 'a, R: Runtime> {
     ctx: ExecutionContext<'ctx, R>,
     parent_objects: ParentObjectSet,
     shape_id: RootFieldsShapeId,
     subgraph_default_cache_ttl: Option<Duration>,
     cache_key: String,
+    limits: SubgraphResponseLimits,
+    // Expired response served instead if the subgraph request fails.
+    stale: Option<Bytes>,
+    served_stale: &'a AtomicBool,
 }
 
-impl<R> ResponseIngester for GraphqlWithCachePutIngester<'_, R>
+impl<R> ResponseIngester for GraphqlWithCachePutIngester<'_, '_, R>
 where
     R: Runtime,
 {
@@ -259,8 +313,27 @@ where
             parent_objects,
             subgraph_default_cache_ttl,
             cache_key,
+            limits,
+            stale,
+            served_stale,
         } = self;
 
+        let failed = match &result {
+            Ok(http_response) => http_response.status().is_server_error(),
+            Err(_) => true,
+        };
+
+        if failed && let Some(stale) = stale {
+            served_stale.store(true, Ordering::Relaxed);
+            return ingest_graphql_data(
+                response_part,
+                &parent_objects,
+                shape_id,
+                limits,
+                Deserializable::Json(&stale),
+            );
+        }
+
         let http_response = match result {
             Ok(http_response) => http_response,
             Err(err) => {
@@ -273,6 +346,7 @@ where
             response_part,
             &parent_objects,
             shape_id,
+            limits,
             Deserializable::Json(http_response.body()),
         );
 
@@ -282,12 +356,13 @@ where
             if let Some(cache_ttl) = cache_ttl {
                 // We could probably put this call into the background at some point, but for
                 // simplicities sake I am not going to do that just now.
-                ctx.runtime()
-                    .entity_cache()
-                    .put(&cache_key, Cow::Borrowed(http_response.body().as_ref()), cache_ttl)
-                    .await
-                    .inspect_err(|err| tracing::warn!("Failed to write the cache key {cache_key}: {err}"))
-                    .ok();
+                super::cache::put_entry(
+                    ctx.runtime().entity_cache(),
+                    &cache_key,
+                    http_response.body(),
+                    cache_ttl,
+                )
+                .await;
             }
         }
 
@@ -299,11 +374,12 @@ pub(super) fn ingest_graphql_data<'ctx, 'de>(
     response_part: ResponsePartBuilder<'ctx>,
     parent_objects: &ParentObjectSet,
     shape_id: RootFieldsShapeId,
+    limits: SubgraphResponseLimits,
     data: impl Into<Deserializable<'de>>,
 ) -> (Option<GraphqlResponseStatus>, ResponsePartBuilder<'ctx>) {
     debug_assert_eq!(parent_objects.len(), 1);
     let parent_object = parent_objects.iter().next().expect("Have at least one parent object");
-    let state = response_part.into_seed_state(shape_id);
+    let state = response_part.into_seed_state(shape_id).with_limits(limits);
     let seed = GraphqlResponseSeed::new(
         state.parent_seed(parent_object),
         GraphqlErrorsSeed::new(&state, convert_root_error_path),
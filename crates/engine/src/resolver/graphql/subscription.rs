@@ -106,7 +106,7 @@ impl GraphqlResolver {
         let http_span = ctx.create_subgraph_request_span(&request.url, &request.method);
         let http_span1 = http_span.clone();
 
-        let stream = retrying_fetch(ctx, move || {
+        let fetch = move |_: usize| {
             let request = request.clone();
             let http_span1 = http_span1.clone();
             async move {
@@ -116,14 +116,17 @@ impl GraphqlResolver {
                     .await;
                 (result, None)
             }
-        })
-        .await;
+        };
+
+        // Only establishing the stream may be retried.
+        let stream = retrying_fetch(ctx, fetch, |_, _| false).await;
 
         let stream = stream.inspect_err(|_| {
             http_span.set_as_http_error(None);
             ctx.set_as_http_error(None);
         })?;
 
+        let limits = endpoint.config.response_limits;
         let stream = stream
             .map_err(move |error| {
                 GraphqlError::from(ExecutionError::Fetch {
@@ -134,7 +137,7 @@ impl GraphqlResolver {
             .map(move |result| {
                 let mut response = new_response();
                 let (parent_object, part) = response.create_root_part();
-                let state = part.into_seed_state(shape_id);
+                let state = part.into_seed_state(shape_id).with_limits(limits);
 
                 match result {
                     Ok(data) => {
@@ -199,7 +202,7 @@ impl GraphqlResolver {
         let fetcher = ctx.runtime().fetcher();
 
         let http_span1 = http_span.clone();
-        let stream = retrying_fetch(ctx, move || {
+        let fetch = move |_: usize| {
             let request = request.clone();
             let http_span1 = http_span1.clone();
             async move {
@@ -209,14 +212,17 @@ impl GraphqlResolver {
                     .await;
                 (result, None)
             }
-        })
-        .await;
+        };
+
+        // Only establishing the stream may be retried.
+        let stream = retrying_fetch(ctx, fetch, |_, _| false).await;
 
         let stream = stream.inspect_err(|err| {
             http_span.set_as_http_error(err.as_fetch_invalid_status_code());
             ctx.set_as_http_error(err.as_fetch_invalid_status_code());
         })?;
 
+        let limits = endpoint.config.response_limits;
         let stream = stream
             .map_err(move |error| {
                 GraphqlError::from(ExecutionError::Fetch {
@@ -227,7 +233,7 @@ impl GraphqlResolver {
             .map(move |result| {
                 let mut response = new_response();
                 let (parent_object, part) = response.create_root_part();
-                let state = part.into_seed_state(shape_id);
+                let state = part.into_seed_state(shape_id).with_limits(limits);
 
                 match result {
                     Ok(bytes) => {
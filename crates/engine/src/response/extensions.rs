@@ -1,4 +1,5 @@
 use grafbase_telemetry::otel::opentelemetry::trace::TraceId;
+use operation::ComplexityCost;
 use schema::Schema;
 use serde::Serialize;
 use walker::Walk;
@@ -46,6 +47,15 @@ pub(crate) struct GrafbaseResponseExtension {
     trace_id: Option<TraceId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     query_plan: Option<QueryPlan>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cost: Option<OperationCost>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OperationCost {
+    estimated: usize,
+    actual: usize,
 }
 
 impl GrafbaseResponseExtension {
@@ -53,6 +63,7 @@ impl GrafbaseResponseExtension {
         Self {
             trace_id: self.trace_id.or(other.trace_id),
             query_plan: self.query_plan.or(other.query_plan),
+            cost: self.cost.or(other.cost),
         }
     }
 }
@@ -63,6 +74,14 @@ impl GrafbaseResponseExtension {
         self
     }
 
+    pub fn with_cost(mut self, estimated: ComplexityCost, actual: ComplexityCost) -> Self {
+        self.cost = Some(OperationCost {
+            estimated: estimated.0,
+            actual: actual.0,
+        });
+        self
+    }
+
     pub fn with_query_plan(mut self, schema: &Schema, prepared_operation: &PreparedOperation) -> Self {
         let mut nodes = Vec::with_capacity(prepared_operation.plan.plans.len());
         // at least one edge.
@@ -93,12 +93,21 @@ where
             values.reserve(size_hint);
         }
 
+        state.check_depth()?;
+
         loop {
             state.local_path_mut().push(list_type.make_response_value_id(index));
             let result = seq.next_element_seed(list_type.seed());
             state.local_path_mut().pop();
             match result {
                 Ok(Some(value)) => {
+                    if let Some(max_list_length) = state.limits.max_list_length
+                        && values.len() >= max_list_length
+                    {
+                        return Err(state.response_limit_exceeded(format!(
+                            "Subgraph response list exceeded the maximum length of {max_list_length}"
+                        )));
+                    }
                     values.push(value);
                     index += 1;
                 }
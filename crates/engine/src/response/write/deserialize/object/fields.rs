@@ -401,6 +401,7 @@ impl<'ctx> ConcreteShapeFieldsSeed<'ctx, '_, '_> {
         }
         .with_query_position_if(included);
 
+        self.state.check_depth()?;
         self.state.local_path_mut().push(ResponseValueId::field(
             self.object_id,
             key,
@@ -414,6 +415,7 @@ impl<'ctx> ConcreteShapeFieldsSeed<'ctx, '_, '_> {
         self.state.local_path_mut().pop();
 
         let value = result?;
+        self.state.record_field_occurrences(field, &value);
 
         response_fields.push(ResponseObjectField { key, value });
 
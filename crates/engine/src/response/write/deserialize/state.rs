@@ -5,16 +5,18 @@ use crate::{
         CachedOperationContext, DataOrLookupFieldId, FieldShapeRecord, OperationPlanContext, PreparedOperation,
         RootFieldsShape, RootFieldsShapeId,
     },
-    response::{ResponsePartBuilder, ResponseValueId},
+    response::{DataPart, GraphqlError, ResponsePartBuilder, ResponseValue, ResponseValueId},
 };
+use error::ErrorCode;
 use operation::ResponseKeys;
-use schema::Schema;
+use schema::{Schema, SubgraphResponseLimits};
 use walker::Walk as _;
 
 pub(crate) struct SeedState<'ctx, 'parent> {
     pub schema: &'ctx Schema,
     pub operation: &'ctx PreparedOperation,
     pub root_shape: RootFieldsShape<'ctx>,
+    pub(super) limits: SubgraphResponseLimits,
 
     // -- mutable parts --
     // Range isn't copy...
@@ -58,6 +60,7 @@ impl<'ctx, 'parent> SeedState<'ctx, 'parent> {
             schema,
             operation,
             root_shape,
+            limits: Default::default(),
             response: RefCell::new(response_part),
             bubbling_up_deser_error: Default::default(),
             local_path: Default::default(),
@@ -65,6 +68,11 @@ impl<'ctx, 'parent> SeedState<'ctx, 'parent> {
         }
     }
 
+    pub fn with_limits(mut self, limits: SubgraphResponseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     pub fn into_response_part(self) -> ResponsePartBuilder<'ctx> {
         self.response.into_inner()
     }
@@ -95,6 +103,49 @@ impl<'ctx, 'parent> SeedState<'ctx, 'parent> {
         (self.parent_path.get(), self.local_path.borrow())
     }
 
+    /// Fails the deserialization if going one level deeper would exceed the configured maximum depth.
+    pub(super) fn check_depth<E: serde::de::Error>(&self) -> Result<(), E> {
+        match self.limits.max_depth {
+            Some(max_depth) if self.local_path.borrow().len() >= max_depth => {
+                Err(self
+                    .response_limit_exceeded(format!("Subgraph response exceeded the maximum depth of {max_depth}")))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Reports a subgraph response limit violation at the current path and returns the error
+    /// stopping the deserialization.
+    pub(super) fn response_limit_exceeded<E: serde::de::Error>(&self, message: String) -> E {
+        if !self.bubbling_up_deser_error.replace(true) {
+            tracing::error!("{message} at path '{}'", self.display_path());
+            let mut resp = self.response.borrow_mut();
+            let path = self.path();
+            resp.propagate_null(&path);
+            resp.errors
+                .push(GraphqlError::new(message.clone(), ErrorCode::SubgraphInvalidResponseError).with_path(path));
+        }
+        E::custom(message)
+    }
+
+    /// Counts how many times a query field appears in the response, list items included, to
+    /// compute the actual cost of the operation.
+    pub(super) fn record_field_occurrences(&self, field: &FieldShapeRecord, value: &ResponseValue) {
+        let DataOrLookupFieldId::Data(id) = field.id else {
+            return;
+        };
+        if self.operation.complexity_cost.is_none() || !self.should_report_error_for(field) {
+            return;
+        }
+
+        let mut resp = self.response.borrow_mut();
+        let occurrences = match field.wrapping.list_wrappings().len() {
+            0 => 1,
+            depth => count_list_items(&resp.data, value, depth),
+        };
+        *resp.field_occurrences.entry(id).or_default() += occurrences;
+    }
+
     pub(super) fn should_report_error_for(&self, field: &FieldShapeRecord) -> bool {
         field.query_position_before_modifications.is_some()
             && match field.id {
@@ -106,6 +157,20 @@ impl<'ctx, 'parent> SeedState<'ctx, 'parent> {
     }
 }
 
+/// Number of items of the innermost lists, so `[[1, 2], [3]]` counts as 3.
+fn count_list_items(data: &DataPart, value: &ResponseValue, depth: usize) -> usize {
+    match *value {
+        ResponseValue::List { id } if depth > 1 => data[id.list_id]
+            .iter()
+            .map(|item| count_list_items(data, item, depth - 1))
+            .sum(),
+        ResponseValue::List { id } => data[id.list_id].len(),
+        ResponseValue::IntList { id } => data[id.list_id].len(),
+        ResponseValue::FloatList { id } => data[id.list_id].len(),
+        _ => 0,
+    }
+}
+
 struct DisplayPath<'a> {
     keys: &'a ResponseKeys,
     parent_path: &'a [ResponseValueId],
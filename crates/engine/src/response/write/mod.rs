@@ -4,7 +4,9 @@ mod part;
 
 use std::sync::Arc;
 
+use fxhash::FxHashMap;
 use grafbase_telemetry::graphql::{GraphqlOperationAttributes, GraphqlResponseStatus};
+use operation::ComplexityCost;
 use schema::{ObjectDefinitionId, Schema};
 use walker::Walk;
 
@@ -12,7 +14,7 @@ use super::{
     DataParts, ErrorPartBuilder, ErrorParts, ExecutedResponse, GraphqlError, Response, ResponseData, ResponseObject,
     ResponseObjectId, ResponseObjectRef, ResponseObjectSet, ResponseValueId,
 };
-use crate::prepare::{OperationPlanContext, PreparedOperation, ResponseObjectSetId};
+use crate::prepare::{DataFieldId, OperationPlanContext, PreparedOperation, ResponseObjectSetId};
 pub(crate) use deserialize::*;
 pub(crate) use part::*;
 
@@ -24,6 +26,9 @@ pub(crate) struct ResponseBuilder<'ctx> {
     pub(super) data_parts: DataParts,
     pub(super) error_parts: ErrorParts,
     errors: ErrorPartBuilder<'ctx>,
+    // Number of times each query field appeared in the response, list items included. Only
+    // collected if the complexity control is enabled.
+    field_occurrences: FxHashMap<DataFieldId, usize>,
 }
 
 impl<'ctx> ResponseBuilder<'ctx> {
@@ -41,6 +46,7 @@ impl<'ctx> ResponseBuilder<'ctx> {
             data_parts,
             error_parts: ErrorParts::default(),
             errors: ErrorPartBuilder::new(operation),
+            field_occurrences: FxHashMap::default(),
         }
     }
 
@@ -89,6 +95,10 @@ impl<'ctx> ResponseBuilder<'ctx> {
             return PartIngestionResult::SubgraphFailure;
         }
 
+        for (id, occurrences) in part.field_occurrences {
+            *self.field_occurrences.entry(id).or_default() += occurrences;
+        }
+
         let mut has_ingested_data = false;
         let ctx = OperationPlanContext::from((self.schema.as_ref(), self.operation.as_ref()));
         for update in part.object_updates {
@@ -132,6 +142,26 @@ impl<'ctx> ResponseBuilder<'ctx> {
         }
     }
 
+    /// Cost of the operation computed from what the subgraphs actually returned rather than the
+    /// assumed list sizes. Only available if the complexity control is enabled.
+    pub fn actual_complexity_cost(&self) -> Option<ComplexityCost> {
+        self.operation.complexity_cost?;
+
+        let mut occurrences = FxHashMap::default();
+        for (&id, &count) in &self.field_occurrences {
+            let field = &self.operation.cached.query_plan[id];
+            *occurrences
+                .entry((field.response_key, field.definition_id))
+                .or_default() += count;
+        }
+
+        Some(self.operation.cached.operation.compute_actual_complexity(
+            self.schema,
+            &self.operation.variables,
+            |key, definition_id| occurrences.get(&(key, definition_id)).copied().unwrap_or_default(),
+        ))
+    }
+
     pub fn build(mut self, operation_attributes: GraphqlOperationAttributes) -> Response {
         self.error_parts.push(self.errors);
 
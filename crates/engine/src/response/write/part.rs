@@ -1,9 +1,12 @@
+use fxhash::FxHashMap;
 use id_newtypes::IdRange;
 use schema::Schema;
 use walker::Walk as _;
 
 use crate::{
-    prepare::{DefaultFieldShapeId, OnRootFieldsError, PreparedOperation, ResponseObjectSetId, RootFieldsShapeId},
+    prepare::{
+        DataFieldId, DefaultFieldShapeId, OnRootFieldsError, PreparedOperation, ResponseObjectSetId, RootFieldsShapeId,
+    },
     response::{
         DataPart, ErrorPartBuilder, GraphqlError, ResponseObjectField, ResponseObjectId, ResponseObjectRef,
         ResponseObjectSet, ResponsePath, ResponseValueId,
@@ -22,6 +25,7 @@ pub(crate) struct ResponsePartBuilder<'ctx> {
     pub(super) propagated_null_at: Vec<ResponseValueId>,
     pub(super) object_updates: Vec<ObjectUpdate>,
     pub(super) object_sets: Vec<(ResponseObjectSetId, ResponseObjectSet)>,
+    pub(super) field_occurrences: FxHashMap<DataFieldId, usize>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, id_derives::Id)]
@@ -39,6 +43,7 @@ impl<'ctx> ResponsePartBuilder<'ctx> {
             propagated_null_up_to_root: false,
             propagated_null_at: Vec::new(),
             object_sets: Vec::new(),
+            field_occurrences: FxHashMap::default(),
         }
     }
 
@@ -33,6 +33,7 @@ pub struct ExecutedOperation {
     pub status: GraphqlResponseStatus,
     pub operation_type: OperationType,
     pub complexity: Option<u64>,
+    pub actual_complexity: Option<u64>,
     pub has_deprecated_fields: bool,
 }
 
@@ -52,6 +53,7 @@ impl ExecutedOperation {
             status: GraphqlResponseStatus::Success,
             operation_type,
             complexity: None,
+            actual_complexity: None,
             has_deprecated_fields: false,
         }
     }
@@ -148,6 +150,9 @@ pub enum CacheStatus {
     Hit,
     /// Part of the response was cached, but some data required fetching
     PartialHit,
+    /// Expired cached data was served, either while being refreshed in the background or because
+    /// the subgraph request failed
+    Stale,
     /// No cached data was available; a full fetch was required
     Miss,
 }
@@ -1,4 +1,10 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+};
 
 use ::engine::CachedOperation;
 use engine::ContractAwareEngine;
@@ -10,7 +16,10 @@ use tokio::{
 use wasi_component_loader::extension::GatewayWasmExtensions;
 
 use super::{EngineBuildContext, EngineRuntime};
-use crate::{events::UpdateEvent, graph::Graph, router::EngineWatcher};
+use crate::{
+    events::UpdateEvent, extensions::create_extension_catalog, graph::Graph, hot_reload::ExtensionWatcher,
+    router::EngineWatcher,
+};
 
 use super::AccessToken;
 
@@ -35,6 +44,9 @@ pub(crate) struct EngineReloaderConfig {
     pub access_token: Option<AccessToken>,
 
     pub gateway_extensions: GatewayWasmExtensions,
+
+    /// Watches the extensions for changes, if hot reload is enabled
+    pub extension_watcher: Option<ExtensionWatcher>,
 }
 
 /// Handles graph and config updates by constructing a new engine
@@ -58,6 +70,7 @@ impl EngineReloader {
             hot_reload_config_path,
             access_token,
             gateway_extensions,
+            extension_watcher,
         }: EngineReloaderConfig,
     ) -> crate::Result<Self> {
         let mut current_config = initial_config;
@@ -72,6 +85,7 @@ impl EngineReloader {
                     current_config = *new_config;
                     continue;
                 }
+                Some(UpdateEvent::Extensions) => continue,
                 None => {
                     return Err(crate::Error::InternalError(
                         "Update channel closed before initial graph definition".into(),
@@ -95,9 +109,16 @@ impl EngineReloader {
         let engine = build_engine(initial_context, graph.clone(), vec![]).await?;
         let (engine_sender, engine_watcher) = watch::channel(engine);
 
+        let extension_watcher = extension_watcher.map(|mut watcher| {
+            watcher.watch(&extension_catalog);
+            Arc::new(Mutex::new(watcher))
+        });
+
         tokio::spawn(async move {
             let mut in_progress_reload: Option<JoinHandle<()>> = None;
 
+            let mut extension_generations = ExtensionGenerations::default();
+
             while let Some(update) = update_receiver.recv().await {
                 // Abort any in-progress reload
                 if let Some(reload) = in_progress_reload.take() {
@@ -106,7 +127,11 @@ impl EngineReloader {
 
                 match update {
                     UpdateEvent::Graph(new_graph) => graph = new_graph,
-                    UpdateEvent::Config(new_config) => current_config = *new_config,
+                    UpdateEvent::Config(new_config) => {
+                        current_config = *new_config;
+                        extension_generations.bump();
+                    }
+                    UpdateEvent::Extensions => extension_generations.bump(),
                 }
 
                 in_progress_reload = Some(tokio::spawn({
@@ -117,21 +142,63 @@ impl EngineReloader {
                     let engine_sender = engine_sender.clone();
                     let logging_filter = logging_filter.clone();
                     let gateway_extensions = gateway_extensions.clone();
+                    let extension_watcher = extension_watcher.clone();
+                    let extension_generations = extension_generations.clone();
 
                     async move {
                         let operations_to_warm = extract_operations_to_warm(&current_config, &engine_sender);
 
+                        let extension_catalog = match create_extension_catalog(&current_config).await {
+                            Ok(catalog) => Arc::new(catalog),
+                            Err(err) => {
+                                tracing::error!("Could not load the extensions: {err}");
+                                return;
+                            }
+                        };
+
+                        let new_gateway_extensions = if extension_generations.needs_rebuild() {
+                            match GatewayWasmExtensions::new(
+                                &extension_catalog,
+                                &current_config,
+                                logging_filter.clone(),
+                            )
+                            .await
+                            {
+                                Ok(extensions) => Some(extensions),
+                                Err(err) => {
+                                    tracing::error!("Could not load the gateway extensions: {err}");
+                                    return;
+                                }
+                            }
+                        } else {
+                            None
+                        };
+
                         let context = EngineBuildContext {
                             gateway_config: &current_config,
                             hot_reload_config_path: hot_reload_config_path.as_ref(),
                             access_token: access_token.as_ref(),
-                            extension_catalog: None, // Will be created by gateway::generate if needed
+                            extension_catalog: Some(&extension_catalog),
                             logging_filter: &logging_filter,
                             gateway_extensions: &gateway_extensions,
                         };
 
                         match build_engine(context, graph, operations_to_warm).await {
                             Ok(new_engine) => {
+                                // Swapped together with the engine. Calls in progress keep their
+                                // instances of the previous extensions until they complete.
+                                if let Some(new_gateway_extensions) = new_gateway_extensions {
+                                    gateway_extensions.replace(new_gateway_extensions);
+                                    extension_generations.mark_applied();
+                                }
+
+                                if let Some(watcher) = extension_watcher {
+                                    watcher
+                                        .lock()
+                                        .unwrap_or_else(PoisonError::into_inner)
+                                        .watch(&extension_catalog);
+                                }
+
                                 if let Err(err) = engine_sender.send(new_engine) {
                                     tracing::error!("Could not send engine: {err:?}");
                                 }
@@ -155,6 +222,29 @@ impl EngineReloader {
     }
 }
 
+/// Tracks whether the gateway extensions must be rebuilt. Configuration and extension changes bump
+/// the requested generation, which only a successful reload applies. Reloads get a copy of it, so
+/// an aborted reload leaves the rebuild pending for the next one.
+#[derive(Clone, Default)]
+struct ExtensionGenerations {
+    requested: u64,
+    applied: Arc<AtomicU64>,
+}
+
+impl ExtensionGenerations {
+    fn bump(&mut self) {
+        self.requested += 1;
+    }
+
+    fn needs_rebuild(&self) -> bool {
+        self.applied.load(Ordering::Acquire) < self.requested
+    }
+
+    fn mark_applied(&self) {
+        self.applied.fetch_max(self.requested, Ordering::AcqRel);
+    }
+}
+
 /// Helper function that builds a new engine instance.
 async fn build_engine(
     context: EngineBuildContext<'_>,
@@ -192,3 +282,47 @@ fn extract_operations_to_warm(
         .take(cache_count * (config.operation_caching.warming_percent as usize / 100))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extension_generations_survive_aborted_reloads() {
+        let mut generations = ExtensionGenerations::default();
+        assert!(!generations.needs_rebuild());
+
+        generations.bump();
+        let aborted_reload = generations.clone();
+        assert!(aborted_reload.needs_rebuild());
+
+        // A graph update aborts the reload before it applied the new extensions, the next reload
+        // must still rebuild them.
+        let graph_reload = generations.clone();
+        assert!(graph_reload.needs_rebuild());
+        graph_reload.mark_applied();
+        assert!(!generations.needs_rebuild());
+
+        // Another graph update doesn't rebuild the extensions again.
+        assert!(!generations.clone().needs_rebuild());
+    }
+
+    #[test]
+    fn extension_generations_ignore_stale_reloads() {
+        let mut generations = ExtensionGenerations::default();
+
+        generations.bump();
+        let stale_reload = generations.clone();
+        generations.bump();
+
+        // A reload started before the latest change doesn't mark it as applied.
+        stale_reload.mark_applied();
+        assert!(generations.needs_rebuild());
+
+        generations.clone().mark_applied();
+        assert!(!generations.needs_rebuild());
+
+        stale_reload.mark_applied();
+        assert!(!generations.needs_rebuild());
+    }
+}
@@ -2,12 +2,16 @@ use std::{env, sync::Arc};
 
 use ::engine::{CachedOperation, Schema};
 use extension_catalog::ExtensionCatalog;
-use gateway_config::{EntityCachingRedisConfig, operation_caching::OperationCacheConfig};
+use gateway_config::{EntityCachingRedisConfig, FieldUsageRedisConfig, operation_caching::OperationCacheConfig};
 use grafbase_telemetry::metrics::EngineMetrics;
 use hive_console_sdk::persisted_documents::PersistedDocumentsManager;
-use runtime::{entity_cache::EntityCache, trusted_documents_client::TrustedDocumentsEnforcementMode};
+use runtime::{
+    entity_cache::EntityCache, field_usage::FieldUsageCollector,
+    trusted_documents_client::TrustedDocumentsEnforcementMode,
+};
 use runtime_local::{
     InMemoryEntityCache, InMemoryOperationCache, NativeFetcher, RedisEntityCache,
+    field_usage::{FieldUsageStorage, LocalFieldUsageCollector},
     operation_cache::{RedisOperationCache, TieredOperationCache},
     rate_limiting::{in_memory::key_based::InMemoryRateLimiter, redis::RedisRateLimiter},
     redis::{RedisPoolFactory, RedisTlsConfig},
@@ -37,6 +41,7 @@ pub struct EngineRuntime {
     entity_cache_config: gateway_config::EntityCachingConfig,
     pub(crate) operation_cache: TieredOperationCache<Arc<CachedOperation>>,
     operation_cache_config: OperationCacheConfig,
+    field_usage_collector: Arc<dyn FieldUsageCollector>,
     redis_factory: Arc<tokio::sync::Mutex<RedisPoolFactory>>,
 }
 
@@ -82,6 +87,9 @@ impl EngineRuntime {
         let entity_cache = build_entity_cache(&ctx.gateway_config.entity_caching, &mut redis_factory)?;
         let operation_cache = build_operation_cache(&ctx.gateway_config.operation_caching, &mut redis_factory)?;
 
+        tracing::debug!("Building field usage collector");
+        let field_usage_collector = build_field_usage_collector(&ctx.gateway_config.field_usage, &mut redis_factory)?;
+
         tracing::debug!("Building extensions");
 
         let extensions = EngineWasmExtensions::new(
@@ -146,6 +154,7 @@ impl EngineRuntime {
             entity_cache_config: ctx.gateway_config.entity_caching.clone(),
             operation_cache,
             operation_cache_config: ctx.gateway_config.operation_caching.clone(),
+            field_usage_collector,
             redis_factory: Arc::new(tokio::sync::Mutex::new(redis_factory)),
         };
 
@@ -182,6 +191,10 @@ impl engine::Runtime for EngineRuntime {
         self.entity_cache.as_ref()
     }
 
+    fn field_usage_collector(&self) -> &dyn FieldUsageCollector {
+        self.field_usage_collector.as_ref()
+    }
+
     fn metrics(&self) -> &grafbase_telemetry::metrics::EngineMetrics {
         &self.metrics
     }
@@ -210,6 +223,8 @@ impl engine::Runtime for EngineRuntime {
             entity_cache_config: self.entity_cache_config.clone(),
             operation_cache,
             operation_cache_config: self.operation_cache_config.clone(),
+            // Contracts are subsets of the main schema, so usage is recorded against the latter.
+            field_usage_collector: self.field_usage_collector.clone(),
             redis_factory: self.redis_factory.clone(),
         })
     }
@@ -261,3 +276,33 @@ fn build_operation_cache(
         }
     })
 }
+
+fn build_field_usage_collector(
+    config: &gateway_config::FieldUsageConfig,
+    redis_factory: &mut RedisPoolFactory,
+) -> Result<Arc<dyn FieldUsageCollector>, crate::Error> {
+    if !config.enabled {
+        return Ok(Arc::new(()));
+    }
+
+    let storage = match config.storage {
+        gateway_config::FieldUsageStorage::File => FieldUsageStorage::File(config.path.clone()),
+        gateway_config::FieldUsageStorage::Redis => {
+            let FieldUsageRedisConfig { url, key_prefix, tls } = &config.redis;
+            let tls = tls.as_ref().map(|tls| RedisTlsConfig {
+                cert: tls.cert.as_deref(),
+                key: tls.key.as_deref(),
+                ca: tls.ca.as_deref(),
+            });
+            let pool = redis_factory
+                .pool(url.as_str(), tls)
+                .map_err(|e| crate::Error::InternalError(e.to_string()))?;
+            FieldUsageStorage::Redis {
+                pool,
+                key_prefix: key_prefix.clone(),
+            }
+        }
+    };
+
+    Ok(Arc::new(LocalFieldUsageCollector::new(storage, config.flush_interval)))
+}
@@ -11,6 +11,8 @@ pub(crate) enum UpdateEvent {
     Graph(Graph),
     /// A configuration update event
     Config(Box<Config>),
+    /// The Wasm module of an extension changed on disk
+    Extensions,
 }
 
 impl std::fmt::Display for UpdateEvent {
@@ -18,6 +20,7 @@ impl std::fmt::Display for UpdateEvent {
         match self {
             Self::Graph(_) => write!(f, "Graph update"),
             Self::Config(_) => write!(f, "Config update"),
+            Self::Extensions => write!(f, "Extensions update"),
         }
     }
 }
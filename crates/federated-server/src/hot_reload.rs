@@ -1,8 +1,11 @@
 use std::{fs, path::PathBuf, sync::OnceLock, time::Duration};
 
+use extension_catalog::ExtensionCatalog;
 use gateway_config::Config;
 use notify::{EventHandler, EventKind, PollWatcher, Watcher};
-use tokio::sync::watch;
+use tokio::sync::{mpsc, watch};
+
+use crate::events::UpdateEvent;
 
 /// A watcher for configuration files that monitors changes and sends updates.
 ///
@@ -118,3 +121,141 @@ impl EventHandler for ConfigWatcher {
         }
     }
 }
+
+/// A watcher for the Wasm modules of the extensions, triggering a reload of the extensions when
+/// they are rebuilt or re-installed.
+pub(crate) struct ExtensionWatcher {
+    watcher: PollWatcher,
+    /// The paths currently being watched.
+    paths: Vec<PathBuf>,
+}
+
+impl ExtensionWatcher {
+    /// Creates a watcher sending an [UpdateEvent::Extensions] whenever a watched extension changes.
+    /// Nothing is watched until [ExtensionWatcher::watch] is called.
+    pub fn new(update_sender: mpsc::Sender<UpdateEvent>) -> crate::Result<Self> {
+        let config = notify::Config::default().with_poll_interval(Duration::from_secs(1));
+        let watcher = PollWatcher::new(ExtensionChangeHandler { update_sender }, config)
+            .map_err(|e| crate::Error::InternalError(format!("extension watch init failed: {e}")))?;
+
+        Ok(Self {
+            watcher,
+            paths: Vec::new(),
+        })
+    }
+
+    /// Watches the extensions of the catalog, replacing the previously watched ones.
+    pub fn watch(&mut self, catalog: &ExtensionCatalog) {
+        for path in self.paths.drain(..) {
+            self.watcher.unwatch(&path).ok();
+        }
+
+        for extension in catalog.iter() {
+            let path = extension.wasm_path.clone();
+
+            match self.watcher.watch(&path, notify::RecursiveMode::NonRecursive) {
+                Ok(()) => self.paths.push(path),
+                Err(e) => tracing::error!("error watching extension {}: {e}", path.display()),
+            }
+        }
+    }
+}
+
+struct ExtensionChangeHandler {
+    update_sender: mpsc::Sender<UpdateEvent>,
+}
+
+impl EventHandler for ExtensionChangeHandler {
+    fn handle_event(&mut self, event: notify::Result<notify::Event>) {
+        match event.map(|e| e.kind) {
+            Ok(EventKind::Any | EventKind::Create(_) | EventKind::Modify(_) | EventKind::Other) => {
+                tracing::info!("detected an extension change, reloading");
+
+                // Called from the watcher thread, outside of the async runtime.
+                self.update_sender.blocking_send(UpdateEvent::Extensions).ok();
+            }
+            Ok(_) => (),
+            Err(e) => {
+                tracing::error!("error watching extensions: {e}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use extension_catalog::{Extension, VersionedManifest};
+
+    use super::*;
+
+    fn catalog(wasm_path: &Path) -> ExtensionCatalog {
+        let manifest: VersionedManifest = serde_json::from_value(serde_json::json!({
+            "manifest": "v1",
+            "id": { "name": "test", "version": "0.1.0" },
+            "type": { "FieldResolver": {} },
+            "sdk_version": "0.1.0",
+            "minimum_gateway_version": "0.1.0",
+            "description": "test extension",
+        }))
+        .unwrap();
+
+        let mut catalog = ExtensionCatalog::default();
+        catalog.push(Extension {
+            config_key: "test".to_owned(),
+            manifest: manifest.into_latest(),
+            wasm_path: wasm_path.to_path_buf(),
+        });
+        catalog
+    }
+
+    fn next_update(rt: &tokio::runtime::Runtime, receiver: &mut mpsc::Receiver<UpdateEvent>) -> Option<UpdateEvent> {
+        rt.block_on(tokio::time::timeout(Duration::from_secs(5), receiver.recv()))
+            .ok()
+            .flatten()
+    }
+
+    #[test]
+    fn wasm_replacement_triggers_extensions_update() {
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let wasm_path = dir.path().join("extension.wasm");
+        fs::write(&wasm_path, b"old module").unwrap();
+
+        let (sender, mut receiver) = mpsc::channel(16);
+        let mut watcher = ExtensionWatcher::new(sender).unwrap();
+        watcher.watch(&catalog(&wasm_path));
+
+        fs::write(&wasm_path, b"the new module").unwrap();
+
+        assert!(matches!(next_update(&rt, &mut receiver), Some(UpdateEvent::Extensions)));
+    }
+
+    #[test]
+    fn extensions_removed_from_the_catalog_are_not_watched() {
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let old_path = dir.path().join("old.wasm");
+        let new_path = dir.path().join("new.wasm");
+        fs::write(&old_path, b"old module").unwrap();
+        fs::write(&new_path, b"new module").unwrap();
+
+        let (sender, mut receiver) = mpsc::channel(16);
+        let mut watcher = ExtensionWatcher::new(sender).unwrap();
+        watcher.watch(&catalog(&old_path));
+        watcher.watch(&catalog(&new_path));
+
+        fs::write(&old_path, b"the old module, modified").unwrap();
+        assert!(next_update(&rt, &mut receiver).is_none());
+
+        fs::write(&new_path, b"the new module, modified").unwrap();
+        assert!(matches!(next_update(&rt, &mut receiver), Some(UpdateEvent::Extensions)));
+    }
+}
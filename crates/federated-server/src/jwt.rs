@@ -0,0 +1,233 @@
+//! Built-in JWT authentication, validating tokens against a JSON Web Key Set or a PEM encoded
+//! public key without going through an authentication extension.
+
+use std::{
+    str::FromStr,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use gateway_config::JwtAuthenticationConfig;
+use http::HeaderMap;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, jwk::JwkSet};
+use runtime::extension::Token;
+
+const DEFAULT_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+/// Attempts made to fetch the JSON Web Key Set before giving up at startup.
+const JWKS_STARTUP_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry of a failed JSON Web Key Set fetch, doubled on each subsequent
+/// failure up to the refresh interval.
+const JWKS_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+pub(crate) struct JwtAuthenticator {
+    keys: RwLock<Arc<[Key]>>,
+    validation: Validation,
+    header_name: http::HeaderName,
+    header_value_prefix: String,
+    cookie_name: Option<String>,
+}
+
+struct Key {
+    id: Option<String>,
+    key: DecodingKey,
+}
+
+impl JwtAuthenticator {
+    /// Validates the configuration and loads the keys. With a `jwks_url`, the key set is fetched
+    /// before returning, retrying a few times, and then refreshed periodically in a background task.
+    pub(crate) async fn new(config: &JwtAuthenticationConfig) -> crate::Result<Arc<Self>> {
+        let invalid = |message: String| format!("Invalid JWT authentication configuration: {message}");
+
+        let sources = [
+            config.jwks_url.is_some(),
+            config.jwks_path.is_some(),
+            config.pem_path.is_some(),
+        ];
+        if sources.into_iter().filter(|is_set| *is_set).count() != 1 {
+            return Err(invalid("exactly one of jwks_url, jwks_path or pem_path must be set".into()).into());
+        }
+
+        let algorithms = if config.algorithms.is_empty() {
+            DEFAULT_ALGORITHMS.to_vec()
+        } else {
+            config
+                .algorithms
+                .iter()
+                .map(|alg| Algorithm::from_str(alg).map_err(|_| invalid(format!("unknown algorithm {alg}"))))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        let mut validation = Validation::new(algorithms[0]);
+        validation.algorithms = algorithms;
+        validation.leeway = config.clock_skew.as_secs();
+        if let Some(issuer) = &config.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        if config.audience.is_empty() {
+            validation.validate_aud = false;
+        } else {
+            validation.set_audience(&config.audience);
+        }
+
+        let header_name = http::HeaderName::from_str(&config.header_name)
+            .map_err(|_| invalid(format!("invalid header name {}", config.header_name)))?;
+
+        let client = reqwest::Client::new();
+
+        let keys: Arc<[Key]> = if let Some(url) = &config.jwks_url {
+            let mut delay = JWKS_RETRY_DELAY;
+            let mut attempt = 1;
+
+            let jwks = loop {
+                match fetch_jwks(&client, url).await {
+                    Ok(jwks) => break jwks,
+                    Err(err) if attempt < JWKS_STARTUP_ATTEMPTS => {
+                        tracing::warn!("Failed to fetch the JSON Web Key Set from {url}, retrying in {delay:?}: {err}");
+                        tokio::time::sleep(delay).await;
+                        delay *= 2;
+                        attempt += 1;
+                    }
+                    Err(err) => {
+                        return Err(format!("Failed to fetch the JSON Web Key Set from {url}: {err}").into());
+                    }
+                }
+            };
+
+            keys_from_jwks(jwks).into()
+        } else if let Some(path) = &config.jwks_path {
+            let content = std::fs::read(path).map_err(|err| invalid(format!("reading {}: {err}", path.display())))?;
+            let jwks = serde_json::from_slice(&content)
+                .map_err(|err| invalid(format!("parsing {}: {err}", path.display())))?;
+            keys_from_jwks(jwks).into()
+        } else if let Some(path) = &config.pem_path {
+            let content = std::fs::read(path).map_err(|err| invalid(format!("reading {}: {err}", path.display())))?;
+            let key = DecodingKey::from_rsa_pem(&content)
+                .or_else(|_| DecodingKey::from_ec_pem(&content))
+                .or_else(|_| DecodingKey::from_ed_pem(&content))
+                .map_err(|err| invalid(format!("parsing {}: {err}", path.display())))?;
+            vec![Key { id: None, key }].into()
+        } else {
+            unreachable!("exactly one key source is set")
+        };
+
+        let authenticator = Arc::new(Self {
+            keys: RwLock::new(keys),
+            validation,
+            header_name,
+            header_value_prefix: config.header_value_prefix.clone(),
+            cookie_name: config.cookie_name.clone(),
+        });
+
+        if let Some(url) = config.jwks_url.clone() {
+            let authenticator = Arc::downgrade(&authenticator);
+            let refresh_interval = config.jwks_refresh_interval;
+
+            tokio::spawn(async move {
+                let mut delay = refresh_interval;
+
+                loop {
+                    tokio::time::sleep(delay).await;
+
+                    let Some(authenticator) = authenticator.upgrade() else {
+                        break;
+                    };
+
+                    delay = match fetch_jwks(&client, &url).await {
+                        Ok(jwks) => {
+                            *authenticator.keys.write().unwrap() = keys_from_jwks(jwks).into();
+                            refresh_interval
+                        }
+                        Err(err) => {
+                            // Retry sooner than the refresh interval, backing off on repeated failures.
+                            let delay = if delay >= refresh_interval {
+                                JWKS_RETRY_DELAY
+                            } else {
+                                delay * 2
+                            }
+                            .min(refresh_interval);
+
+                            tracing::error!(
+                                "Failed to fetch the JSON Web Key Set from {url}, retrying in {delay:?}: {err}"
+                            );
+                            delay
+                        }
+                    };
+                }
+            });
+        }
+
+        Ok(authenticator)
+    }
+
+    /// Returns the claims of the request token if present and valid.
+    pub(crate) fn authenticate(&self, headers: &HeaderMap) -> Option<Token> {
+        let token = self.extract_token(headers)?;
+        let header = jsonwebtoken::decode_header(token).ok()?;
+
+        if !self.validation.algorithms.contains(&header.alg) {
+            return None;
+        }
+
+        // The validation algorithms must all belong to the family of the key.
+        let mut validation = self.validation.clone();
+        validation.algorithms = vec![header.alg];
+
+        let keys = self.keys.read().unwrap().clone();
+
+        keys.iter()
+            .filter(|key| header.kid.is_none() || key.id.is_none() || key.id == header.kid)
+            .find_map(|key| {
+                jsonwebtoken::decode::<serde_json::Map<String, serde_json::Value>>(token, &key.key, &validation).ok()
+            })
+            .and_then(|data| serde_json::to_vec(&data.claims).ok())
+            .map(|claims| Token::Bytes(claims.into()))
+    }
+
+    fn extract_token<'h>(&self, headers: &'h HeaderMap) -> Option<&'h str> {
+        if let Some(value) = headers.get(&self.header_name) {
+            return value.to_str().ok()?.strip_prefix(&self.header_value_prefix);
+        }
+
+        let cookie_name = self.cookie_name.as_deref()?;
+
+        headers
+            .get_all(http::header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find_map(|(name, value)| (name == cookie_name).then_some(value))
+    }
+}
+
+async fn fetch_jwks(client: &reqwest::Client, url: &url::Url) -> Result<JwkSet, reqwest::Error> {
+    client.get(url.clone()).send().await?.error_for_status()?.json().await
+}
+
+fn keys_from_jwks(jwks: JwkSet) -> Vec<Key> {
+    jwks.keys
+        .iter()
+        .filter_map(|jwk| match DecodingKey::from_jwk(jwk) {
+            Ok(key) => Some(Key {
+                id: jwk.common.key_id.clone(),
+                key,
+            }),
+            Err(err) => {
+                tracing::warn!("Ignoring invalid JSON Web Key: {err}");
+                None
+            }
+        })
+        .collect()
+}
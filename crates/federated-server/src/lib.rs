@@ -14,6 +14,7 @@ mod error;
 mod events;
 mod extensions;
 mod graph;
+mod jwt;
 pub mod router;
 mod serve;
 
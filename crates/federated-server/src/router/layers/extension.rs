@@ -9,7 +9,7 @@ use http::{Request, Response};
 use runtime::extension::{GatewayExtensions, OnRequest, Token};
 use tower::Layer;
 
-use crate::engine::into_axum_response;
+use crate::{engine::into_axum_response, jwt::JwtAuthenticator};
 
 #[derive(Clone)]
 pub struct ExtensionLayer<Ext>(Arc<ExtensionLayerInner<Ext>>);
@@ -17,6 +17,7 @@ pub struct ExtensionLayer<Ext>(Arc<ExtensionLayerInner<Ext>>);
 struct ExtensionLayerInner<Ext> {
     extensions: Ext,
     default_contract_key: Option<String>,
+    jwt: Option<Arc<JwtAuthenticator>>,
     authentication_extension_ids: Vec<ExtensionId>,
     default_authentication_behavior: Option<DefaultAuthenticationBehavior>,
     error_code_mapping: ErrorCodeMapping,
@@ -29,6 +30,7 @@ where
     pub fn new(
         extensions: Ext,
         default_contract_key: Option<String>,
+        jwt: Option<Arc<JwtAuthenticator>>,
         authentication_extension_ids: Vec<ExtensionId>,
         default_authentication_behavior: Option<DefaultAuthenticationBehavior>,
         error_code_mapping: ErrorCodeMapping,
@@ -36,6 +38,7 @@ where
         Self(Arc::new(ExtensionLayerInner {
             extensions,
             default_contract_key,
+            jwt,
             authentication_extension_ids,
             default_authentication_behavior,
             error_code_mapping,
@@ -105,8 +108,16 @@ where
                 }
             };
 
-            let result = if layer.authentication_extension_ids.is_empty() {
-                match layer.default_authentication_behavior {
+            // A configured built-in JWT authentication denies requests without a valid token unless
+            // told otherwise, like authentication extensions do.
+            let default_authentication_behavior = layer
+                .default_authentication_behavior
+                .or(layer.jwt.as_ref().map(|_| DefaultAuthenticationBehavior::Deny));
+
+            let result = if let Some(token) = layer.jwt.as_ref().and_then(|jwt| jwt.authenticate(&parts.headers)) {
+                Ok(token)
+            } else if layer.authentication_extension_ids.is_empty() {
+                match default_authentication_behavior {
                     Some(DefaultAuthenticationBehavior::Anonymous) | None => Ok(Token::Anonymous),
                     Some(DefaultAuthenticationBehavior::Deny) => {
                         Err(ErrorResponse::new(http::StatusCode::UNAUTHORIZED)
@@ -127,7 +138,7 @@ where
                 parts.headers = headers;
                 match result {
                     Ok(token) => Ok(token),
-                    Err(err) => match layer.default_authentication_behavior {
+                    Err(err) => match default_authentication_behavior {
                         Some(DefaultAuthenticationBehavior::Anonymous) => Ok(Token::Anonymous),
                         Some(DefaultAuthenticationBehavior::Deny) | None => Err(err),
                     },
@@ -18,9 +18,12 @@ use tower_http::{
     cors::CorsLayer,
 };
 
-use crate::router::{
-    layers::{ExtensionLayer, TelemetryLayer},
-    state::ServerState,
+use crate::{
+    jwt::JwtAuthenticator,
+    router::{
+        layers::{ExtensionLayer, TelemetryLayer},
+        state::ServerState,
+    },
 };
 
 use super::ServerRuntime;
@@ -56,6 +59,11 @@ where
     SR: ServerRuntime,
     E: GatewayExtensions,
 {
+    let jwt = match &config.authentication.jwt {
+        Some(jwt_config) => Some(JwtAuthenticator::new(jwt_config).await?),
+        None => None,
+    };
+
     let telemetry = TelemetryLayer::new_from_global_meter_provider(listen_address);
     let common_layers = {
         let cors = match config.cors {
@@ -123,6 +131,7 @@ where
                     &config,
                     &extension_catalog,
                     &extensions,
+                    jwt.clone(),
                     &config.authentication.protected_resources.graphql,
                 )?),
         );
@@ -162,6 +171,7 @@ where
                             &config,
                             &extension_catalog,
                             &extensions,
+                            jwt.clone(),
                             &config.authentication.protected_resources.mcp,
                         )?),
                 ),
@@ -189,6 +199,7 @@ fn build_extension_layer<E: GatewayExtensions>(
     gateway_config: &Config,
     extension_catalog: &ExtensionCatalog,
     extensions: &E,
+    jwt: Option<Arc<JwtAuthenticator>>,
     config: &AuthenticationResourcesConfig,
 ) -> crate::Result<ExtensionLayer<E>> {
     let extension_ids = config
@@ -224,6 +235,7 @@ fn build_extension_layer<E: GatewayExtensions>(
     Ok(layers::ExtensionLayer::new(
         extensions.clone(),
         gateway_config.graph.contracts.default_key.clone(),
+        jwt,
         extension_ids,
         config.default.or(gateway_config.authentication.default),
         gateway_config.graph.error_code_mapping.clone(),
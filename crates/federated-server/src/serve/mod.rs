@@ -19,6 +19,7 @@ use crate::{
     engine::{EngineReloader, EngineReloaderConfig},
     events::UpdateEvent,
     extensions::create_extension_catalog,
+    hot_reload::ExtensionWatcher,
     router::{self, RouterConfig},
 };
 
@@ -29,7 +30,7 @@ pub struct ServeConfig {
     pub config_receiver: watch::Receiver<Config>,
     /// The config file path for hot reload.
     pub config_path: Option<PathBuf>,
-    /// If true, watches changes to the config
+    /// If true, watches changes to the config and the extensions
     /// and reloads _some_ of the things.
     pub config_hot_reload: bool,
     /// The way of loading the graph for the gateway.
@@ -77,10 +78,15 @@ pub async fn serve(
     // Start the graph producer
     graph_loader.start_producer(update_sender.clone()).await?;
 
-    // Bridge config updates to the central channel if hot reload is enabled
-    if config_hot_reload {
+    // Bridge config updates to the central channel if hot reload is enabled, and reload the
+    // extensions when they are rebuilt.
+    let extension_watcher = if config_hot_reload {
+        let watcher = ExtensionWatcher::new(update_sender.clone())?;
         spawn_config_reloader(config_receiver, update_sender);
-    }
+        Some(watcher)
+    } else {
+        None
+    };
 
     // We separate the hooks extension, which runs outside of the engine in the axum layers.
     let extension_catalog = Arc::new(create_extension_catalog(&config).await?);
@@ -99,6 +105,7 @@ pub async fn serve(
         hot_reload_config_path: config_hot_reload.then_some(config_path).flatten(),
         access_token: grafbase_access_token,
         gateway_extensions: gateway_extensions.clone(),
+        extension_watcher,
     })
     .await?;
 
@@ -140,8 +147,10 @@ pub async fn serve(
 
 fn spawn_config_reloader(mut config_receiver: watch::Receiver<Config>, update_sender: mpsc::Sender<UpdateEvent>) {
     tokio::spawn(async move {
-        // drop the initial value
-        config_receiver.changed().await.ok();
+        // The initial configuration is already in use. Marking it as seen rather than waiting for
+        // the first change ensures that one isn't dropped when the sender only sends actual
+        // changes, like the dev command does.
+        config_receiver.borrow_and_update();
 
         while let Ok(()) = config_receiver.changed().await {
             let new_config = Box::new(config_receiver.borrow().clone());
@@ -0,0 +1,260 @@
+use std::{
+    fmt,
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+};
+
+/// An entry of the network allowlist of an extension. Written as a host with an optional port:
+///
+/// - `api.example.com` or `api.example.com:443` for a domain,
+/// - `*.example.com` for any subdomain of `example.com`,
+/// - `10.0.0.12`, `10.0.0.0/8:5432` or `[fd00::/8]:443` for an IP address or network.
+///
+/// IP address and network entries only match hosts written as IP addresses. Domains are matched
+/// before name resolution, so a domain resolving into an allowed network is not allowed by it.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(try_from = "String")]
+pub struct AllowedHost {
+    host: HostPattern,
+    port: Option<u16>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum HostPattern {
+    Domain(String),
+    Subdomains(String),
+    Network { address: IpAddr, prefix_len: u8 },
+}
+
+impl AllowedHost {
+    /// Whether a connection to the given host, a domain or an IP address, is allowed. Entries
+    /// with a port only allow connections with that same port.
+    pub fn allows(&self, host: &str, port: Option<u16>) -> bool {
+        if self.port.is_some() && self.port != port {
+            return false;
+        }
+
+        let host = host.trim_start_matches('[').trim_end_matches(']').trim_end_matches('.');
+
+        match &self.host {
+            HostPattern::Domain(domain) => host.eq_ignore_ascii_case(domain),
+            HostPattern::Subdomains(domain) => host
+                .len()
+                .checked_sub(domain.len() + 1)
+                .filter(|&dot| host.as_bytes()[dot] == b'.')
+                .is_some_and(|dot| host[dot + 1..].eq_ignore_ascii_case(domain)),
+            HostPattern::Network { address, prefix_len } => host
+                .parse::<IpAddr>()
+                .is_ok_and(|ip| network_contains(*address, *prefix_len, ip)),
+        }
+    }
+
+    /// Whether a connection to the given socket address is allowed. Only IP address and network
+    /// entries match, as domains cannot be checked once resolved.
+    pub fn allows_address(&self, address: SocketAddr) -> bool {
+        matches!(self.host, HostPattern::Network { .. }) && self.allows(&address.ip().to_string(), Some(address.port()))
+    }
+}
+
+fn network_contains(network: IpAddr, prefix_len: u8, ip: IpAddr) -> bool {
+    match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(prefix_len)).unwrap_or(0);
+            u32::from(network) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(prefix_len)).unwrap_or(0);
+            u128::from(network) & mask == u128::from(ip) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V4(ip)) => {
+            network_contains(IpAddr::V6(network), prefix_len, IpAddr::V6(ip.to_ipv6_mapped()))
+        }
+        (IpAddr::V4(_), IpAddr::V6(ip)) => ip
+            .to_ipv4_mapped()
+            .is_some_and(|ip| network_contains(network, prefix_len, IpAddr::V4(ip))),
+    }
+}
+
+impl FromStr for AllowedHost {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (host, port) = split_port(value.trim())?;
+
+        if host.is_empty() {
+            return Err(format!("invalid allowed host `{value}`: the host is missing"));
+        }
+
+        let host = if let Some((address, prefix_len)) = host.split_once('/') {
+            let address = address
+                .parse::<IpAddr>()
+                .map_err(|err| format!("invalid allowed host `{value}`: {err}"))?;
+
+            let prefix_len = prefix_len
+                .parse::<u8>()
+                .ok()
+                .filter(|&prefix_len| prefix_len <= full_prefix_len(address))
+                .ok_or_else(|| format!("invalid allowed host `{value}`: invalid network prefix length"))?;
+
+            HostPattern::Network { address, prefix_len }
+        } else if let Ok(address) = host.parse::<IpAddr>() {
+            HostPattern::Network {
+                address,
+                prefix_len: full_prefix_len(address),
+            }
+        } else if let Some(domain) = host.strip_prefix("*.") {
+            HostPattern::Subdomains(domain.trim_end_matches('.').to_ascii_lowercase())
+        } else if host.contains('*') {
+            return Err(format!(
+                "invalid allowed host `{value}`: wildcards are only supported as the first label, as in `*.example.com`"
+            ));
+        } else {
+            HostPattern::Domain(host.trim_end_matches('.').to_ascii_lowercase())
+        };
+
+        Ok(AllowedHost { host, port })
+    }
+}
+
+/// Splits the optional port from the host. IPv6 addresses must be between brackets to have a port.
+fn split_port(value: &str) -> Result<(&str, Option<u16>), String> {
+    let parse_port = |port: &str| {
+        port.parse::<u16>()
+            .map(Some)
+            .map_err(|_| format!("invalid allowed host `{value}`: invalid port `{port}`"))
+    };
+
+    if let Some(rest) = value.strip_prefix('[') {
+        let (host, rest) = rest
+            .split_once(']')
+            .ok_or_else(|| format!("invalid allowed host `{value}`: missing closing bracket"))?;
+
+        return match rest.strip_prefix(':') {
+            Some(port) => Ok((host, parse_port(port)?)),
+            None if rest.is_empty() => Ok((host, None)),
+            None => Err(format!("invalid allowed host `{value}`")),
+        };
+    }
+
+    match value.split_once(':') {
+        // More than one colon: an IPv6 address without port.
+        Some((_, rest)) if rest.contains(':') => Ok((value, None)),
+        Some((host, port)) => Ok((host, parse_port(port)?)),
+        None => Ok((value, None)),
+    }
+}
+
+impl TryFrom<String> for AllowedHost {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl fmt::Display for AllowedHost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.host {
+            HostPattern::Domain(domain) => f.write_str(domain)?,
+            HostPattern::Subdomains(domain) => write!(f, "*.{domain}")?,
+            HostPattern::Network { address, prefix_len } if *prefix_len == full_prefix_len(*address) => {
+                if address.is_ipv6() && self.port.is_some() {
+                    write!(f, "[{address}]")?
+                } else {
+                    write!(f, "{address}")?
+                }
+            }
+            HostPattern::Network { address, prefix_len } => {
+                if address.is_ipv6() && self.port.is_some() {
+                    write!(f, "[{address}/{prefix_len}]")?
+                } else {
+                    write!(f, "{address}/{prefix_len}")?
+                }
+            }
+        }
+
+        if let Some(port) = self.port {
+            write!(f, ":{port}")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn full_prefix_len(address: IpAddr) -> u8 {
+    if address.is_ipv4() { 32 } else { 128 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowed(entry: &str) -> AllowedHost {
+        entry.parse().unwrap()
+    }
+
+    #[test]
+    fn domains() {
+        let entry = allowed("api.example.com");
+
+        assert!(entry.allows("api.example.com", Some(443)));
+        assert!(entry.allows("API.example.com.", None));
+        assert!(!entry.allows("example.com", Some(443)));
+        assert!(!entry.allows("evil-api.example.com", Some(443)));
+    }
+
+    #[test]
+    fn subdomains() {
+        let entry = allowed("*.example.com:443");
+
+        assert!(entry.allows("api.example.com", Some(443)));
+        assert!(entry.allows("a.b.example.com", Some(443)));
+        assert!(!entry.allows("example.com", Some(443)));
+        assert!(!entry.allows("badexample.com", Some(443)));
+        assert!(!entry.allows("api.example.com", Some(80)));
+    }
+
+    #[test]
+    fn networks() {
+        let entry = allowed("10.0.0.0/8:5432");
+
+        assert!(entry.allows("10.1.2.3", Some(5432)));
+        assert!(!entry.allows("11.1.2.3", Some(5432)));
+        assert!(!entry.allows("10.1.2.3", Some(5433)));
+        assert!(entry.allows_address("10.1.2.3:5432".parse().unwrap()));
+
+        // Networks never match domains, whatever they resolve to.
+        assert!(!allowed("127.0.0.0/8").allows("localhost", Some(5432)));
+
+        let entry = allowed("[fd00::/8]:443");
+
+        assert!(entry.allows("[fd12::1]", Some(443)));
+        assert!(!entry.allows("fe80::1", Some(443)));
+
+        let entry = allowed("::1");
+
+        assert!(entry.allows("::1", Some(8080)));
+        assert!(!allowed("localhost").allows_address("127.0.0.1:80".parse().unwrap()));
+    }
+
+    #[test]
+    fn display_roundtrip() {
+        for entry in [
+            "api.example.com",
+            "*.example.com:443",
+            "10.0.0.0/8:5432",
+            "10.0.0.12",
+            "[fd00::/8]:443",
+            "::1",
+        ] {
+            assert_eq!(entry, allowed(entry).to_string());
+        }
+    }
+
+    #[test]
+    fn invalid_entries() {
+        insta::assert_snapshot!(AllowedHost::from_str("example.com:http").unwrap_err(), @"invalid allowed host `example.com:http`: invalid port `http`");
+        insta::assert_snapshot!(AllowedHost::from_str("10.0.0.0/33").unwrap_err(), @"invalid allowed host `10.0.0.0/33`: invalid network prefix length");
+        insta::assert_snapshot!(AllowedHost::from_str("api.*.com").unwrap_err(), @"invalid allowed host `api.*.com`: wildcards are only supported as the first label, as in `*.example.com`");
+    }
+}
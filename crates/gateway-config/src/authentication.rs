@@ -1,11 +1,78 @@
+use std::{path::PathBuf, time::Duration};
+
 /// Configures the GraphQL server JWT authentication
 #[derive(Default, Debug, PartialEq, serde::Deserialize, Clone)]
 #[serde(default, deny_unknown_fields)]
 pub struct AuthenticationConfig {
     pub default: Option<DefaultAuthenticationBehavior>,
+    /// Built-in JWT authentication, tried before any authentication extension.
+    pub jwt: Option<JwtAuthenticationConfig>,
     pub protected_resources: AuthenticationResources,
 }
 
+/// Validates JSON Web Tokens without an authentication extension. The claims of a valid token
+/// become the token of the request. Exactly one of `jwks_url`, `jwks_path` or `pem_path` must be
+/// set.
+#[derive(Debug, PartialEq, serde::Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct JwtAuthenticationConfig {
+    /// URL of the JSON Web Key Set, fetched at startup and refreshed every `jwks_refresh_interval`.
+    pub jwks_url: Option<url::Url>,
+    /// Path to a local JSON Web Key Set file.
+    pub jwks_path: Option<PathBuf>,
+    /// Path to a local PEM encoded public key.
+    pub pem_path: Option<PathBuf>,
+    /// Expected `iss` claim.
+    pub issuer: Option<String>,
+    /// Accepted `aud` claim values. If set, the token audience must contain one of them.
+    pub audience: Vec<String>,
+    /// Accepted signature algorithms, e.g. `RS256` or `ES256`. Defaults to all the asymmetric ones.
+    pub algorithms: Vec<String>,
+    /// Header the token is read from.
+    pub header_name: String,
+    /// Prefix stripped from the header value.
+    pub header_value_prefix: String,
+    /// Cookie the token is read from when the header is absent.
+    pub cookie_name: Option<String>,
+    /// Leeway applied when validating the `exp` and `nbf` claims.
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub clock_skew: Duration,
+    /// How often the JSON Web Key Set is fetched again from `jwks_url`. Must not be zero.
+    #[serde(deserialize_with = "deserialize_jwks_refresh_interval")]
+    pub jwks_refresh_interval: Duration,
+}
+
+impl Default for JwtAuthenticationConfig {
+    fn default() -> Self {
+        Self {
+            jwks_url: None,
+            jwks_path: None,
+            pem_path: None,
+            issuer: None,
+            audience: Vec::new(),
+            algorithms: Vec::new(),
+            header_name: String::from("Authorization"),
+            header_value_prefix: String::from("Bearer "),
+            cookie_name: None,
+            clock_skew: Duration::from_secs(60),
+            jwks_refresh_interval: Duration::from_secs(300),
+        }
+    }
+}
+
+fn deserialize_jwks_refresh_interval<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let interval = duration_str::deserialize_duration(deserializer)?;
+
+    if interval.is_zero() {
+        return Err(serde::de::Error::custom("jwks_refresh_interval cannot be 0"));
+    }
+
+    Ok(interval)
+}
+
 #[derive(Default, Debug, PartialEq, serde::Deserialize, Clone)]
 #[serde(default, deny_unknown_fields)]
 pub struct AuthenticationResources {
@@ -1,7 +1,13 @@
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use semver::VersionReq;
 use serde::{Deserialize, Deserializer};
+use size::Size;
+
+use crate::{AllowedHost, EntityCachingRedisConfig, EntityCachingStorage};
 
 #[derive(PartialEq, Debug, Clone)]
 pub enum ExtensionConfig {
@@ -52,23 +58,50 @@ pub struct StructuredExtensionConfig {
     pub version: VersionReq,
     pub path: Option<PathBuf>,
     pub networking: Option<bool>,
+    /// Hosts, networks and ports the extension may connect to when networking is enabled. All
+    /// hosts are allowed if not set.
+    pub allowed_hosts: Option<Vec<AllowedHost>>,
     pub stdout: Option<bool>,
     pub stderr: Option<bool>,
     pub environment_variables: Option<bool>,
     pub max_pool_size: Option<usize>,
+    /// Maximum linear memory of a single extension instance.
+    #[serde(deserialize_with = "crate::size_ext::deserialize_option_positive_size")]
+    pub max_memory: Option<Size>,
+    /// Maximum duration of a single call into the extension. A call exceeding it is interrupted.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration")]
+    pub max_execution_time: Option<Duration>,
+    /// Maximum number of elements in a single table of an extension instance.
+    pub max_table_elements: Option<usize>,
+    /// Storage of the named caches of the extension.
+    pub cache: Option<ExtensionCacheConfig>,
     pub config: Option<toml::Value>,
 }
 
+#[derive(PartialEq, serde::Deserialize, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExtensionCacheConfig {
+    /// With Redis, caches are shared by all gateway instances and are not bounded in size.
+    pub storage: EntityCachingStorage,
+    /// Defaults to the entity caching Redis configuration.
+    pub redis: Option<EntityCachingRedisConfig>,
+}
+
 impl Default for StructuredExtensionConfig {
     fn default() -> Self {
         Self {
             version: VersionReq::parse("*").unwrap(),
             path: None,
             networking: None,
+            allowed_hosts: None,
             stdout: None,
             stderr: None,
             environment_variables: None,
             max_pool_size: None,
+            max_memory: None,
+            max_execution_time: None,
+            max_table_elements: None,
+            cache: None,
             config: None,
         }
     }
@@ -89,6 +122,13 @@ impl ExtensionConfig {
         }
     }
 
+    pub fn allowed_hosts(&self) -> Option<&[AllowedHost]> {
+        match self {
+            ExtensionConfig::Version(_) => None,
+            ExtensionConfig::Structured(config) => config.allowed_hosts.as_deref(),
+        }
+    }
+
     pub fn stdout(&self) -> Option<bool> {
         match self {
             ExtensionConfig::Version(_) => None,
@@ -117,6 +157,34 @@ impl ExtensionConfig {
         }
     }
 
+    pub fn max_memory(&self) -> Option<Size> {
+        match self {
+            ExtensionConfig::Version(_) => None,
+            ExtensionConfig::Structured(config) => config.max_memory,
+        }
+    }
+
+    pub fn max_execution_time(&self) -> Option<Duration> {
+        match self {
+            ExtensionConfig::Version(_) => None,
+            ExtensionConfig::Structured(config) => config.max_execution_time,
+        }
+    }
+
+    pub fn max_table_elements(&self) -> Option<usize> {
+        match self {
+            ExtensionConfig::Version(_) => None,
+            ExtensionConfig::Structured(config) => config.max_table_elements,
+        }
+    }
+
+    pub fn cache(&self) -> Option<&ExtensionCacheConfig> {
+        match self {
+            ExtensionConfig::Version(_) => None,
+            ExtensionConfig::Structured(config) => config.cache.as_ref(),
+        }
+    }
+
     pub fn path(&self) -> Option<&Path> {
         match self {
             ExtensionConfig::Version(_) => None,
@@ -167,4 +235,43 @@ mod tests {
 
         toml::from_str::<StructuredExtensionConfig>(toml).unwrap();
     }
+
+    #[test]
+    fn instance_limits() {
+        let toml = r#"
+            version = "1.0"
+            max_memory = "64MiB"
+            max_execution_time = "2s"
+            max_table_elements = 20000
+        "#;
+
+        let config = ExtensionConfig::Structured(toml::from_str::<StructuredExtensionConfig>(toml).unwrap());
+
+        assert_eq!(Some(Size::from_mebibytes(64)), config.max_memory());
+        assert_eq!(Some(Duration::from_secs(2)), config.max_execution_time());
+        assert_eq!(Some(20000), config.max_table_elements());
+    }
+
+    #[test]
+    fn redis_cache() {
+        let toml = r#"
+            version = "1.0"
+
+            [cache]
+            storage = "redis"
+
+            [cache.redis]
+            url = "redis://cache:6379"
+            key_prefix = "my-gateway"
+        "#;
+
+        let config = ExtensionConfig::Structured(toml::from_str::<StructuredExtensionConfig>(toml).unwrap());
+        let cache = config.cache().unwrap();
+
+        assert_eq!(EntityCachingStorage::Redis, cache.storage);
+
+        let redis = cache.redis.as_ref().unwrap();
+        assert_eq!("redis://cache:6379", redis.url.as_str());
+        assert_eq!("my-gateway", redis.key_prefix);
+    }
 }
@@ -0,0 +1,62 @@
+use std::{path::PathBuf, time::Duration};
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FieldUsageConfig {
+    /// If the gateway should record which fields, arguments and enum values are used.
+    pub enabled: bool,
+    /// Where the aggregated usage is stored.
+    pub storage: FieldUsageStorage,
+    /// Path of the usage file, with the file storage. Defaults to `field-usage.json`.
+    pub path: PathBuf,
+    pub redis: FieldUsageRedisConfig,
+    /// How often the usage collected in memory is merged into the storage. Defaults to 60s
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub flush_interval: Duration,
+}
+
+impl Default for FieldUsageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            storage: Default::default(),
+            path: PathBuf::from("field-usage.json"),
+            redis: Default::default(),
+            flush_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldUsageStorage {
+    #[default]
+    File,
+    Redis,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FieldUsageRedisConfig {
+    pub url: url::Url,
+    pub key_prefix: String,
+    pub tls: Option<FieldUsageRedisTlsConfig>,
+}
+
+impl Default for FieldUsageRedisConfig {
+    fn default() -> Self {
+        Self {
+            url: url::Url::parse("redis://localhost:6379").expect("must be correct"),
+            key_prefix: String::from("grafbase-field-usage"),
+            tls: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldUsageRedisTlsConfig {
+    pub cert: Option<PathBuf>,
+    pub key: Option<PathBuf>,
+    pub ca: Option<PathBuf>,
+}
@@ -91,6 +91,13 @@ pub struct HeaderInsert {
     pub name: AsciiString,
     /// The value of the header.
     pub value: AsciiString,
+    /// Whether the value is a template, otherwise it's inserted as is. Templates can reference the
+    /// token claims (`{{ token.sub }}`), the client (`{{ client.name }}`, `{{ client.version }}`),
+    /// the operation name (`{{ operation.name }}`, subgraph rules only), the trace id
+    /// (`{{ trace_id }}`) and the incoming request headers (`{{ headers.x-request-id }}`). The header
+    /// is not inserted if the template renders to an empty string.
+    #[serde(default)]
+    pub template: bool,
 }
 
 /// Header removal rules
@@ -101,3 +108,36 @@ pub struct HeaderRemove {
     #[serde(flatten)]
     pub name: NameOrPattern,
 }
+
+/// Propagates headers returned by the subgraphs to the client response. Only actual subgraph
+/// responses are taken into account: data served from the entity cache contributes no headers.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ResponseHeaderRule {
+    /// Name or pattern of the subgraph response header to propagate.
+    #[serde(flatten)]
+    pub name: NameOrPattern,
+    /// If no subgraph returned the header, insert this value.
+    pub default: Option<AsciiString>,
+    /// Use this name instead of the original in the client response.
+    pub rename: Option<AsciiString>,
+    /// How to combine the values if several subgraphs returned the header.
+    #[serde(default)]
+    pub merge: ResponseHeaderMerge,
+}
+
+/// Strategy used to combine a header returned by multiple subgraph responses.
+#[derive(Deserialize, serde::Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseHeaderMerge {
+    /// Keep the value from the first subgraph response.
+    #[default]
+    First,
+    /// Keep the value from the last subgraph response.
+    Last,
+    /// Keep all the values.
+    Append,
+    /// Combine Cache-Control values into the most restrictive policy: the smallest max-age wins and
+    /// no-store, no-cache and private are kept if any subgraph returned them.
+    MostRestrictive,
+}
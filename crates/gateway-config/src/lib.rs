@@ -3,12 +3,14 @@ use operation_caching::OperationCacheConfig;
 use rapidhash::RapidHashMap;
 use serde::Deserialize;
 
+mod allowed_host;
 pub mod apq;
 pub mod authentication;
 mod complexity_control;
 pub mod cors;
 pub mod entity_caching;
 pub mod extensions;
+pub mod field_usage;
 pub mod header;
 pub mod health;
 pub mod hooks;
@@ -21,6 +23,7 @@ mod size_ext;
 mod subscription_protocol;
 pub mod telemetry;
 mod traffic_shaping;
+mod transport;
 mod trusted_documents;
 mod wasm;
 mod websockets_config;
@@ -35,6 +38,7 @@ use std::{
 };
 
 pub use self::{
+    allowed_host::AllowedHost,
     log_level::*,
     mcp::{McpTransport, ModelControlProtocolConfig},
     subscription_protocol::SubscriptionProtocol,
@@ -46,6 +50,7 @@ pub use complexity_control::*;
 pub use cors::*;
 pub use entity_caching::*;
 pub use extensions::*;
+pub use field_usage::*;
 pub use header::*;
 pub use health::*;
 pub use hooks::*;
@@ -54,6 +59,7 @@ pub use rate_limit::*;
 use size::Size;
 pub use telemetry::*;
 pub use traffic_shaping::*;
+pub use transport::*;
 use url::Url;
 pub use wasm::*;
 
@@ -205,6 +211,8 @@ pub struct Config {
     pub authentication: AuthenticationConfig,
     /// Header bypass configuration
     pub headers: Vec<HeaderRule>,
+    /// Subgraph response headers propagated to the client
+    pub response_headers: Vec<ResponseHeaderRule>,
     /// Subgraph configuration
     pub subgraphs: BTreeMap<String, SubgraphConfig>,
     /// Hooks configuration
@@ -221,6 +229,8 @@ pub struct Config {
     pub apq: AutomaticPersistedQueries,
     /// Operation caching configuration
     pub operation_caching: OperationCacheConfig,
+    /// Collection of field usage statistics for operation checks
+    pub field_usage: FieldUsageConfig,
     /// Websockets configuration
     pub websockets: WebsocketsConfig,
     /// Model Control Protocol configuration
@@ -266,6 +276,10 @@ impl Config {
             }
         }
 
+        if self.field_usage.path.is_relative() {
+            self.field_usage.path = parent.join(&self.field_usage.path);
+        }
+
         if let Some(wasm) = &mut self.wasm
             && let Some(dir) = &mut wasm.cache_path
             && dir.is_relative()
@@ -295,6 +309,7 @@ impl Default for Config {
             trusted_documents: Default::default(),
             authentication: Default::default(),
             headers: Default::default(),
+            response_headers: Default::default(),
             subgraphs: Default::default(),
             hooks: Default::default(),
             health: Default::default(),
@@ -302,6 +317,7 @@ impl Default for Config {
             complexity_control: Default::default(),
             apq: Default::default(),
             operation_caching: Default::default(),
+            field_usage: Default::default(),
             websockets: Default::default(),
             extensions: Default::default(),
             mcp: Default::default(),
@@ -319,6 +335,28 @@ pub struct BatchingConfig {
     pub limit: Option<u8>,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubgraphBatchingConfig {
+    /// If concurrent requests to the subgraph should be grouped into a single batch request.
+    pub enabled: bool,
+    /// How many requests can a batch have.
+    pub max_size: usize,
+    /// How long the first request of a batch waits for others to join it.
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub max_wait: Duration,
+}
+
+impl Default for SubgraphBatchingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_size: 32,
+            max_wait: Duration::from_millis(1),
+        }
+    }
+}
+
 #[derive(Clone, Debug, serde::Deserialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct GatewayConfig {
@@ -338,6 +376,8 @@ pub struct GatewayConfig {
     pub batching: BatchingConfig,
     /// Global message signatures config
     pub message_signatures: MessageSignaturesConfig,
+    /// Global transport settings of the subgraph connections
+    pub transport: TransportConfig,
 }
 
 impl Default for GatewayConfig {
@@ -350,6 +390,7 @@ impl Default for GatewayConfig {
             access_logs: Default::default(),
             batching: Default::default(),
             message_signatures: Default::default(),
+            transport: Default::default(),
         }
     }
 }
@@ -381,6 +422,18 @@ pub enum LogMode {
     NonBlocking,
 }
 
+#[derive(Debug, Default, serde::Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum LogCompression {
+    /// Rotated files are kept as they are
+    #[default]
+    None,
+    /// Rotated files are compressed with gzip, with the `.gz` extension
+    Gzip,
+    /// Rotated files are compressed with zstd, with the `.zst` extension
+    Zstd,
+}
+
 #[derive(Debug, Default, serde::Deserialize, Clone)]
 #[serde(default, deny_unknown_fields)]
 pub struct AccessLogsConfig {
@@ -392,6 +445,13 @@ pub struct AccessLogsConfig {
     pub rotate: RotateMode,
     /// What happens if the log channel is full
     pub mode: LogMode,
+    /// The maximum number of rotated files to keep. The oldest files are deleted first.
+    pub max_files: Option<usize>,
+    /// Rotated files older than this are deleted.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration")]
+    pub max_age: Option<Duration>,
+    /// Compression of the rotated files. Defaults to none.
+    pub compression: LogCompression,
 }
 
 impl AccessLogsConfig {
@@ -407,6 +467,8 @@ pub struct SubgraphConfig {
     pub url: Option<Url>,
     /// Header bypass configuration
     pub headers: Vec<HeaderRule>,
+    /// Response headers of this subgraph propagated to the client
+    pub response_headers: Vec<ResponseHeaderRule>,
     /// The URL to use for GraphQL websocket calls.
     pub websocket_url: Option<Url>,
     /// Rate limiting configuration specifically for this Subgraph
@@ -415,6 +477,14 @@ pub struct SubgraphConfig {
     #[serde(deserialize_with = "duration_str::deserialize_duration")]
     pub timeout: Duration,
     pub retry: Option<RetryConfig>,
+    /// Limits on the size and shape of the subgraph responses
+    pub response_limits: Option<SubgraphResponseLimitsConfig>,
+    /// Concurrency limits for this subgraph, overriding the global traffic shaping settings
+    pub traffic_shaping: Option<SubgraphTrafficShapingConfig>,
+    /// Compression of the request bodies sent to this subgraph
+    pub request_compression: Option<RequestCompressionConfig>,
+    /// Batching of the concurrent requests sent to this subgraph
+    pub batching: Option<SubgraphBatchingConfig>,
     /// Subgraph specific entity caching config  this overrides the global config if there
     /// is any
     pub entity_caching: Option<SubgraphEntityCachingConfig>,
@@ -430,6 +500,8 @@ pub struct SubgraphConfig {
     pub subscription_protocol: Option<SubscriptionProtocol>,
     /// Mutual TLS (mTLS) configuration for the subgraph
     pub mtls: Option<MtlsConfig>,
+    /// Transport settings for the subgraph, overriding the global ones
+    pub transport: Option<TransportConfig>,
 }
 
 impl SubgraphConfig {
@@ -443,10 +515,15 @@ impl Default for SubgraphConfig {
         Self {
             url: Default::default(),
             headers: Default::default(),
+            response_headers: Default::default(),
             websocket_url: Default::default(),
             rate_limit: Default::default(),
             timeout: DEFAULT_SUBGRAPH_TIMEOUT,
             retry: Default::default(),
+            response_limits: Default::default(),
+            traffic_shaping: Default::default(),
+            request_compression: Default::default(),
+            batching: Default::default(),
             entity_caching: Default::default(),
             message_signatures: Default::default(),
             schema_path: Default::default(),
@@ -454,11 +531,12 @@ impl Default for SubgraphConfig {
             introspection_headers: Default::default(),
             subscription_protocol: Default::default(),
             mtls: Default::default(),
+            transport: Default::default(),
         }
     }
 }
 
-#[derive(Debug, serde::Deserialize, Clone, Copy, Default, PartialEq)]
+#[derive(Debug, serde::Deserialize, Clone, Default, PartialEq)]
 #[serde(default, deny_unknown_fields)]
 pub struct RetryConfig {
     /// Should we retry or not.
@@ -472,6 +550,113 @@ pub struct RetryConfig {
     pub retry_percent: Option<f32>,
     /// Whether mutations should be retried at all. False by default.
     pub retry_mutations: bool,
+    /// Maximum number of attempts of a single request, including the initial one. Only limited
+    /// by the budget by default.
+    pub max_attempts: Option<u32>,
+    /// Delay before the first retry. Default: 100ms.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration")]
+    pub initial_backoff: Option<Duration>,
+    /// Upper bound of the delay between two retries. Unbounded by default.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration")]
+    pub max_backoff: Option<Duration>,
+    /// Factor applied to the delay after each retry, at least 1. Default: 2.
+    #[serde(deserialize_with = "deserialize_option_backoff_multiplier")]
+    pub backoff_multiplier: Option<f64>,
+    /// Whether the delay is randomized, between zero and twice its value. True by default.
+    pub jitter: Option<bool>,
+    /// Which failures are retried. Any failed request is retried by default.
+    pub retry_on: Option<RetryOnConfig>,
+}
+
+#[derive(Debug, serde::Deserialize, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryOnConfig {
+    /// Retry when the connection to the subgraph could not be established or was interrupted.
+    pub connection_errors: bool,
+    /// Retry when the subgraph didn't respond in time.
+    pub timeouts: bool,
+    /// HTTP status codes which are retried. Any 5xx and 429 by default.
+    pub status_codes: Option<Vec<u16>>,
+    /// Retry successful responses containing a GraphQL error with one of those codes in its
+    /// extensions.
+    pub graphql_error_codes: Vec<String>,
+}
+
+impl Default for RetryOnConfig {
+    fn default() -> Self {
+        Self {
+            connection_errors: true,
+            timeouts: true,
+            status_codes: None,
+            graphql_error_codes: Vec::new(),
+        }
+    }
+}
+
+fn deserialize_option_backoff_multiplier<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let multiplier = f64::deserialize(deserializer)?;
+
+    if !multiplier.is_finite() || multiplier < 1.0 {
+        return Err(serde::de::Error::custom(
+            "backoff_multiplier must be a finite number greater than or equal to 1",
+        ));
+    }
+
+    Ok(Some(multiplier))
+}
+
+#[derive(Debug, serde::Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubgraphResponseLimitsConfig {
+    /// Maximum size of the response body. Bigger responses are aborted while being received.
+    #[serde(deserialize_with = "size_ext::deserialize_option_positive_size")]
+    pub max_size: Option<Size>,
+    /// Maximum number of items of a single list in the response data.
+    pub max_list_length: Option<usize>,
+    /// Maximum nesting of objects and lists in the response data.
+    pub max_depth: Option<usize>,
+}
+
+#[derive(Debug, serde::Deserialize, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RequestCompressionConfig {
+    /// Algorithm used to compress the request body, advertised in the Content-Encoding header.
+    pub algorithm: RequestCompressionAlgorithm,
+    /// Bodies smaller than this are sent uncompressed. Default: 1KiB.
+    #[serde(deserialize_with = "size_ext::deserialize_positive_size")]
+    pub min_size: Size,
+}
+
+impl Default for RequestCompressionConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: RequestCompressionAlgorithm::Gzip,
+            min_size: Size::from_kibibytes(1),
+        }
+    }
+}
+
+#[derive(Debug, Default, serde::Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RequestCompressionAlgorithm {
+    #[default]
+    Gzip,
+    Zstd,
+    Brotli,
+}
+
+impl RequestCompressionAlgorithm {
+    /// Value of the Content-Encoding header for this algorithm.
+    pub fn content_encoding(&self) -> &'static str {
+        match self {
+            RequestCompressionAlgorithm::Gzip => "gzip",
+            RequestCompressionAlgorithm::Zstd => "zstd",
+            RequestCompressionAlgorithm::Brotli => "br",
+        }
+    }
 }
 
 #[derive(Clone, Debug, serde::Deserialize)]
@@ -1201,6 +1386,69 @@ mod tests {
         "###);
     }
 
+    #[test]
+    fn authentication_jwt() {
+        let input = indoc! {r#"
+            [authentication.jwt]
+            jwks_path = "jwks.json"
+            issuer = "https://example.com"
+            audience = ["my-api"]
+            algorithms = ["RS256", "ES256"]
+            cookie_name = "session"
+            clock_skew = "5s"
+        "#};
+
+        let config = toml::from_str::<Config>(input).unwrap();
+
+        insta::assert_debug_snapshot!(config.authentication.jwt, @r#"
+        Some(
+            JwtAuthenticationConfig {
+                jwks_url: None,
+                jwks_path: Some(
+                    "jwks.json",
+                ),
+                pem_path: None,
+                issuer: Some(
+                    "https://example.com",
+                ),
+                audience: [
+                    "my-api",
+                ],
+                algorithms: [
+                    "RS256",
+                    "ES256",
+                ],
+                header_name: "Authorization",
+                header_value_prefix: "Bearer ",
+                cookie_name: Some(
+                    "session",
+                ),
+                clock_skew: 5s,
+                jwks_refresh_interval: 300s,
+            },
+        )
+        "#);
+    }
+
+    #[test]
+    fn authentication_jwt_zero_jwks_refresh_interval() {
+        let input = indoc! {r#"
+            [authentication.jwt]
+            jwks_url = "https://example.com/.well-known/jwks.json"
+            jwks_refresh_interval = "0s"
+        "#};
+
+        let error = toml::from_str::<Config>(input).unwrap_err();
+
+        insta::assert_snapshot!(&error.to_string(), @r#"
+        TOML parse error at line 3, column 25
+          |
+        3 | jwks_refresh_interval = "0s"
+          |                         ^^^^
+        jwks_refresh_interval cannot be 0
+        "#);
+    }
+
     #[test]
     fn telemetry() {
         // prepare
@@ -1499,6 +1747,7 @@ mod tests {
                 HeaderInsert {
                     name: "content-type",
                     value: "application/json",
+                    template: false,
                 },
             ),
         ]
@@ -1523,6 +1772,7 @@ mod tests {
                     HeaderInsert {
                         name: "content-type",
                         value: "{{ env.CONTENT_TYPE }}",
+                        template: false,
                     },
                 ),
             ]
@@ -1612,6 +1862,87 @@ mod tests {
         "#);
     }
 
+    #[test]
+    fn response_headers() {
+        let input = indoc! {r#"
+            [[response_headers]]
+            name = "cache-control"
+            merge = "most_restrictive"
+
+            [[response_headers]]
+            pattern = "^x-rate-limit-"
+            rename = "x-upstream-rate-limit"
+
+            [[subgraphs.products.response_headers]]
+            name = "set-cookie"
+            default = "session=none"
+            merge = "append"
+        "#};
+
+        let result: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&result.response_headers, @r#"
+        [
+            ResponseHeaderRule {
+                name: Name(
+                    "cache-control",
+                ),
+                default: None,
+                rename: None,
+                merge: MostRestrictive,
+            },
+            ResponseHeaderRule {
+                name: Pattern(
+                    NamePattern(
+                        Regex(
+                            "^x-rate-limit-",
+                        ),
+                    ),
+                ),
+                default: None,
+                rename: Some(
+                    "x-upstream-rate-limit",
+                ),
+                merge: First,
+            },
+        ]
+        "#);
+
+        insta::assert_debug_snapshot!(&result.subgraphs["products"].response_headers, @r#"
+        [
+            ResponseHeaderRule {
+                name: Name(
+                    "set-cookie",
+                ),
+                default: Some(
+                    "session=none",
+                ),
+                rename: None,
+                merge: Append,
+            },
+        ]
+        "#);
+    }
+
+    #[test]
+    fn response_headers_invalid_merge() {
+        let input = indoc! {r#"
+            [[response_headers]]
+            name = "cache-control"
+            merge = "random"
+        "#};
+
+        let error = toml::from_str::<Config>(input).unwrap_err();
+
+        insta::assert_snapshot!(&error.to_string(), @r#"
+        TOML parse error at line 1, column 1
+          |
+        1 | [[response_headers]]
+          | ^^^^^^^^^^^^^^^^^^^^
+        unknown variant `random`, expected one of `first`, `last`, `append`, `most_restrictive`
+        "#);
+    }
+
     #[test]
     fn subgraph_header_forward_static() {
         let input = indoc! {r#"
@@ -1637,10 +1968,15 @@ mod tests {
                         },
                     ),
                 ],
+                response_headers: [],
                 websocket_url: None,
                 rate_limit: None,
                 timeout: 30s,
                 retry: None,
+                response_limits: None,
+                traffic_shaping: None,
+                request_compression: None,
+                batching: None,
                 entity_caching: None,
                 message_signatures: None,
                 schema_path: None,
@@ -1648,6 +1984,7 @@ mod tests {
                 introspection_headers: None,
                 subscription_protocol: None,
                 mtls: None,
+                transport: None,
             },
         }
         "#);
@@ -1711,12 +2048,21 @@ mod tests {
                 ttl: None,
                 retry_percent: None,
                 retry_mutations: false,
+                max_attempts: None,
+                initial_backoff: None,
+                max_backoff: None,
+                backoff_multiplier: None,
+                jitter: None,
+                retry_on: None,
             },
             access_logs: AccessLogsConfig {
                 enabled: false,
                 path: "",
                 rotate: Never,
                 mode: Blocking,
+                max_files: None,
+                max_age: None,
+                compression: None,
             },
             batching: BatchingConfig {
                 enabled: false,
@@ -1734,6 +2080,10 @@ mod tests {
                 derived_components: None,
                 signature_parameters: None,
             },
+            transport: TransportConfig {
+                proxy: None,
+                http2_prior_knowledge: None,
+            },
         }
         "#);
     }
@@ -2073,6 +2423,12 @@ mod tests {
             ttl: None,
             retry_percent: None,
             retry_mutations: false,
+            max_attempts: None,
+            initial_backoff: None,
+            max_backoff: None,
+            backoff_multiplier: None,
+            jitter: None,
+            retry_on: None,
         }
         "###);
     }
@@ -2091,6 +2447,7 @@ mod tests {
             "products": SubgraphConfig {
                 url: None,
                 headers: [],
+                response_headers: [],
                 websocket_url: None,
                 rate_limit: None,
                 timeout: 30s,
@@ -2101,8 +2458,18 @@ mod tests {
                         ttl: None,
                         retry_percent: None,
                         retry_mutations: false,
+                        max_attempts: None,
+                        initial_backoff: None,
+                        max_backoff: None,
+                        backoff_multiplier: None,
+                        jitter: None,
+                        retry_on: None,
                     },
                 ),
+                response_limits: None,
+                traffic_shaping: None,
+                request_compression: None,
+                batching: None,
                 entity_caching: None,
                 message_signatures: None,
                 schema_path: None,
@@ -2110,9 +2477,286 @@ mod tests {
                 introspection_headers: None,
                 subscription_protocol: None,
                 mtls: None,
+                transport: None,
+            },
+        }
+        "#);
+    }
+
+    #[test]
+    fn subgraph_retry_backoff_and_rules() {
+        let input = indoc! {r#"
+            [subgraphs.products.retry]
+            enabled = true
+            max_attempts = 3
+            initial_backoff = "50ms"
+            max_backoff = "1s"
+            backoff_multiplier = 1.5
+            jitter = false
+
+            [subgraphs.products.retry.retry_on]
+            timeouts = false
+            status_codes = [502, 503]
+            graphql_error_codes = ["UNAVAILABLE"]
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.subgraphs["products"].retry, @r#"
+        Some(
+            RetryConfig {
+                enabled: true,
+                min_per_second: None,
+                ttl: None,
+                retry_percent: None,
+                retry_mutations: false,
+                max_attempts: Some(
+                    3,
+                ),
+                initial_backoff: Some(
+                    50ms,
+                ),
+                max_backoff: Some(
+                    1s,
+                ),
+                backoff_multiplier: Some(
+                    1.5,
+                ),
+                jitter: Some(
+                    false,
+                ),
+                retry_on: Some(
+                    RetryOnConfig {
+                        connection_errors: true,
+                        timeouts: false,
+                        status_codes: Some(
+                            [
+                                502,
+                                503,
+                            ],
+                        ),
+                        graphql_error_codes: [
+                            "UNAVAILABLE",
+                        ],
+                    },
+                ),
             },
+        )
+        "#);
+    }
+
+    #[test]
+    fn subgraph_retry_backoff_multiplier_below_one() {
+        let input = indoc! {r#"
+            [subgraphs.products.retry]
+            enabled = true
+            backoff_multiplier = 0.5
+        "#};
+
+        let error = toml::from_str::<Config>(input).unwrap_err();
+
+        insta::assert_snapshot!(&error.to_string(), @r#"
+        TOML parse error at line 3, column 22
+          |
+        3 | backoff_multiplier = 0.5
+          |                      ^^^
+        backoff_multiplier must be a finite number greater than or equal to 1
+        "#);
+    }
+
+    #[test]
+    fn subgraph_retry_backoff_multiplier_not_finite() {
+        let input = indoc! {r#"
+            [subgraphs.products.retry]
+            enabled = true
+            backoff_multiplier = inf
+        "#};
+
+        let error = toml::from_str::<Config>(input).unwrap_err();
+
+        insta::assert_snapshot!(&error.to_string(), @r#"
+        TOML parse error at line 3, column 22
+          |
+        3 | backoff_multiplier = inf
+          |                      ^^^
+        backoff_multiplier must be a finite number greater than or equal to 1
+        "#);
+    }
+
+    #[test]
+    fn subgraph_response_limits() {
+        let input = indoc! {r#"
+            [subgraphs.products.response_limits]
+            max_size = "10MiB"
+            max_list_length = 1000
+            max_depth = 32
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+        let limits = config.subgraphs["products"].response_limits.unwrap();
+
+        assert_eq!(
+            SubgraphResponseLimitsConfig {
+                max_size: Some(Size::from_mebibytes(10)),
+                max_list_length: Some(1000),
+                max_depth: Some(32),
+            },
+            limits
+        );
+    }
+
+    #[test]
+    fn subgraph_batching() {
+        let input = indoc! {r#"
+            [subgraphs.products.batching]
+            enabled = true
+            max_size = 10
+            max_wait = "5ms"
+
+            [subgraphs.reviews.batching]
+            enabled = true
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            Some(SubgraphBatchingConfig {
+                enabled: true,
+                max_size: 10,
+                max_wait: Duration::from_millis(5),
+            }),
+            config.subgraphs["products"].batching
+        );
+
+        assert_eq!(
+            Some(SubgraphBatchingConfig {
+                enabled: true,
+                ..Default::default()
+            }),
+            config.subgraphs["reviews"].batching
+        );
+    }
+
+    #[test]
+    fn subgraph_transport() {
+        let input = indoc! {r#"
+            [gateway.transport.proxy]
+            url = "http://proxy.internal:3128"
+            no_proxy = ["localhost", ".svc.cluster.local"]
+            username = "gateway"
+            password = "secret"
+
+            [subgraphs.products.transport]
+            http2_prior_knowledge = true
+
+            [subgraphs.reviews.transport.proxy]
+            url = "socks5://127.0.0.1:1080"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+        let global = &config.gateway.transport;
+
+        let products = global.merge(config.subgraphs["products"].transport.as_ref());
+        assert_eq!(products.proxy, global.proxy);
+        assert_eq!(products.http2_prior_knowledge, Some(true));
+
+        let reviews = global.merge(config.subgraphs["reviews"].transport.as_ref());
+        let proxy = reviews.proxy.unwrap();
+        assert_eq!(proxy.url.as_str(), "socks5://127.0.0.1:1080");
+        assert!(proxy.no_proxy.is_empty());
+        assert_eq!(proxy.username, None);
+        assert_eq!(reviews.http2_prior_knowledge, None);
+
+        let proxy = global.proxy.as_ref().unwrap();
+        assert_eq!(proxy.no_proxy, ["localhost", ".svc.cluster.local"]);
+        assert_eq!(proxy.username.as_deref(), Some("gateway"));
+        assert_eq!(proxy.password.as_deref(), Some("secret"));
+    }
+
+    #[test]
+    fn subgraph_request_compression() {
+        let input = indoc! {r#"
+            [subgraphs.products.request_compression]
+            algorithm = "zstd"
+            min_size = "4KiB"
+
+            [subgraphs.reviews.request_compression]
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        assert_eq!(
+            Some(RequestCompressionConfig {
+                algorithm: RequestCompressionAlgorithm::Zstd,
+                min_size: Size::from_kibibytes(4),
+            }),
+            config.subgraphs["products"].request_compression
+        );
+
+        assert_eq!(
+            Some(RequestCompressionConfig::default()),
+            config.subgraphs["reviews"].request_compression
+        );
+    }
+
+    #[test]
+    fn traffic_shaping() {
+        let input = indoc! {r#"
+            [traffic_shaping]
+            max_concurrent_requests = 100
+            max_queue_size = 1000
+            queue_timeout = "1s"
+
+            [traffic_shaping.load_shedding]
+            latency_threshold = "500ms"
+
+            [subgraphs.products.traffic_shaping]
+            max_concurrent_requests = 10
+            queue_timeout = "200ms"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.traffic_shaping, @r#"
+        TrafficShapingConfig {
+            inflight_deduplication: true,
+            max_concurrent_requests: Some(
+                100,
+            ),
+            max_queue_size: 1000,
+            queue_timeout: Some(
+                1s,
+            ),
+            load_shedding: Some(
+                LoadSheddingConfig {
+                    latency_threshold: 500ms,
+                    max_rejection_ratio: 0.9,
+                    retry_after: 1s,
+                },
+            ),
         }
         "#);
+
+        assert_eq!(
+            Some(SubgraphTrafficShapingConfig {
+                max_concurrent_requests: std::num::NonZeroUsize::new(10),
+                max_queue_size: None,
+                queue_timeout: Some(Duration::from_millis(200)),
+            }),
+            config.subgraphs["products"].traffic_shaping
+        );
+    }
+
+    #[test]
+    fn traffic_shaping_rejects_zero_concurrent_requests() {
+        let input = indoc! {r#"
+            [subgraphs.products.traffic_shaping]
+            max_concurrent_requests = 0
+        "#};
+
+        let error = toml::from_str::<Config>(input).unwrap_err();
+
+        assert!(error.to_string().contains("expected a nonzero usize"), "{error}");
     }
 
     #[test]
@@ -2131,6 +2775,9 @@ mod tests {
             path: "/path",
             rotate: Never,
             mode: Blocking,
+            max_files: None,
+            max_age: None,
+            compression: None,
         }
         "###);
     }
@@ -2152,6 +2799,9 @@ mod tests {
             path: "/path",
             rotate: Minutely,
             mode: Blocking,
+            max_files: None,
+            max_age: None,
+            compression: None,
         }
         "###);
     }
@@ -2175,6 +2825,9 @@ mod tests {
                 1024 bytes,
             ),
             mode: Blocking,
+            max_files: None,
+            max_age: None,
+            compression: None,
         }
         "###);
 
@@ -2195,10 +2848,44 @@ mod tests {
                 1024 bytes,
             ),
             mode: Blocking,
+            max_files: None,
+            max_age: None,
+            compression: None,
         }
         "###);
     }
 
+    #[test]
+    fn access_logs_retention() {
+        let input = indoc! {r#"
+            [gateway.access_logs]
+            enabled = true
+            path = "/path"
+            rotate = "daily"
+            max_files = 7
+            max_age = "30d"
+            compression = "zstd"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        insta::assert_debug_snapshot!(&config.gateway.access_logs, @r#"
+        AccessLogsConfig {
+            enabled: true,
+            path: "/path",
+            rotate: Daily,
+            mode: Blocking,
+            max_files: Some(
+                7,
+            ),
+            max_age: Some(
+                2592000s,
+            ),
+            compression: Zstd,
+        }
+        "#);
+    }
+
     #[test]
     fn batching_default() {
         let input = indoc! {r#"
@@ -2287,6 +2974,37 @@ mod tests {
         assert_eq!(500, config.operation_caching.limit);
     }
 
+    #[test]
+    fn field_usage_defaults() {
+        let config: Config = toml::from_str("").unwrap();
+
+        assert!(!config.field_usage.enabled);
+        assert_eq!(FieldUsageStorage::File, config.field_usage.storage);
+        assert_eq!(Duration::from_secs(60), config.field_usage.flush_interval);
+    }
+
+    #[test]
+    fn field_usage_redis() {
+        let input = indoc! {r#"
+            [field_usage]
+            enabled = true
+            storage = "redis"
+            flush_interval = "10s"
+
+            [field_usage.redis]
+            url = "redis://usage.example.com:6379"
+            key_prefix = "my-usage"
+        "#};
+
+        let config: Config = toml::from_str(input).unwrap();
+
+        assert!(config.field_usage.enabled);
+        assert_eq!(FieldUsageStorage::Redis, config.field_usage.storage);
+        assert_eq!(Duration::from_secs(10), config.field_usage.flush_interval);
+        assert_eq!("redis://usage.example.com:6379", config.field_usage.redis.url.as_str());
+        assert_eq!("my-usage", config.field_usage.redis.key_prefix);
+    }
+
     #[test]
     fn extension_only_version() {
         let input = indoc! {r#"
@@ -2354,6 +3072,7 @@ mod tests {
                     networking: Some(
                         false,
                     ),
+                    allowed_hosts: None,
                     stdout: Some(
                         false,
                     ),
@@ -2366,6 +3085,10 @@ mod tests {
                     max_pool_size: Some(
                         1000,
                     ),
+                    max_memory: None,
+                    max_execution_time: None,
+                    max_table_elements: None,
+                    cache: None,
                     config: None,
                 },
             ),
@@ -2453,10 +3176,15 @@ mod tests {
                     },
                     path: None,
                     networking: None,
+                    allowed_hosts: None,
                     stdout: None,
                     stderr: None,
                     environment_variables: None,
                     max_pool_size: None,
+                    max_memory: None,
+                    max_execution_time: None,
+                    max_table_elements: None,
+                    cache: None,
                     config: Some(
                         Table(
                             {
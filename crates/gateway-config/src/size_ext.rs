@@ -43,3 +43,10 @@ where
         Ok(size)
     }
 }
+
+pub(crate) fn deserialize_option_positive_size<'de, D>(deserializer: D) -> Result<Option<Size>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_positive_size(deserializer).map(Some)
+}
@@ -1,13 +1,66 @@
+use std::{num::NonZeroUsize, time::Duration};
+
 #[derive(Clone, Debug, serde::Deserialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct TrafficShapingConfig {
     pub inflight_deduplication: bool,
+    /// Maximum number of concurrent requests sent to a single subgraph. Unlimited by default.
+    pub max_concurrent_requests: Option<NonZeroUsize>,
+    /// Maximum number of requests waiting for a free slot once the concurrency limit is reached.
+    /// Requests beyond it are rejected immediately.
+    pub max_queue_size: usize,
+    /// How long a request may wait in the queue before being rejected.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration")]
+    pub queue_timeout: Option<Duration>,
+    /// Gateway-wide load shedding based on the operation latency.
+    pub load_shedding: Option<LoadSheddingConfig>,
 }
 
 impl Default for TrafficShapingConfig {
     fn default() -> Self {
         Self {
             inflight_deduplication: true,
+            max_concurrent_requests: None,
+            max_queue_size: 0,
+            queue_timeout: None,
+            load_shedding: None,
+        }
+    }
+}
+
+/// Concurrency limits of a single subgraph, overriding the global traffic shaping settings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubgraphTrafficShapingConfig {
+    /// Maximum number of concurrent requests sent to the subgraph.
+    pub max_concurrent_requests: Option<NonZeroUsize>,
+    /// Maximum number of requests waiting for a free slot once the concurrency limit is reached.
+    pub max_queue_size: Option<usize>,
+    /// How long a request may wait in the queue before being rejected.
+    #[serde(deserialize_with = "duration_str::deserialize_option_duration")]
+    pub queue_timeout: Option<Duration>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoadSheddingConfig {
+    /// Average operation latency above which incoming requests start to be rejected. The further
+    /// the latency goes above it, the more requests are rejected.
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub latency_threshold: Duration,
+    /// Maximum fraction of the incoming requests that can be rejected, capped at 0.99.
+    pub max_rejection_ratio: f64,
+    /// Value of the Retry-After header sent with rejected requests.
+    #[serde(deserialize_with = "duration_str::deserialize_duration")]
+    pub retry_after: Duration,
+}
+
+impl Default for LoadSheddingConfig {
+    fn default() -> Self {
+        Self {
+            latency_threshold: Duration::from_secs(1),
+            max_rejection_ratio: 0.9,
+            retry_after: Duration::from_secs(1),
         }
     }
 }
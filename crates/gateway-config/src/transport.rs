@@ -0,0 +1,44 @@
+use url::Url;
+
+/// How the gateway connects to the subgraphs. Settings of a subgraph override the global ones
+/// field by field.
+#[derive(Debug, Default, serde::Deserialize, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransportConfig {
+    /// Forward proxy through which the subgraph requests are sent.
+    pub proxy: Option<ProxyConfig>,
+    /// Speak HTTP/2 directly without negotiating it first, h2c for plain HTTP subgraphs.
+    pub http2_prior_knowledge: Option<bool>,
+}
+
+impl TransportConfig {
+    /// Applies the subgraph settings on top of the global ones.
+    pub fn merge(&self, subgraph: Option<&TransportConfig>) -> TransportConfig {
+        let Some(subgraph) = subgraph else {
+            return self.clone();
+        };
+
+        TransportConfig {
+            proxy: subgraph.proxy.clone().or_else(|| self.proxy.clone()),
+            http2_prior_knowledge: subgraph.http2_prior_knowledge.or(self.http2_prior_knowledge),
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+#[derive(Debug, serde::Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ProxyConfig {
+    /// URL of the proxy, with one of the `http`, `https` or `socks5` schemes.
+    pub url: Url,
+    /// Hosts reached directly rather than through the proxy, in the same format as the
+    /// `NO_PROXY` environment variable.
+    #[serde(default)]
+    pub no_proxy: Vec<String>,
+    /// Basic authentication against the proxy.
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
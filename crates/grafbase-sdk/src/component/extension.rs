@@ -120,4 +120,14 @@ pub(crate) trait AnyExtension {
     ) -> Result<(), Error> {
         Ok(())
     }
+
+    fn authorize_policies(
+        &mut self,
+        ctx: &AuthenticatedRequestContext,
+        policies: &[String],
+    ) -> Result<Vec<bool>, Error> {
+        Err(Error::new(
+            "Hooks extension not initialized correctly. Is it defined with the appropriate type?",
+        ))
+    }
 }
@@ -75,4 +75,16 @@ impl wit::HooksGuest for Component {
                 .map_err(Into::into)
         })
     }
+
+    fn authorize_policies(
+        event_queue: wit::EventQueue,
+        ctx: wit::AuthenticatedRequestContext,
+        policies: Vec<String>,
+    ) -> Result<Vec<bool>, wit::Error> {
+        state::with_event_queue(event_queue, || {
+            state::extension()?
+                .authorize_policies(&(ctx.into()), &policies)
+                .map_err(Into::into)
+        })
+    }
 }
@@ -2,8 +2,8 @@ use crate::{
     component::AnyExtension,
     host_io::{event_queue::EventQueue, http::StatusCode},
     types::{
-        AuthorizedOperationContext, Configuration, Error, ErrorResponse, GatewayHeaders, Headers, HttpRequestParts,
-        OnRequestOutput, RequestContext,
+        AuthenticatedRequestContext, AuthorizedOperationContext, Configuration, Error, ErrorResponse, GatewayHeaders,
+        Headers, HttpRequestParts, OnRequestOutput, RequestContext,
     },
 };
 
@@ -146,6 +146,18 @@ pub trait HooksExtension: Sized + 'static {
     ) -> Result<(), Error> {
         Ok(())
     }
+
+    /// Called once per operation using fields protected by the `@policy` directive, with every
+    /// policy they depend on. Returns whether each policy is granted, in the same order.
+    ///
+    /// By default all policies are denied.
+    fn authorize_policies(
+        &mut self,
+        ctx: &AuthenticatedRequestContext,
+        policies: &[String],
+    ) -> Result<Vec<bool>, Error> {
+        Ok(vec![false; policies.len()])
+    }
 }
 
 pub trait IntoOnRequestOutput {
@@ -207,6 +219,14 @@ pub fn register<T: HooksExtension>() {
         ) -> Result<(), Error> {
             self.0.on_virtual_subgraph_request(ctx, subgraph_name, headers)
         }
+
+        fn authorize_policies(
+            &mut self,
+            ctx: &AuthenticatedRequestContext,
+            policies: &[String],
+        ) -> Result<Vec<bool>, Error> {
+            self.0.authorize_policies(ctx, policies)
+        }
     }
 
     crate::component::register_extension(Box::new(|_, config| {
@@ -7,6 +7,9 @@ use crate::wit;
 /// The cache is a key-value store shared across Wasm instances. As Wasm is single threaded, the
 /// gateway uses a pool of Wasm instances to execute extensions. Cache with the same name will be
 /// the same across those instances and share the same data.
+///
+/// With `storage = "redis"` in the `cache` section of the extension configuration, caches are
+/// stored in Redis instead and shared by all gateway instances. The size is then ignored.
 pub struct Cache {
     inner: wit::Cache,
     timeout: Duration,
@@ -359,6 +359,9 @@ pub enum CacheStatus {
     PartialHit,
     /// No cached data was available; the entire response was fetched from the subgraph.
     Miss,
+    /// Expired data was served from cache, either while it is being refreshed or because the
+    /// subgraph request failed.
+    Stale,
 }
 
 impl From<wit::CacheStatus> for CacheStatus {
@@ -367,6 +370,7 @@ impl From<wit::CacheStatus> for CacheStatus {
             wit::CacheStatus::Hit => Self::Hit,
             wit::CacheStatus::PartialHit => Self::PartialHit,
             wit::CacheStatus::Miss => Self::Miss,
+            wit::CacheStatus::Stale => Self::Stale,
         }
     }
 }
@@ -378,6 +382,7 @@ impl CacheStatus {
             Self::Hit => "hit",
             Self::PartialHit => "partial_hit",
             Self::Miss => "miss",
+            Self::Stale => "stale",
         }
     }
 }
@@ -8,7 +8,8 @@ pub use serde_json::Error as JsonDeserializeError;
 pub use url::Url;
 
 use crate::{
-    types::{AsHeaderName, AsHeaderValue},
+    Subscription,
+    types::{AsHeaderName, AsHeaderValue, Error, Response, SubscriptionItem},
     wit::{self, HttpClient},
 };
 use serde::Serialize;
@@ -56,6 +57,16 @@ pub fn execute_many(requests: BatchHttpRequest) -> Vec<Result<HttpResponse, Http
         .collect()
 }
 
+/// Executes a single HTTP request and returns as soon as the response headers are received. The
+/// body is then read in chunks with [HttpStreamResponse::next_chunk], without buffering it
+/// entirely in the guest memory. Useful for chunked, NDJSON or SSE upstreams.
+pub fn execute_streaming(request: impl Into<HttpRequest>) -> Result<HttpStreamResponse, HttpError> {
+    let request: HttpRequest = request.into();
+    wit::HttpStreamClient::execute(request.0)
+        .map(Into::into)
+        .map_err(Into::into)
+}
+
 impl From<http::Method> for HttpMethod {
     fn from(value: http::Method) -> Self {
         if value == http::Method::GET {
@@ -377,6 +388,23 @@ impl HttpRequestBuilder {
     }
 }
 
+impl HttpRequestBuilder {
+    /// Sends the request headers and returns a writer for the body, which is sent in chunks as
+    /// they are written. Any body set on the builder is ignored.
+    pub fn send_streaming(self) -> Result<HttpBodyWriter, HttpError> {
+        let request = wit::HttpStreamRequest {
+            method: self.method.into(),
+            url: self.url.to_string(),
+            headers: self.headers.into(),
+            timeout_ms: self.timeout.map(|d| d.as_millis() as u64),
+        };
+
+        let inner = wit::HttpStreamClient::start(request)?;
+
+        Ok(HttpBodyWriter { inner })
+    }
+}
+
 impl From<HttpRequestBuilder> for HttpRequest {
     fn from(builder: HttpRequestBuilder) -> Self {
         builder.build()
@@ -477,3 +505,109 @@ impl HttpResponse {
         serde_json::from_slice(&self.body)
     }
 }
+
+/// The body of a request being sent in chunks, created with [HttpRequestBuilder::send_streaming].
+/// Dropping it before calling [HttpBodyWriter::finish] aborts the request.
+pub struct HttpBodyWriter {
+    inner: wit::HttpBodyWriter,
+}
+
+impl HttpBodyWriter {
+    /// Sends a chunk of the body, blocking while the server is not reading fast enough.
+    pub fn write(&self, chunk: &[u8]) -> Result<(), HttpError> {
+        Ok(self.inner.write(chunk)?)
+    }
+
+    /// Ends the body and waits for the response headers.
+    pub fn finish(self) -> Result<HttpStreamResponse, HttpError> {
+        Ok(wit::HttpBodyWriter::finish(self.inner)?.into())
+    }
+}
+
+/// An HTTP response whose body is received in chunks.
+pub struct HttpStreamResponse {
+    status_code: http::StatusCode,
+    headers: Headers,
+    body: wit::HttpBodyReader,
+    buffer: Vec<u8>,
+}
+
+impl From<wit::HttpStreamResponse> for HttpStreamResponse {
+    fn from(response: wit::HttpStreamResponse) -> Self {
+        Self {
+            status_code: http::StatusCode::from_u16(response.status).expect("Provided by the host"),
+            headers: response.headers.into(),
+            body: response.body,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl HttpStreamResponse {
+    /// Returns the status code of the HTTP response.
+    pub fn status(&self) -> http::StatusCode {
+        self.status_code
+    }
+
+    /// Returns the headers of the HTTP response.
+    pub fn headers(&self) -> &Headers {
+        &self.headers
+    }
+
+    /// Returns the next chunk of the body as sent by the server, or `None` at the end of the body.
+    pub fn next_chunk(&mut self) -> Result<Option<Vec<u8>>, HttpError> {
+        if !self.buffer.is_empty() {
+            return Ok(Some(std::mem::take(&mut self.buffer)));
+        }
+
+        Ok(self.body.next()?)
+    }
+
+    /// Returns the next line of the body without its line ending, or `None` at the end of the
+    /// body. Suited for NDJSON and server-sent events.
+    pub fn next_line(&mut self) -> Result<Option<Vec<u8>>, HttpError> {
+        loop {
+            if let Some(position) = self.buffer.iter().position(|&byte| byte == b'\n') {
+                let mut line = self.buffer.drain(..=position).collect::<Vec<_>>();
+                line.pop();
+
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+
+                return Ok(Some(line));
+            }
+
+            match self.body.next()? {
+                Some(chunk) => self.buffer.extend_from_slice(&chunk),
+                None if self.buffer.is_empty() => return Ok(None),
+                None => return Ok(Some(std::mem::take(&mut self.buffer))),
+            }
+        }
+    }
+
+    /// Reads the remaining body entirely.
+    pub fn into_bytes(mut self) -> Result<Vec<u8>, HttpError> {
+        let mut body = std::mem::take(&mut self.buffer);
+
+        while let Some(chunk) = self.body.next()? {
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(body)
+    }
+}
+
+/// Each non-empty line of the body is a subscription item in JSON, as with NDJSON upstreams.
+impl Subscription for HttpStreamResponse {
+    fn next(&mut self) -> Result<Option<SubscriptionItem>, Error> {
+        loop {
+            match self.next_line() {
+                Ok(Some(line)) if line.iter().all(u8::is_ascii_whitespace) => continue,
+                Ok(Some(line)) => return Ok(Some(Response::json(line).into())),
+                Ok(None) => return Ok(None),
+                Err(err) => return Err(format!("Error receiving HTTP body: {err}").into()),
+            }
+        }
+    }
+}
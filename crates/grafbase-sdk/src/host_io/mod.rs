@@ -12,3 +12,4 @@ pub mod kafka;
 pub mod logger;
 pub mod nats;
 pub mod postgres;
+pub mod redis;
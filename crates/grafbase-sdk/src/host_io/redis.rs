@@ -0,0 +1,302 @@
+//! Client interface for interacting with Redis
+//!
+//! Connections are pooled by the gateway and shared by all instances of the extension using the
+//! same pool identifier. Values are raw bytes, with JSON helpers for convenience.
+//!
+//! ```rust,no_run
+//! # use grafbase_sdk::{SdkError, host_io::redis};
+//! # use std::time::Duration;
+//! # fn main() -> Result<(), SdkError> {
+//! let pool = redis::Pool::connect("my-redis", "redis://localhost:6379")?;
+//!
+//! pool.set("greeting", &"hello", Some(Duration::from_secs(60)))?;
+//! let greeting: Option<String> = pool.get("greeting")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use crate::{
+    SdkError, Subscription,
+    types::{Error, Response, SubscriptionItem},
+    wit,
+};
+
+/// TLS configuration of a Redis connection, with paths to PEM files on the gateway host.
+pub struct TlsConfig(wit::RedisTlsConfig);
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self(wit::RedisTlsConfig {
+            cert: None,
+            key: None,
+            ca: None,
+        })
+    }
+}
+
+impl TlsConfig {
+    /// Creates a new TLS configuration, verifying the server with the system root certificates.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the client certificate and private key, for mutual TLS.
+    pub fn client_certificate(mut self, cert: impl Into<String>, key: impl Into<String>) -> Self {
+        self.0.cert = Some(cert.into());
+        self.0.key = Some(key.into());
+        self
+    }
+
+    /// Sets the certificate authority used to verify the server.
+    pub fn ca(mut self, ca: impl Into<String>) -> Self {
+        self.0.ca = Some(ca.into());
+        self
+    }
+}
+
+/// Configuration options for a Redis connection pool.
+pub struct PoolOptions(wit::RedisPoolOptions);
+
+impl Default for PoolOptions {
+    fn default() -> Self {
+        Self(wit::RedisPoolOptions {
+            max_connections: None,
+            acquisition_timeout_ms: None,
+            tls: None,
+        })
+    }
+}
+
+impl PoolOptions {
+    /// Creates a new `PoolOptions` instance with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of connections in the pool.
+    pub fn max_connections(mut self, max_connections: u32) -> Self {
+        self.0.max_connections = Some(max_connections);
+        self
+    }
+
+    /// Sets the maximum time to wait when acquiring a connection from the pool.
+    pub fn acquire_timeout(mut self, acquire_timeout: Duration) -> Self {
+        self.0.acquisition_timeout_ms = Some(acquire_timeout.as_millis() as u64);
+        self
+    }
+
+    /// Sets the TLS configuration of the connections.
+    pub fn tls(mut self, tls: TlsConfig) -> Self {
+        self.0.tls = Some(tls.0);
+        self
+    }
+}
+
+/// A value returned by a Lua script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// No value, `nil` in Lua.
+    Nil,
+    /// An integer.
+    Integer(i64),
+    /// A string, as raw bytes.
+    Bytes(Vec<u8>),
+    /// A flat array of values.
+    Array(Vec<Value>),
+}
+
+impl From<wit::RedisValue> for Value {
+    fn from(value: wit::RedisValue) -> Self {
+        match value {
+            wit::RedisValue::Nil => Value::Nil,
+            wit::RedisValue::Integer(value) => Value::Integer(value),
+            wit::RedisValue::Bytes(value) => Value::Bytes(value),
+            wit::RedisValue::Array(values) => Value::Array(values.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl From<wit::RedisScalar> for Value {
+    fn from(value: wit::RedisScalar) -> Self {
+        match value {
+            wit::RedisScalar::Nil => Value::Nil,
+            wit::RedisScalar::Integer(value) => Value::Integer(value),
+            wit::RedisScalar::Bytes(value) => Value::Bytes(value),
+        }
+    }
+}
+
+/// A Redis connection pool managed by the gateway.
+pub struct Pool(wit::RedisPool);
+
+impl Pool {
+    /// Creates a new connection pool with default options.
+    ///
+    /// # Parameters
+    /// * `identifier` - A unique identifier for the pool
+    /// * `url` - The Redis connection URL, `redis://` or `rediss://` for TLS
+    ///
+    /// # Returns
+    /// A new connection pool or an error if the configuration is invalid
+    pub fn connect(identifier: &str, url: &str) -> Result<Self, SdkError> {
+        Self::connect_with_options(identifier, url, Default::default())
+    }
+
+    /// Creates a new connection pool with custom options.
+    ///
+    /// # Parameters
+    /// * `identifier` - A unique identifier for the pool
+    /// * `url` - The Redis connection URL, `redis://` or `rediss://` for TLS
+    /// * `options` - Configuration options for the connection pool
+    ///
+    /// # Returns
+    /// A new connection pool or an error if the configuration is invalid
+    pub fn connect_with_options(identifier: &str, url: &str, options: PoolOptions) -> Result<Self, SdkError> {
+        let pool = wit::RedisPool::connect(identifier, url, &options.0)?;
+        Ok(Self(pool))
+    }
+
+    /// Retrieves the value of a key in JSON format.
+    pub fn get<T>(&self, key: &str) -> Result<Option<T>, SdkError>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        match self.get_bytes(key)? {
+            Some(ref value) => Ok(Some(serde_json::from_slice(value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieves the raw bytes of a key.
+    pub fn get_bytes(&self, key: &str) -> Result<Option<Vec<u8>>, SdkError> {
+        Ok(self.0.get(key)?)
+    }
+
+    /// Sets the value of a key in JSON format, expiring after `ttl` if provided.
+    pub fn set<T>(&self, key: &str, value: &T, ttl: Option<Duration>) -> Result<(), SdkError>
+    where
+        T: serde::Serialize,
+    {
+        self.set_bytes(key, &serde_json::to_vec(value)?, ttl)
+    }
+
+    /// Sets the raw bytes of a key, expiring after `ttl` if provided.
+    pub fn set_bytes(&self, key: &str, value: &[u8], ttl: Option<Duration>) -> Result<(), SdkError> {
+        let ttl_ms = ttl.map(|ttl| ttl.as_millis() as u64);
+        Ok(self.0.set(key, value, ttl_ms)?)
+    }
+
+    /// Deletes a key. Returns whether it existed.
+    pub fn delete(&self, key: &str) -> Result<bool, SdkError> {
+        Ok(self.0.delete(key)?)
+    }
+
+    /// Increments the integer value of a key, starting from zero, and returns the new value.
+    pub fn incr(&self, key: &str, delta: i64) -> Result<i64, SdkError> {
+        Ok(self.0.incr(key, delta)?)
+    }
+
+    /// Sets the time to live of a key. Returns whether the key exists.
+    pub fn expire(&self, key: &str, ttl: Duration) -> Result<bool, SdkError> {
+        Ok(self.0.expire(key, ttl.as_millis() as u64)?)
+    }
+
+    /// Retrieves the raw bytes of a field in a hash.
+    pub fn hget(&self, key: &str, field: &str) -> Result<Option<Vec<u8>>, SdkError> {
+        Ok(self.0.hget(key, field)?)
+    }
+
+    /// Sets the raw bytes of a field in a hash.
+    pub fn hset(&self, key: &str, field: &str, value: &[u8]) -> Result<(), SdkError> {
+        Ok(self.0.hset(key, field, value)?)
+    }
+
+    /// Deletes a field of a hash. Returns whether it existed.
+    pub fn hdel(&self, key: &str, field: &str) -> Result<bool, SdkError> {
+        Ok(self.0.hdel(key, field)?)
+    }
+
+    /// Retrieves all fields of a hash with their raw bytes.
+    pub fn hgetall(&self, key: &str) -> Result<Vec<(String, Vec<u8>)>, SdkError> {
+        Ok(self.0.hgetall(key)?)
+    }
+
+    /// Runs a Lua script with the given keys and arguments.
+    pub fn eval(&self, script: &str, keys: &[&str], args: &[&[u8]]) -> Result<Value, SdkError> {
+        let keys = keys.iter().map(|key| key.to_string()).collect::<Vec<_>>();
+        let args = args.iter().map(|arg| arg.to_vec()).collect::<Vec<_>>();
+
+        Ok(self.0.eval(script, &keys, &args)?.into())
+    }
+
+    /// Publishes a message in JSON format on a channel. Returns the number of subscribers which
+    /// received it.
+    pub fn publish<T>(&self, channel: &str, message: &T) -> Result<u64, SdkError>
+    where
+        T: serde::Serialize,
+    {
+        self.publish_bytes(channel, &serde_json::to_vec(message)?)
+    }
+
+    /// Publishes raw bytes on a channel. Returns the number of subscribers which received it.
+    pub fn publish_bytes(&self, channel: &str, message: &[u8]) -> Result<u64, SdkError> {
+        Ok(self.0.publish(channel, message)?)
+    }
+
+    /// Subscribes to one or more channels, on a dedicated connection.
+    pub fn subscribe(&self, channels: &[&str]) -> Result<RedisSubscription, SdkError> {
+        let channels = channels.iter().map(|channel| channel.to_string()).collect::<Vec<_>>();
+        let inner = self.0.subscribe(&channels)?;
+
+        Ok(RedisSubscription { inner })
+    }
+}
+
+/// A subscription to Redis channels, usable as a GraphQL subscription with JSON messages.
+pub struct RedisSubscription {
+    inner: wit::RedisSubscriber,
+}
+
+impl RedisSubscription {
+    /// Gets the next message from the subscription, or `None` if the connection was closed.
+    pub fn next(&self) -> Result<Option<RedisMessage>, SdkError> {
+        Ok(self.inner.next()?.map(|inner| RedisMessage { inner }))
+    }
+}
+
+impl Subscription for RedisSubscription {
+    fn next(&mut self) -> Result<Option<SubscriptionItem>, Error> {
+        match RedisSubscription::next(self) {
+            Ok(Some(msg)) => Ok(Some(Response::json(msg.inner.payload).into())),
+            Ok(None) => Ok(None),
+            Err(err) => Err(format!("Error receiving Redis message: {err}").into()),
+        }
+    }
+}
+
+/// A message received on a Redis channel.
+pub struct RedisMessage {
+    inner: wit::RedisMessage,
+}
+
+impl RedisMessage {
+    /// Gets the payload of the message in JSON format.
+    pub fn payload<T>(&self) -> Result<T, SdkError>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        Ok(serde_json::from_slice(self.payload_bytes())?)
+    }
+
+    /// Gets the raw bytes of the message payload.
+    pub fn payload_bytes(&self) -> &[u8] {
+        &self.inner.payload
+    }
+
+    /// Gets the channel the message was published to.
+    pub fn channel(&self) -> &str {
+        &self.inner.channel
+    }
+}
@@ -4,12 +4,15 @@
 //! - Configuring and starting a gateway instance
 //! - Executing GraphQL queries against the gateway
 //! - Building and loading extensions
+//! - Driving the extension in-process with its host IO mocked
 
 mod config;
+mod extension;
 mod gateway;
 mod request;
 
 pub use config::LogLevel;
+pub use extension::*;
 pub use gateway::{TestGateway, TestGatewayBuilder};
 pub use grafbase_sdk_mock::{
     EntityResolverContext, GraphqlSubgraph, GraphqlSubgraphBuilder, ResolverContext, VirtualSubgraph,
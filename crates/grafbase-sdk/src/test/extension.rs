@@ -0,0 +1,61 @@
+//! In-process test harness for the current extension, with all its host IO mocked.
+
+use std::path::Path;
+
+use anyhow::{Context, anyhow};
+
+use crate::test::config::CLI_BINARY_NAME;
+
+pub use wasi_component_loader::test_harness::{
+    DirectiveSite, HostIoMocks, MockPgRow, MockedGrpcRequest, MockedHttpRequest, MockedKafkaMessage, MockedMessage,
+    MockedRedisEval, QueryElement, TestExtension,
+};
+
+/// Builds the extension in the current directory with the Grafbase CLI and loads it in-process.
+/// Contrary to the [TestGateway](crate::test::TestGateway), no gateway is started and every HTTP,
+/// NATS, Kafka, Postgres, gRPC and Redis call goes to the [HostIoMocks] of the extension.
+/// `config` is the TOML configuration of the extension as it would appear in the gateway
+/// configuration.
+pub async fn load_current_extension(config: &str) -> anyhow::Result<TestExtension> {
+    let cli_path = which::which(CLI_BINARY_NAME).context("Could not find grafbase binary in the PATH.")?;
+    let extension_path = std::env::current_dir()?;
+
+    build_current_extension(&cli_path, &extension_path, false)?;
+
+    TestExtension::load(extension_path.join("build"), config).await
+}
+
+pub(super) fn build_current_extension(
+    cli_path: &Path,
+    extension_path: &Path,
+    stream_stdout_stderr: bool,
+) -> anyhow::Result<()> {
+    let lock_path = extension_path.join(".build.lock");
+    let mut lock_file = fslock::LockFile::open(&lock_path)?;
+    lock_file.lock()?;
+
+    let output = {
+        let cmd = duct::cmd(cli_path, &["extension", "build", "--debug"]).dir(extension_path);
+        if stream_stdout_stderr {
+            cmd
+        } else {
+            cmd.stdout_capture().stderr_capture()
+        }
+    }
+    .unchecked()
+    .stderr_to_stdout()
+    .run()?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Failed to build extension: {}\n{}\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    lock_file.unlock()?;
+
+    Ok(())
+}
@@ -10,6 +10,7 @@ use crate::test::{
     config::{
         CLI_BINARY_NAME, ExtensionConfig, ExtensionToml, GATEWAY_BINARY_NAME, GatewayToml, StructuredExtensionConfig,
     },
+    extension::build_current_extension,
     request::{Body, IntrospectionRequest},
 };
 
@@ -170,36 +171,8 @@ impl TestGatewayBuilder {
                 .name;
 
         // Ensure current extension is built and up to date.
-        {
-            println!("* Building current extension.");
-            let lock_path = extension_path.join(".build.lock");
-            let mut lock_file = fslock::LockFile::open(&lock_path)?;
-            lock_file.lock()?;
-
-            let output = {
-                let cmd = duct::cmd(&cli_path, &["extension", "build", "--debug"]).dir(&extension_path);
-                if self.stream_stdout_stderr.unwrap_or(false) {
-                    cmd
-                } else {
-                    cmd.stdout_capture().stderr_capture()
-                }
-            }
-            .unchecked()
-            .stderr_to_stdout()
-            .run()?;
-
-            if !output.status.success() {
-                return Err(anyhow!(
-                    "Failed to build extension: {}\n{}\n{}",
-                    output.status,
-                    String::from_utf8_lossy(&output.stdout),
-                    String::from_utf8_lossy(&output.stderr)
-                ));
-            }
-
-            lock_file.unlock()?;
-            anyhow::Ok(())
-        }?;
+        println!("* Building current extension.");
+        build_current_extension(&cli_path, &extension_path, self.stream_stdout_stderr.unwrap_or(false))?;
 
         println!("* Preparing the grafbase.toml & schema.graphql files.");
         // Update grafbase TOML with current extension path.
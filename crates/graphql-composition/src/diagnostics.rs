@@ -165,6 +165,17 @@ pub enum CompositeSchemasErrorCode {
     PostMerge(CompositeSchemasPostMergeValidationErrorCode),
 }
 
+impl CompositeSchemasErrorCode {
+    /// The error code as written in the spec, e.g. `QUERY_ROOT_TYPE_INACCESSIBLE`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompositeSchemasErrorCode::SourceSchema(code) => code.as_str(),
+            CompositeSchemasErrorCode::PreMerge(code) => code.as_str(),
+            CompositeSchemasErrorCode::PostMerge(code) => code.as_str(),
+        }
+    }
+}
+
 impl From<CompositeSchemasPostMergeValidationErrorCode> for CompositeSchemasErrorCode {
     fn from(v: CompositeSchemasPostMergeValidationErrorCode) -> Self {
         Self::PostMerge(v)
@@ -198,6 +209,18 @@ pub enum CompositeSchemasSourceSchemaValidationErrorCode {
 }
 
 impl CompositeSchemasSourceSchemaValidationErrorCode {
+    /// The error code as written in the spec.
+    pub fn as_str(&self) -> &'static str {
+        use CompositeSchemasSourceSchemaValidationErrorCode::*;
+
+        match self {
+            QueryRootTypeInaccessible => "QUERY_ROOT_TYPE_INACCESSIBLE",
+            LookupReturnsNonNullableType => "LOOKUP_RETURNS_NON_NULLABLE_TYPE",
+            OverrideFromSelf => "OVERRIDE_FROM_SELF",
+            ProvidesDirectiveInFieldsArgument => "PROVIDES_DIRECTIVE_IN_FIELDS_ARGUMENT",
+        }
+    }
+
     fn severity(&self) -> Severity {
         use CompositeSchemasSourceSchemaValidationErrorCode::*;
 
@@ -219,6 +242,16 @@ pub enum CompositeSchemasPreMergeValidationErrorCode {
 }
 
 impl CompositeSchemasPreMergeValidationErrorCode {
+    /// The error code as written in the spec.
+    pub fn as_str(&self) -> &'static str {
+        use CompositeSchemasPreMergeValidationErrorCode::*;
+
+        match self {
+            TypeKindMismatch => "TYPE_KIND_MISMATCH",
+            OverrideSourceHasOverride => "OVERRIDE_SOURCE_HAS_OVERRIDE",
+        }
+    }
+
     fn severity(&self) -> Severity {
         use CompositeSchemasPreMergeValidationErrorCode::*;
 
@@ -236,6 +269,15 @@ pub enum CompositeSchemasPostMergeValidationErrorCode {
 }
 
 impl CompositeSchemasPostMergeValidationErrorCode {
+    /// The error code as written in the spec.
+    pub fn as_str(&self) -> &'static str {
+        use CompositeSchemasPostMergeValidationErrorCode::*;
+
+        match self {
+            InvalidFieldSharing => "INVALID_FIELD_SHARING",
+        }
+    }
+
     fn severity(&self) -> Severity {
         use CompositeSchemasPostMergeValidationErrorCode::*;
 
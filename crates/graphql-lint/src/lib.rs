@@ -1,10 +1,11 @@
-use cynic_parser::TypeSystemDocument;
 use cynic_parser::type_system::{
     Definition, Directive, DirectiveDefinition, EnumDefinition, EnumValueDefinition, FieldDefinition,
     InputObjectDefinition, InputValueDefinition, InterfaceDefinition, ObjectDefinition, ScalarDefinition,
     TypeDefinition, UnionDefinition,
 };
+use cynic_parser::{Span, TypeSystemDocument};
 use heck::{ToLowerCamelCase, ToPascalCase, ToShoutySnakeCase};
+use std::ops::Range;
 use thiserror::Error;
 
 enum CaseMatch<'a> {
@@ -18,10 +19,48 @@ enum Case {
     Camel,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Severity {
     Warning,
 }
 
+/// The lint rule that produced a [Diagnostic].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// Names must follow the GraphQL naming conventions, e.g. `PascalCase` types and `camelCase` fields.
+    NamingConvention,
+    /// Names must not start with a redundant prefix, e.g. `Type` on types or `get` on query fields.
+    ForbiddenPrefix,
+    /// Names must not end with a redundant suffix, e.g. `Enum` on enums or `Query` on query fields.
+    ForbiddenSuffix,
+    /// Usages of `@deprecated` must populate the `reason` argument.
+    DeprecatedWithoutReason,
+}
+
+impl Rule {
+    /// A stable identifier for the rule, e.g. `naming-convention`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Rule::NamingConvention => "naming-convention",
+            Rule::ForbiddenPrefix => "forbidden-prefix",
+            Rule::ForbiddenSuffix => "forbidden-suffix",
+            Rule::DeprecatedWithoutReason => "deprecated-without-reason",
+        }
+    }
+}
+
+/// A lint diagnostic, with the rule that produced it and the location of the linted element.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub message: String,
+    pub severity: Severity,
+    pub rule: Rule,
+    /// The schema coordinate of the linted element, e.g. `Query.user(id:)` or `@key`.
+    pub coordinate: String,
+    /// The byte offsets of the linted element in the schema.
+    pub span: Range<usize>,
+}
+
 #[derive(Error, Debug)]
 pub enum LinterError {
     #[error("encountered a parsing error:\n{0}")]
@@ -29,13 +68,21 @@ pub enum LinterError {
 }
 
 pub fn lint(schema: &str) -> Result<Vec<(String, Severity)>, LinterError> {
+    Ok(lint_diagnostics(schema)?
+        .into_iter()
+        .map(|diagnostic| (diagnostic.message, diagnostic.severity))
+        .collect())
+}
+
+/// Lints the schema like [lint], with the rule and the location of each diagnostic.
+pub fn lint_diagnostics(schema: &str) -> Result<Vec<Diagnostic>, LinterError> {
     let parsed_schema =
         cynic_parser::parse_type_system_document(schema).map_err(|error| LinterError::Parse(error.to_string()))?;
     Ok(SchemaLinter::new().lint(&parsed_schema))
 }
 
 struct SchemaLinter {
-    diagnostics: Vec<(String, Severity)>,
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl<'a> SchemaLinter {
@@ -45,7 +92,7 @@ impl<'a> SchemaLinter {
         }
     }
 
-    pub fn lint(mut self, schema: &'a TypeSystemDocument) -> Vec<(String, Severity)> {
+    pub fn lint(mut self, schema: &'a TypeSystemDocument) -> Vec<Diagnostic> {
         schema.definitions().for_each(|definition| match definition {
             Definition::Schema(_) => {}
             Definition::SchemaExtension(_) => {}
@@ -132,6 +179,16 @@ impl<'a> SchemaLinter {
         self.diagnostics
     }
 
+    fn push(&mut self, rule: Rule, coordinate: &str, span: Span, message: String) {
+        self.diagnostics.push(Diagnostic {
+            message,
+            severity: Severity::Warning,
+            rule,
+            coordinate: coordinate.to_owned(),
+            span: span.start..span.end,
+        });
+    }
+
     fn case_check(current: &'a str, case: Case) -> CaseMatch<'a> {
         use regex::RegexSet;
         use std::sync::LazyLock;
@@ -162,39 +219,45 @@ impl<'a> SchemaLinter {
         argument: InputValueDefinition<'_>,
     ) {
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(argument.name(), Case::Camel) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                &format!("{}.{}({}:)", parent_type.name(), field.name(), argument.name()),
+                argument.span(),
                 format!(
                     "argument '{current}' on field '{}' on {} '{}' should be renamed to '{fix}'",
                     field.name(),
                     Self::type_definition_display(parent_type),
                     parent_type.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
     pub fn visit_directive_argument(&mut self, directive: DirectiveDefinition<'_>, argument: InputValueDefinition<'_>) {
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(argument.name(), Case::Camel) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                &format!("@{}({}:)", directive.name(), argument.name()),
+                argument.span(),
                 format!(
                     "argument '{current}' on directive '{}' should be renamed to '{fix}'",
                     directive.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
     pub fn visit_input_value(&mut self, parent: TypeDefinition<'_>, value: InputValueDefinition<'_>) {
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(value.name(), Case::Camel) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                &format!("{}.{}", parent.name(), value.name()),
+                value.span(),
                 format!(
                     "input value '{current}' on input '{}' should be renamed to '{fix}'",
                     parent.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -217,63 +280,79 @@ impl<'a> SchemaLinter {
             return;
         }
 
+        let coordinate = format!("{}.{field_name}", parent.name());
+
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(field_name, Case::Camel) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                &coordinate,
+                field.span(),
                 format!(
                     "field '{current}' on {} '{}' should be renamed to '{fix}'",
                     Self::type_definition_display(parent),
                     parent.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
         match parent.name() {
             "Query" => {
                 for prefix in ["query", "get", "list"] {
                     if field_name.starts_with(prefix) {
-                        self.diagnostics.push((
+                        self.push(
+                            Rule::ForbiddenPrefix,
+                            &coordinate,
+                            field.span(),
                             format!("field '{field_name}' on type 'Query' has a forbidden prefix: '{prefix}'"),
-                            Severity::Warning,
-                        ));
+                        );
                         break;
                     }
                 }
                 if field_name.ends_with("Query") {
-                    self.diagnostics.push((
+                    self.push(
+                        Rule::ForbiddenSuffix,
+                        &coordinate,
+                        field.span(),
                         format!("field '{field_name}' on type 'Query' has a forbidden suffix: 'Query'"),
-                        Severity::Warning,
-                    ));
+                    );
                 }
             }
             "Mutation" => {
                 for prefix in ["mutation", "put", "post", "patch"] {
                     if field_name.starts_with(prefix) {
-                        self.diagnostics.push((
+                        self.push(
+                            Rule::ForbiddenPrefix,
+                            &coordinate,
+                            field.span(),
                             format!("field '{field_name}' on type 'Mutation' has a forbidden prefix: '{prefix}'"),
-                            Severity::Warning,
-                        ));
+                        );
                         break;
                     }
                 }
                 if field_name.ends_with("Mutation") {
-                    self.diagnostics.push((
+                    self.push(
+                        Rule::ForbiddenSuffix,
+                        &coordinate,
+                        field.span(),
                         format!("field '{field_name}' on type 'Mutation' has a forbidden suffix: 'Mutation'"),
-                        Severity::Warning,
-                    ));
+                    );
                 }
             }
             "Subscription" => {
                 if field_name.starts_with("subscription") {
-                    self.diagnostics.push((
+                    self.push(
+                        Rule::ForbiddenPrefix,
+                        &coordinate,
+                        field.span(),
                         format!("field '{field_name}' on type 'Subscription' has a forbidden prefix: 'subscription'"),
-                        Severity::Warning,
-                    ));
+                    );
                 }
                 if field_name.ends_with("Subscription") {
-                    self.diagnostics.push((
+                    self.push(
+                        Rule::ForbiddenSuffix,
+                        &coordinate,
+                        field.span(),
                         format!("field '{field_name}' on type 'Subscription' has a forbidden suffix: 'Subscription'"),
-                        Severity::Warning,
-                    ));
+                    );
                 }
             }
             _ => {}
@@ -282,23 +361,27 @@ impl<'a> SchemaLinter {
 
     pub fn visit_directive(&mut self, directive: DirectiveDefinition<'_>) {
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(directive.name(), Case::Camel) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                &format!("@{}", directive.name()),
+                directive.span(),
                 format!("directive '{current}' should be renamed to '{fix}'"),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
     pub fn visit_directive_usage(&mut self, parent: TypeDefinition<'_>, directive: Directive<'_>) {
         if directive.name() == "deprecated" && !directive.arguments().any(|argument| argument.name() == "reason") {
-            self.diagnostics.push((
+            self.push(
+                Rule::DeprecatedWithoutReason,
+                parent.name(),
+                directive.name_span(),
                 format!(
                     "usage of directive 'deprecated' on {} '{}' does not populate the 'reason' argument",
                     Self::type_definition_display(parent),
                     parent.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -309,15 +392,17 @@ impl<'a> SchemaLinter {
         directive: Directive<'_>,
     ) {
         if directive.name() == "deprecated" && !directive.arguments().any(|argument| argument.name() == "reason") {
-            self.diagnostics.push((
+            self.push(
+                Rule::DeprecatedWithoutReason,
+                &format!("{}.{}", parent_type.name(), parent_field.name()),
+                directive.name_span(),
                 format!(
                     "usage of directive 'deprecated' on field '{}' on {} '{}' does not populate the 'reason' argument",
                     parent_field.name(),
                     Self::type_definition_display(parent_type),
                     parent_type.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -328,14 +413,16 @@ impl<'a> SchemaLinter {
         directive: Directive<'_>,
     ) {
         if directive.name() == "deprecated" && !directive.arguments().any(|argument| argument.name() == "reason") {
-            self.diagnostics.push((
+            self.push(
+                Rule::DeprecatedWithoutReason,
+                &format!("{}.{}", parent_input.name(), parent_input_value.name()),
+                directive.name_span(),
                 format!(
                     "usage of directive 'deprecated' on input value '{}' on input '{}' does not populate the 'reason' argument",
                     parent_input_value.name(),
                     parent_input.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -346,14 +433,16 @@ impl<'a> SchemaLinter {
         directive: Directive<'_>,
     ) {
         if directive.name() == "deprecated" && !directive.arguments().any(|argument| argument.name() == "reason") {
-            self.diagnostics.push((
+            self.push(
+                Rule::DeprecatedWithoutReason,
+                &format!("{}.{}", parent_enum.name(), parent_value.value()),
+                directive.name_span(),
                 format!(
                     "usage of directive 'deprecated' on enum value '{}' on enum '{}' does not populate the 'reason' argument",
                     parent_value.value(),
                     parent_enum.name()
                 ),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -362,16 +451,20 @@ impl<'a> SchemaLinter {
     pub fn visit_union(&mut self, union: UnionDefinition<'_>) {
         let union_name = union.name();
         if union_name.starts_with("Union") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenPrefix,
+                union_name,
+                union.span(),
                 format!("union '{union_name}' has a forbidden prefix: 'Union'"),
-                Severity::Warning,
-            ));
+            );
         }
         if union_name.ends_with("Union") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenSuffix,
+                union_name,
+                union.span(),
                 format!("union '{union_name}' has a forbidden suffix: 'Union'"),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -380,16 +473,20 @@ impl<'a> SchemaLinter {
     pub fn visit_interface(&mut self, object: InterfaceDefinition<'_>) {
         let interface_name = object.name();
         if interface_name.starts_with("Interface") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenPrefix,
+                interface_name,
+                object.span(),
                 format!("interface '{interface_name}' has a forbidden prefix: 'Interface'"),
-                Severity::Warning,
-            ));
+            );
         }
         if interface_name.ends_with("Interface") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenSuffix,
+                interface_name,
+                object.span(),
                 format!("interface '{interface_name}' has a forbidden suffix: 'Interface'"),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -397,44 +494,56 @@ impl<'a> SchemaLinter {
         let object_name = object.name();
 
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(object_name, Case::Pascal) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                object_name,
+                object.span(),
                 format!("type '{current}' should be renamed to '{fix}'"),
-                Severity::Warning,
-            ));
+            );
         }
         if object_name.starts_with("Type") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenPrefix,
+                object_name,
+                object.span(),
                 format!("type '{object_name}' has a forbidden prefix: 'Type'"),
-                Severity::Warning,
-            ));
+            );
         }
         if object_name.ends_with("Type") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenSuffix,
+                object_name,
+                object.span(),
                 format!("type '{object_name}' has a forbidden suffix: 'Type'"),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
     pub fn visit_enum(&mut self, r#enum: EnumDefinition<'_>) {
         let enum_name = r#enum.name();
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(enum_name, Case::Pascal) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                enum_name,
+                r#enum.span(),
                 format!("enum '{current}' should be renamed to '{fix}'"),
-                Severity::Warning,
-            ));
+            );
         }
         if enum_name.starts_with("Enum") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenPrefix,
+                enum_name,
+                r#enum.span(),
                 format!("enum '{enum_name}' has a forbidden prefix: 'Enum'"),
-                Severity::Warning,
-            ));
+            );
         }
         if enum_name.ends_with("Enum") {
-            self.diagnostics.push((
+            self.push(
+                Rule::ForbiddenSuffix,
+                enum_name,
+                r#enum.span(),
                 format!("enum '{enum_name}' has a forbidden suffix: 'Enum'"),
-                Severity::Warning,
-            ));
+            );
         }
     }
 
@@ -443,10 +552,12 @@ impl<'a> SchemaLinter {
 
         let name = enum_value.value();
         if let CaseMatch::Incorrect { current, fix } = Self::case_check(name, Case::ShoutySnake) {
-            self.diagnostics.push((
+            self.push(
+                Rule::NamingConvention,
+                &format!("{enum_name}.{name}"),
+                enum_value.span(),
                 format!("value '{current}' on enum '{enum_name}' should be renamed to '{fix}'"),
-                Severity::Warning,
-            ));
+            );
         }
     }
 }
@@ -593,6 +704,15 @@ fn linter() {
         .iter()
         .for_each(|message| assert!(messages.contains(&message.to_string()), "expected '{message}' to be included in diagnostics"));
 
+    let diagnostic = lint_diagnostics(schema)
+        .unwrap()
+        .into_iter()
+        .find(|diagnostic| diagnostic.coordinate == "hello.Test(NAME:)")
+        .unwrap();
+
+    assert_eq!(diagnostic.rule, Rule::NamingConvention);
+    assert_eq!(&schema[diagnostic.span], "NAME: String");
+
     let schema = r#"
         directive @withDeprecatedArgs(
           arg: String @deprecated(reason: "Use `newArg`")
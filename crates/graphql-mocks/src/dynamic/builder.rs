@@ -1,6 +1,6 @@
 #![allow(clippy::panic)]
 
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use async_graphql::{
     ServerError,
@@ -11,12 +11,19 @@ use serde::Deserialize;
 
 use crate::dynamic::entity_resolvers::EntityResolverContext;
 
-use super::{DynamicSchema, DynamicSubgraph, entity_resolvers::EntityResolver, resolvers::Resolver};
+use super::{
+    DynamicSchema, DynamicSubgraph, entity_resolvers::EntityResolver, generated::ValueGenerator, resolvers::Resolver,
+};
+
+/// Maximum length of generated lists unless configured otherwise.
+const DEFAULT_MAX_GENERATED_LIST_LENGTH: u64 = 3;
 
 pub struct DynamicSchemaBuilder {
     sdl: String,
     field_resolvers: ResolverMap,
     entity_resolvers: EntityResolverMap,
+    generated_values_seed: Option<u64>,
+    max_generated_list_length: u64,
 }
 
 type ResolverMap = HashMap<(String, String), Box<dyn Resolver>>;
@@ -28,9 +35,25 @@ impl DynamicSchemaBuilder {
             sdl: sdl.into(),
             field_resolvers: Default::default(),
             entity_resolvers: Default::default(),
+            generated_values_seed: None,
+            max_generated_list_length: DEFAULT_MAX_GENERATED_LIST_LENGTH,
         }
     }
 
+    /// Generates deterministic values, derived from `seed`, for every field and entity that has no
+    /// explicit resolver instead of only reading fields from the parent value.
+    pub fn with_generated_values(mut self, seed: u64) -> Self {
+        self.generated_values_seed = Some(seed);
+        self
+    }
+
+    /// Sets the maximum length of generated lists. Only relevant with [Self::with_generated_values],
+    /// which can be called before or after.
+    pub fn with_max_generated_list_length(mut self, max_list_length: u64) -> Self {
+        self.max_generated_list_length = max_list_length;
+        self
+    }
+
     pub fn with_resolver(mut self, ty: &str, field: &str, resolver: impl Resolver + 'static) -> Self {
         self.field_resolvers
             .insert((ty.into(), field.into()), Box::new(resolver));
@@ -50,15 +73,23 @@ impl DynamicSchemaBuilder {
     }
 
     pub fn finish(self) -> DynamicSchema {
+        self.try_finish().unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [Self::finish], but returns an error for documents that can't be turned into a schema.
+    pub fn try_finish(self) -> Result<DynamicSchema, String> {
         let Self {
             sdl,
             mut field_resolvers,
-            entity_resolvers,
+            mut entity_resolvers,
+            generated_values_seed,
+            max_generated_list_length,
         } = self;
 
-        let schema = cynic_parser::parse_type_system_document(&sdl)
-            .map_err(|e| e.to_report(&sdl))
-            .expect("a valid document");
+        let schema = cynic_parser::parse_type_system_document(&sdl).map_err(|error| error.to_string())?;
+
+        let generator =
+            generated_values_seed.map(|seed| Arc::new(ValueGenerator::new(&schema, seed, max_generated_list_length)));
 
         let (query_type, ..) = root_types(&schema);
 
@@ -72,6 +103,15 @@ impl DynamicSchemaBuilder {
             builder = builder.register(entity_type(&entities));
         }
 
+        if let Some(generator) = &generator {
+            for entity in &entities {
+                let generator = generator.clone();
+                entity_resolvers.entry(entity.to_string()).or_insert_with(|| {
+                    Box::new(move |context: EntityResolverContext<'_>| Some(generator.entity(&context)))
+                });
+            }
+        }
+
         let mut entity_resolvers = if !entity_resolvers.is_empty() {
             Some(entity_resolvers)
         } else {
@@ -80,7 +120,7 @@ impl DynamicSchemaBuilder {
         for definition in schema.definitions() {
             match definition {
                 parser::Definition::Type(def) => {
-                    let mut ty = convert_type(def, &mut field_resolvers);
+                    let mut ty = convert_type(def, &mut field_resolvers, generator.as_ref());
                     if def.name() == query_type
                         && let Some(entity_resolvers) = entity_resolvers.take()
                     {
@@ -103,9 +143,9 @@ impl DynamicSchemaBuilder {
             ));
         }
 
-        let schema = builder.finish().unwrap();
+        let schema = builder.finish().map_err(|error| error.to_string())?;
 
-        DynamicSchema { schema, sdl }
+        Ok(DynamicSchema { schema, sdl })
     }
 }
 
@@ -122,10 +162,14 @@ fn find_entities(schema: &parser::TypeSystemDocument) -> Vec<&str> {
         .collect()
 }
 
-fn convert_type(def: parser::TypeDefinition<'_>, resolvers: &mut ResolverMap) -> async_graphql::dynamic::Type {
+fn convert_type(
+    def: parser::TypeDefinition<'_>,
+    resolvers: &mut ResolverMap,
+    generator: Option<&Arc<ValueGenerator>>,
+) -> async_graphql::dynamic::Type {
     match def {
         parser::TypeDefinition::Scalar(def) => async_graphql::dynamic::Scalar::new(def.name()).into(),
-        parser::TypeDefinition::Object(def) => convert_object(def, resolvers),
+        parser::TypeDefinition::Object(def) => convert_object(def, resolvers, generator),
         parser::TypeDefinition::Interface(def) => convert_iface(def),
         parser::TypeDefinition::Union(def) => convert_union(def),
         parser::TypeDefinition::Enum(def) => convert_enum(def),
@@ -133,7 +177,11 @@ fn convert_type(def: parser::TypeDefinition<'_>, resolvers: &mut ResolverMap) ->
     }
 }
 
-fn convert_object(def: parser::ObjectDefinition<'_>, resolvers: &mut ResolverMap) -> async_graphql::dynamic::Type {
+fn convert_object(
+    def: parser::ObjectDefinition<'_>,
+    resolvers: &mut ResolverMap,
+    generator: Option<&Arc<ValueGenerator>>,
+) -> async_graphql::dynamic::Type {
     use async_graphql::dynamic::*;
 
     let mut object = Object::new(def.name());
@@ -147,7 +195,12 @@ fn convert_object(def: parser::ObjectDefinition<'_>, resolvers: &mut ResolverMap
         let resolver = std::sync::Mutex::new(
             resolvers
                 .remove(&(def.name().into(), field_def.name().into()))
-                .unwrap_or_else(|| Box::new(default_field_resolver(field_def.name()))),
+                .unwrap_or_else(|| match generator {
+                    Some(generator) => {
+                        Box::new(generator.field_resolver(field_def.name(), type_ref.clone())) as Box<dyn Resolver>
+                    }
+                    None => Box::new(default_field_resolver(field_def.name())),
+                }),
         );
 
         let mut field = Field::new(field_def.name(), type_ref, move |context| {
@@ -0,0 +1,331 @@
+//! Deterministic, seeded generation of plausible values for fields without an explicit resolver.
+//!
+//! Every generated object carries a hidden seed which child fields derive their own seed from, so
+//! the same query against the same schema and seed always yields the same response.
+
+use std::collections::HashMap;
+
+use async_graphql::dynamic::{ResolverContext, TypeRef};
+use cynic_parser::type_system as parser;
+use serde_json::{Value, json};
+
+use super::{entity_resolvers::EntityResolverContext, resolvers::Resolver};
+
+/// Key under which the seed of a generated object is stored. It is never part of the response as
+/// it isn't a field of the schema.
+const SEED_KEY: &str = "__mock_seed";
+
+const FIRST_NAMES: &[&str] = &[
+    "Ada",
+    "Alan",
+    "Barbara",
+    "Claude",
+    "Donald",
+    "Edsger",
+    "Frances",
+    "Grace",
+    "Hedy",
+    "John",
+    "Katherine",
+    "Linus",
+    "Margaret",
+    "Niklaus",
+    "Radia",
+    "Tim",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Lovelace",
+    "Turing",
+    "Liskov",
+    "Shannon",
+    "Knuth",
+    "Dijkstra",
+    "Allen",
+    "Hopper",
+    "Lamarr",
+    "Backus",
+    "Johnson",
+    "Torvalds",
+    "Hamilton",
+    "Wirth",
+    "Perlman",
+    "Berners-Lee",
+];
+
+const WORDS: &[&str] = &[
+    "amber", "bright", "cedar", "delta", "ember", "falcon", "garden", "harbor", "island", "jade", "kettle", "lantern",
+    "meadow", "nimbus", "orchard", "pebble", "quartz", "river", "summit", "timber", "umbra", "valley", "willow",
+    "zephyr",
+];
+
+const CITIES: &[&str] = &[
+    "Berlin", "Lisbon", "Montreal", "Nairobi", "Osaka", "Paris", "Santiago", "Sydney", "Toronto", "Warsaw",
+];
+
+const COUNTRIES: &[&str] = &[
+    "Australia",
+    "Brazil",
+    "Canada",
+    "France",
+    "Germany",
+    "Japan",
+    "Kenya",
+    "Poland",
+    "Portugal",
+    "Spain",
+];
+
+/// Generates values for the output types of a schema.
+pub(super) struct ValueGenerator {
+    seed: u64,
+    max_list_length: u64,
+    types: HashMap<String, TypeKind>,
+}
+
+enum TypeKind {
+    Scalar,
+    Enum(Vec<String>),
+    Object,
+    Abstract(Vec<String>),
+}
+
+impl ValueGenerator {
+    pub(super) fn new(document: &parser::TypeSystemDocument, seed: u64, max_list_length: u64) -> Self {
+        let mut types = HashMap::new();
+        let mut implementors: HashMap<String, Vec<String>> = HashMap::new();
+
+        for definition in document.definitions() {
+            let (parser::Definition::Type(def) | parser::Definition::TypeExtension(def)) = definition else {
+                continue;
+            };
+
+            let kind = match def {
+                parser::TypeDefinition::Scalar(_) | parser::TypeDefinition::InputObject(_) => TypeKind::Scalar,
+                parser::TypeDefinition::Enum(def) => {
+                    TypeKind::Enum(def.values().map(|value| value.value().to_string()).collect())
+                }
+                parser::TypeDefinition::Object(def) => {
+                    for interface in def.implements_interfaces() {
+                        implementors
+                            .entry(interface.to_string())
+                            .or_default()
+                            .push(def.name().to_string());
+                    }
+                    TypeKind::Object
+                }
+                parser::TypeDefinition::Interface(_) => TypeKind::Abstract(Vec::new()),
+                parser::TypeDefinition::Union(def) => {
+                    TypeKind::Abstract(def.members().map(|member| member.name().to_string()).collect())
+                }
+            };
+
+            types.entry(def.name().to_string()).or_insert(kind);
+        }
+
+        for (interface, objects) in implementors {
+            if let Some(TypeKind::Abstract(possible_types)) = types.get_mut(&interface) {
+                possible_types.extend(objects);
+            }
+        }
+
+        ValueGenerator {
+            seed,
+            max_list_length: max_list_length.max(1),
+            types,
+        }
+    }
+
+    /// A resolver returning the parent value's field when present and a generated value otherwise.
+    pub(super) fn field_resolver(
+        self: &std::sync::Arc<Self>,
+        field_name: &str,
+        ty: TypeRef,
+    ) -> impl Resolver + 'static {
+        let generator = self.clone();
+        let field_name = async_graphql::Name::new(field_name);
+
+        move |context: ResolverContext<'_>| {
+            let parent = context.parent_value.as_value();
+
+            if let Some(async_graphql::Value::Object(map)) = parent
+                && let Some(value) = map.get(&field_name)
+            {
+                return value.clone().into_json().ok();
+            }
+
+            let mut seed = match parent {
+                Some(value @ async_graphql::Value::Object(map)) => match map.get(SEED_KEY) {
+                    Some(async_graphql::Value::String(seed)) => seed.parse().unwrap_or(generator.seed),
+                    _ => hash(generator.seed, value.to_string().as_bytes()),
+                },
+                _ => generator.seed,
+            };
+
+            seed = hash(seed, field_name.as_bytes());
+            for (name, value) in context.args.iter() {
+                seed = hash(seed, name.as_bytes());
+                seed = hash(seed, value.as_value().to_string().as_bytes());
+            }
+
+            Some(generator.generate(&ty, &field_name, seed))
+        }
+    }
+
+    /// Resolves an entity by echoing its representation, so key fields match what the gateway
+    /// asked for, and generating every other field from a seed derived from the representation.
+    pub(super) fn entity(&self, context: &EntityResolverContext<'_>) -> Value {
+        let mut entity = context.representation.clone();
+        let seed = hash(self.seed, Value::Object(entity.clone()).to_string().as_bytes());
+        entity.insert(SEED_KEY.into(), Value::String(seed.to_string()));
+        Value::Object(entity)
+    }
+
+    fn generate(&self, ty: &TypeRef, field_name: &str, seed: u64) -> Value {
+        match ty {
+            TypeRef::NonNull(inner) => self.generate(inner, field_name, seed),
+            TypeRef::List(inner) => {
+                let length = 1 + Rng::new(seed).below(self.max_list_length);
+                (0..length)
+                    .map(|index| self.generate(inner, field_name, hash(seed, &index.to_le_bytes())))
+                    .collect()
+            }
+            TypeRef::Named(name) => self.generate_named(name, field_name, seed),
+        }
+    }
+
+    fn generate_named(&self, type_name: &str, field_name: &str, seed: u64) -> Value {
+        let mut rng = Rng::new(seed);
+
+        match self.types.get(type_name) {
+            Some(TypeKind::Enum(values)) if !values.is_empty() => Value::String(rng.pick(values).clone()),
+            Some(TypeKind::Object) => generated_object(type_name, seed),
+            Some(TypeKind::Abstract(possible_types)) if !possible_types.is_empty() => {
+                generated_object(rng.pick(possible_types), seed)
+            }
+            _ => scalar(type_name, field_name, &mut rng),
+        }
+    }
+}
+
+fn generated_object(type_name: &str, seed: u64) -> Value {
+    json!({ "__typename": type_name, SEED_KEY: seed.to_string() })
+}
+
+fn scalar(type_name: &str, field_name: &str, rng: &mut Rng) -> Value {
+    let field_name = field_name.to_ascii_lowercase();
+
+    match type_name {
+        "ID" => Value::String(format!("{:016x}", rng.next_u64())),
+        "Int" => Value::from(int(&field_name, rng)),
+        "Float" => Value::from((rng.below(100_000) as f64) / 100.0),
+        "Boolean" => Value::Bool(rng.below(2) == 1),
+        "String" => Value::String(string(&field_name, rng)),
+        "DateTime" | "Timestamp" => {
+            Value::String(format!("{}T{:02}:{:02}:00Z", date(rng), rng.below(24), rng.below(60)))
+        }
+        "Date" => Value::String(date(rng)),
+        "UUID" | "Uuid" => {
+            let (high, low) = (rng.next_u64(), rng.next_u64());
+            Value::String(format!(
+                "{:08x}-{:04x}-4{:03x}-8{:03x}-{:012x}",
+                high >> 32,
+                (high >> 16) & 0xffff,
+                high & 0xfff,
+                (low >> 48) & 0xfff,
+                low & 0xffff_ffff_ffff
+            ))
+        }
+        "URL" | "Url" => Value::String(format!("https://example.com/{}", rng.pick(WORDS))),
+        "JSON" | "Json" | "JSONObject" => json!({ "key": rng.pick(WORDS) }),
+        _ => Value::String(string(&field_name, rng)),
+    }
+}
+
+fn int(field_name: &str, rng: &mut Rng) -> i64 {
+    let (min, max) = if field_name.contains("age") {
+        (18, 90)
+    } else if field_name.contains("year") {
+        (1990, 2030)
+    } else if field_name.contains("count") || field_name.contains("total") || field_name.contains("quantity") {
+        (0, 100)
+    } else {
+        (0, 1000)
+    };
+
+    min + rng.below((max - min + 1) as u64) as i64
+}
+
+fn string(field_name: &str, rng: &mut Rng) -> String {
+    if field_name.contains("email") {
+        format!(
+            "{}.{}@example.com",
+            rng.pick(FIRST_NAMES).to_ascii_lowercase(),
+            rng.pick(LAST_NAMES).to_ascii_lowercase()
+        )
+    } else if field_name.contains("url") || field_name.contains("link") || field_name.contains("website") {
+        format!("https://example.com/{}", rng.pick(WORDS))
+    } else if field_name.contains("firstname") {
+        rng.pick(FIRST_NAMES).to_string()
+    } else if field_name.contains("lastname") || field_name.contains("surname") {
+        rng.pick(LAST_NAMES).to_string()
+    } else if field_name.contains("name") {
+        format!("{} {}", rng.pick(FIRST_NAMES), rng.pick(LAST_NAMES))
+    } else if field_name.contains("city") {
+        rng.pick(CITIES).to_string()
+    } else if field_name.contains("country") {
+        rng.pick(COUNTRIES).to_string()
+    } else if field_name.contains("phone") {
+        format!("+1 555 {:04}", rng.below(10_000))
+    } else if field_name.contains("description") || field_name.contains("body") || field_name.contains("text") {
+        let words = (0..8).map(|_| *rng.pick(WORDS)).collect::<Vec<_>>().join(" ");
+        format!("{}{}.", words[..1].to_ascii_uppercase(), &words[1..])
+    } else {
+        let word = rng.pick(WORDS);
+        format!("{}{} {}", word[..1].to_ascii_uppercase(), &word[1..], rng.pick(WORDS))
+    }
+}
+
+fn date(rng: &mut Rng) -> String {
+    format!(
+        "{}-{:02}-{:02}",
+        2015 + rng.below(10),
+        1 + rng.below(12),
+        1 + rng.below(28)
+    )
+}
+
+/// FNV-1a, chosen over the std hasher so that generated values are stable across builds.
+fn hash(seed: u64, bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325 ^ seed;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+/// splitmix64
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
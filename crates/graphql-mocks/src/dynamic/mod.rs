@@ -1,5 +1,6 @@
 mod builder;
 mod entity_resolvers;
+mod generated;
 mod resolvers;
 
 use std::sync::Arc;
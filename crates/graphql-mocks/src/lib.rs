@@ -11,7 +11,8 @@ use std::{
     time::Duration,
 };
 
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use async_graphql::{BatchRequest, BatchResponse};
+use async_graphql_axum::{GraphQLBatchRequest, GraphQLResponse};
 use axum::{
     Router,
     extract::{FromRequestParts, State},
@@ -102,6 +103,7 @@ impl MockGraphQlServer {
         let state = AppState {
             schema: schema.clone(),
             received_requests: Default::default(),
+            received_batch_sizes: Default::default(),
             next_responses: Default::default(),
             additional_headers: Default::default(),
             signature_key: Default::default(),
@@ -158,6 +160,12 @@ impl MockGraphQlServer {
         std::iter::from_fn(|| self.state.received_requests.pop())
     }
 
+    /// Sizes of the batch requests received, their individual requests are recorded like any
+    /// other request.
+    pub fn drain_received_batch_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        std::iter::from_fn(|| self.state.received_batch_sizes.pop())
+    }
+
     pub fn force_next_response(&self, response: impl IntoResponse) {
         self.state.next_responses.push(response.into_response());
     }
@@ -167,6 +175,15 @@ impl MockGraphQlServer {
         self
     }
 
+    pub fn with_additional_raw_header(self, name: http::HeaderName, value: &'static str) -> Self {
+        self.state
+            .additional_headers
+            .lock()
+            .unwrap()
+            .append(name, http::HeaderValue::from_static(value));
+        self
+    }
+
     pub fn with_message_signing_validation(self, key: VerifyingKey, id: Option<String>) -> Self {
         *self.state.signature_key.lock().unwrap() = Some((key, id));
 
@@ -178,16 +195,22 @@ async fn graphql_handler(
     State(state): State<AppState>,
     _sig: ValidMessageSignature,
     headers: HeaderMap,
-    req: GraphQLRequest,
+    req: GraphQLBatchRequest,
 ) -> axum::response::Response {
     let req = req.into_inner();
 
+    if let BatchRequest::Batch(requests) = &req {
+        state.received_batch_sizes.push(requests.len());
+    }
+
     // Record the request incase tests want to inspect it.
     // async_graphql::Request isn't clone so we do a deser roundtrip instead
-    state.received_requests.push(ReceivedRequest {
-        headers: headers.clone(),
-        body: serde_json::from_value(serde_json::to_value(&req).unwrap()).unwrap(),
-    });
+    for request in req.iter() {
+        state.received_requests.push(ReceivedRequest {
+            headers: headers.clone(),
+            body: serde_json::from_value(serde_json::to_value(request).unwrap()).unwrap(),
+        });
+    }
 
     if let Some(response) = state.next_responses.pop() {
         return response;
@@ -198,7 +221,16 @@ async fn graphql_handler(
         .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).to_string()))
         .collect();
 
-    let response: GraphQLResponse = state.schema.execute(headers, req).await.into();
+    let response: GraphQLResponse = match req {
+        BatchRequest::Single(request) => state.schema.execute(headers, request).await.into(),
+        BatchRequest::Batch(requests) => {
+            let mut responses = Vec::with_capacity(requests.len());
+            for request in requests {
+                responses.push(state.schema.execute(headers.clone(), request).await);
+            }
+            BatchResponse::Batch(responses).into()
+        }
+    };
     let mut http_response = response.into_response();
 
     http_response
@@ -212,6 +244,7 @@ async fn graphql_handler(
 struct AppState {
     schema: Arc<dyn Schema>,
     received_requests: Arc<crossbeam_queue::SegQueue<ReceivedRequest>>,
+    received_batch_sizes: Arc<crossbeam_queue::SegQueue<usize>>,
     next_responses: Arc<crossbeam_queue::SegQueue<axum::response::Response>>,
     additional_headers: Arc<Mutex<http::HeaderMap>>,
     #[expect(clippy::type_complexity)]
@@ -25,6 +25,14 @@ pub struct Change {
     /// - [AddEnumValue]/[RemoveEnumValue]: empty
     /// - [AddFieldArgument]: the value of the argument, potentially with the default
     /// - [AddFieldArgumentDefault]/[ChangeFieldArgumentDefault]: the default value of the argument
+    /// - [AddDirectiveUsage]/[ChangeDirectiveUsage]/[AddDeprecation]/[ChangeDeprecationReason]: the directive name and its arguments, without the leading `@` and the closing parenthesis
+    /// - [RemoveDirectiveUsage]/[RemoveDeprecation]/[RemoveDescription]: empty
+    /// - [AddDescription]/[ChangeDescription]: the new description
+    /// - [AddDirectiveDefinitionArgument]/[ChangeDirectiveDefinitionArgument]: the whole argument definition
+    /// - [RemoveDirectiveDefinitionArgument]: empty
+    /// - [AddDirectiveDefinitionLocation]/[RemoveDirectiveDefinitionLocation]: empty, the location is part of the path
+    /// - [AddInputFieldDefault]/[ChangeInputFieldDefault]: the default value of the input field
+    /// - [RemoveInputFieldDefault]: empty
     pub span: Span,
 }
 
@@ -70,6 +78,23 @@ pub enum ChangeKind {
     RemoveFieldArgumentDefault,
     ChangeFieldArgumentDefault,
     ChangeFieldArgumentType,
+    AddDirectiveUsage,
+    RemoveDirectiveUsage,
+    ChangeDirectiveUsage,
+    AddDeprecation,
+    RemoveDeprecation,
+    ChangeDeprecationReason,
+    AddDescription,
+    RemoveDescription,
+    ChangeDescription,
+    AddDirectiveDefinitionArgument,
+    RemoveDirectiveDefinitionArgument,
+    ChangeDirectiveDefinitionArgument,
+    AddDirectiveDefinitionLocation,
+    RemoveDirectiveDefinitionLocation,
+    AddInputFieldDefault,
+    RemoveInputFieldDefault,
+    ChangeInputFieldDefault,
 }
 
 impl ChangeKind {
@@ -114,6 +139,23 @@ impl ChangeKind {
             RemoveFieldArgumentDefault => "RemoveFieldArgumentDefault",
             ChangeFieldArgumentDefault => "ChangeFieldArgumentDefault",
             ChangeFieldArgumentType => "ChangeFieldArgumentType",
+            AddDirectiveUsage => "AddDirectiveUsage",
+            RemoveDirectiveUsage => "RemoveDirectiveUsage",
+            ChangeDirectiveUsage => "ChangeDirectiveUsage",
+            AddDeprecation => "AddDeprecation",
+            RemoveDeprecation => "RemoveDeprecation",
+            ChangeDeprecationReason => "ChangeDeprecationReason",
+            AddDescription => "AddDescription",
+            RemoveDescription => "RemoveDescription",
+            ChangeDescription => "ChangeDescription",
+            AddDirectiveDefinitionArgument => "AddDirectiveDefinitionArgument",
+            RemoveDirectiveDefinitionArgument => "RemoveDirectiveDefinitionArgument",
+            ChangeDirectiveDefinitionArgument => "ChangeDirectiveDefinitionArgument",
+            AddDirectiveDefinitionLocation => "AddDirectiveDefinitionLocation",
+            RemoveDirectiveDefinitionLocation => "RemoveDirectiveDefinitionLocation",
+            AddInputFieldDefault => "AddInputFieldDefault",
+            RemoveInputFieldDefault => "RemoveInputFieldDefault",
+            ChangeInputFieldDefault => "ChangeInputFieldDefault",
         }
     }
 }
@@ -159,6 +201,23 @@ impl std::str::FromStr for ChangeKind {
             "RemoveFieldArgumentDefault" => Self::RemoveFieldArgumentDefault,
             "ChangeFieldArgumentDefault" => Self::ChangeFieldArgumentDefault,
             "ChangeFieldArgumentType" => Self::ChangeFieldArgumentType,
+            "AddDirectiveUsage" => Self::AddDirectiveUsage,
+            "RemoveDirectiveUsage" => Self::RemoveDirectiveUsage,
+            "ChangeDirectiveUsage" => Self::ChangeDirectiveUsage,
+            "AddDeprecation" => Self::AddDeprecation,
+            "RemoveDeprecation" => Self::RemoveDeprecation,
+            "ChangeDeprecationReason" => Self::ChangeDeprecationReason,
+            "AddDescription" => Self::AddDescription,
+            "RemoveDescription" => Self::RemoveDescription,
+            "ChangeDescription" => Self::ChangeDescription,
+            "AddDirectiveDefinitionArgument" => Self::AddDirectiveDefinitionArgument,
+            "RemoveDirectiveDefinitionArgument" => Self::RemoveDirectiveDefinitionArgument,
+            "ChangeDirectiveDefinitionArgument" => Self::ChangeDirectiveDefinitionArgument,
+            "AddDirectiveDefinitionLocation" => Self::AddDirectiveDefinitionLocation,
+            "RemoveDirectiveDefinitionLocation" => Self::RemoveDirectiveDefinitionLocation,
+            "AddInputFieldDefault" => Self::AddInputFieldDefault,
+            "RemoveInputFieldDefault" => Self::RemoveInputFieldDefault,
+            "ChangeInputFieldDefault" => Self::ChangeInputFieldDefault,
             _ => return Err(()),
         })
     }
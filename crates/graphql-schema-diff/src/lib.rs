@@ -86,6 +86,23 @@ pub fn resolve_spans<'a: 'b, 'b>(
             ChangeKind::RemoveFieldArgumentDefault => source,
             ChangeKind::ChangeFieldArgumentDefault => target,
             ChangeKind::ChangeFieldArgumentType => target,
+            ChangeKind::AddDirectiveUsage => target,
+            ChangeKind::RemoveDirectiveUsage => source,
+            ChangeKind::ChangeDirectiveUsage => target,
+            ChangeKind::AddDeprecation => target,
+            ChangeKind::RemoveDeprecation => source,
+            ChangeKind::ChangeDeprecationReason => target,
+            ChangeKind::AddDescription => target,
+            ChangeKind::RemoveDescription => source,
+            ChangeKind::ChangeDescription => target,
+            ChangeKind::AddDirectiveDefinitionArgument => target,
+            ChangeKind::RemoveDirectiveDefinitionArgument => source,
+            ChangeKind::ChangeDirectiveDefinitionArgument => target,
+            ChangeKind::AddDirectiveDefinitionLocation => target,
+            ChangeKind::RemoveDirectiveDefinitionLocation => source,
+            ChangeKind::AddInputFieldDefault => target,
+            ChangeKind::RemoveInputFieldDefault => source,
+            ChangeKind::ChangeInputFieldDefault => target,
         };
 
         &relevant_schema[change.span]
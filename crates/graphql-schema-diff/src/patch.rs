@@ -1,3 +1,4 @@
+mod descriptions;
 mod directives;
 mod paths;
 mod schema_definitions;
@@ -44,6 +45,8 @@ where
         }
     }
 
+    let mut type_directive_indexes = type_definitions::TypeDirectiveIndexes::default();
+
     for definition in parsed.definitions() {
         match definition {
             cynic_parser::type_system::Definition::Schema(def) => {
@@ -58,10 +61,22 @@ where
                 schema_definitions::patch_schema_definition(def, DefinitionOrExtension::Extension, &mut schema, &paths);
             }
             cynic_parser::type_system::Definition::Type(ty) => {
-                type_definitions::patch_type_definition(ty, DefinitionOrExtension::Definition, &mut schema, &paths);
+                type_definitions::patch_type_definition(
+                    ty,
+                    DefinitionOrExtension::Definition,
+                    &mut type_directive_indexes,
+                    &mut schema,
+                    &paths,
+                );
             }
             cynic_parser::type_system::Definition::TypeExtension(ty) => {
-                type_definitions::patch_type_definition(ty, DefinitionOrExtension::Extension, &mut schema, &paths);
+                type_definitions::patch_type_definition(
+                    ty,
+                    DefinitionOrExtension::Extension,
+                    &mut type_directive_indexes,
+                    &mut schema,
+                    &paths,
+                );
             }
             cynic_parser::type_system::Definition::Directive(directive_definition) => {
                 directives::patch_directive_definition(directive_definition, &mut schema, &paths);
@@ -0,0 +1,27 @@
+use cynic_parser::type_system::Description;
+
+use crate::ChangeKind;
+
+use super::paths::Paths;
+
+/// The description of the element at `path` in the patched schema.
+pub(super) fn patch_description<'a, T: AsRef<str>>(
+    description: Option<Description<'_>>,
+    path: [&'a str; 3],
+    paths: &'a Paths<'a, T>,
+) -> Option<&'a str> {
+    let mut patched = description.map(|description| {
+        let span = description.span();
+        &paths.source()[span.start..span.end]
+    });
+
+    for change in paths.iter_exact(path) {
+        match change.kind() {
+            ChangeKind::AddDescription | ChangeKind::ChangeDescription => patched = Some(change.resolved_str()),
+            ChangeKind::RemoveDescription => patched = None,
+            _ => (),
+        }
+    }
+
+    patched
+}
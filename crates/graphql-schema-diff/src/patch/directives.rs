@@ -1,38 +1,186 @@
+use std::collections::HashMap;
+
 use cynic_parser::type_system::{Directive, DirectiveDefinition};
 
-use crate::ChangeKind;
+use crate::{ChangeKind, state::directive_location_str};
 
-use super::paths::Paths;
+use super::{descriptions::patch_description, paths::Paths};
 
 pub(super) fn patch_directive_definition<T: AsRef<str>>(
     directive_definition: DirectiveDefinition<'_>,
     schema: &mut String,
     paths: &Paths<'_, T>,
 ) {
-    if paths
-        .iter_exact([directive_definition.name(), "", ""])
-        .any(|change| matches!(change.kind(), ChangeKind::RemoveDirectiveDefinition))
-    {
+    let path = format!("@{}", directive_definition.name());
+    let mut is_changed = false;
+
+    for change in paths.iter_exact([path.as_str(), "", ""]) {
+        match change.kind() {
+            ChangeKind::RemoveDirectiveDefinition => return,
+            ChangeKind::AddDescription | ChangeKind::RemoveDescription | ChangeKind::ChangeDescription => {
+                is_changed = true
+            }
+            kind => {
+                debug_assert!(false, "Unhandled change at `{path}`: {kind:?}", path = change.path())
+            }
+        }
+    }
+
+    let mut added_arguments = Vec::new();
+    let mut removed_arguments = Vec::new();
+    let mut changed_arguments = Vec::new();
+    let mut added_locations = Vec::new();
+    let mut removed_locations = Vec::new();
+
+    for change in paths.iter_second_level(path.as_str()) {
+        is_changed = true;
+
+        match change.kind() {
+            ChangeKind::AddDirectiveDefinitionArgument => added_arguments.push(change.resolved_str()),
+            ChangeKind::RemoveDirectiveDefinitionArgument => removed_arguments.push(
+                change
+                    .second_level()
+                    .expect("RemoveDirectiveDefinitionArgument without argument name"),
+            ),
+            ChangeKind::ChangeDirectiveDefinitionArgument => changed_arguments.push((
+                change
+                    .second_level()
+                    .expect("ChangeDirectiveDefinitionArgument without argument name"),
+                change.resolved_str(),
+            )),
+            ChangeKind::AddDirectiveDefinitionLocation => {
+                added_locations.push(change.second_and_third_level()[1]);
+            }
+            ChangeKind::RemoveDirectiveDefinitionLocation => {
+                removed_locations.push(change.second_and_third_level()[1]);
+            }
+            kind => {
+                debug_assert!(false, "Unhandled change at `{path}`: {kind:?}", path = change.path())
+            }
+        }
+    }
+
+    if !is_changed {
+        let span = directive_definition.span();
+
+        schema.push_str(&paths.source()[span.start..span.end]);
+        schema.push_str("\n\n");
+
         return;
     }
 
-    let span = directive_definition.span();
+    if let Some(description) = patch_description(directive_definition.description(), [path.as_str(), "", ""], paths) {
+        schema.push_str(description);
+        schema.push('\n');
+    }
+
+    schema.push_str("directive ");
+    schema.push_str(&path);
+
+    let arguments = directive_definition
+        .arguments()
+        .filter(|argument| !removed_arguments.contains(&argument.name()))
+        .map(|argument| {
+            changed_arguments
+                .iter()
+                .find(|(name, _)| *name == argument.name())
+                .map(|(_, changed)| *changed)
+                .unwrap_or_else(|| {
+                    let span = argument.span();
+                    &paths.source()[span.start..span.end]
+                })
+        })
+        .chain(added_arguments)
+        .collect::<Vec<_>>();
+
+    if !arguments.is_empty() {
+        schema.push('(');
+        schema.push_str(&arguments.join(", "));
+        schema.push(')');
+    }
+
+    if directive_definition.is_repeatable() {
+        schema.push_str(" repeatable");
+    }
+
+    let locations = directive_definition
+        .locations()
+        .map(directive_location_str)
+        .filter(|location| !removed_locations.contains(location))
+        .chain(added_locations)
+        .collect::<Vec<_>>();
 
-    schema.push_str(&paths.source()[span.start..span.end]);
+    schema.push_str(" on ");
+    schema.push_str(&locations.join(" | "));
     schema.push_str("\n\n");
 }
 
+/// Renders the directives of the element at `path`, removing and changing them according to the diff.
+///
+/// The directives are counted by name in `indexes`, so the counts carry over from a type definition to its extensions.
 pub(in crate::patch) fn patch_directives<'a, T>(
     directives: impl Iterator<Item = Directive<'a>>,
+    path: [&str; 3],
+    indexes: &mut HashMap<&'a str, usize>,
     schema: &mut String,
     paths: &Paths<'_, T>,
 ) where
     T: AsRef<str>,
 {
-    // TODO: patching. Depends on thorough diffing implementation, which is missing.
+    let usages = paths.iter_directive_usages(path).collect::<Vec<_>>();
+    let deprecation = paths.iter_exact(path).find(|change| {
+        matches!(
+            change.kind(),
+            ChangeKind::RemoveDeprecation | ChangeKind::ChangeDeprecationReason
+        )
+    });
 
     for directive in directives {
-        render_directive(directive, schema, paths);
+        let change = if directive.name() == "deprecated" {
+            deprecation
+        } else {
+            let index = indexes.entry(directive.name()).or_default();
+            let idx = *index;
+            *index += 1;
+
+            usages
+                .iter()
+                .find(|(name, index, _)| *name == directive.name() && *index == idx)
+                .map(|(_, _, change)| *change)
+        };
+
+        match change.map(|change| (change.kind(), change.resolved_str())) {
+            Some((ChangeKind::RemoveDirectiveUsage | ChangeKind::RemoveDeprecation, _)) => (),
+            Some((ChangeKind::ChangeDirectiveUsage | ChangeKind::ChangeDeprecationReason, resolved)) => {
+                render_resolved_directive(resolved, schema)
+            }
+            _ => render_directive(directive, schema, paths),
+        }
+    }
+}
+
+/// Renders the directives added to the element at `path`, after its existing directives.
+pub(in crate::patch) fn add_directives<T: AsRef<str>>(path: [&str; 3], schema: &mut String, paths: &Paths<'_, T>) {
+    for (_, _, change) in paths.iter_directive_usages(path) {
+        if let ChangeKind::AddDirectiveUsage = change.kind() {
+            render_resolved_directive(change.resolved_str(), schema);
+        }
+    }
+
+    for change in paths.iter_exact(path) {
+        if let ChangeKind::AddDeprecation = change.kind() {
+            render_resolved_directive(change.resolved_str(), schema);
+        }
+    }
+}
+
+/// The span of a directive in the diff ends with its last argument, without the closing parenthesis.
+fn render_resolved_directive(resolved: &str, schema: &mut String) {
+    schema.push_str(" @");
+    schema.push_str(resolved);
+
+    if resolved.contains('(') {
+        schema.push(')');
     }
 }
 
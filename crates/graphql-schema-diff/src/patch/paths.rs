@@ -56,6 +56,27 @@ where
             .map(move |(_, idx)| ChangeView { paths: self, idx: *idx })
     }
 
+    /// The changes to the directives used on the element at `path`, with the name and index of each directive. E.g.
+    /// `["Foo", "bar", ""]` for the changes at `Foo.bar.@key[0]`.
+    pub(super) fn iter_directive_usages<'b: 'a>(
+        &'b self,
+        path: [&'b str; 3],
+    ) -> impl Iterator<Item = (&'a str, usize, ChangeView<'a, T>)> + 'b {
+        let depth = path.iter().position(|segment| segment.is_empty());
+
+        self.paths.iter().filter_map(move |(change, idx)| {
+            let depth = depth?;
+
+            if change[..depth] != path[..depth] || change[depth + 1..].iter().any(|segment| !segment.is_empty()) {
+                return None;
+            }
+
+            let (name, index) = split_directive_segment(change[depth])?;
+
+            Some((name, index, ChangeView { paths: self, idx: *idx }))
+        })
+    }
+
     pub(crate) fn source(&self) -> &'a str {
         self.source
     }
@@ -129,6 +150,14 @@ where
     }
 }
 
+/// Splits `@key[1]` into `("key", 1)`.
+fn split_directive_segment(segment: &str) -> Option<(&str, usize)> {
+    let (name, index) = segment.strip_prefix('@')?.split_once('[')?;
+    let index = index.strip_suffix(']')?.parse().ok()?;
+
+    Some((name, index))
+}
+
 fn split_path(path: &str) -> [&str; 3] {
     let mut segments = path.split('.');
     let path = std::array::from_fn(|_| segments.next().unwrap_or(""));
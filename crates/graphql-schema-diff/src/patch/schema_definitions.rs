@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use cynic_parser::type_system::SchemaDefinition;
 
 use crate::ChangeKind;
@@ -42,7 +44,14 @@ pub(super) fn patch_schema_definition<T: AsRef<str>>(
 
     schema.push_str("schema");
 
-    patch_directives(definition.directives(), schema, paths);
+    // Directives on schema definitions are not diffed yet.
+    patch_directives(
+        definition.directives(),
+        [":schema", "", ""],
+        &mut HashMap::new(),
+        schema,
+        paths,
+    );
 
     let any_root_type_defined = new_query_type.is_some()
         || new_mutation_type.is_some()
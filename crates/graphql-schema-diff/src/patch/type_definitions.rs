@@ -1,14 +1,25 @@
+use std::collections::HashMap;
+
 use cynic_parser::type_system::{
     EnumValueDefinition, FieldDefinition, InputValueDefinition, TypeDefinition, UnionMember,
 };
 
 use crate::ChangeKind;
 
-use super::{DefinitionOrExtension, INDENTATION, directives::patch_directives, paths::Paths};
+use super::{
+    DefinitionOrExtension, INDENTATION,
+    descriptions::patch_description,
+    directives::{add_directives, patch_directives},
+    paths::Paths,
+};
+
+/// The directives on each type, counted by name across the type definition and its extensions.
+pub(super) type TypeDirectiveIndexes<'a> = HashMap<&'a str, HashMap<&'a str, usize>>;
 
-pub(super) fn patch_type_definition<T: AsRef<str>>(
-    ty: TypeDefinition<'_>,
+pub(super) fn patch_type_definition<'a, T: AsRef<str>>(
+    ty: TypeDefinition<'a>,
     definition_or_extension: super::DefinitionOrExtension,
+    type_directive_indexes: &mut TypeDirectiveIndexes<'a>,
     schema: &mut String,
     paths: &Paths<'_, T>,
 ) {
@@ -20,15 +31,33 @@ pub(super) fn patch_type_definition<T: AsRef<str>>(
             | ChangeKind::RemoveScalar
             | ChangeKind::RemoveInterface
             | ChangeKind::RemoveInputObject => return,
+            ChangeKind::AddDescription
+            | ChangeKind::RemoveDescription
+            | ChangeKind::ChangeDescription
+            | ChangeKind::AddDeprecation
+            | ChangeKind::RemoveDeprecation
+            | ChangeKind::ChangeDeprecationReason => (), // handled below
             kind => {
                 debug_assert!(false, "Unhandled change at `{path}`: {kind:?}", path = change.path())
             }
         }
     }
 
-    if let Some(description) = ty.description() {
-        let span = description.span();
-        schema.push_str(&paths.source()[span.start..span.end]);
+    // Changes to the description and added directives apply to the first definition or extension of the type.
+    let is_first = !type_directive_indexes.contains_key(ty.name());
+    let directive_indexes = type_directive_indexes.entry(ty.name()).or_default();
+
+    let description = if is_first {
+        patch_description(ty.description(), [ty.name(), "", ""], paths)
+    } else {
+        ty.description().map(|description| {
+            let span = description.span();
+            &paths.source()[span.start..span.end]
+        })
+    };
+
+    if let Some(description) = description {
+        schema.push_str(description);
         schema.push('\n');
     }
 
@@ -65,7 +94,11 @@ pub(super) fn patch_type_definition<T: AsRef<str>>(
         schema.push_str(&implements.join(" & "));
     }
 
-    patch_directives(ty.directives(), schema, paths);
+    patch_directives(ty.directives(), [ty.name(), "", ""], directive_indexes, schema, paths);
+
+    if is_first {
+        add_directives([ty.name(), "", ""], schema, paths);
+    }
 
     match ty {
         TypeDefinition::Scalar(_) => (),
@@ -107,6 +140,18 @@ fn patch_input_object<'a, T: AsRef<str>>(
                 schema.push_str(change.resolved_str().trim());
                 schema.push('\n');
             }
+            ChangeKind::AddDescription
+            | ChangeKind::RemoveDescription
+            | ChangeKind::ChangeDescription
+            | ChangeKind::AddDeprecation
+            | ChangeKind::RemoveDeprecation
+            | ChangeKind::ChangeDeprecationReason
+            | ChangeKind::AddDirectiveUsage
+            | ChangeKind::RemoveDirectiveUsage
+            | ChangeKind::ChangeDirectiveUsage
+            | ChangeKind::AddInputFieldDefault
+            | ChangeKind::RemoveInputFieldDefault
+            | ChangeKind::ChangeInputFieldDefault => (), // handled when rendering the field
             kind => {
                 debug_assert!(false, "Unhandled change at `{path}`: {kind:?}", path = change.path())
             }
@@ -120,6 +165,12 @@ fn patch_input_object<'a, T: AsRef<str>>(
             continue;
         }
 
+        if let Some(description) = patch_description(field.description(), [parent, field.name(), ""], paths) {
+            schema.push_str(INDENTATION);
+            schema.push_str(description);
+            schema.push('\n');
+        }
+
         schema.push_str(INDENTATION);
         schema.push_str(field.name());
 
@@ -131,7 +182,29 @@ fn patch_input_object<'a, T: AsRef<str>>(
             schema.push_str(&field.ty().to_string());
         }
 
-        patch_directives(field.directives(), schema, paths);
+        let mut default_value = field.default_value().map(|_| {
+            let span = field.default_value_span();
+            &paths.source()[span.start..span.end]
+        });
+
+        for change in paths.iter_exact([parent, field.name(), ""]) {
+            match change.kind() {
+                ChangeKind::AddInputFieldDefault | ChangeKind::ChangeInputFieldDefault => {
+                    default_value = Some(change.resolved_str())
+                }
+                ChangeKind::RemoveInputFieldDefault => default_value = None,
+                _ => (),
+            }
+        }
+
+        if let Some(default_value) = default_value {
+            schema.push(' ');
+            schema.push_str(default_value);
+        }
+
+        let path = [parent, field.name(), ""];
+        patch_directives(field.directives(), path, &mut HashMap::new(), schema, paths);
+        add_directives(path, schema, paths);
 
         schema.push('\n');
     }
@@ -179,6 +252,15 @@ fn patch_fields<'a, T>(
             ChangeKind::RemoveFieldArgument => {
                 removed_arguments.push(change.second_and_third_level());
             }
+            ChangeKind::AddDescription
+            | ChangeKind::RemoveDescription
+            | ChangeKind::ChangeDescription
+            | ChangeKind::AddDeprecation
+            | ChangeKind::RemoveDeprecation
+            | ChangeKind::ChangeDeprecationReason
+            | ChangeKind::AddDirectiveUsage
+            | ChangeKind::RemoveDirectiveUsage
+            | ChangeKind::ChangeDirectiveUsage => (), // handled when rendering the field
             kind => {
                 debug_assert!(false, "Unhandled change at `{path}`: {kind:?}", path = change.path())
             }
@@ -194,10 +276,9 @@ fn patch_fields<'a, T>(
             continue;
         }
 
-        if let Some(description) = field.description() {
-            let span = description.span();
+        if let Some(description) = patch_description(field.description(), [parent, field.name(), ""], paths) {
             schema.push_str(INDENTATION);
-            schema.push_str(&paths.source()[span.start..span.end]);
+            schema.push_str(description);
             schema.push('\n');
         }
 
@@ -224,9 +305,10 @@ fn patch_fields<'a, T>(
             }
 
             while let Some(argument) = arguments.next() {
-                if let Some(description) = argument.description() {
-                    let span = description.span();
-                    schema.push_str(&paths.source()[span.start..span.end]);
+                let argument_path = [parent, field.name(), argument.name()];
+
+                if let Some(description) = patch_description(argument.description(), argument_path, paths) {
+                    schema.push_str(description);
                     schema.push(' ');
                 }
 
@@ -240,7 +322,8 @@ fn patch_fields<'a, T>(
                     schema.push_str(&paths.source()[span.start..span.end]);
                 }
 
-                patch_directives(argument.directives(), schema, paths);
+                patch_directives(argument.directives(), argument_path, &mut HashMap::new(), schema, paths);
+                add_directives(argument_path, schema, paths);
 
                 if arguments.peek().is_some() {
                     schema.push_str(", ");
@@ -258,7 +341,9 @@ fn patch_fields<'a, T>(
             schema.push_str(&field.ty().to_string());
         }
 
-        patch_directives(field.directives(), schema, paths);
+        let path = [parent, field.name(), ""];
+        patch_directives(field.directives(), path, &mut HashMap::new(), schema, paths);
+        add_directives(path, schema, paths);
 
         schema.push('\n');
     }
@@ -289,6 +374,15 @@ fn patch_enum_values<'a, T>(
                 let value = change.second_level().expect("RemoveEnumValue without value");
                 removed_enum_values.push(value);
             }
+            ChangeKind::AddDescription
+            | ChangeKind::RemoveDescription
+            | ChangeKind::ChangeDescription
+            | ChangeKind::AddDeprecation
+            | ChangeKind::RemoveDeprecation
+            | ChangeKind::ChangeDeprecationReason
+            | ChangeKind::AddDirectiveUsage
+            | ChangeKind::RemoveDirectiveUsage
+            | ChangeKind::ChangeDirectiveUsage => (), // handled when rendering the value
             kind => {
                 debug_assert!(false, "Unhandled change at `{path}`: {kind:?}", path = change.path())
             }
@@ -302,10 +396,18 @@ fn patch_enum_values<'a, T>(
             continue;
         }
 
+        if let Some(description) = patch_description(value.description(), [enum_name, value.value(), ""], paths) {
+            schema.push_str(INDENTATION);
+            schema.push_str(description);
+            schema.push('\n');
+        }
+
         schema.push_str(INDENTATION);
         schema.push_str(value.value());
 
-        patch_directives(value.directives(), schema, paths);
+        let path = [enum_name, value.value(), ""];
+        patch_directives(value.directives(), path, &mut HashMap::new(), schema, paths);
+        add_directives(path, schema, paths);
 
         schema.push('\n');
     }
@@ -26,30 +26,38 @@ pub struct ParseError;
 /// Second level:
 ///
 /// - Fields, union members, enum values and input object fields are unprefixed.
-/// - Directives on types and schema definitions are prefixed with an `@` and followed by an index: `@key[0]`.
+/// - Directives on types and schema definitions are prefixed with an `@` and followed by an index: `@key[0]`. The index counts the uses of the directive with the same name on the same type.
 /// - Interface implementations are prefixed with an `&`: `&SomeInterface`.
+/// - Directive definition arguments are unprefixed: `@authorized.rule`.
 ///
 /// Third level:
 ///
 /// - Field arguments are unprefixed.
 /// - Directives on fields and enum values are prefixed with an `@` and followed by an index: `@include[0]`.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+/// - Directive definition locations follow an `on` segment: `@authorized.on.FIELD_DEFINITION`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
 pub enum Path<'a> {
     SchemaDefinition,
     SchemaExtension(usize),
     TypeDefinition(&'a str, Option<PathInType<'a>>),
     TypeExtension(&'a str, usize, Option<PathInType<'a>>),
-    DirectiveDefinition(&'a str),
+    DirectiveDefinition(&'a str, Option<PathInDirectiveDefinition<'a>>),
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub enum PathInDirectiveDefinition<'a> {
+    Argument(&'a str),
+    Location(&'a str),
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
 pub enum PathInType<'a> {
     InField(&'a str, Option<PathInField<'a>>),
     InDirective(&'a str, usize),
     InterfaceImplementation(&'a str),
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
 pub enum PathInField<'a> {
     InArgument(&'a str),
     InDirective(&'a str, usize),
@@ -115,6 +123,10 @@ mod tests {
             "@test[0]",
             "myObject.&MyInterface.a",
             "myObject.&MyInterface.",
+            // Directive definition locations without the `on` segment.
+            "@test.arg.FIELD_DEFINITION",
+            "@test.on.",
+            "@test.on.FIELD_DEFINITION.more",
         ] {
             expect_error(case);
         }
@@ -139,6 +151,9 @@ mod tests {
             "@deprecated",
             "@something__else",
             "@join__type",
+            "@authorized.rule",
+            "@authorized.on",
+            "@authorized.on.FIELD_DEFINITION",
             "my_union",
             "__my_input_object[32]",
             "_my_object.id",
@@ -29,9 +29,28 @@ impl fmt::Display for Path<'_> {
                     Ok(())
                 }
             }
-            Path::DirectiveDefinition(directive_name) => {
+            Path::DirectiveDefinition(directive_name, path_in_directive_definition) => {
                 f.write_str("@")?;
-                f.write_str(directive_name)
+                f.write_str(directive_name)?;
+
+                if let Some(path_in_directive_definition) = path_in_directive_definition {
+                    f.write_str(".")?;
+                    path_in_directive_definition.fmt(f)
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for PathInDirectiveDefinition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathInDirectiveDefinition::Argument(argument_name) => f.write_str(argument_name),
+            PathInDirectiveDefinition::Location(location) => {
+                f.write_str("on.")?;
+                f.write_str(location)
             }
         }
     }
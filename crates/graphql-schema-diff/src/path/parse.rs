@@ -26,11 +26,10 @@ impl<'a> Path<'a> {
                 return Err(ParseError);
             }
 
-            if segments.next().is_some() {
-                return Err(ParseError);
-            }
-
-            return Ok(Path::DirectiveDefinition(name));
+            return Ok(Path::DirectiveDefinition(
+                name,
+                Self::parse_path_in_directive_definition(segments)?,
+            ));
         }
 
         // The only remaining possibility is a type definition
@@ -43,6 +42,34 @@ impl<'a> Path<'a> {
         }
     }
 
+    fn parse_path_in_directive_definition(
+        mut segments: impl Iterator<Item = &'a str>,
+    ) -> ParseResult<Option<PathInDirectiveDefinition<'a>>> {
+        let segment = match segments.next() {
+            Some("") => return Err(ParseError),
+            Some(segment) => segment,
+            None => return Ok(None),
+        };
+
+        if !is_valid_graphql_name(segment) {
+            return Err(ParseError);
+        }
+
+        let path = match segments.next() {
+            None => PathInDirectiveDefinition::Argument(segment),
+            Some(location) if segment == "on" && is_valid_graphql_name(location) => {
+                PathInDirectiveDefinition::Location(location)
+            }
+            Some(_) => return Err(ParseError),
+        };
+
+        if segments.next().is_some() {
+            return Err(ParseError);
+        }
+
+        Ok(Some(path))
+    }
+
     fn parse_path_in_type(mut segments: impl Iterator<Item = &'a str>) -> ParseResult<Option<PathInType<'a>>> {
         let segment = match segments.next() {
             Some("") => return Err(ParseError),
@@ -1,5 +1,5 @@
 use change::Span;
-use path::PathInType;
+use path::{PathInDirectiveDefinition, PathInField, PathInType};
 
 use crate::*;
 
@@ -13,12 +13,23 @@ pub(crate) struct DiffState<'a> {
     pub(crate) fields_map: DiffMap<[&'a str; 2], (Option<ast::Type<'a>>, Span)>,
     pub(crate) interface_impls: DiffMap<&'a str, Vec<&'a str>>,
     pub(crate) arguments_map: DiffMap<[&'a str; 3], ast::InputValueDefinition<'a>>,
+    pub(crate) input_fields_map: DiffMap<[&'a str; 2], ast::InputValueDefinition<'a>>,
+    pub(crate) directive_definitions_map: DiffMap<&'a str, ast::DirectiveDefinition<'a>>,
+    pub(crate) directive_arguments_map: DiffMap<[&'a str; 2], ast::InputValueDefinition<'a>>,
+    pub(crate) annotations_map: DiffMap<path::Path<'a>, Annotations<'a>>,
+}
+
+/// The description and directives of a type, field, enum value, argument or directive definition.
+/// For types, the directives of all the extensions are included, in order.
+#[derive(Default)]
+pub(crate) struct Annotations<'a> {
+    pub(crate) description: Option<ast::Description<'a>>,
+    pub(crate) directives: Vec<ast::Directive<'a>>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 #[repr(u8)]
 pub(crate) enum DefinitionKind {
-    Directive,
     Enum,
     InputObject,
     Interface,
@@ -39,7 +50,7 @@ impl DefinitionKind {
                 ast::TypeDefinition::Enum(_) => Some(DefinitionKind::Enum),
                 ast::TypeDefinition::InputObject(_) => Some(DefinitionKind::InputObject),
             },
-            ast::Definition::Directive(_) => Some(DefinitionKind::Directive),
+            ast::Definition::Directive(_) => None,
         }
     }
 }
@@ -53,6 +64,10 @@ impl DiffState<'_> {
             fields_map,
             arguments_map,
             interface_impls,
+            input_fields_map,
+            directive_definitions_map,
+            directive_arguments_map,
+            annotations_map,
         } = self;
 
         let mut changes = Vec::new();
@@ -71,6 +86,12 @@ impl DiffState<'_> {
         push_definition_changes(&types_map, &mut push_change);
         push_field_changes(&fields_map, &types_map, &mut push_change);
         push_argument_changes(&fields_map, &arguments_map, &mut push_change);
+        push_input_field_default_changes(&input_fields_map, &mut push_change);
+
+        push_directive_definition_changes(&directive_definitions_map, &mut push_change);
+        push_directive_argument_changes(&directive_definitions_map, &directive_arguments_map, &mut push_change);
+
+        push_annotation_changes(&annotations_map, &types_map, &mut push_change);
 
         changes.sort();
 
@@ -190,6 +211,290 @@ fn push_argument_changes(
     }
 }
 
+fn push_input_field_default_changes(
+    input_fields_map: &DiffMap<[&str; 2], ast::InputValueDefinition<'_>>,
+    push_change: PushChangeFn<'_>,
+) {
+    for ([type_name, field_name], entries) in input_fields_map {
+        // Added and removed input fields are handled in push_field_changes.
+        let (Some(src_field), Some(target_field)) = entries else {
+            continue;
+        };
+
+        let field_path = path::Path::TypeDefinition(type_name, Some(PathInType::InField(field_name, None)));
+
+        match (src_field.default_value(), target_field.default_value()) {
+            (None, Some(_)) => push_change(
+                field_path,
+                ChangeKind::AddInputFieldDefault,
+                target_field.default_value_span().into(),
+            ),
+            (Some(_), None) => push_change(field_path, ChangeKind::RemoveInputFieldDefault, Span::empty()),
+            (Some(a), Some(b)) if a != b => push_change(
+                field_path,
+                ChangeKind::ChangeInputFieldDefault,
+                target_field.default_value_span().into(),
+            ),
+            _ => (),
+        }
+    }
+}
+
+fn push_directive_definition_changes(
+    directive_definitions_map: &DiffMap<&str, ast::DirectiveDefinition<'_>>,
+    push_change: PushChangeFn<'_>,
+) {
+    for (name, entries) in directive_definitions_map {
+        let (src, target) = match entries {
+            (None, None) => unreachable!(),
+            (None, Some(definition)) => {
+                push_change(
+                    path::Path::DirectiveDefinition(name, None),
+                    ChangeKind::AddDirectiveDefinition,
+                    definition.span().into(),
+                );
+                continue;
+            }
+            (Some(definition), None) => {
+                push_change(
+                    path::Path::DirectiveDefinition(name, None),
+                    ChangeKind::RemoveDirectiveDefinition,
+                    definition.span().into(),
+                );
+                continue;
+            }
+            (Some(src), Some(target)) => (src, target),
+        };
+
+        let src_locations = src.locations().map(directive_location_str).collect::<Vec<_>>();
+        let target_locations = target.locations().map(directive_location_str).collect::<Vec<_>>();
+
+        for &location in &target_locations {
+            if !src_locations.contains(&location) {
+                push_change(
+                    path::Path::DirectiveDefinition(name, Some(PathInDirectiveDefinition::Location(location))),
+                    ChangeKind::AddDirectiveDefinitionLocation,
+                    Span::empty(),
+                );
+            }
+        }
+
+        for &location in &src_locations {
+            if !target_locations.contains(&location) {
+                push_change(
+                    path::Path::DirectiveDefinition(name, Some(PathInDirectiveDefinition::Location(location))),
+                    ChangeKind::RemoveDirectiveDefinitionLocation,
+                    Span::empty(),
+                );
+            }
+        }
+    }
+}
+
+fn push_directive_argument_changes(
+    directive_definitions_map: &DiffMap<&str, ast::DirectiveDefinition<'_>>,
+    directive_arguments_map: &DiffMap<[&str; 2], ast::InputValueDefinition<'_>>,
+    push_change: PushChangeFn<'_>,
+) {
+    for ([directive_name, argument_name], (src, target)) in directive_arguments_map {
+        // Arguments of added and removed directive definitions are part of the definition change.
+        if !matches!(directive_definitions_map[directive_name], (Some(_), Some(_))) {
+            continue;
+        }
+
+        let argument_path =
+            path::Path::DirectiveDefinition(directive_name, Some(PathInDirectiveDefinition::Argument(argument_name)));
+
+        match (src, target) {
+            (None, None) => unreachable!(),
+            (None, Some(target)) => push_change(
+                argument_path,
+                ChangeKind::AddDirectiveDefinitionArgument,
+                target.span().into(),
+            ),
+            (Some(_), None) => push_change(
+                argument_path,
+                ChangeKind::RemoveDirectiveDefinitionArgument,
+                Span::empty(),
+            ),
+            (Some(src), Some(target)) if src.ty() != target.ty() || src.default_value() != target.default_value() => {
+                push_change(
+                    argument_path,
+                    ChangeKind::ChangeDirectiveDefinitionArgument,
+                    target.span().into(),
+                )
+            }
+            (Some(_), Some(_)) => (),
+        }
+    }
+}
+
+fn push_annotation_changes(
+    annotations_map: &DiffMap<path::Path<'_>, Annotations<'_>>,
+    types_map: &DiffMap<&str, ast::Definition<'_>>,
+    push_change: PushChangeFn<'_>,
+) {
+    for (element_path, entries) in annotations_map {
+        // Annotations of added and removed elements are part of the addition or removal.
+        let (Some(src), Some(target)) = entries else {
+            continue;
+        };
+
+        if let path::Path::TypeDefinition(type_name, _) = element_path {
+            match types_map.get(type_name) {
+                Some((Some(a), Some(b))) if DefinitionKind::new(a) == DefinitionKind::new(b) => (),
+                _ => continue,
+            }
+        }
+
+        match (&src.description, &target.description) {
+            (None, Some(description)) => push_change(
+                element_path.clone(),
+                ChangeKind::AddDescription,
+                description.span().into(),
+            ),
+            (Some(_), None) => push_change(element_path.clone(), ChangeKind::RemoveDescription, Span::empty()),
+            (Some(a), Some(b)) if a.to_cow() != b.to_cow() => {
+                push_change(element_path.clone(), ChangeKind::ChangeDescription, b.span().into())
+            }
+            _ => (),
+        }
+
+        match (deprecated_directive(src), deprecated_directive(target)) {
+            (None, Some(directive)) => push_change(
+                element_path.clone(),
+                ChangeKind::AddDeprecation,
+                directive_span(&directive),
+            ),
+            (Some(_), None) => push_change(element_path.clone(), ChangeKind::RemoveDeprecation, Span::empty()),
+            (Some(a), Some(b)) if !same_directive_arguments(&a, &b) => push_change(
+                element_path.clone(),
+                ChangeKind::ChangeDeprecationReason,
+                directive_span(&b),
+            ),
+            _ => (),
+        }
+
+        let src_directives = index_directives(&src.directives);
+        let target_directives = index_directives(&target.directives);
+
+        for (&(name, idx), directive) in &target_directives {
+            // Paths can't point to directives on arguments yet.
+            let Some(directive_path) = directive_usage_path(element_path, name, idx) else {
+                break;
+            };
+
+            match src_directives.get(&(name, idx)) {
+                None => push_change(directive_path, ChangeKind::AddDirectiveUsage, directive_span(directive)),
+                Some(src_directive) if !same_directive_arguments(src_directive, directive) => push_change(
+                    directive_path,
+                    ChangeKind::ChangeDirectiveUsage,
+                    directive_span(directive),
+                ),
+                Some(_) => (),
+            }
+        }
+
+        for &(name, idx) in src_directives.keys() {
+            if target_directives.contains_key(&(name, idx)) {
+                continue;
+            }
+
+            let Some(directive_path) = directive_usage_path(element_path, name, idx) else {
+                break;
+            };
+
+            push_change(directive_path, ChangeKind::RemoveDirectiveUsage, Span::empty());
+        }
+    }
+}
+
+fn deprecated_directive<'a>(annotations: &Annotations<'a>) -> Option<ast::Directive<'a>> {
+    annotations
+        .directives
+        .iter()
+        .find(|directive| directive.name() == "deprecated")
+        .copied()
+}
+
+/// The directives by name and index among the directives with the same name, `@deprecated` excluded.
+fn index_directives<'a>(directives: &[ast::Directive<'a>]) -> HashMap<(&'a str, usize), ast::Directive<'a>> {
+    let mut counts = HashMap::<&str, usize>::new();
+
+    directives
+        .iter()
+        .filter(|directive| directive.name() != "deprecated")
+        .map(|directive| {
+            let count = counts.entry(directive.name()).or_default();
+            let idx = *count;
+            *count += 1;
+
+            ((directive.name(), idx), *directive)
+        })
+        .collect()
+}
+
+fn directive_usage_path<'a>(element_path: &path::Path<'a>, name: &'a str, idx: usize) -> Option<path::Path<'a>> {
+    match *element_path {
+        path::Path::TypeDefinition(type_name, None) => Some(path::Path::TypeDefinition(
+            type_name,
+            Some(PathInType::InDirective(name, idx)),
+        )),
+        path::Path::TypeDefinition(type_name, Some(PathInType::InField(field_name, None))) => {
+            Some(path::Path::TypeDefinition(
+                type_name,
+                Some(PathInType::InField(
+                    field_name,
+                    Some(PathInField::InDirective(name, idx)),
+                )),
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn same_directive_arguments(a: &ast::Directive<'_>, b: &ast::Directive<'_>) -> bool {
+    a.arguments()
+        .map(|argument| (argument.name(), argument.value()))
+        .eq(b.arguments().map(|argument| (argument.name(), argument.value())))
+}
+
+/// The span of a directive, from its name to the end of its last argument.
+fn directive_span(directive: &ast::Directive<'_>) -> Span {
+    let name_span = directive.name_span();
+    let end = directive
+        .arguments()
+        .last()
+        .map(|argument| argument.span().end)
+        .unwrap_or(name_span.end);
+
+    Span::new(name_span.start, end)
+}
+
+pub(crate) fn directive_location_str(location: ast::DirectiveLocation) -> &'static str {
+    match location {
+        ast::DirectiveLocation::Query => "QUERY",
+        ast::DirectiveLocation::Mutation => "MUTATION",
+        ast::DirectiveLocation::Subscription => "SUBSCRIPTION",
+        ast::DirectiveLocation::Field => "FIELD",
+        ast::DirectiveLocation::FragmentDefinition => "FRAGMENT_DEFINITION",
+        ast::DirectiveLocation::FragmentSpread => "FRAGMENT_SPREAD",
+        ast::DirectiveLocation::InlineFragment => "INLINE_FRAGMENT",
+        ast::DirectiveLocation::VariableDefinition => "VARIABLE_DEFINITION",
+        ast::DirectiveLocation::Schema => "SCHEMA",
+        ast::DirectiveLocation::Scalar => "SCALAR",
+        ast::DirectiveLocation::Object => "OBJECT",
+        ast::DirectiveLocation::FieldDefinition => "FIELD_DEFINITION",
+        ast::DirectiveLocation::ArgumentDefinition => "ARGUMENT_DEFINITION",
+        ast::DirectiveLocation::Interface => "INTERFACE",
+        ast::DirectiveLocation::Union => "UNION",
+        ast::DirectiveLocation::Enum => "ENUM",
+        ast::DirectiveLocation::EnumValue => "ENUM_VALUE",
+        ast::DirectiveLocation::InputObject => "INPUT_OBJECT",
+        ast::DirectiveLocation::InputFieldDefinition => "INPUT_FIELD_DEFINITION",
+    }
+}
+
 fn push_field_changes(
     fields_map: &DiffMap<[&str; 2], (Option<ast::Type<'_>>, Span)>,
     types_map: &DiffMap<&str, ast::Definition<'_>>,
@@ -209,7 +514,7 @@ fn push_field_changes(
         };
 
         let change_kind = match (src, target, DefinitionKind::new(&definition).unwrap()) {
-            (None, None, _) | (_, _, DefinitionKind::Scalar | DefinitionKind::Directive) => {
+            (None, None, _) | (_, _, DefinitionKind::Scalar) => {
                 unreachable!()
             }
             (
@@ -273,7 +578,6 @@ fn push_added_type(name: &str, definition: ast::Definition<'_>, push_change: Pus
     };
 
     let change_kind = match kind {
-        DefinitionKind::Directive => ChangeKind::AddDirectiveDefinition,
         DefinitionKind::Enum => ChangeKind::AddEnum,
         DefinitionKind::InputObject => ChangeKind::AddInputObject,
         DefinitionKind::Interface => ChangeKind::AddInterface,
@@ -291,7 +595,6 @@ fn push_added_type(name: &str, definition: ast::Definition<'_>, push_change: Pus
 
 fn push_removed_type(name: &str, definition: ast::Definition<'_>, push_change: PushChangeFn<'_>) {
     let change_kind = match DefinitionKind::new(&definition).unwrap() {
-        DefinitionKind::Directive => ChangeKind::RemoveDirectiveDefinition,
         DefinitionKind::Enum => ChangeKind::RemoveEnum,
         DefinitionKind::InputObject => ChangeKind::RemoveInputObject,
         DefinitionKind::Interface => ChangeKind::RemoveInterface,
@@ -1,4 +1,7 @@
-use crate::{DiffMap, DiffState, ast};
+use crate::{
+    Annotations, DiffMap, DiffState, ast,
+    path::{Path, PathInField, PathInType},
+};
 use std::{collections::hash_map::Entry, hash::Hash};
 
 /// Traverse the source and target schemas, populating the `DiffState`.
@@ -31,11 +34,34 @@ fn traverse_source<'a>(source: &'a ast::TypeSystemDocument, state: &mut DiffStat
                 state.schema_definition_map[0] = Some(def);
             }
             ast::Definition::Directive(directive_def) => {
-                insert_source(&mut state.types_map, directive_def.name(), definition);
+                let directive_name = directive_def.name();
+
+                insert_source(&mut state.directive_definitions_map, directive_name, directive_def);
+                annotate_source(
+                    state,
+                    Path::DirectiveDefinition(directive_name, None),
+                    directive_def.description(),
+                    std::iter::empty(),
+                );
+
+                for argument in directive_def.arguments() {
+                    insert_source(
+                        &mut state.directive_arguments_map,
+                        [directive_name, argument.name()],
+                        argument,
+                    );
+                }
             }
             ast::Definition::Type(tpe) | ast::Definition::TypeExtension(tpe) => {
                 let type_name = tpe.name();
 
+                annotate_source(
+                    state,
+                    Path::TypeDefinition(type_name, None),
+                    tpe.description(),
+                    tpe.directives(),
+                );
+
                 match &tpe {
                     ast::TypeDefinition::Scalar(_) => {
                         state.types_map.insert(type_name, (Some(definition), None));
@@ -56,9 +82,15 @@ fn traverse_source<'a>(source: &'a ast::TypeSystemDocument, state: &mut DiffStat
                                 [type_name, field_name],
                                 (Some(field.ty()), field.span().into()),
                             );
+                            annotate_source(
+                                state,
+                                field_path(type_name, field_name),
+                                field.description(),
+                                field.directives(),
+                            );
 
                             let mut args = field.arguments();
-                            fill_args_src(&mut state.arguments_map, type_name, field_name, &mut args);
+                            fill_args_src(state, type_name, field_name, &mut args);
                         }
                     }
                     ast::TypeDefinition::Interface(iface) => {
@@ -77,8 +109,14 @@ fn traverse_source<'a>(source: &'a ast::TypeSystemDocument, state: &mut DiffStat
                                 [type_name, field_name],
                                 (Some(field.ty()), field.span().into()),
                             );
+                            annotate_source(
+                                state,
+                                field_path(type_name, field_name),
+                                field.description(),
+                                field.directives(),
+                            );
 
-                            fill_args_src(&mut state.arguments_map, type_name, field_name, &mut field.arguments());
+                            fill_args_src(state, type_name, field_name, &mut field.arguments());
                         }
                     }
                     ast::TypeDefinition::Union(union) => {
@@ -101,6 +139,12 @@ fn traverse_source<'a>(source: &'a ast::TypeSystemDocument, state: &mut DiffStat
                                 [type_name, value.value()],
                                 (None, value.span().into()),
                             );
+                            annotate_source(
+                                state,
+                                field_path(type_name, value.value()),
+                                value.description(),
+                                value.directives(),
+                            );
                         }
                     }
                     ast::TypeDefinition::InputObject(input) => {
@@ -112,6 +156,13 @@ fn traverse_source<'a>(source: &'a ast::TypeSystemDocument, state: &mut DiffStat
                                 [type_name, field.name()],
                                 (Some(field.ty()), field.span().into()),
                             );
+                            insert_source(&mut state.input_fields_map, [type_name, field.name()], field);
+                            annotate_source(
+                                state,
+                                field_path(type_name, field.name()),
+                                field.description(),
+                                field.directives(),
+                            );
                         }
                     }
                 }
@@ -138,11 +189,33 @@ fn traverse_target<'a>(target: &'a ast::TypeSystemDocument, state: &mut DiffStat
                 state.schema_definition_map[1] = Some(def);
             }
             ast::Definition::Directive(directive_def) => {
-                merge_target(state.types_map.entry(directive_def.name()), definition);
+                let directive_name = directive_def.name();
+
+                merge_target(state.directive_definitions_map.entry(directive_name), directive_def);
+                annotate_target(
+                    state,
+                    Path::DirectiveDefinition(directive_name, None),
+                    directive_def.description(),
+                    std::iter::empty(),
+                );
+
+                for argument in directive_def.arguments() {
+                    merge_target(
+                        state.directive_arguments_map.entry([directive_name, argument.name()]),
+                        argument,
+                    );
+                }
             }
             ast::Definition::Type(tpe) | ast::Definition::TypeExtension(tpe) => {
                 let type_name = tpe.name();
 
+                annotate_target(
+                    state,
+                    Path::TypeDefinition(type_name, None),
+                    tpe.description(),
+                    tpe.directives(),
+                );
+
                 match tpe {
                     ast::TypeDefinition::Scalar(_) => {
                         state.types_map.entry(type_name).or_default().1 = Some(definition);
@@ -159,8 +232,14 @@ fn traverse_target<'a>(target: &'a ast::TypeSystemDocument, state: &mut DiffStat
                                 state.fields_map.entry([type_name, field.name()]),
                                 (Some(field.ty()), field.span().into()),
                             );
+                            annotate_target(
+                                state,
+                                field_path(type_name, field.name()),
+                                field.description(),
+                                field.directives(),
+                            );
                             let mut args = field.arguments();
-                            args_target(&mut state.arguments_map, type_name, field.name(), &mut args);
+                            args_target(state, type_name, field.name(), &mut args);
                         }
                     }
                     ast::TypeDefinition::Interface(iface) => {
@@ -177,7 +256,13 @@ fn traverse_target<'a>(target: &'a ast::TypeSystemDocument, state: &mut DiffStat
                                 state.fields_map.entry([type_name, field_name]),
                                 (Some(field.ty()), field.span().into()),
                             );
-                            args_target(&mut state.arguments_map, type_name, field_name, &mut field.arguments());
+                            annotate_target(
+                                state,
+                                field_path(type_name, field_name),
+                                field.description(),
+                                field.directives(),
+                            );
+                            args_target(state, type_name, field_name, &mut field.arguments());
                         }
                     }
                     ast::TypeDefinition::Union(union) => {
@@ -198,6 +283,12 @@ fn traverse_target<'a>(target: &'a ast::TypeSystemDocument, state: &mut DiffStat
                                 state.fields_map.entry([type_name, value.value()]),
                                 (None, value.span().into()),
                             );
+                            annotate_target(
+                                state,
+                                field_path(type_name, value.value()),
+                                value.description(),
+                                value.directives(),
+                            );
                         }
                     }
                     ast::TypeDefinition::InputObject(input) => {
@@ -208,6 +299,13 @@ fn traverse_target<'a>(target: &'a ast::TypeSystemDocument, state: &mut DiffStat
                                 state.fields_map.entry([type_name, field.name()]),
                                 (Some(field.ty()), field.span().into()),
                             );
+                            merge_target(state.input_fields_map.entry([type_name, field.name()]), field);
+                            annotate_target(
+                                state,
+                                field_path(type_name, field.name()),
+                                field.description(),
+                                field.directives(),
+                            );
                         }
                     }
                 }
@@ -218,28 +316,86 @@ fn traverse_target<'a>(target: &'a ast::TypeSystemDocument, state: &mut DiffStat
 
 // Insert the arguments of a field into the DiffState.
 fn fill_args_src<'a>(
-    arguments_map: &mut DiffMap<[&'a str; 3], ast::InputValueDefinition<'a>>,
+    state: &mut DiffState<'a>,
     parent: &'a str,
     field: &'a str,
     args: &mut (dyn Iterator<Item = ast::InputValueDefinition<'a>> + 'a),
 ) {
     for arg in args {
-        insert_source(arguments_map, [parent, field, (arg.name())], arg)
+        insert_source(&mut state.arguments_map, [parent, field, (arg.name())], arg);
+        annotate_source(
+            state,
+            argument_path(parent, field, arg.name()),
+            arg.description(),
+            arg.directives(),
+        );
     }
 }
 
 // Merge the arguments of a field in the target schema into the DiffState.
 fn args_target<'a>(
-    arguments_map: &mut DiffMap<[&'a str; 3], ast::InputValueDefinition<'a>>,
+    state: &mut DiffState<'a>,
     parent: &'a str,
     field: &'a str,
     args: &mut (dyn Iterator<Item = ast::InputValueDefinition<'a>> + 'a),
 ) {
     for arg in args {
-        merge_target(arguments_map.entry([parent, field, arg.name()]), arg)
+        merge_target(state.arguments_map.entry([parent, field, arg.name()]), arg);
+        annotate_target(
+            state,
+            argument_path(parent, field, arg.name()),
+            arg.description(),
+            arg.directives(),
+        );
     }
 }
 
+fn field_path<'a>(parent: &'a str, field: &'a str) -> Path<'a> {
+    Path::TypeDefinition(parent, Some(PathInType::InField(field, None)))
+}
+
+fn argument_path<'a>(parent: &'a str, field: &'a str, argument: &'a str) -> Path<'a> {
+    Path::TypeDefinition(
+        parent,
+        Some(PathInType::InField(field, Some(PathInField::InArgument(argument)))),
+    )
+}
+
+fn annotate_source<'a>(
+    state: &mut DiffState<'a>,
+    path: Path<'a>,
+    description: Option<ast::Description<'a>>,
+    directives: impl Iterator<Item = ast::Directive<'a>>,
+) {
+    let annotations = &mut state.annotations_map.entry(path).or_default().0;
+    annotate(annotations, description, directives);
+}
+
+fn annotate_target<'a>(
+    state: &mut DiffState<'a>,
+    path: Path<'a>,
+    description: Option<ast::Description<'a>>,
+    directives: impl Iterator<Item = ast::Directive<'a>>,
+) {
+    let annotations = &mut state.annotations_map.entry(path).or_default().1;
+    annotate(annotations, description, directives);
+}
+
+// Type extensions add their directives to the ones of the type definition.
+fn annotate<'a>(
+    annotations: &mut Option<Annotations<'a>>,
+    description: Option<ast::Description<'a>>,
+    directives: impl Iterator<Item = ast::Directive<'a>>,
+) {
+    let annotations = annotations.get_or_insert_with(Default::default);
+
+    if annotations.description.is_none() {
+        annotations.description = description;
+    }
+
+    annotations.directives.extend(directives);
+}
+
 fn insert_source<K: Hash + Eq, V>(map: &mut DiffMap<K, V>, key: K, source: V) {
     map.insert(key, (Some(source), None));
 }
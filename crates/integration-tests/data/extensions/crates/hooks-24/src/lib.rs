@@ -0,0 +1,57 @@
+use grafbase_sdk::{
+    HooksExtension,
+    host_io::{
+        event_queue::{Event, EventQueue},
+        http::StatusCode,
+    },
+    types::{AuthenticatedRequestContext, Configuration, Error, Headers, RequestContext},
+};
+
+#[derive(HooksExtension)]
+struct Hooks {
+    config: TestConfig,
+}
+
+#[derive(Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TestConfig {
+    granted_policies: Vec<String>,
+    cache_status_header_name: Option<String>,
+}
+
+impl HooksExtension for Hooks {
+    fn new(config: Configuration) -> Result<Self, Error> {
+        let config = config.deserialize::<TestConfig>()?;
+
+        Ok(Self { config })
+    }
+
+    fn on_response(
+        &mut self,
+        _: &RequestContext,
+        _: &mut StatusCode,
+        headers: &mut Headers,
+        queue: EventQueue,
+    ) -> Result<(), Error> {
+        if let Some(ref name) = self.config.cache_status_header_name {
+            let mut statuses = Vec::new();
+
+            while let Some(event) = queue.pop() {
+                if let Event::Subgraph(subgraph) = event {
+                    statuses.push(subgraph.cache_status.as_str());
+                }
+            }
+
+            headers.append(name, statuses.join(","));
+        }
+
+        Ok(())
+    }
+
+    fn authorize_policies(&mut self, _: &AuthenticatedRequestContext, policies: &[String]) -> Result<Vec<bool>, Error> {
+        Ok(policies
+            .iter()
+            .map(|policy| self.config.granted_policies.contains(policy))
+            .collect())
+    }
+}
@@ -22,7 +22,7 @@ impl GatewayHooksExtension for GatewayTestExtensions {
     }
 }
 
-impl EngineHooksExtension<engine::EngineOperationContext> for EngineTestExtensions {
+impl EngineHooksExtension<engine::EngineRequestContext, engine::EngineOperationContext> for EngineTestExtensions {
     async fn on_graphql_subgraph_request<'r>(
         &self,
         context: engine::EngineOperationContext,
@@ -40,4 +40,12 @@ impl EngineHooksExtension<engine::EngineOperationContext> for EngineTestExtensio
     ) -> Result<http::HeaderMap, GraphqlError> {
         self.wasm.on_virtual_subgraph_request(context, subgraph, headers).await
     }
+
+    async fn authorize_policies(
+        &self,
+        context: engine::EngineRequestContext,
+        policies: &[&str],
+    ) -> Result<Vec<bool>, GraphqlError> {
+        self.wasm.authorize_policies(context, policies).await
+    }
 }
@@ -7,7 +7,10 @@ use engine::{CachedOperation, Schema};
 use extension_catalog::ExtensionCatalog;
 use gateway_config::Config;
 use grafbase_telemetry::metrics::{self, EngineMetrics};
-use runtime::{entity_cache::EntityCache, fetch::dynamic::DynamicFetcher, trusted_documents_client};
+use runtime::{
+    entity_cache::EntityCache, fetch::dynamic::DynamicFetcher, field_usage::FieldUsageCollector,
+    trusted_documents_client,
+};
 use runtime_local::{
     InMemoryEntityCache, NativeFetcher,
     operation_cache::{InMemoryOperationCache, RedisOperationCache, TieredOperationCache},
@@ -121,6 +124,10 @@ impl engine::Runtime for TestRuntime {
         &self.entity_cache
     }
 
+    fn field_usage_collector(&self) -> &dyn FieldUsageCollector {
+        &()
+    }
+
     fn metrics(&self) -> &EngineMetrics {
         &self.metrics
     }
@@ -0,0 +1,283 @@
+use graphql_mocks::dynamic::{DynamicSchema, DynamicSubgraph};
+use integration_tests::{
+    gateway::{AuthenticationExt, Gateway},
+    runtime,
+};
+
+use crate::gateway::extensions::authentication::static_auth::StaticAuth;
+
+fn subgraph() -> DynamicSubgraph {
+    DynamicSchema::builder(
+        r#"
+        type Query {
+            greeting: String
+            me: String @authenticated
+            admin: String @requiresScopes(scopes: [["read", "admin"], ["root"]])
+            secret: String @policy(policies: [["secret"]])
+            news: String @policy(policies: [["public"]])
+        }
+        "#,
+    )
+    .with_resolver("Query", "greeting", serde_json::Value::String("Hi!".to_owned()))
+    .with_resolver("Query", "me", serde_json::Value::String("I am a user".to_owned()))
+    .with_resolver("Query", "admin", serde_json::Value::String("I am an admin".to_owned()))
+    .with_resolver("Query", "secret", serde_json::Value::String("42".to_owned()))
+    .with_resolver("Query", "news", serde_json::Value::String("Nothing new".to_owned()))
+    .into_subgraph("x")
+}
+
+#[test]
+fn anonymous() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_extension(AuthenticationExt::new(StaticAuth::anonymous()))
+            .build()
+            .await;
+
+        let response = engine.post("query { greeting me admin }").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "greeting": "Hi!",
+            "me": null,
+            "admin": null
+          },
+          "errors": [
+            {
+              "message": "Unauthenticated",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 18
+                }
+              ],
+              "path": [
+                "me"
+              ],
+              "extensions": {
+                "code": "UNAUTHENTICATED"
+              }
+            },
+            {
+              "message": "Unauthenticated",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 21
+                }
+              ],
+              "path": [
+                "admin"
+              ],
+              "extensions": {
+                "code": "UNAUTHENTICATED"
+              }
+            }
+          ]
+        }
+        "#);
+
+        let sent = engine.drain_graphql_requests_sent_to_by_name("x");
+        insta::assert_json_snapshot!(sent, @r#"
+        [
+          {
+            "query": "query { greeting }",
+            "operationName": null,
+            "variables": {},
+            "extensions": {}
+          }
+        ]
+        "#)
+    });
+}
+
+#[test]
+fn authenticated_without_scopes() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_extension(AuthenticationExt::new(StaticAuth::claims(&[("sub", "user")])))
+            .build()
+            .await;
+
+        let response = engine.post("query { greeting me admin }").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "greeting": "Hi!",
+            "me": "I am a user",
+            "admin": null
+          },
+          "errors": [
+            {
+              "message": "Not authorized: insufficient scopes",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 21
+                }
+              ],
+              "path": [
+                "admin"
+              ],
+              "extensions": {
+                "code": "UNAUTHORIZED"
+              }
+            }
+          ]
+        }
+        "#);
+    });
+}
+
+#[test]
+fn requires_scopes() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_extension(AuthenticationExt::new(StaticAuth::claims(&[("scope", "read write")])))
+            .build()
+            .await;
+
+        let response = engine.post("query { admin }").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "admin": null
+          },
+          "errors": [
+            {
+              "message": "Not authorized: insufficient scopes",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 9
+                }
+              ],
+              "path": [
+                "admin"
+              ],
+              "extensions": {
+                "code": "UNAUTHORIZED"
+              }
+            }
+          ]
+        }
+        "#);
+
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_extension(AuthenticationExt::new(StaticAuth::claims(&[("scope", "read admin")])))
+            .build()
+            .await;
+
+        let response = engine.post("query { admin }").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "admin": "I am an admin"
+          }
+        }
+        "#);
+
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_extension(AuthenticationExt::new(StaticAuth::claims(&[("scope", "root")])))
+            .build()
+            .await;
+
+        let response = engine.post("query { admin }").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "admin": "I am an admin"
+          }
+        }
+        "#);
+    });
+}
+
+#[test]
+fn policies_are_denied_without_hook() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_extension(AuthenticationExt::new(StaticAuth::claims(&[("scope", "root")])))
+            .build()
+            .await;
+
+        let response = engine.post("query { greeting secret }").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "greeting": "Hi!",
+            "secret": null
+          },
+          "errors": [
+            {
+              "message": "Not authorized",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 18
+                }
+              ],
+              "path": [
+                "secret"
+              ],
+              "extensions": {
+                "code": "UNAUTHORIZED"
+              }
+            }
+          ]
+        }
+        "#);
+    });
+}
+
+#[test]
+fn policies_are_evaluated_by_hooks() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_extension(AuthenticationExt::new(StaticAuth::claims(&[("scope", "root")])))
+            .with_extension("hooks-24")
+            .with_toml_config(
+                r#"
+                [extensions.hooks-24.config]
+                granted_policies = ["public"]
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post("query { greeting news secret }").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "greeting": "Hi!",
+            "news": "Nothing new",
+            "secret": null
+          },
+          "errors": [
+            {
+              "message": "Not authorized",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 23
+                }
+              ],
+              "path": [
+                "secret"
+              ],
+              "extensions": {
+                "code": "UNAUTHORIZED"
+              }
+            }
+          ]
+        }
+        "#);
+    });
+}
@@ -1213,3 +1213,117 @@ fn header_regex_remove_should_remove_duplicates() {
     }
     "#);
 }
+
+#[test]
+fn test_templated_header_values() {
+    let response = runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(EchoSchema::default())
+            .with_toml_config(
+                r#"
+                    [[headers]]
+                    rule = "insert"
+                    name = "x-user"
+                    value = "user:{{ headers.x-source-user }}"
+                    template = true
+
+                    [[headers]]
+                    rule = "insert"
+                    name = "x-client"
+                    value = "{{ client.name }}/{{ client.version }}"
+                    template = true
+
+                    [[subgraphs.echo.headers]]
+                    rule = "insert"
+                    name = "x-operation"
+                    value = "op:{{ operation.name }}"
+                    template = true
+                "#,
+            )
+            .build()
+            .await;
+
+        engine
+            .post(
+                r#"query Templates {
+                    user: header(name: "x-user")
+                    client: header(name: "x-client")
+                    operation: header(name: "x-operation")
+                }"#,
+            )
+            .header("x-source-user", "alice")
+            .header("x-grafbase-client-name", "ios")
+            .header("x-grafbase-client-version", "1.2")
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r#"
+    {
+      "data": {
+        "user": "user:alice",
+        "client": "ios/1.2",
+        "operation": "op:Templates"
+      }
+    }
+    "#);
+}
+
+#[test]
+fn test_static_header_values_are_not_templates() {
+    let response = runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(EchoSchema::default())
+            .with_toml_config(
+                r#"
+                    [[headers]]
+                    rule = "insert"
+                    name = "x-static"
+                    value = "{{ headers.x-source-user }}"
+                "#,
+            )
+            .build()
+            .await;
+
+        engine
+            .post(r#"query { static: header(name: "x-static") }"#)
+            .header("x-source-user", "alice")
+            .await
+    });
+
+    insta::assert_json_snapshot!(response, @r#"
+    {
+      "data": {
+        "static": "{{ headers.x-source-user }}"
+      }
+    }
+    "#);
+}
+
+#[test]
+fn test_empty_templated_header_values_are_not_sent() {
+    let response = runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(EchoSchema::default())
+            .with_toml_config(
+                r#"
+                    [[headers]]
+                    rule = "insert"
+                    name = "x-user-id"
+                    value = "{{ token.sub }}"
+                    template = true
+                "#,
+            )
+            .build()
+            .await;
+
+        engine.post(r#"query { userId: header(name: "x-user-id") }"#).await
+    });
+
+    insta::assert_json_snapshot!(response, @r#"
+    {
+      "data": {
+        "userId": null
+      }
+    }
+    "#);
+}
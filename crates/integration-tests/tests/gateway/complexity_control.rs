@@ -131,6 +131,143 @@ fn complexity_control_should_work_with_virtual_subgraphs() {
     });
 }
 
+#[test]
+fn actual_cost_is_reported_in_the_response_extension() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph_sdl(
+                "x",
+                r#"
+                extend schema
+                    @link(url: "resolver", import: ["@resolve"])
+
+                type Query {
+                    items: [Item] @resolve @listSize(assumedSize: 10)
+                }
+
+                type Item {
+                    name: String @cost(weight: 5)
+                }
+                "#,
+            )
+            .with_extension(ResolverExt::json(json!([{"name": "a"}, {"name": "b"}])))
+            .with_toml_config(
+                r#"
+                [complexity_control]
+                mode = "measure"
+
+                [telemetry.exporters.response_extension]
+                trace_id = false
+                query_plan = false
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine
+            .post("query { items { name } }")
+            .header("x-grafbase-telemetry", "yes")
+            .await;
+
+        // estimated: 10 * (1 + 5), actual: 2 * (1 + 5)
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "items": [
+              {
+                "name": "a"
+              },
+              {
+                "name": "b"
+              }
+            ]
+          },
+          "extensions": {
+            "grafbase": {
+              "cost": {
+                "estimated": 60,
+                "actual": 12
+              }
+            }
+          }
+        }
+        "#);
+    });
+}
+
+#[test]
+fn actual_cost_counts_the_items_of_nested_lists() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph_sdl(
+                "x",
+                r#"
+                extend schema
+                    @link(url: "resolver", import: ["@resolve"])
+
+                type Query {
+                    matrix: [[Item]] @resolve @listSize(assumedSize: 10)
+                }
+
+                type Item {
+                    name: String @cost(weight: 5)
+                }
+                "#,
+            )
+            .with_extension(ResolverExt::json(
+                json!([[{"name": "a"}, {"name": "b"}], [{"name": "c"}]]),
+            ))
+            .with_toml_config(
+                r#"
+                [complexity_control]
+                mode = "measure"
+
+                [telemetry.exporters.response_extension]
+                trace_id = false
+                query_plan = false
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine
+            .post("query { matrix { name } }")
+            .header("x-grafbase-telemetry", "yes")
+            .await;
+
+        // estimated: 10 * (1 + 5), actual: 3 * (1 + 5) as the three inner items are counted
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "matrix": [
+              [
+                {
+                  "name": "a"
+                },
+                {
+                  "name": "b"
+                }
+              ],
+              [
+                {
+                  "name": "c"
+                }
+              ]
+            ]
+          },
+          "extensions": {
+            "grafbase": {
+              "cost": {
+                "estimated": 60,
+                "actual": 18
+              }
+            }
+          }
+        }
+        "#);
+    });
+}
+
 #[test]
 fn test_complex_query_with_measure() {
     runtime().block_on(async move {
@@ -5,6 +5,7 @@ use integration_tests::{gateway::Gateway, runtime};
 use serde_json::json;
 
 mod redis;
+mod stale;
 mod subgraph_cache_control;
 
 #[test]
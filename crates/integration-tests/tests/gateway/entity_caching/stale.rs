@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use graphql_mocks::{FederatedInventorySchema, FederatedProductsSchema, FederatedReviewsSchema};
+use integration_tests::{gateway::Gateway, runtime};
+
+struct StaleProductSubgraph {
+    cache_control: &'static str,
+}
+
+impl graphql_mocks::Subgraph for StaleProductSubgraph {
+    fn name(&self) -> String {
+        "products".into()
+    }
+
+    async fn start(self) -> graphql_mocks::MockGraphQlServer {
+        FederatedProductsSchema::default()
+            .start()
+            .await
+            .with_additional_raw_header(http::header::CACHE_CONTROL, self.cache_control)
+    }
+}
+
+struct StaleReviewSubgraph {
+    cache_control: &'static str,
+}
+
+impl graphql_mocks::Subgraph for StaleReviewSubgraph {
+    fn name(&self) -> String {
+        "reviews".into()
+    }
+
+    async fn start(self) -> graphql_mocks::MockGraphQlServer {
+        FederatedReviewsSchema::default()
+            .start()
+            .await
+            .with_additional_raw_header(http::header::CACHE_CONTROL, self.cache_control)
+    }
+}
+
+#[test]
+fn stale_while_revalidate_root_request() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(StaleProductSubgraph {
+                cache_control: "max-age=1, stale-while-revalidate=60",
+            })
+            .with_toml_config(
+                r#"
+                [entity_caching]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = "{ topProducts { upc name } }";
+
+        let first_response = engine.post(QUERY).await.into_data();
+
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+
+        // The stale response is served right away, so the failure of the background refresh
+        // doesn't matter.
+        engine
+            .subgraph::<StaleProductSubgraph>()
+            .force_next_response(http::StatusCode::INTERNAL_SERVER_ERROR);
+
+        let second_response = engine.post(QUERY).await;
+        assert!(second_response.errors().is_empty());
+        assert_eq!(second_response.into_data(), first_response);
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        assert_eq!(engine.drain_graphql_requests_sent_to::<StaleProductSubgraph>().len(), 2);
+    })
+}
+
+#[test]
+fn stale_if_error_root_request() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(StaleProductSubgraph {
+                cache_control: "max-age=1, stale-if-error=60",
+            })
+            .with_toml_config(
+                r#"
+                [entity_caching]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = "{ topProducts { upc name } }";
+
+        let first_response = engine.post(QUERY).await.into_data();
+
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+
+        engine
+            .subgraph::<StaleProductSubgraph>()
+            .force_next_response(http::StatusCode::INTERNAL_SERVER_ERROR);
+
+        let second_response = engine.post(QUERY).await;
+        assert!(second_response.errors().is_empty());
+        assert_eq!(second_response.into_data(), first_response);
+
+        assert_eq!(engine.drain_graphql_requests_sent_to::<StaleProductSubgraph>().len(), 2);
+    })
+}
+
+#[test]
+fn stale_if_error_entity_request() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_subgraph(StaleReviewSubgraph {
+                cache_control: "max-age=1, stale-if-error=60",
+            })
+            .with_subgraph(FederatedInventorySchema::default())
+            .with_toml_config(
+                r#"
+                [entity_caching]
+                enabled = true
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = "{ topProducts { upc reviews { id body } } }";
+
+        let first_response = engine.post(QUERY).await.into_data();
+
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+
+        engine
+            .subgraph::<StaleReviewSubgraph>()
+            .force_next_response(http::StatusCode::INTERNAL_SERVER_ERROR);
+
+        let second_response = engine.post(QUERY).await;
+        assert!(second_response.errors().is_empty());
+        assert_eq!(second_response.into_data(), first_response);
+
+        assert_eq!(
+            engine.drain_graphql_requests_sent_to::<FederatedProductsSchema>().len(),
+            1
+        );
+        assert_eq!(engine.drain_graphql_requests_sent_to::<StaleReviewSubgraph>().len(), 2);
+    })
+}
+
+#[test]
+fn stale_cache_status_is_reported_to_hooks() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(StaleProductSubgraph {
+                cache_control: "max-age=1, stale-while-revalidate=60",
+            })
+            .with_extension("hooks-24")
+            .with_toml_config(
+                r#"
+                [entity_caching]
+                enabled = true
+
+                [extensions.hooks-24.config]
+                cache_status_header_name = "x-cache-status"
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = "{ topProducts { upc name } }";
+
+        let first_response = engine.post(QUERY).await;
+        assert_eq!(first_response.headers.get("x-cache-status").unwrap(), "miss");
+
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+
+        let second_response = engine.post(QUERY).await;
+        assert!(second_response.errors().is_empty());
+        assert_eq!(second_response.headers.get("x-cache-status").unwrap(), "stale");
+    })
+}
@@ -0,0 +1,272 @@
+use graphql_mocks::dynamic::{DynamicSchema, DynamicSubgraph};
+use integration_tests::{gateway::Gateway, runtime};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+const SECRET: &[u8] = b"secret";
+// "c2VjcmV0" is SECRET, base64url encoded.
+const JWKS: &str = r#"{"keys":[{"kty":"oct","kid":"key","k":"c2VjcmV0"}]}"#;
+
+fn subgraph() -> DynamicSubgraph {
+    DynamicSchema::builder(
+        r#"
+        type Query {
+            greeting: String
+            me: String @authenticated
+        }
+        "#,
+    )
+    .with_resolver("Query", "greeting", serde_json::Value::String("Hi!".to_owned()))
+    .with_resolver("Query", "me", serde_json::Value::String("I am a user".to_owned()))
+    .into_subgraph("x")
+}
+
+fn jwks_file() -> tempfile::NamedTempFile {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), JWKS).unwrap();
+    file
+}
+
+fn config(jwks: &tempfile::NamedTempFile, default: &str) -> String {
+    format!(
+        r#"
+        [authentication]
+        default = "{default}"
+
+        [authentication.jwt]
+        jwks_path = "{}"
+        issuer = "gateway-tests"
+        audience = ["api"]
+        algorithms = ["HS256"]
+        cookie_name = "session"
+        "#,
+        jwks.path().display()
+    )
+}
+
+fn token(issuer: &str) -> String {
+    let mut header = Header::new(Algorithm::HS256);
+    header.kid = Some("key".into());
+    let exp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        + 3600;
+    let claims = serde_json::json!({ "iss": issuer, "aud": "api", "sub": "user", "exp": exp });
+
+    jsonwebtoken::encode(&header, &claims, &EncodingKey::from_secret(SECRET)).unwrap()
+}
+
+#[test]
+fn valid_token() {
+    runtime().block_on(async move {
+        let jwks = jwks_file();
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_toml_config(config(&jwks, "deny"))
+            .build()
+            .await;
+
+        let response = engine
+            .post("query { greeting me }")
+            .header("Authorization", format!("Bearer {}", token("gateway-tests")))
+            .await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "greeting": "Hi!",
+            "me": "I am a user"
+          }
+        }
+        "#);
+
+        let response = engine
+            .post("query { greeting me }")
+            .header("Cookie", format!("theme=dark; session={}", token("gateway-tests")))
+            .await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "greeting": "Hi!",
+            "me": "I am a user"
+          }
+        }
+        "#);
+    });
+}
+
+#[test]
+fn invalid_token_is_denied() {
+    runtime().block_on(async move {
+        let jwks = jwks_file();
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_toml_config(config(&jwks, "deny"))
+            .build()
+            .await;
+
+        let response = engine
+            .post("query { greeting me }")
+            .header("Authorization", format!("Bearer {}", token("someone-else")))
+            .await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "errors": [
+            {
+              "message": "Unauthenticated",
+              "extensions": {
+                "code": "UNAUTHENTICATED"
+              }
+            }
+          ]
+        }
+        "#);
+
+        let response = engine.post("query { greeting me }").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "errors": [
+            {
+              "message": "Unauthenticated",
+              "extensions": {
+                "code": "UNAUTHENTICATED"
+              }
+            }
+          ]
+        }
+        "#);
+    });
+}
+
+#[test]
+fn missing_token_with_anonymous_default() {
+    runtime().block_on(async move {
+        let jwks = jwks_file();
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_toml_config(config(&jwks, "anonymous"))
+            .build()
+            .await;
+
+        let response = engine.post("query { greeting me }").await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "greeting": "Hi!",
+            "me": null
+          },
+          "errors": [
+            {
+              "message": "Unauthenticated",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 18
+                }
+              ],
+              "path": [
+                "me"
+              ],
+              "extensions": {
+                "code": "UNAUTHENTICATED"
+              }
+            }
+          ]
+        }
+        "#);
+    });
+}
+
+fn jwks_url_config(url: &str) -> String {
+    format!(
+        r#"
+        [authentication]
+        default = "deny"
+
+        [authentication.jwt]
+        jwks_url = "{url}/jwks.json"
+        jwks_refresh_interval = "1h"
+        issuer = "gateway-tests"
+        audience = ["api"]
+        algorithms = ["HS256"]
+        "#
+    )
+}
+
+#[test]
+fn jwks_url_is_fetched_before_serving() {
+    runtime().block_on(async move {
+        let jwks_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/jwks.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(JWKS, "application/json"))
+            .expect(1)
+            .mount(&jwks_server)
+            .await;
+
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_toml_config(jwks_url_config(&jwks_server.uri()))
+            .build()
+            .await;
+
+        // The very first request must already be validated against the fetched key set.
+        let response = engine
+            .post("query { greeting me }")
+            .header("Authorization", format!("Bearer {}", token("gateway-tests")))
+            .await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "greeting": "Hi!",
+            "me": "I am a user"
+          }
+        }
+        "#);
+    });
+}
+
+#[test]
+fn jwks_url_fetch_is_retried_at_startup() {
+    runtime().block_on(async move {
+        let jwks_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/jwks.json"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&jwks_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/jwks.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(JWKS, "application/json"))
+            .expect(1)
+            .mount(&jwks_server)
+            .await;
+
+        let engine = Gateway::builder()
+            .with_subgraph(subgraph())
+            .with_toml_config(jwks_url_config(&jwks_server.uri()))
+            .build()
+            .await;
+
+        let response = engine
+            .post("query { greeting me }")
+            .header("Authorization", format!("Bearer {}", token("gateway-tests")))
+            .await;
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "greeting": "Hi!",
+            "me": "I am a user"
+          }
+        }
+        "#);
+    });
+}
@@ -1,4 +1,5 @@
 mod apq;
+mod authorization;
 mod basic;
 mod complexity_control;
 mod composite;
@@ -11,11 +12,15 @@ mod graphql_over_http;
 mod inaccessible;
 mod introspection;
 mod issues;
+mod jwt_authentication;
 mod mcp;
 mod message_signing;
 mod mtls;
 mod response_extensions;
 mod router;
+mod subgraph_batching;
+mod subgraph_response_headers;
+mod subgraph_response_limits;
 mod subgraph_retries;
 mod subgraphs;
 mod subscriptions;
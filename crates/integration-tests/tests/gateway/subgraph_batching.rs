@@ -0,0 +1,84 @@
+use graphql_mocks::{FederatedAccountsSchema, FederatedProductsSchema, FederatedReviewsSchema};
+use integration_tests::{gateway::Gateway, runtime};
+
+// Both entity requests to the reviews subgraph are executed concurrently, with different bodies.
+const QUERY: &str = r"
+    query {
+        me { id reviews { body } }
+        topProducts { upc reviews { body } }
+    }
+";
+
+#[test]
+fn concurrent_requests_with_different_bodies_are_batched() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedAccountsSchema::default())
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_subgraph(FederatedReviewsSchema::default())
+            .with_toml_config(
+                r#"
+                [subgraphs.reviews.batching]
+                enabled = true
+                max_size = 2
+                max_wait = "5s"
+                "#,
+            )
+            .build()
+            .await;
+
+        let reference = Gateway::builder()
+            .with_subgraph(FederatedAccountsSchema::default())
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_subgraph(FederatedReviewsSchema::default())
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).await;
+        assert!(response.errors().is_empty(), "{response:#?}");
+        assert_eq!(response.into_value(), reference.post(QUERY).await.into_value());
+
+        let batch_sizes = engine
+            .subgraph::<FederatedReviewsSchema>()
+            .drain_received_batch_sizes()
+            .collect::<Vec<_>>();
+        assert_eq!(batch_sizes, [2]);
+
+        let requests = engine.drain_http_requests_sent_to::<FederatedReviewsSchema>();
+        assert_eq!(requests.len(), 2);
+        assert_ne!(requests[0].body.query, requests[1].body.query);
+
+        // Both requests were received through the same HTTP request, whose Content-Length covers
+        // the whole JSON array.
+        let content_length = |index: usize| -> usize {
+            requests[index].headers[http::header::CONTENT_LENGTH]
+                .to_str()
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+        assert_eq!(content_length(0), content_length(1));
+    })
+}
+
+#[test]
+fn requests_are_not_batched_by_default() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(FederatedAccountsSchema::default())
+            .with_subgraph(FederatedProductsSchema::default())
+            .with_subgraph(FederatedReviewsSchema::default())
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).await;
+        assert!(response.errors().is_empty(), "{response:#?}");
+
+        let batch_sizes = engine
+            .subgraph::<FederatedReviewsSchema>()
+            .drain_received_batch_sizes()
+            .count();
+        assert_eq!(batch_sizes, 0);
+        assert_eq!(engine.drain_http_requests_sent_to::<FederatedReviewsSchema>().len(), 2);
+    })
+}
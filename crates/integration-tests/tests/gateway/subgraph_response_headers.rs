@@ -0,0 +1,165 @@
+use graphql_mocks::{FederatedInventorySchema, FederatedProductsSchema, FederatedReviewsSchema};
+use integration_tests::{gateway::Gateway, runtime};
+
+const QUERY: &str = "{ topProducts { upc reviews { id } } }";
+
+struct ProductsSubgraph;
+
+impl graphql_mocks::Subgraph for ProductsSubgraph {
+    fn name(&self) -> String {
+        "products".into()
+    }
+
+    async fn start(self) -> graphql_mocks::MockGraphQlServer {
+        FederatedProductsSchema::default()
+            .start()
+            .await
+            .with_additional_raw_header(http::header::SET_COOKIE, "products=1")
+            .with_additional_raw_header(http::header::CACHE_CONTROL, "public, max-age=60")
+            .with_additional_raw_header(http::HeaderName::from_static("x-rate-limit-remaining"), "10")
+    }
+}
+
+struct ReviewsSubgraph;
+
+impl graphql_mocks::Subgraph for ReviewsSubgraph {
+    fn name(&self) -> String {
+        "reviews".into()
+    }
+
+    async fn start(self) -> graphql_mocks::MockGraphQlServer {
+        FederatedReviewsSchema::default()
+            .start()
+            .await
+            .with_additional_raw_header(http::header::SET_COOKIE, "reviews=2")
+            .with_additional_raw_header(http::header::CACHE_CONTROL, "private, max-age=30")
+            .with_additional_raw_header(http::HeaderName::from_static("x-rate-limit-remaining"), "5")
+    }
+}
+
+fn values(headers: &http::HeaderMap, name: &str) -> Vec<String> {
+    headers
+        .get_all(name)
+        .iter()
+        .map(|value| value.to_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn append_and_most_restrictive() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(ProductsSubgraph)
+            .with_subgraph(ReviewsSubgraph)
+            .with_subgraph(FederatedInventorySchema)
+            .with_toml_config(
+                r#"
+                [[response_headers]]
+                name = "set-cookie"
+                merge = "append"
+
+                [[response_headers]]
+                name = "cache-control"
+                merge = "most_restrictive"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).await;
+        assert!(response.errors().is_empty(), "{response:#?}");
+
+        assert_eq!(values(&response.headers, "set-cookie"), ["products=1", "reviews=2"]);
+        assert_eq!(values(&response.headers, "cache-control"), ["private, max-age=30"]);
+        assert!(response.headers.get("x-rate-limit-remaining").is_none());
+    })
+}
+
+#[test]
+fn first_and_last() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(ProductsSubgraph)
+            .with_subgraph(ReviewsSubgraph)
+            .with_subgraph(FederatedInventorySchema)
+            .with_toml_config(
+                r#"
+                [[response_headers]]
+                name = "set-cookie"
+
+                [[response_headers]]
+                pattern = "^x-rate-limit-"
+                merge = "last"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).await;
+        assert!(response.errors().is_empty(), "{response:#?}");
+
+        assert_eq!(values(&response.headers, "set-cookie"), ["products=1"]);
+        assert_eq!(values(&response.headers, "x-rate-limit-remaining"), ["5"]);
+    })
+}
+
+#[test]
+fn subgraph_specific_rule_with_rename_and_default() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(ProductsSubgraph)
+            .with_subgraph(ReviewsSubgraph)
+            .with_subgraph(FederatedInventorySchema)
+            .with_toml_config(
+                r#"
+                [[subgraphs.reviews.response_headers]]
+                pattern = "^x-rate-limit-"
+                rename = "x-reviews-rate-limit"
+
+                [[subgraphs.reviews.response_headers]]
+                name = "x-missing"
+                default = "none"
+                "#,
+            )
+            .build()
+            .await;
+
+        let response = engine.post(QUERY).await;
+        assert!(response.errors().is_empty(), "{response:#?}");
+
+        assert_eq!(values(&response.headers, "x-reviews-rate-limit"), ["5"]);
+        assert_eq!(values(&response.headers, "x-missing"), ["none"]);
+        assert!(response.headers.get("x-rate-limit-remaining").is_none());
+        assert!(response.headers.get("set-cookie").is_none());
+    })
+}
+
+#[test]
+fn entity_cache_hits_contribute_no_headers() {
+    runtime().block_on(async move {
+        let engine = Gateway::builder()
+            .with_subgraph(ProductsSubgraph)
+            .with_toml_config(
+                r#"
+                [entity_caching]
+                enabled = true
+
+                [[response_headers]]
+                name = "set-cookie"
+                "#,
+            )
+            .build()
+            .await;
+
+        const QUERY: &str = "{ topProducts { upc name } }";
+
+        let response = engine.post(QUERY).await;
+        assert_eq!(values(&response.headers, "set-cookie"), ["products=1"]);
+
+        // Served from the entity cache, no subgraph response to take headers from.
+        let response = engine.post(QUERY).await;
+        assert!(response.errors().is_empty(), "{response:#?}");
+        assert!(response.headers.get("set-cookie").is_none());
+        assert_eq!(engine.drain_graphql_requests_sent_to::<ProductsSubgraph>().len(), 1);
+    })
+}
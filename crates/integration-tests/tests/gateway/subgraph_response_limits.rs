@@ -0,0 +1,206 @@
+use graphql_mocks::dynamic::{DynamicSchema, DynamicSubgraph};
+use integration_tests::{gateway::Gateway, runtime};
+use serde_json::json;
+
+fn limited_subgraph() -> DynamicSubgraph {
+    DynamicSchema::builder(
+        r#"
+        type Node {
+            name: String!
+            child: Node
+        }
+
+        type Query {
+            items: [String!]
+            node: Node
+        }
+        "#,
+    )
+    .with_resolver("Query", "items", json!(["a", "b", "c", "d"]))
+    .with_resolver(
+        "Query",
+        "node",
+        json!({"name": "1", "child": {"name": "2", "child": {"name": "3", "child": null}}}),
+    )
+    .into_subgraph("limited")
+}
+
+fn other_subgraph() -> DynamicSubgraph {
+    DynamicSchema::builder(
+        r#"
+        type Query {
+            greeting: String
+        }
+        "#,
+    )
+    .with_resolver("Query", "greeting", json!("Hi!"))
+    .into_subgraph("other")
+}
+
+#[test]
+fn within_limits() {
+    runtime().block_on(async move {
+        let response = Gateway::builder()
+            .with_subgraph(limited_subgraph())
+            .with_subgraph(other_subgraph())
+            .with_toml_config(
+                r#"
+                [subgraphs.limited.response_limits]
+                max_size = "10KiB"
+                max_list_length = 4
+                max_depth = 3
+                "#,
+            )
+            .build()
+            .await
+            .post("{ items node { name child { name child { name } } } greeting }")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "items": [
+              "a",
+              "b",
+              "c",
+              "d"
+            ],
+            "node": {
+              "name": "1",
+              "child": {
+                "name": "2",
+                "child": {
+                  "name": "3"
+                }
+              }
+            },
+            "greeting": "Hi!"
+          }
+        }
+        "#);
+    })
+}
+
+#[test]
+fn max_size_exceeded() {
+    runtime().block_on(async move {
+        let response = Gateway::builder()
+            .with_subgraph(limited_subgraph())
+            .with_subgraph(other_subgraph())
+            .with_toml_config(
+                r#"
+                [subgraphs.limited.response_limits]
+                max_size = "16B"
+                "#,
+            )
+            .build()
+            .await
+            .post("{ items greeting }")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "items": null,
+            "greeting": "Hi!"
+          },
+          "errors": [
+            {
+              "message": "Request to subgraph 'limited' failed.",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 3
+                }
+              ],
+              "path": [
+                "items"
+              ],
+              "extensions": {
+                "code": "SUBGRAPH_REQUEST_ERROR"
+              }
+            }
+          ]
+        }
+        "#);
+    })
+}
+
+#[test]
+fn max_list_length_exceeded() {
+    runtime().block_on(async move {
+        let response = Gateway::builder()
+            .with_subgraph(limited_subgraph())
+            .with_subgraph(other_subgraph())
+            .with_toml_config(
+                r#"
+                [subgraphs.limited.response_limits]
+                max_list_length = 2
+                "#,
+            )
+            .build()
+            .await
+            .post("{ items greeting }")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "items": null,
+            "greeting": "Hi!"
+          },
+          "errors": [
+            {
+              "message": "Subgraph response list exceeded the maximum length of 2",
+              "path": [
+                "items"
+              ],
+              "extensions": {
+                "code": "SUBGRAPH_INVALID_RESPONSE_ERROR"
+              }
+            }
+          ]
+        }
+        "#);
+    })
+}
+
+#[test]
+fn max_depth_exceeded() {
+    runtime().block_on(async move {
+        let response = Gateway::builder()
+            .with_subgraph(limited_subgraph())
+            .with_subgraph(other_subgraph())
+            .with_toml_config(
+                r#"
+                [subgraphs.limited.response_limits]
+                max_depth = 2
+                "#,
+            )
+            .build()
+            .await
+            .post("{ node { name child { name child { name } } } greeting }")
+            .await;
+
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "node": null,
+            "greeting": "Hi!"
+          },
+          "errors": [
+            {
+              "message": "Subgraph response exceeded the maximum depth of 2",
+              "path": [
+                "node",
+                "child"
+              ],
+              "extensions": {
+                "code": "SUBGRAPH_INVALID_RESPONSE_ERROR"
+              }
+            }
+          ]
+        }
+        "#);
+    })
+}
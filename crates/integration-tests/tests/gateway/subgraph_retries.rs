@@ -124,3 +124,150 @@ fn subgraph_retries_mutations_enabled() {
         "#);
     });
 }
+
+#[test]
+fn subgraph_retries_max_attempts() {
+    runtime().block_on(async move {
+        let config = indoc::indoc! {r#"
+            [subgraphs.stateful.retry]
+            enabled = true
+            max_attempts = 2
+            initial_backoff = "1ms"
+            jitter = false
+        "#};
+
+        let engine = Gateway::builder()
+            .with_subgraph(Stateful::default())
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let response = engine.post("query { incrementAndFailIfLessThan(n: 3) }").await;
+
+        insta::assert_json_snapshot!(response, {
+            ".errors[0].message" => "REDACTED".to_owned(),
+        }, @r#"
+        {
+          "data": null,
+          "errors": [
+            {
+              "message": "REDACTED",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 9
+                }
+              ],
+              "path": [
+                "incrementAndFailIfLessThan"
+              ],
+              "extensions": {
+                "code": "SUBGRAPH_REQUEST_ERROR"
+              }
+            }
+          ]
+        }
+        "#);
+
+        // The initial request and a single retry.
+        let response = engine.post("query { value }").await;
+
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "value": 2
+          }
+        }
+        "#);
+    });
+}
+
+#[test]
+fn subgraph_retries_single_attempt() {
+    runtime().block_on(async move {
+        let config = indoc::indoc! {r#"
+            [subgraphs.stateful.retry]
+            enabled = true
+            max_attempts = 1
+            initial_backoff = "1ms"
+        "#};
+
+        let engine = Gateway::builder()
+            .with_subgraph(Stateful::default())
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let response = engine.post("query { incrementAndFailIfLessThan(n: 2) }").await;
+        assert_eq!(response.errors().len(), 1, "{response:#?}");
+
+        // Only the initial request.
+        let response = engine.post("query { value }").await;
+
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "value": 1
+          }
+        }
+        "#);
+    });
+}
+
+#[test]
+fn subgraph_retries_only_configured_status_codes() {
+    runtime().block_on(async move {
+        let config = indoc::indoc! {r#"
+            [subgraphs.stateful.retry]
+            enabled = true
+            initial_backoff = "1ms"
+
+            [subgraphs.stateful.retry.retry_on]
+            status_codes = [502, 503]
+        "#};
+
+        let engine = Gateway::builder()
+            .with_subgraph(Stateful::default())
+            .with_toml_config(config)
+            .build()
+            .await;
+
+        let response = engine.post("query { incrementAndFailIfLessThan(n: 1) }").await;
+
+        insta::assert_json_snapshot!(response, {
+            ".errors[0].message" => "REDACTED".to_owned(),
+        }, @r#"
+        {
+          "data": null,
+          "errors": [
+            {
+              "message": "REDACTED",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 9
+                }
+              ],
+              "path": [
+                "incrementAndFailIfLessThan"
+              ],
+              "extensions": {
+                "code": "SUBGRAPH_REQUEST_ERROR"
+              }
+            }
+          ]
+        }
+        "#);
+
+        // A 500 isn't retried.
+        let response = engine.post("query { value }").await;
+
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "data": {
+            "value": 1
+          }
+        }
+        "#);
+    });
+}
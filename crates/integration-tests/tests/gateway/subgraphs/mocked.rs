@@ -0,0 +1,136 @@
+use graphql_mocks::dynamic::DynamicSchema;
+use integration_tests::{gateway::Gateway, runtime};
+use serde_json::json;
+
+#[test]
+fn generated_values_are_deterministic_and_entities_keep_their_keys() {
+    runtime().block_on(async move {
+        let gateway = Gateway::builder()
+            .with_subgraph(
+                DynamicSchema::builder(
+                    r#"
+                    type Query {
+                        users: [User!]!
+                    }
+
+                    type User @key(fields: "id") {
+                        id: ID!
+                    }
+                    "#,
+                )
+                .with_generated_values(7)
+                .into_subgraph("users"),
+            )
+            .with_subgraph(
+                DynamicSchema::builder(
+                    r#"
+                    type User @key(fields: "id") {
+                        id: ID!
+                        email: String!
+                        role: Role!
+                    }
+
+                    enum Role {
+                        ADMIN
+                        MEMBER
+                    }
+                    "#,
+                )
+                .with_generated_values(7)
+                .into_subgraph("accounts"),
+            )
+            .build()
+            .await;
+
+        let query = "query { users { id email role } }";
+        let first = gateway.post(query).await;
+        let second = gateway.post(query).await;
+
+        assert!(first.errors().is_empty(), "{first}");
+        assert_eq!(first.body, second.body);
+
+        let users = first["data"]["users"].as_array().unwrap();
+        assert!(!users.is_empty());
+
+        for user in users {
+            assert!(user["id"].as_str().is_some_and(|id| !id.is_empty()));
+            assert!(user["email"].as_str().unwrap().ends_with("@example.com"));
+            assert!([json!("ADMIN"), json!("MEMBER")].contains(&user["role"]));
+        }
+    })
+}
+
+#[test]
+fn fixtures_take_precedence_over_generated_values() {
+    runtime().block_on(async move {
+        let gateway = Gateway::builder()
+            .with_subgraph(
+                DynamicSchema::builder(
+                    r#"
+                    type Query {
+                        me: User!
+                    }
+
+                    type User {
+                        name: String!
+                        friends: [User!]!
+                    }
+                    "#,
+                )
+                .with_generated_values(0)
+                .with_resolver("Query", "me", json!({"name": "Jane"}))
+                .into_subgraph("users"),
+            )
+            .build()
+            .await;
+
+        let response = gateway.post("query { me { name friends { name } } }").await;
+
+        assert!(response.errors().is_empty(), "{response}");
+        assert_eq!(response["data"]["me"]["name"], json!("Jane"));
+        assert!(!response["data"]["me"]["friends"].as_array().unwrap().is_empty());
+    })
+}
+
+#[test]
+fn max_generated_list_length_before_generated_values() {
+    runtime().block_on(async move {
+        let gateway = Gateway::builder()
+            .with_subgraph(
+                DynamicSchema::builder(
+                    r#"
+                    type Query {
+                        users: [User!]!
+                    }
+
+                    type User {
+                        tags: [String!]!
+                        friends: [User!]!
+                    }
+                    "#,
+                )
+                .with_max_generated_list_length(1)
+                .with_generated_values(7)
+                .into_subgraph("users"),
+            )
+            .build()
+            .await;
+
+        let response = gateway
+            .post("query { users { tags friends { tags friends { tags } } } }")
+            .await;
+        assert!(response.errors().is_empty(), "{response}");
+
+        let users = response["data"]["users"].as_array().unwrap();
+        assert_eq!(users.len(), 1);
+
+        let user = &users[0];
+        assert_eq!(user["tags"].as_array().unwrap().len(), 1);
+        assert_eq!(user["friends"].as_array().unwrap().len(), 1);
+
+        let friend = &user["friends"][0];
+        assert_eq!(friend["tags"].as_array().unwrap().len(), 1);
+        assert_eq!(friend["friends"].as_array().unwrap().len(), 1);
+        assert_eq!(friend["friends"][0]["tags"].as_array().unwrap().len(), 1);
+    })
+}
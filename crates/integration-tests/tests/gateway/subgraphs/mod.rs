@@ -1,4 +1,5 @@
 mod interface_object;
+mod mocked;
 mod not_reachable;
 mod overrride;
 mod provides;
@@ -1,7 +1,10 @@
 use futures::StreamExt;
 use futures::stream::FuturesUnordered;
 use graphql_mocks::SlowSchema;
-use integration_tests::{gateway::Gateway, runtime};
+use integration_tests::{
+    gateway::{Gateway, GraphqlResponse},
+    runtime,
+};
 
 #[test]
 fn inflight_deduplication_enabled() {
@@ -143,3 +146,147 @@ fn mutations_not_deduplicated_even_when_enabled() {
         assert_eq!(gateway.drain_graphql_requests_sent_to::<SlowSchema>().len(), 10);
     })
 }
+
+fn error_codes(responses: &[GraphqlResponse]) -> Vec<String> {
+    let mut codes = responses
+        .iter()
+        .map(|response| match response.errors().first() {
+            Some(error) => error["extensions"]["code"].as_str().unwrap().to_owned(),
+            None => "OK".to_owned(),
+        })
+        .collect::<Vec<_>>();
+    codes.sort_unstable();
+    codes
+}
+
+#[test]
+fn subgraph_concurrency_limit_without_queue() {
+    runtime().block_on(async move {
+        let gateway = Gateway::builder()
+            .with_subgraph(SlowSchema::default())
+            .with_toml_config(
+                r###"
+                [traffic_shaping]
+                inflight_deduplication = false
+
+                [subgraphs.slow.traffic_shaping]
+                max_concurrent_requests = 1
+                "###,
+            )
+            .build()
+            .await;
+
+        let (first, second) = futures::join!(
+            gateway.post("query { delay(ms: 300) }"),
+            gateway.post("query { delay(ms: 301) }")
+        );
+
+        assert_eq!(error_codes(&[first, second]), ["OK", "SERVICE_UNAVAILABLE"]);
+        assert_eq!(gateway.drain_graphql_requests_sent_to::<SlowSchema>().len(), 1);
+    })
+}
+
+#[test]
+fn subgraph_concurrency_limit_with_queue() {
+    runtime().block_on(async move {
+        let gateway = Gateway::builder()
+            .with_subgraph(SlowSchema::default())
+            .with_toml_config(
+                r###"
+                [traffic_shaping]
+                inflight_deduplication = false
+
+                [subgraphs.slow.traffic_shaping]
+                max_concurrent_requests = 1
+                max_queue_size = 1
+                "###,
+            )
+            .build()
+            .await;
+
+        let (first, second) = futures::join!(
+            gateway.post("query { delay(ms: 100) }"),
+            gateway.post("query { delay(ms: 101) }")
+        );
+
+        // The second request waited for the first one to complete.
+        assert_eq!(error_codes(&[first, second]), ["OK", "OK"]);
+        assert_eq!(gateway.drain_graphql_requests_sent_to::<SlowSchema>().len(), 2);
+    })
+}
+
+#[test]
+fn subgraph_concurrency_limit_queue_timeout() {
+    runtime().block_on(async move {
+        let gateway = Gateway::builder()
+            .with_subgraph(SlowSchema::default())
+            .with_toml_config(
+                r###"
+                [traffic_shaping]
+                inflight_deduplication = false
+
+                [subgraphs.slow.traffic_shaping]
+                max_concurrent_requests = 1
+                max_queue_size = 1
+                queue_timeout = "50ms"
+                "###,
+            )
+            .build()
+            .await;
+
+        let (first, second) = futures::join!(
+            gateway.post("query { delay(ms: 500) }"),
+            gateway.post("query { delay(ms: 501) }")
+        );
+
+        assert_eq!(error_codes(&[first, second]), ["OK", "SERVICE_UNAVAILABLE"]);
+        assert_eq!(gateway.drain_graphql_requests_sent_to::<SlowSchema>().len(), 1);
+    })
+}
+
+#[test]
+fn load_shedding_rejects_requests_with_retry_after() {
+    runtime().block_on(async move {
+        let gateway = Gateway::builder()
+            .with_subgraph(SlowSchema::default())
+            .with_toml_config(
+                r###"
+                [traffic_shaping.load_shedding]
+                latency_threshold = "1ms"
+                max_rejection_ratio = 1.0
+                retry_after = "3s"
+                "###,
+            )
+            .build()
+            .await;
+
+        // Far above the threshold, so almost every following request is rejected.
+        let response = gateway.post("query { delay(ms: 200) }").await;
+        assert!(response.errors().is_empty(), "{response:#?}");
+
+        let mut rejected = Vec::new();
+        for _ in 0..20 {
+            let response = gateway.post("query { delay(ms: 1) }").await;
+            if response.status == http::StatusCode::SERVICE_UNAVAILABLE {
+                rejected.push(response);
+            }
+        }
+
+        assert!(!rejected.is_empty());
+
+        let response = &rejected[0];
+        assert_eq!(response.headers[http::header::RETRY_AFTER], "3");
+        insta::assert_json_snapshot!(response, @r#"
+        {
+          "errors": [
+            {
+              "message": "Service unavailable",
+              "extensions": {
+                "code": "SERVICE_UNAVAILABLE"
+              }
+            }
+          ]
+        }
+        "#);
+    })
+}
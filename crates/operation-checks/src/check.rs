@@ -135,11 +135,31 @@ fn check_change<T: UsageProvider>(args: CheckArgs<'_, '_, T>) -> Option<CheckDia
         // Directives do not directly affect the shape of the API.
         | ChangeKind::AddDirectiveDefinition
         | ChangeKind::RemoveDirectiveDefinition
+        | ChangeKind::AddDirectiveDefinitionArgument
+        | ChangeKind::RemoveDirectiveDefinitionArgument
+        | ChangeKind::ChangeDirectiveDefinitionArgument
+        | ChangeKind::AddDirectiveDefinitionLocation
+        | ChangeKind::RemoveDirectiveDefinitionLocation
+        | ChangeKind::AddDirectiveUsage
+        | ChangeKind::RemoveDirectiveUsage
+        | ChangeKind::ChangeDirectiveUsage
+
+        // Descriptions and deprecations are documentation, queries keep working.
+        | ChangeKind::AddDescription
+        | ChangeKind::RemoveDescription
+        | ChangeKind::ChangeDescription
+        | ChangeKind::AddDeprecation
+        | ChangeKind::RemoveDeprecation
+        | ChangeKind::ChangeDeprecationReason
 
         // Adding or changing the default on an argument will not break clients.
         | ChangeKind::AddFieldArgumentDefault
         | ChangeKind::ChangeFieldArgumentDefault
 
+        // Same for input fields.
+        | ChangeKind::AddInputFieldDefault
+        | ChangeKind::ChangeInputFieldDefault
+
         // Making an object or an interface implement a new interface is safe.
         | ChangeKind::AddInterfaceImplementation
 
@@ -186,6 +206,8 @@ fn check_change<T: UsageProvider>(args: CheckArgs<'_, '_, T>) -> Option<CheckDia
         ChangeKind::RemoveEnumValue => rules::remove_enum_value(args),
 
         ChangeKind::RemoveFieldArgumentDefault  => rules::remove_field_argument_default(args),
+
+        ChangeKind::RemoveInputFieldDefault => rules::remove_input_field_default(args),
     }
 }
 
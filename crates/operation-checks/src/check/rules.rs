@@ -141,6 +141,41 @@ pub(super) fn add_field<T: UsageProvider>(
     }
 }
 
+/// Removing the default value of a required input field is breaking if the input object is used,
+/// like adding a required input field.
+pub(super) fn remove_input_field_default<T: UsageProvider>(
+    CheckArgs {
+        change,
+        check_params,
+        used_input_types,
+        ..
+    }: CheckArgs<'_, '_, T>,
+) -> Option<CheckDiagnostic> {
+    let (type_name, field_name) = change.path.split_once('.').unwrap();
+
+    let field_id = check_params.target.find_field(type_name, field_name)?;
+
+    if !check_params.target[field_id].is_required() {
+        return None;
+    }
+
+    let used_input_types = used_input_types.get_or_insert_with(|| find_used_input_types(check_params));
+
+    if used_input_types.contains(type_name) {
+        Some(CheckDiagnostic {
+            message: format!(
+                "The default value of the required field at `{}` was removed, it would break clients that are not providing it.",
+                change.path
+            ),
+            severity: Severity::Error,
+            path: change.path.clone(),
+            change_kind: change.kind,
+        })
+    } else {
+        None
+    }
+}
+
 /// Changing the type of an argument or removing an argument is safe iff the argument is not in
 /// use or if it was required and became optional (keeping the same inner type).
 pub(super) fn change_field_argument_type<T: UsageProvider>(
@@ -7,6 +7,10 @@
 //! - Run the checks with [check()].
 //! - Alternatively, you can use [check_assuming_all_used()] which assumes all fields, arguments,
 //!   and enum values are in use.
+//!
+//! Usage that needs to outlive a single schema version, for example when it is accumulated by
+//! the gateway over time, is stored as a [SchemaCoordinateUsage] and resolved back to a
+//! [FieldUsage] for the schema being checked.
 
 #![deny(missing_docs)]
 
@@ -14,8 +18,10 @@ mod aggregate_field_usage;
 mod check;
 mod operation;
 mod schema;
+mod schema_coordinate_usage;
 
 pub use aggregate_field_usage::{AssumeAllUsed, FieldUsage, UsageProvider, aggregate_field_usage};
 pub use check::{CheckDiagnostic, CheckParams, Severity, check, check_assuming_all_used};
 pub use operation::Operation;
 pub use schema::Schema;
+pub use schema_coordinate_usage::SchemaCoordinateUsage;
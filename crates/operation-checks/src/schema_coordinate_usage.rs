@@ -0,0 +1,235 @@
+use crate::{FieldUsage, schema};
+use std::collections::BTreeMap;
+
+/// Usage counts keyed by schema coordinates (`Type.field`, `Type.field.argument`, `Enum.VALUE`)
+/// instead of ids into a specific [Schema](schema::Schema).
+///
+/// This is the form in which usage can be persisted and accumulated over time, across schema
+/// versions. Use [SchemaCoordinateUsage::to_field_usage()] to resolve it against the schema you
+/// want to check.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SchemaCoordinateUsage {
+    /// "Type.field" -> usage count
+    pub fields: BTreeMap<String, u64>,
+    /// "Type.field.argument" -> usage count
+    pub arguments: BTreeMap<String, u64>,
+    /// "Enum.VALUE" -> usage count
+    pub enum_values: BTreeMap<String, u64>,
+    /// "Type.field.argument" -> number of times a required argument with a default was left out
+    pub arguments_with_defaults_left_out: BTreeMap<String, u64>,
+    /// "parent_type_name.implementer_type_name" -> usage count
+    pub type_conditions: BTreeMap<String, u64>,
+}
+
+impl SchemaCoordinateUsage {
+    /// Convert field usage aggregated against `schema` to schema coordinates.
+    pub fn from_field_usage(usage: &FieldUsage, schema: &schema::Schema) -> Self {
+        let field_coordinate = |id: &schema::FieldId| {
+            let field = &schema[*id];
+            [field.type_name.as_str(), field.field_name.as_str()].join(".")
+        };
+
+        let argument_coordinate = |id: &schema::ArgumentId| {
+            let argument = &schema[*id];
+            [
+                argument.type_name.as_str(),
+                argument.field_name.as_str(),
+                argument.argument_name.as_str(),
+            ]
+            .join(".")
+        };
+
+        SchemaCoordinateUsage {
+            fields: usage
+                .count_per_field
+                .iter()
+                .map(|(id, count)| (field_coordinate(id), *count))
+                .collect(),
+            arguments: usage
+                .count_per_field_argument
+                .iter()
+                .map(|(id, count)| (argument_coordinate(id), *count))
+                .collect(),
+            enum_values: usage
+                .count_per_enum_value
+                .iter()
+                .map(|(value, count)| (value.clone(), *count))
+                .collect(),
+            arguments_with_defaults_left_out: usage
+                .arguments_with_defaults_left_out_count
+                .iter()
+                .map(|(id, count)| (argument_coordinate(id), *count))
+                .collect(),
+            type_conditions: usage
+                .type_condition_counts
+                .iter()
+                .map(|(type_condition, count)| (type_condition.clone(), *count))
+                .collect(),
+        }
+    }
+
+    /// Resolve the coordinates against `schema`. Coordinates that do not exist in the schema are
+    /// ignored.
+    pub fn to_field_usage(&self, schema: &schema::Schema) -> FieldUsage {
+        let find_argument = |coordinate: &str| {
+            let mut parts = coordinate.splitn(3, '.');
+            let (type_name, field_name, argument_name) = (parts.next()?, parts.next()?, parts.next()?);
+            schema.find_argument((type_name, field_name, argument_name))
+        };
+
+        let mut usage = FieldUsage::default();
+
+        for (coordinate, count) in &self.fields {
+            let Some(field_id) = coordinate
+                .split_once('.')
+                .and_then(|(type_name, field_name)| schema.find_field(type_name, field_name))
+            else {
+                continue;
+            };
+
+            *usage.count_per_field.entry(field_id).or_insert(0) += count;
+        }
+
+        for (coordinate, count) in &self.arguments {
+            if let Some(argument_id) = find_argument(coordinate) {
+                *usage.count_per_field_argument.entry(argument_id).or_insert(0) += count;
+            }
+        }
+
+        for (coordinate, count) in &self.arguments_with_defaults_left_out {
+            if let Some(argument_id) = find_argument(coordinate) {
+                *usage
+                    .arguments_with_defaults_left_out_count
+                    .entry(argument_id)
+                    .or_insert(0) += count;
+            }
+        }
+
+        usage.count_per_enum_value.extend(self.enum_values.clone());
+        usage.type_condition_counts.extend(self.type_conditions.clone());
+
+        usage
+    }
+
+    /// Add the counts of `other` to this usage.
+    pub fn merge(&mut self, other: &SchemaCoordinateUsage) {
+        fn merge_counts(into: &mut BTreeMap<String, u64>, from: &BTreeMap<String, u64>) {
+            for (key, count) in from {
+                let entry = into.entry(key.clone()).or_insert(0);
+                *entry = entry.saturating_add(*count);
+            }
+        }
+
+        merge_counts(&mut self.fields, &other.fields);
+        merge_counts(&mut self.arguments, &other.arguments);
+        merge_counts(&mut self.enum_values, &other.enum_values);
+        merge_counts(
+            &mut self.arguments_with_defaults_left_out,
+            &other.arguments_with_defaults_left_out,
+        );
+        merge_counts(&mut self.type_conditions, &other.type_conditions);
+    }
+
+    /// Whether no usage was recorded at all.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+            && self.arguments.is_empty()
+            && self.enum_values.is_empty()
+            && self.arguments_with_defaults_left_out.is_empty()
+            && self.type_conditions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{UsageProvider, aggregate_field_usage};
+
+    const SCHEMA: &str = r#"
+        type Query {
+            pets(kind: PetKind, first: Int! = 10): [Pet!]!
+        }
+
+        enum PetKind {
+            CAT
+            DOG
+        }
+
+        interface Pet {
+            name: String!
+        }
+
+        type Cat implements Pet {
+            name: String!
+            lives: Int!
+        }
+
+        type Dog implements Pet {
+            name: String!
+        }
+    "#;
+
+    #[test]
+    fn roundtrip_through_coordinates() {
+        let schema: schema::Schema = async_graphql_parser::parse_schema(SCHEMA).unwrap().into();
+        let operation = async_graphql_parser::parse_query("{ pets(kind: CAT) { name ... on Cat { lives } } }")
+            .unwrap()
+            .into();
+
+        let mut usage = FieldUsage::default();
+        usage.set_increment(3);
+        aggregate_field_usage(&operation, &schema, &mut usage);
+
+        let coordinates = SchemaCoordinateUsage::from_field_usage(&usage, &schema);
+
+        insta::assert_json_snapshot!(coordinates, @r#"
+        {
+          "fields": {
+            "Cat.lives": 3,
+            "Pet.name": 3,
+            "Query.pets": 3
+          },
+          "arguments": {
+            "Query.pets.kind": 3
+          },
+          "enum_values": {
+            "PetKind.CAT": 3
+          },
+          "arguments_with_defaults_left_out": {
+            "Query.pets.first": 3
+          },
+          "type_conditions": {
+            "Pet.Cat": 1
+          }
+        }
+        "#);
+
+        let resolved = coordinates.to_field_usage(&schema);
+
+        assert!(resolved.field_is_used(schema.find_field("Cat", "lives").unwrap()));
+        assert!(!resolved.field_is_used(schema.find_field("Dog", "name").unwrap()));
+        assert!(resolved.argument_is_used(schema.find_argument(("Query", "pets", "kind")).unwrap()));
+        assert!(resolved.argument_is_left_out(schema.find_argument(("Query", "pets", "first")).unwrap()));
+        assert!(resolved.enum_value_is_used("PetKind.CAT"));
+        assert!(!resolved.enum_value_is_used("PetKind.DOG"));
+        assert!(resolved.type_condition_is_used("Pet.Cat"));
+    }
+
+    #[test]
+    fn merge_adds_counts() {
+        let mut usage = SchemaCoordinateUsage::default();
+        usage.fields.insert("Query.pets".into(), 2);
+
+        let mut other = SchemaCoordinateUsage::default();
+        other.fields.insert("Query.pets".into(), 5);
+        other.enum_values.insert("PetKind.DOG".into(), 1);
+
+        usage.merge(&other);
+
+        assert_eq!(usage.fields["Query.pets"], 7);
+        assert_eq!(usage.enum_values["PetKind.DOG"], 1);
+        assert!(!usage.is_empty());
+        assert!(SchemaCoordinateUsage::default().is_empty());
+    }
+}
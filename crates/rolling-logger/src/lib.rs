@@ -7,6 +7,8 @@
 //!
 //! * `path`: The base file path for the current log file.
 //! * `file`: The current log file being written to.
+//! * `housekeeper`: The optional background thread compressing and deleting
+//!   rotated files according to a `RetentionPolicy`.
 //!
 //! The `RollingLogger` struct provides methods to create a new logger,
 //! write data to the log, flush the log, and rotate the log file based on
@@ -15,11 +17,14 @@
 #![deny(missing_docs)]
 
 mod log_file;
+mod retention;
 mod strategy;
 
+pub use retention::{Compression, RetentionPolicy};
 pub use strategy::RotateStrategy;
 
 use log_file::LogFile;
+use retention::Housekeeper;
 use std::{
     io::{self, Write},
     path::{Path, PathBuf},
@@ -31,10 +36,12 @@ use std::{
 ///
 /// * `path` - The base file path for the current log file.
 /// * `file` - The current log file being written to.
+/// * `housekeeper` - Compresses and deletes rotated files, if a retention policy is set.
 #[derive(Debug)]
 pub struct RollingLogger {
     path: PathBuf,
     file: LogFile,
+    housekeeper: Option<Housekeeper>,
 }
 
 impl RollingLogger {
@@ -50,17 +57,53 @@ impl RollingLogger {
     /// An `io::Result` which is `Ok` if the `RollingLogger` was successfully created,
     /// or an `io::Error` if there was a problem creating the log file.
     pub fn new(path: impl AsRef<Path>, rotate_strategy: RotateStrategy) -> io::Result<Self> {
+        Self::with_retention(path, rotate_strategy, RetentionPolicy::default())
+    }
+
+    /// Creates a new `RollingLogger` which also applies the given retention policy to the
+    /// rotated files.
+    ///
+    /// Compression and deletion of the rotated files happen in a background thread, and never
+    /// block writing to the log. The existing rotated files are pruned when the logger is created.
+    /// Dropping the logger waits for the pending housekeeping to finish.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - A reference to the base file path for the current log file.
+    /// * `rotate_strategy` - The strategy to use for rotating the log file.
+    /// * `retention` - What to do with the rotated files.
+    ///
+    /// # Returns
+    ///
+    /// An `io::Result` which is `Ok` if the `RollingLogger` was successfully created,
+    /// or an `io::Error` if there was a problem creating the log file.
+    pub fn with_retention(
+        path: impl AsRef<Path>,
+        rotate_strategy: RotateStrategy,
+        retention: RetentionPolicy,
+    ) -> io::Result<Self> {
         let path = path.as_ref().to_owned();
         let file = LogFile::new(&path, rotate_strategy)?;
 
-        Ok(Self { path, file })
+        let housekeeper = if retention.keeps_everything() {
+            None
+        } else {
+            Some(Housekeeper::spawn(&path, retention)?)
+        };
+
+        Ok(Self {
+            path,
+            file,
+            housekeeper,
+        })
     }
 
     /// Flushes the current log file and rotates it according to the specified strategy.
     ///
     /// This method first flushes the current log file to ensure all pending data is written.
-    /// It then renames the current log file by appending a timestamp to its name and creates
-    /// a new log file to continue logging.
+    /// It then renames the current log file by appending its creation timestamp in milliseconds
+    /// to its name, and creates a new log file to continue logging. The rotated file is handed
+    /// over to the housekeeping thread, if any.
     ///
     /// # Returns
     ///
@@ -76,6 +119,10 @@ impl RollingLogger {
 
         self.file = LogFile::new(&self.path, self.file.copy_new_rotate())?;
 
+        if let Some(housekeeper) = &self.housekeeper {
+            housekeeper.rotated(PathBuf::from(path));
+        }
+
         Ok(())
     }
 
@@ -122,13 +169,25 @@ impl Write for RollingLogger {
 
 #[cfg(test)]
 mod tests {
-    use super::{RollingLogger, RotateStrategy};
+    use super::{Compression, RetentionPolicy, RollingLogger, RotateStrategy};
     use std::{
-        io::Write,
+        io::{Read, Write},
+        path::Path,
         time::{Duration, SystemTime},
     };
     use tempfile::TempDir;
 
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names = dir
+            .read_dir()
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+
+        names.sort();
+        names
+    }
+
     #[test]
     fn never_rotate() {
         let dir = TempDir::new().unwrap();
@@ -278,4 +337,156 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn retention_max_files() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("access.log");
+        let retention = RetentionPolicy::default().with_max_files(1);
+
+        let mut logger = RollingLogger::with_retention(&path, RotateStrategy::size(3), retention).unwrap();
+
+        writeln!(&mut logger, "foo").unwrap();
+
+        logger.set_rotate_start(SystemTime::UNIX_EPOCH + Duration::from_millis(1));
+        writeln!(&mut logger, "bar").unwrap();
+
+        logger.set_rotate_start(SystemTime::UNIX_EPOCH + Duration::from_millis(2));
+        writeln!(&mut logger, "lol").unwrap();
+
+        drop(logger);
+
+        insta::assert_debug_snapshot!(file_names(dir.path()), @r#"
+        [
+            "access.log",
+            "access.log.2",
+        ]
+        "#);
+
+        let data = std::fs::read_to_string(dir.path().join("access.log.2")).unwrap();
+
+        insta::assert_snapshot!(&data, @r###"
+        bar
+        "###);
+    }
+
+    #[test]
+    fn retention_max_age() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("access.log");
+        let retention = RetentionPolicy::default().with_max_age(Duration::from_secs(60 * 60));
+
+        let mut logger = RollingLogger::with_retention(&path, RotateStrategy::size(3), retention).unwrap();
+
+        logger.set_rotate_start(SystemTime::now() - Duration::from_secs(60 * 60 * 2));
+        writeln!(&mut logger, "foo").unwrap();
+        writeln!(&mut logger, "bar").unwrap();
+
+        let recent = SystemTime::now() - Duration::from_secs(60);
+        logger.set_rotate_start(recent);
+        writeln!(&mut logger, "lol").unwrap();
+
+        drop(logger);
+
+        let recent = recent.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis();
+        let data = std::fs::read_to_string(dir.path().join(format!("access.log.{recent}"))).unwrap();
+
+        insta::assert_snapshot!(&data, @r###"
+        bar
+        "###);
+
+        assert_eq!(file_names(dir.path()).len(), 2);
+    }
+
+    #[test]
+    fn retention_prunes_existing_files() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("access.log");
+
+        for name in [
+            "access.log.1",
+            "access.log.2.gz",
+            "access.log.3.zst",
+            "access.log.old",
+            "other.log.1",
+        ] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        let retention = RetentionPolicy::default().with_max_files(2);
+        let logger = RollingLogger::with_retention(&path, RotateStrategy::never(), retention).unwrap();
+
+        drop(logger);
+
+        insta::assert_debug_snapshot!(file_names(dir.path()), @r#"
+        [
+            "access.log",
+            "access.log.2.gz",
+            "access.log.3.zst",
+            "access.log.old",
+            "other.log.1",
+        ]
+        "#);
+    }
+
+    #[test]
+    fn retention_gzip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("access.log");
+        let retention = RetentionPolicy::default().with_compression(Compression::Gzip);
+
+        let mut logger = RollingLogger::with_retention(&path, RotateStrategy::size(3), retention).unwrap();
+
+        writeln!(&mut logger, "foo").unwrap();
+
+        logger.set_rotate_start(SystemTime::UNIX_EPOCH + Duration::from_millis(1));
+        writeln!(&mut logger, "bar").unwrap();
+
+        drop(logger);
+
+        insta::assert_debug_snapshot!(file_names(dir.path()), @r#"
+        [
+            "access.log",
+            "access.log.1.gz",
+        ]
+        "#);
+
+        let file = std::fs::File::open(dir.path().join("access.log.1.gz")).unwrap();
+        let mut data = String::new();
+        flate2::read::GzDecoder::new(file).read_to_string(&mut data).unwrap();
+
+        insta::assert_snapshot!(&data, @r###"
+        foo
+        "###);
+    }
+
+    #[test]
+    fn retention_zstd() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("access.log");
+        let retention = RetentionPolicy::default().with_compression(Compression::Zstd);
+
+        let mut logger = RollingLogger::with_retention(&path, RotateStrategy::size(3), retention).unwrap();
+
+        writeln!(&mut logger, "foo").unwrap();
+
+        logger.set_rotate_start(SystemTime::UNIX_EPOCH + Duration::from_millis(1));
+        writeln!(&mut logger, "bar").unwrap();
+
+        drop(logger);
+
+        insta::assert_debug_snapshot!(file_names(dir.path()), @r#"
+        [
+            "access.log",
+            "access.log.1.zst",
+        ]
+        "#);
+
+        let file = std::fs::File::open(dir.path().join("access.log.1.zst")).unwrap();
+        let data = String::from_utf8(zstd::decode_all(file).unwrap()).unwrap();
+
+        insta::assert_snapshot!(&data, @r###"
+        foo
+        "###);
+    }
 }
@@ -0,0 +1,274 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread::JoinHandle,
+    time::{Duration, SystemTime},
+};
+
+/// The compression algorithm applied to rotated log files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    /// Rotated files are kept as they are.
+    #[default]
+    None,
+    /// Rotated files are compressed with gzip, and get the `.gz` extension.
+    Gzip,
+    /// Rotated files are compressed with zstd, and get the `.zst` extension.
+    Zstd,
+}
+
+impl Compression {
+    /// The extension appended to the name of a rotated file compressed with this algorithm.
+    fn extension(self) -> Option<&'static str> {
+        match self {
+            Compression::None => None,
+            Compression::Gzip => Some("gz"),
+            Compression::Zstd => Some("zst"),
+        }
+    }
+}
+
+/// Defines what happens to log files after they have been rotated.
+///
+/// A rotated file is named after the log file, followed by the Unix timestamp in milliseconds
+/// of the moment the file was created, and the compression extension if any. For a log file
+/// `access.log`, the rotated files look like `access.log.1718000000000` or
+/// `access.log.1718000000000.gz`.
+///
+/// By default all rotated files are kept uncompressed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// The maximum number of rotated files to keep.
+    max_files: Option<usize>,
+    /// The maximum age of a rotated file, measured from the moment the file was created.
+    max_age: Option<Duration>,
+    /// The compression algorithm for rotated files.
+    compression: Compression,
+}
+
+impl RetentionPolicy {
+    /// Keeps at most the given number of rotated files, deleting the oldest ones first.
+    ///
+    /// # Arguments
+    ///
+    /// - `max_files`: The maximum number of rotated files to keep.
+    ///
+    /// # Returns
+    ///
+    /// The `RetentionPolicy` with the file limit set.
+    pub fn with_max_files(mut self, max_files: usize) -> Self {
+        self.max_files = Some(max_files);
+        self
+    }
+
+    /// Deletes rotated files created longer ago than the given duration.
+    ///
+    /// # Arguments
+    ///
+    /// - `max_age`: The maximum age of a rotated file.
+    ///
+    /// # Returns
+    ///
+    /// The `RetentionPolicy` with the age limit set.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Compresses rotated files with the given algorithm.
+    ///
+    /// # Arguments
+    ///
+    /// - `compression`: The compression algorithm to use.
+    ///
+    /// # Returns
+    ///
+    /// The `RetentionPolicy` with the compression set.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Returns `true` if rotated files are kept forever without compression.
+    pub(crate) fn keeps_everything(&self) -> bool {
+        self.max_files.is_none() && self.max_age.is_none() && self.compression == Compression::None
+    }
+}
+
+/// The work sent to the housekeeping thread.
+enum Job {
+    /// Compress the given rotated file, if needed, and apply the retention policy.
+    Rotated(PathBuf),
+    /// Apply the retention policy to the existing rotated files.
+    Prune,
+}
+
+/// Compresses and deletes rotated files in a background thread, so that writing the logs is
+/// never blocked by the housekeeping.
+#[derive(Debug)]
+pub(crate) struct Housekeeper {
+    sender: Option<mpsc::Sender<Job>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Housekeeper {
+    /// Starts the housekeeping thread for the log file at the given path. The existing rotated
+    /// files are pruned right away.
+    pub fn spawn(path: &Path, policy: RetentionPolicy) -> io::Result<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let path = path.to_owned();
+
+        let thread = std::thread::Builder::new()
+            .name(String::from("rolling-logger-housekeeping"))
+            .spawn(move || {
+                while let Ok(job) = receiver.recv() {
+                    let result = match job {
+                        Job::Rotated(rotated) => {
+                            compress(&rotated, policy.compression).and_then(|_| prune(&path, policy))
+                        }
+                        Job::Prune => prune(&path, policy),
+                    };
+
+                    if let Err(err) = result {
+                        tracing::error!("Error cleaning up the rotated files of {}: {err}", path.display());
+                    }
+                }
+            })?;
+
+        // The receiver lives as long as the thread, which only stops once the sender is dropped.
+        sender.send(Job::Prune).ok();
+
+        Ok(Self {
+            sender: Some(sender),
+            thread: Some(thread),
+        })
+    }
+
+    /// Schedules the housekeeping of a freshly rotated file.
+    pub fn rotated(&self, path: PathBuf) {
+        if let Some(sender) = &self.sender {
+            sender.send(Job::Rotated(path)).ok();
+        }
+    }
+}
+
+impl Drop for Housekeeper {
+    /// Waits for the scheduled housekeeping to finish.
+    fn drop(&mut self) {
+        drop(self.sender.take());
+
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
+/// Compresses the rotated file, replacing it with the compressed version.
+fn compress(path: &Path, compression: Compression) -> io::Result<()> {
+    let Some(extension) = compression.extension() else {
+        return Ok(());
+    };
+
+    let mut target = path.as_os_str().to_os_string();
+    target.push(format!(".{extension}"));
+
+    let mut partial = target.clone();
+    partial.push(".partial");
+
+    let mut input = BufReader::new(File::open(path)?);
+    let output = BufWriter::new(File::create(&partial)?);
+
+    let result = match compression {
+        Compression::None => unreachable!("no extension for uncompressed files"),
+        Compression::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(output, flate2::Compression::default());
+            io::copy(&mut input, &mut encoder).and_then(|_| encoder.finish()?.flush())
+        }
+        Compression::Zstd => zstd::stream::Encoder::new(output, 0).and_then(|mut encoder| {
+            io::copy(&mut input, &mut encoder)?;
+            encoder.finish()?.flush()
+        }),
+    };
+
+    if let Err(err) = result {
+        fs::remove_file(&partial).ok();
+        return Err(err);
+    }
+
+    fs::rename(&partial, &target)?;
+    fs::remove_file(path)
+}
+
+/// Deletes the rotated files of the log file exceeding the limits of the retention policy.
+fn prune(path: &Path, policy: RetentionPolicy) -> io::Result<()> {
+    if policy.max_files.is_none() && policy.max_age.is_none() {
+        return Ok(());
+    }
+
+    let mut rotated = rotated_files(path)?;
+
+    // Newest first.
+    rotated.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+
+    let oldest_allowed = policy.max_age.and_then(|max_age| {
+        let cutoff = SystemTime::now().checked_sub(max_age)?;
+        cutoff
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .ok()
+            .map(|d| d.as_millis())
+    });
+
+    for (i, (timestamp, file)) in rotated.into_iter().enumerate() {
+        let too_many = policy.max_files.is_some_and(|max_files| i >= max_files);
+        let too_old = oldest_allowed.is_some_and(|oldest| timestamp < oldest);
+
+        // A file that can't be deleted must not keep the older ones around.
+        if (too_many || too_old)
+            && let Err(err) = fs::remove_file(&file)
+        {
+            tracing::error!("Error deleting the rotated log file {}: {err}", file.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Lists the rotated files of the log file with their creation timestamp in milliseconds.
+fn rotated_files(path: &Path) -> io::Result<Vec<(u128, PathBuf)>> {
+    let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+        return Ok(Vec::new());
+    };
+
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let mut rotated = Vec::new();
+
+    for entry in fs::read_dir(directory)? {
+        let entry = entry?;
+        let name = entry.file_name();
+
+        let Some(suffix) = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(file_name))
+            .and_then(|suffix| suffix.strip_prefix('.'))
+        else {
+            continue;
+        };
+
+        let timestamp = [".gz", ".zst"]
+            .iter()
+            .find_map(|extension| suffix.strip_suffix(extension))
+            .unwrap_or(suffix);
+
+        if let Ok(timestamp) = timestamp.parse::<u128>() {
+            rotated.push((timestamp, entry.path()));
+        }
+    }
+
+    Ok(rotated)
+}
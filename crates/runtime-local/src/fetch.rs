@@ -1,3 +1,4 @@
+mod compression;
 mod signing;
 mod traffic_shaping;
 
@@ -6,7 +7,7 @@ use std::ops::Deref;
 use std::sync::Arc;
 use std::time::Duration;
 
-use anyhow::bail;
+use anyhow::{Context as _, bail};
 use bytes::Bytes;
 use engine::Schema;
 use engine_schema::GraphqlSubgraphId;
@@ -14,9 +15,9 @@ use event_queue::{SubgraphResponse, SubgraphResponseBuilder};
 use futures_util::Stream;
 use futures_util::{StreamExt, TryStreamExt};
 use fxhash::FxHashMap;
-use gateway_config::Config;
+use gateway_config::{Config, MtlsConfig, TransportConfig};
 use rapidhash::fast::RapidHashMap;
-use reqwest::{Certificate, Identity, RequestBuilder};
+use reqwest::{Certificate, ClientBuilder, Identity, NoProxy, Proxy, RequestBuilder, Url};
 use reqwest_eventsource::RequestBuilderExt;
 use runtime::fetch::{FetchError, FetchRequest, FetchResult, Fetcher, WebsocketRequest};
 
@@ -28,6 +29,7 @@ const ENABLE_HICKORY_DNS: bool = true;
 pub struct NativeFetcherInner {
     client: reqwest::Client,
     signer: signing::RequestSigner,
+    compression: compression::RequestCompression,
     dedicated_clients: FxHashMap<GraphqlSubgraphId, reqwest::Client>,
     traffic_shaping: traffic_shaping::TrafficShaping,
 }
@@ -50,13 +52,16 @@ impl NativeFetcher {
             .map(|s| (s.name(), s.id))
             .collect::<RapidHashMap<_, _>>();
         let signer = signing::RequestSigner::new(config, &name_to_id)?;
-        let dedicated_clients = generate_dedicated_http_clients(config, &name_to_id)?;
+        let dedicated_clients = generate_dedicated_http_clients(config, schema)?;
 
         Ok(NativeFetcher(Arc::new(NativeFetcherInner {
-            client: client_builder().build()?,
+            client: with_transport(client_builder(), &config.gateway.transport)
+                .context("invalid global transport configuration")?
+                .build()?,
             signer,
+            compression: compression::RequestCompression::new(config, &name_to_id),
             dedicated_clients,
-            traffic_shaping: TrafficShaping::new(&config.traffic_shaping),
+            traffic_shaping: TrafficShaping::new(config, &name_to_id),
         })))
     }
 }
@@ -84,10 +89,19 @@ struct FetchResponse {
 }
 
 impl NativeFetcherInner {
-    async fn execute(&self, fetch_req: FetchRequest<'_>) -> FetchResponse {
+    async fn execute(&self, mut fetch_req: FetchRequest<'_>) -> FetchResponse {
         let mut info = SubgraphResponse::builder();
 
+        // Compressed before signing so that a body digest matches what is actually sent.
+        if let Err(error) = self.compression.compress(&mut fetch_req) {
+            return FetchResponse {
+                result: Err(error),
+                info: None,
+            };
+        }
+
         let subgraph_id = fetch_req.subgraph_id;
+        let max_response_size = fetch_req.max_response_size;
         let request = into_reqwest(fetch_req);
 
         let request = match self.signer.sign(subgraph_id, request).await {
@@ -117,7 +131,10 @@ impl NativeFetcherInner {
         let headers = std::mem::take(resp.headers_mut());
         let extensions = std::mem::take(resp.extensions_mut());
         let version = resp.version();
-        let result = resp.bytes().await;
+        let result = match max_response_size {
+            Some(limit) => read_body_with_limit(resp, limit).await,
+            None => resp.bytes().await.map_err(Into::into),
+        };
 
         info.track_response();
 
@@ -125,7 +142,7 @@ impl NativeFetcherInner {
             Ok(bytes) => bytes,
             Err(e) => {
                 return FetchResponse {
-                    result: Err(e.into()),
+                    result: Err(e),
                     info: Some(info),
                 };
             }
@@ -156,7 +173,18 @@ impl Fetcher for NativeFetcher {
     ) -> (FetchResult<http::Response<Bytes>>, Option<SubgraphResponseBuilder>) {
         let FetchResponse { result, info } = self
             .traffic_shaping
-            .deduplicate(request, |request| async move { self.execute(request).await })
+            .deduplicate(request, |request| async move {
+                let _permit = match self.traffic_shaping.acquire_slot(request.subgraph_id).await {
+                    Ok(permit) => permit,
+                    Err(error) => {
+                        return FetchResponse {
+                            result: Err(error),
+                            info: None,
+                        };
+                    }
+                };
+                self.execute(request).await
+            })
             .await;
         (result, info)
     }
@@ -235,8 +263,32 @@ impl Fetcher for NativeFetcher {
     }
 }
 
+/// Reads the response body chunk by chunk, failing as soon as it exceeds the limit rather than
+/// buffering a response of arbitrary size.
+async fn read_body_with_limit(mut response: reqwest::Response, limit: usize) -> FetchResult<Bytes> {
+    if response.content_length().is_some_and(|length| length > limit as u64) {
+        return Err(FetchError::ResponseTooLarge(limit));
+    }
+
+    let mut body = bytes::BytesMut::new();
+
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > limit {
+            return Err(FetchError::ResponseTooLarge(limit));
+        }
+
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body.freeze())
+}
+
 fn into_reqwest(request: FetchRequest<'_>) -> reqwest::Request {
-    let mut req = reqwest::Request::new(request.method, request.url.into_owned());
+    let url = match unix_socket_path(&request.url) {
+        Some(_) => unix_socket_request_url(&request.url),
+        None => request.url.into_owned(),
+    };
+    let mut req = reqwest::Request::new(request.method, url);
     *req.headers_mut() = request.headers;
     *req.body_mut() = Some(request.body.into());
     *req.timeout_mut() = Some(request.timeout);
@@ -251,101 +303,164 @@ fn ws_to_reqwest(request: WebsocketRequest<'_, Bytes>) -> reqwest::Request {
     req
 }
 
-/// Creates a HashMap of dedicated HTTP clients for subgraphs that require mTLS.
+/// Creates a HashMap of dedicated HTTP clients for subgraphs that require mTLS, specific transport
+/// settings or are reached through a Unix socket.
 fn generate_dedicated_http_clients(
     config: &Config,
-    subgraph_name_to_id: &RapidHashMap<&str, GraphqlSubgraphId>,
+    schema: &Schema,
 ) -> anyhow::Result<FxHashMap<GraphqlSubgraphId, reqwest::Client>> {
     let mut clients = FxHashMap::default();
 
-    for (name, config) in &config.subgraphs {
-        let Some(mtls_config) = config.mtls.as_ref() else {
-            continue;
-        };
+    for subgraph in schema.graphql_subgraphs() {
+        let name = subgraph.name();
+        let subgraph_config = config.subgraphs.get(name);
+
+        let mtls_config = subgraph_config
+            .and_then(|config| config.mtls.as_ref())
+            .filter(|mtls_config| mtls_config.root.is_some() || mtls_config.identity.is_some());
+        let transport = subgraph_config.and_then(|config| config.transport.as_ref());
+        let unix_socket = unix_socket_path(subgraph.url());
 
-        if mtls_config.root.is_none() && mtls_config.identity.is_none() {
+        if mtls_config.is_none() && transport.is_none() && unix_socket.is_none() {
             continue;
         }
 
-        let id = match subgraph_name_to_id.get(name.as_str()) {
-            Some(id) => *id,
-            None => {
-                continue;
-            }
-        };
+        let transport = config.gateway.transport.merge(transport);
+        let mut builder = with_transport(client_builder(), &transport)
+            .with_context(|| format!("invalid transport configuration for subgraph `{name}`"))?;
 
-        let mut builder = client_builder().danger_accept_invalid_certs(mtls_config.accept_invalid_certs);
+        if let Some(path) = unix_socket {
+            builder = with_unix_socket(builder, path, name)?;
+        }
 
-        if let Some(ref root) = mtls_config.root {
-            let ca_cert_bytes = match std::fs::read(&root.certificate) {
-                Ok(bytes) => bytes,
-                Err(e) => {
-                    bail!(
-                        "failed to open root certificate `{}` for subgraph `{name}`: {e}",
-                        root.certificate.display()
-                    );
-                }
-            };
+        if let Some(mtls_config) = mtls_config {
+            builder = with_mtls(builder, mtls_config, name)?;
+        }
 
-            if root.is_bundle {
-                let certificates = match Certificate::from_pem_bundle(&ca_cert_bytes) {
-                    Ok(certificates) => certificates,
-                    Err(e) => {
-                        bail!(
-                            "failed to parse root certificate `{}` for subgraph `{name}`: {e}",
-                            root.certificate.display()
-                        );
-                    }
-                };
+        clients.insert(subgraph.id, builder.build()?);
+    }
 
-                for certificate in certificates {
-                    builder = builder.add_root_certificate(certificate);
-                }
-            } else {
-                let certificate = match Certificate::from_pem(&ca_cert_bytes) {
-                    Ok(certificate) => certificate,
-                    Err(e) => {
-                        bail!(
-                            "failed to parse root certificate `{}` for subgraph `{name}`: {e}",
-                            root.certificate.display()
-                        );
-                    }
-                };
+    Ok(clients)
+}
 
-                builder = builder.add_root_certificate(certificate);
-            };
+fn with_transport(mut builder: ClientBuilder, transport: &TransportConfig) -> anyhow::Result<ClientBuilder> {
+    if let Some(proxy_config) = &transport.proxy {
+        let mut proxy = Proxy::all(proxy_config.url.as_str())
+            .with_context(|| format!("invalid proxy URL `{}`", proxy_config.url))?;
+
+        if let Some(username) = &proxy_config.username {
+            proxy = proxy.basic_auth(username, proxy_config.password.as_deref().unwrap_or_default());
         }
 
-        let Some(ref identity_path) = mtls_config.identity else {
-            clients.insert(id, builder.build()?);
-            continue;
-        };
+        if !proxy_config.no_proxy.is_empty() {
+            proxy = proxy.no_proxy(NoProxy::from_string(&proxy_config.no_proxy.join(",")));
+        }
+
+        builder = builder.proxy(proxy);
+    }
+
+    if transport.http2_prior_knowledge.unwrap_or_default() {
+        builder = builder.http2_prior_knowledge();
+    }
+
+    Ok(builder)
+}
+
+#[cfg(unix)]
+fn with_unix_socket(builder: ClientBuilder, path: &str, _name: &str) -> anyhow::Result<ClientBuilder> {
+    Ok(builder.unix_socket(std::path::PathBuf::from(path)))
+}
+
+#[cfg(not(unix))]
+fn with_unix_socket(_builder: ClientBuilder, _path: &str, name: &str) -> anyhow::Result<ClientBuilder> {
+    bail!("subgraph `{name}` uses a Unix socket URL, which is not supported on this platform")
+}
+
+/// A `unix:///path/to/socket?path=/graphql` subgraph URL is reached through the Unix socket at
+/// the URL path. Its dedicated client ignores the request host, the request targets the `path`
+/// query parameter, `/` by default.
+fn unix_socket_path(url: &Url) -> Option<&str> {
+    (url.scheme() == "unix").then(|| url.path())
+}
+
+fn unix_socket_request_url(url: &Url) -> Url {
+    let mut request_url = Url::parse("http://localhost/").expect("valid URL");
 
-        let identity = match std::fs::read(identity_path) {
-            Ok(identity) => identity,
+    if let Some((_, path)) = url.query_pairs().find(|(name, _)| name == "path") {
+        request_url.set_path(&path);
+    }
+
+    request_url
+}
+
+fn with_mtls(mut builder: ClientBuilder, mtls_config: &MtlsConfig, name: &str) -> anyhow::Result<ClientBuilder> {
+    builder = builder.danger_accept_invalid_certs(mtls_config.accept_invalid_certs);
+
+    if let Some(ref root) = mtls_config.root {
+        let ca_cert_bytes = match std::fs::read(&root.certificate) {
+            Ok(bytes) => bytes,
             Err(e) => {
                 bail!(
-                    "failed to read identity file `{}` for subgraph `{name}`: {e}",
-                    identity_path.display()
+                    "failed to open root certificate `{}` for subgraph `{name}`: {e}",
+                    root.certificate.display()
                 );
             }
         };
 
-        let identity = match Identity::from_pem(&identity) {
-            Ok(identity) => identity,
-            Err(e) => {
-                bail!(
-                    "failed to parse identity file `{}` for subgraph `{name}`: {e}",
-                    identity_path.display()
-                )
+        if root.is_bundle {
+            let certificates = match Certificate::from_pem_bundle(&ca_cert_bytes) {
+                Ok(certificates) => certificates,
+                Err(e) => {
+                    bail!(
+                        "failed to parse root certificate `{}` for subgraph `{name}`: {e}",
+                        root.certificate.display()
+                    );
+                }
+            };
+
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
             }
-        };
+        } else {
+            let certificate = match Certificate::from_pem(&ca_cert_bytes) {
+                Ok(certificate) => certificate,
+                Err(e) => {
+                    bail!(
+                        "failed to parse root certificate `{}` for subgraph `{name}`: {e}",
+                        root.certificate.display()
+                    );
+                }
+            };
 
-        builder = builder.identity(identity);
-        clients.insert(id, builder.build()?);
+            builder = builder.add_root_certificate(certificate);
+        };
     }
 
-    Ok(clients)
+    let Some(ref identity_path) = mtls_config.identity else {
+        return Ok(builder);
+    };
+
+    let identity = match std::fs::read(identity_path) {
+        Ok(identity) => identity,
+        Err(e) => {
+            bail!(
+                "failed to read identity file `{}` for subgraph `{name}`: {e}",
+                identity_path.display()
+            );
+        }
+    };
+
+    let identity = match Identity::from_pem(&identity) {
+        Ok(identity) => identity,
+        Err(e) => {
+            bail!(
+                "failed to parse identity file `{}` for subgraph `{name}`: {e}",
+                identity_path.display()
+            )
+        }
+    };
+
+    Ok(builder.identity(identity))
 }
 
 #[derive(serde::Serialize)]
@@ -360,3 +475,21 @@ impl<T: serde::Serialize> graphql_ws_client::graphql::GraphqlOperation for Graph
         Ok(data)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unix_socket_urls() {
+        let url = Url::parse("unix:///var/run/products.sock?path=/graphql").unwrap();
+        assert_eq!(unix_socket_path(&url), Some("/var/run/products.sock"));
+        assert_eq!(unix_socket_request_url(&url).as_str(), "http://localhost/graphql");
+
+        let url = Url::parse("unix:///var/run/products.sock").unwrap();
+        assert_eq!(unix_socket_request_url(&url).as_str(), "http://localhost/");
+
+        let url = Url::parse("http://products.internal/graphql").unwrap();
+        assert_eq!(unix_socket_path(&url), None);
+    }
+}
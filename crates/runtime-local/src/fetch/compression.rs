@@ -0,0 +1,105 @@
+use std::io::Write;
+
+use engine_schema::GraphqlSubgraphId;
+use fxhash::FxHashMap;
+use gateway_config::{Config, RequestCompressionAlgorithm, RequestCompressionConfig};
+use rapidhash::fast::RapidHashMap;
+use runtime::fetch::{FetchError, FetchRequest};
+
+/// Compresses the request bodies of the subgraphs with a `request_compression` configuration.
+pub(super) struct RequestCompression {
+    subgraphs: FxHashMap<GraphqlSubgraphId, RequestCompressionConfig>,
+}
+
+impl RequestCompression {
+    pub fn new(config: &Config, subgraph_name_to_id: &RapidHashMap<&str, GraphqlSubgraphId>) -> Self {
+        let subgraphs = config
+            .subgraphs
+            .iter()
+            .filter_map(|(name, subgraph)| {
+                let id = *subgraph_name_to_id.get(name.as_str())?;
+                Some((id, subgraph.request_compression?))
+            })
+            .collect();
+
+        Self { subgraphs }
+    }
+
+    /// Replaces the body with its compressed version and sets the Content-Encoding header if the
+    /// subgraph has compression enabled and the body is big enough.
+    pub fn compress(&self, request: &mut FetchRequest<'_>) -> Result<(), FetchError> {
+        let Some(config) = self.subgraphs.get(&request.subgraph_id) else {
+            return Ok(());
+        };
+
+        if (request.body.len() as u64) < config.min_size.bytes() as u64 {
+            return Ok(());
+        }
+
+        let compressed = compress(config.algorithm, &request.body)
+            .map_err(|err| FetchError::Message(format!("Could not compress the request body: {err}")))?;
+
+        request.body = compressed.into();
+        request.headers.insert(
+            http::header::CONTENT_ENCODING,
+            http::HeaderValue::from_static(config.algorithm.content_encoding()),
+        );
+        request.headers.remove(http::header::CONTENT_LENGTH);
+
+        Ok(())
+    }
+}
+
+fn compress(algorithm: RequestCompressionAlgorithm, body: &[u8]) -> std::io::Result<Vec<u8>> {
+    match algorithm {
+        RequestCompressionAlgorithm::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(body)?;
+            encoder.finish()
+        }
+        RequestCompressionAlgorithm::Zstd => zstd::bulk::compress(body, zstd::DEFAULT_COMPRESSION_LEVEL),
+        RequestCompressionAlgorithm::Brotli => {
+            // Favour speed over ratio, the body is compressed on every request.
+            let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 4, 22);
+            encoder.write_all(body)?;
+            Ok(encoder.into_inner())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    const BODY: &[u8] = br#"{"query":"query($representations:[_Any!]!){_entities(representations:$representations){...on Product{name}}}","variables":{"representations":[{"__typename":"Product","upc":"1"},{"__typename":"Product","upc":"2"}]}}"#;
+
+    fn decompress(algorithm: RequestCompressionAlgorithm, body: &[u8]) -> Vec<u8> {
+        let mut output = Vec::new();
+        match algorithm {
+            RequestCompressionAlgorithm::Gzip => {
+                flate2::read::GzDecoder::new(body).read_to_end(&mut output).unwrap();
+            }
+            RequestCompressionAlgorithm::Zstd => {
+                output = zstd::decode_all(body).unwrap();
+            }
+            RequestCompressionAlgorithm::Brotli => {
+                brotli::Decompressor::new(body, 4096).read_to_end(&mut output).unwrap();
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn roundtrip() {
+        for algorithm in [
+            RequestCompressionAlgorithm::Gzip,
+            RequestCompressionAlgorithm::Zstd,
+            RequestCompressionAlgorithm::Brotli,
+        ] {
+            let compressed = compress(algorithm, BODY).unwrap();
+            assert_eq!(decompress(algorithm, &compressed), BODY, "{algorithm:?}");
+        }
+    }
+}
@@ -1,23 +1,62 @@
-use std::{future::Future, hash::Hash, sync::Arc};
+use std::{
+    future::Future,
+    hash::Hash,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
 
 use bytes::Bytes;
 use dashmap::DashMap;
 use engine_schema::GraphqlSubgraphId;
-use gateway_config::TrafficShapingConfig;
-use runtime::fetch::FetchRequest;
+use fxhash::FxHashMap;
+use gateway_config::Config;
+use rapidhash::fast::RapidHashMap;
+use runtime::fetch::{FetchError, FetchRequest};
+use tokio::sync::{Semaphore, SemaphorePermit};
 
 use crate::fetch::FetchResponse;
 
 pub struct TrafficShaping {
-    config: TrafficShapingConfig,
+    inflight_deduplication: bool,
     inflight: DashMap<Key, InflightRequest, rapidhash::fast::RandomState>,
+    concurrency_limits: FxHashMap<GraphqlSubgraphId, ConcurrencyLimit>,
 }
 
 impl TrafficShaping {
-    pub fn new(config: &TrafficShapingConfig) -> Self {
+    pub fn new(config: &Config, subgraph_name_to_id: &RapidHashMap<&str, GraphqlSubgraphId>) -> Self {
+        let global = &config.traffic_shaping;
+        let mut concurrency_limits = FxHashMap::default();
+
+        for (name, id) in subgraph_name_to_id {
+            let subgraph = config
+                .subgraphs
+                .get(*name)
+                .and_then(|subgraph| subgraph.traffic_shaping)
+                .unwrap_or_default();
+
+            let Some(max_concurrent_requests) = subgraph.max_concurrent_requests.or(global.max_concurrent_requests)
+            else {
+                continue;
+            };
+
+            concurrency_limits.insert(
+                *id,
+                ConcurrencyLimit {
+                    semaphore: Semaphore::new(max_concurrent_requests.get()),
+                    max_queue_size: subgraph.max_queue_size.unwrap_or(global.max_queue_size),
+                    queue_timeout: subgraph.queue_timeout.or(global.queue_timeout),
+                    queued: AtomicUsize::new(0),
+                },
+            );
+        }
+
         Self {
-            config: config.clone(),
+            inflight_deduplication: global.inflight_deduplication,
             inflight: DashMap::default(),
+            concurrency_limits,
         }
     }
 
@@ -29,7 +68,7 @@ impl TrafficShaping {
     where
         F: Future<Output = FetchResponse> + Send,
     {
-        if !self.config.inflight_deduplication || request.is_mutation {
+        if !self.inflight_deduplication || request.is_mutation {
             return f(request).await;
         }
         let key = Key(Arc::new(RequestKey::from(&request)));
@@ -54,6 +93,60 @@ impl TrafficShaping {
             Err(cell) => cell.get().unwrap().clone(),
         }
     }
+
+    /// Waits for a free slot if the subgraph has a concurrency limit. The request is rejected if
+    /// the wait queue is full or if it waited for too long. The slot is released once the returned
+    /// permit is dropped.
+    pub async fn acquire_slot(
+        &self,
+        subgraph_id: GraphqlSubgraphId,
+    ) -> Result<Option<SemaphorePermit<'_>>, FetchError> {
+        match self.concurrency_limits.get(&subgraph_id) {
+            Some(limit) => limit.acquire().await.map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+struct ConcurrencyLimit {
+    semaphore: Semaphore,
+    max_queue_size: usize,
+    queue_timeout: Option<Duration>,
+    queued: AtomicUsize,
+}
+
+impl ConcurrencyLimit {
+    async fn acquire(&self) -> Result<SemaphorePermit<'_>, FetchError> {
+        if let Ok(permit) = self.semaphore.try_acquire() {
+            return Ok(permit);
+        }
+
+        if self.queued.fetch_add(1, Ordering::AcqRel) >= self.max_queue_size {
+            self.queued.fetch_sub(1, Ordering::AcqRel);
+            return Err(FetchError::Overloaded);
+        }
+
+        // Leaves the queue even if the request is cancelled while waiting.
+        let _queued = QueuedGuard(&self.queued);
+
+        let permit = match self.queue_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, self.semaphore.acquire())
+                .await
+                .map_err(|_| FetchError::Overloaded)?,
+            None => self.semaphore.acquire().await,
+        };
+
+        // The semaphore is never closed.
+        permit.map_err(|_| FetchError::Overloaded)
+    }
+}
+
+struct QueuedGuard<'a>(&'a AtomicUsize);
+
+impl Drop for QueuedGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
 }
 
 struct InflightRequest {
@@ -201,6 +294,7 @@ mod tests {
             url: Cow::Owned(url.parse().unwrap()),
             body: Bytes::copy_from_slice(body),
             timeout: Duration::from_secs(30),
+            max_response_size: None,
         })
             .into()
     }
@@ -821,4 +915,45 @@ mod tests {
         let unique_hashes: std::collections::HashSet<_> = hashes.iter().collect();
         assert_eq!(hashes.len(), unique_hashes.len(), "All method hashes should be unique");
     }
+
+    fn concurrency_limit(max_concurrent_requests: usize, max_queue_size: usize) -> ConcurrencyLimit {
+        ConcurrencyLimit {
+            semaphore: Semaphore::new(max_concurrent_requests),
+            max_queue_size,
+            queue_timeout: Some(Duration::from_millis(20)),
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_when_queue_is_full() {
+        let limit = concurrency_limit(1, 0);
+
+        let permit = limit.acquire().await.unwrap();
+        assert!(matches!(limit.acquire().await, Err(FetchError::Overloaded)));
+
+        drop(permit);
+        assert!(limit.acquire().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn queued_request_times_out() {
+        let limit = concurrency_limit(1, 1);
+
+        let _permit = limit.acquire().await.unwrap();
+        assert!(matches!(limit.acquire().await, Err(FetchError::Overloaded)));
+        assert_eq!(limit.queued.load(Ordering::Acquire), 0);
+    }
+
+    #[tokio::test]
+    async fn queued_request_gets_released_slot() {
+        let limit = concurrency_limit(1, 1);
+
+        let permit = limit.acquire().await.unwrap();
+        let (result, _) = tokio::join!(limit.acquire(), async move {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            drop(permit);
+        });
+        assert!(result.is_ok());
+    }
 }
@@ -0,0 +1,146 @@
+mod storage;
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use operation_checks::SchemaCoordinateUsage;
+use runtime::field_usage::OperationFieldUsage;
+use tokio::sync::oneshot;
+
+pub use storage::FieldUsageStorage;
+
+/// Accumulates the usage of executed operations in memory and periodically merges it into a
+/// [FieldUsageStorage]. Whatever is still pending when the collector is dropped is flushed one
+/// last time in the background.
+pub struct LocalFieldUsageCollector {
+    pending: Arc<Mutex<SchemaCoordinateUsage>>,
+    // Dropping the sender stops the flush task after a final flush.
+    _shutdown: oneshot::Sender<()>,
+}
+
+impl LocalFieldUsageCollector {
+    /// Starts collecting usage. Must be called within a Tokio runtime.
+    pub fn new(storage: FieldUsageStorage, flush_interval: Duration) -> Self {
+        let pending = Arc::new(Mutex::new(SchemaCoordinateUsage::default()));
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel();
+
+        tokio::spawn(flush_periodically(
+            storage,
+            pending.clone(),
+            flush_interval,
+            shutdown_receiver,
+        ));
+
+        LocalFieldUsageCollector {
+            pending,
+            _shutdown: shutdown_sender,
+        }
+    }
+}
+
+impl runtime::field_usage::FieldUsageCollector for LocalFieldUsageCollector {
+    fn record(&self, usage: OperationFieldUsage) {
+        add(&mut self.pending.lock().unwrap(), usage);
+    }
+}
+
+fn add(pending: &mut SchemaCoordinateUsage, usage: OperationFieldUsage) {
+    fn add_counts(into: &mut BTreeMap<String, u64>, from: BTreeMap<String, u64>) {
+        for (coordinate, count) in from {
+            let entry = into.entry(coordinate).or_insert(0);
+            *entry = entry.saturating_add(count);
+        }
+    }
+
+    add_counts(&mut pending.fields, usage.fields);
+    add_counts(&mut pending.arguments, usage.arguments);
+    add_counts(&mut pending.enum_values, usage.enum_values);
+    add_counts(
+        &mut pending.arguments_with_defaults_left_out,
+        usage.arguments_with_defaults_left_out,
+    );
+    add_counts(&mut pending.type_conditions, usage.type_conditions);
+}
+
+async fn flush_periodically(
+    storage: FieldUsageStorage,
+    pending: Arc<Mutex<SchemaCoordinateUsage>>,
+    flush_interval: Duration,
+    mut shutdown: oneshot::Receiver<()>,
+) {
+    let mut interval = tokio::time::interval(flush_interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    // The first tick completes immediately.
+    interval.tick().await;
+
+    loop {
+        let is_shutting_down = tokio::select! {
+            _ = interval.tick() => false,
+            _ = &mut shutdown => true,
+        };
+
+        let usage = std::mem::take(&mut *pending.lock().unwrap());
+
+        if !usage.is_empty()
+            && let Err(err) = storage.merge(&usage).await
+        {
+            tracing::error!("Failed to store field usage: {err}");
+        }
+
+        if is_shutting_down {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use runtime::field_usage::FieldUsageCollector as _;
+
+    use super::*;
+
+    fn usage(fields: &[&str], enum_values: &[&str]) -> OperationFieldUsage {
+        let counts = |coordinates: &[&str]| coordinates.iter().map(|c| (c.to_string(), 1)).collect();
+
+        OperationFieldUsage {
+            fields: counts(fields),
+            enum_values: counts(enum_values),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn recorded_usage_is_flushed_to_the_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("field-usage.json");
+
+        let collector = LocalFieldUsageCollector::new(FieldUsageStorage::File(path.clone()), Duration::from_secs(3600));
+        collector.record(usage(&["Query.pets", "PetFilter.kind"], &["PetKind.CAT"]));
+        collector.record(usage(&["Query.pets"], &["PetKind.DOG"]));
+
+        // Dropping the collector triggers the final flush.
+        drop(collector);
+
+        let mut stored = SchemaCoordinateUsage::default();
+        for _ in 0..100 {
+            stored = FieldUsageStorage::File(path.clone()).load().await.unwrap();
+            if !stored.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(
+            stored.fields.into_iter().collect::<Vec<_>>(),
+            [("PetFilter.kind".to_string(), 1), ("Query.pets".to_string(), 2)]
+        );
+        assert_eq!(
+            stored.enum_values.into_iter().collect::<Vec<_>>(),
+            [("PetKind.CAT".to_string(), 1), ("PetKind.DOG".to_string(), 1)]
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use operation_checks::SchemaCoordinateUsage;
+
+#[cfg(feature = "redis")]
+use crate::redis::Pool;
+
+/// Where the aggregated field usage is persisted. Counts are only ever added to, so several
+/// gateway instances can share the same storage.
+pub enum FieldUsageStorage {
+    /// A JSON file, only suited to a single gateway instance.
+    File(PathBuf),
+    /// One Redis hash per kind of schema coordinate.
+    #[cfg(feature = "redis")]
+    Redis { pool: Pool, key_prefix: String },
+}
+
+impl FieldUsageStorage {
+    /// Loads all the usage recorded so far.
+    pub async fn load(&self) -> anyhow::Result<SchemaCoordinateUsage> {
+        match self {
+            FieldUsageStorage::File(path) => {
+                let path = path.clone();
+                tokio::task::spawn_blocking(move || read_file(&path)).await?
+            }
+            #[cfg(feature = "redis")]
+            FieldUsageStorage::Redis { pool, key_prefix } => redis::load(pool, key_prefix).await,
+        }
+    }
+
+    /// Adds the counts of `usage` to the stored usage.
+    pub async fn merge(&self, usage: &SchemaCoordinateUsage) -> anyhow::Result<()> {
+        match self {
+            FieldUsageStorage::File(path) => {
+                let (path, usage) = (path.clone(), usage.clone());
+                tokio::task::spawn_blocking(move || merge_file(&path, &usage)).await?
+            }
+            #[cfg(feature = "redis")]
+            FieldUsageStorage::Redis { pool, key_prefix } => redis::merge(pool, key_prefix, usage).await,
+        }
+    }
+}
+
+/// The read-modify-write happens under a lock file, so that concurrent merges, from another
+/// gateway process or from the collector of a previous schema still flushing, don't lose counts.
+fn merge_file(path: &Path, usage: &SchemaCoordinateUsage) -> anyhow::Result<()> {
+    let lock_path = path.with_extension("json.lock");
+    let mut lock_file =
+        fslock::LockFile::open(&lock_path).with_context(|| format!("opening {}", lock_path.display()))?;
+
+    lock_file
+        .lock()
+        .with_context(|| format!("locking {}", lock_path.display()))?;
+
+    let mut stored = read_file(path)?;
+    stored.merge(usage);
+
+    let contents = serde_json::to_vec_pretty(&stored)?;
+    let tmp_path = path.with_extension("json.tmp");
+
+    std::fs::write(&tmp_path, contents).with_context(|| format!("writing {}", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, path).with_context(|| format!("writing {}", path.display()))
+}
+
+fn read_file(path: &Path) -> anyhow::Result<SchemaCoordinateUsage> {
+    match std::fs::read(path) {
+        Ok(contents) => {
+            serde_json::from_slice(&contents).with_context(|| format!("invalid field usage in {}", path.display()))
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(SchemaCoordinateUsage::default()),
+        Err(err) => Err(err).with_context(|| format!("reading {}", path.display())),
+    }
+}
+
+#[cfg(feature = "redis")]
+mod redis {
+    use deadpool::managed::Object;
+    use operation_checks::SchemaCoordinateUsage;
+    use redis::AsyncCommands;
+
+    use crate::redis::{Manager, Pool};
+
+    const FIELDS: &str = "fields";
+    const ARGUMENTS: &str = "arguments";
+    const ENUM_VALUES: &str = "enum-values";
+    const ARGUMENTS_WITH_DEFAULTS_LEFT_OUT: &str = "arguments-with-defaults-left-out";
+    const TYPE_CONDITIONS: &str = "type-conditions";
+
+    pub(super) async fn load(pool: &Pool, key_prefix: &str) -> anyhow::Result<SchemaCoordinateUsage> {
+        let mut connection = connection(pool).await?;
+        let key = |suffix: &str| format!("{key_prefix}-{suffix}");
+
+        Ok(SchemaCoordinateUsage {
+            fields: connection.hgetall(key(FIELDS)).await?,
+            arguments: connection.hgetall(key(ARGUMENTS)).await?,
+            enum_values: connection.hgetall(key(ENUM_VALUES)).await?,
+            arguments_with_defaults_left_out: connection.hgetall(key(ARGUMENTS_WITH_DEFAULTS_LEFT_OUT)).await?,
+            type_conditions: connection.hgetall(key(TYPE_CONDITIONS)).await?,
+        })
+    }
+
+    pub(super) async fn merge(pool: &Pool, key_prefix: &str, usage: &SchemaCoordinateUsage) -> anyhow::Result<()> {
+        let mut connection = connection(pool).await?;
+        let mut pipe = redis::pipe();
+
+        for (suffix, counts) in [
+            (FIELDS, &usage.fields),
+            (ARGUMENTS, &usage.arguments),
+            (ENUM_VALUES, &usage.enum_values),
+            (
+                ARGUMENTS_WITH_DEFAULTS_LEFT_OUT,
+                &usage.arguments_with_defaults_left_out,
+            ),
+            (TYPE_CONDITIONS, &usage.type_conditions),
+        ] {
+            let key = format!("{key_prefix}-{suffix}");
+
+            for (coordinate, count) in counts {
+                pipe.hincr(&key, coordinate, *count).ignore();
+            }
+        }
+
+        pipe.query_async::<()>(&mut *connection).await?;
+
+        Ok(())
+    }
+
+    async fn connection(pool: &Pool) -> anyhow::Result<Object<Manager>> {
+        match pool.get().await {
+            Ok(connection) => Ok(connection),
+            Err(error) => {
+                tracing::error!("error fetching a Redis connection: {error}");
+                anyhow::bail!("error fetching a redis connection: {error}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(field: &str, count: u64) -> SchemaCoordinateUsage {
+        let mut usage = SchemaCoordinateUsage::default();
+        usage.fields.insert(field.to_string(), count);
+        usage
+    }
+
+    #[tokio::test]
+    async fn file_merge_adds_to_the_stored_counts() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FieldUsageStorage::File(dir.path().join("field-usage.json"));
+
+        assert!(storage.load().await.unwrap().is_empty());
+
+        storage.merge(&usage("Query.pets", 2)).await.unwrap();
+        storage.merge(&usage("Query.pets", 3)).await.unwrap();
+        storage.merge(&usage("Pet.name", 1)).await.unwrap();
+
+        let stored = storage.load().await.unwrap();
+
+        assert_eq!(
+            stored.fields.into_iter().collect::<Vec<_>>(),
+            [("Pet.name".to_string(), 1), ("Query.pets".to_string(), 5)]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_file_merges_are_not_lost() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("field-usage.json");
+
+        let merges = (0..20).map(|_| {
+            let storage = FieldUsageStorage::File(path.clone());
+            tokio::spawn(async move { storage.merge(&usage("Query.pets", 1)).await })
+        });
+
+        for merge in merges.collect::<Vec<_>>() {
+            merge.await.unwrap().unwrap();
+        }
+
+        let stored = FieldUsageStorage::File(path).load().await.unwrap();
+
+        assert_eq!(stored.fields["Query.pets"], 20);
+    }
+}
@@ -1,5 +1,6 @@
 mod entity_cache;
 mod fetch;
+pub mod field_usage;
 pub mod operation_cache;
 pub mod rate_limiting;
 #[cfg(feature = "redis")]
@@ -31,7 +31,7 @@ pub struct ReqwestParts<'a> {
     pub headers: http::HeaderMap,
 }
 
-pub trait EngineHooksExtension<OperationContext>: Send + Sync + 'static {
+pub trait EngineHooksExtension<RequestContext, OperationContext>: Send + Sync + 'static {
     fn on_graphql_subgraph_request<'r>(
         &self,
         context: OperationContext,
@@ -45,4 +45,13 @@ pub trait EngineHooksExtension<OperationContext>: Send + Sync + 'static {
         subgraph: VirtualSubgraph<'_>,
         headers: http::HeaderMap,
     ) -> impl Future<Output = Result<http::HeaderMap, GraphqlError>> + Send;
+
+    /// Evaluates the `@policy` policies used by an operation with the hooks extension, returning
+    /// whether each of them is granted in the same order. Without a hooks extension able to
+    /// evaluate them, all policies are denied.
+    fn authorize_policies(
+        &self,
+        context: RequestContext,
+        policies: &[&str],
+    ) -> impl Future<Output = Result<Vec<bool>, GraphqlError>> + Send;
 }
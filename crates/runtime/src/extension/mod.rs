@@ -23,7 +23,7 @@ pub trait EngineExtensions<RequestContext, OperationContext>:
     + SelectionSetResolverExtension
     + ResolverExtension<OperationContext>
     + ContractsExtension
-    + EngineHooksExtension<OperationContext>
+    + EngineHooksExtension<RequestContext, OperationContext>
     + Send
     + Sync
     + 'static
@@ -3,7 +3,7 @@ use std::{borrow::Cow, future::Future, time::Duration};
 use bytes::Bytes;
 use engine_schema::GraphqlSubgraphId;
 use event_queue::SubgraphResponseBuilder;
-use futures_util::{stream::BoxStream, Stream, StreamExt, TryFutureExt};
+use futures_util::{Stream, StreamExt, TryFutureExt, stream::BoxStream};
 use http::Response;
 
 #[derive(Debug, Clone, thiserror::Error)]
@@ -16,11 +16,29 @@ pub enum FetchError {
     MessageSigningFailed(String),
     #[error("Request error: {0}")]
     Reqwest(String),
+    #[error("Request timed out: {0}")]
+    Timeout(String),
+    #[error("Connection error: {0}")]
+    Connection(String),
+    #[error("Response body exceeds the limit of {0} bytes")]
+    ResponseTooLarge(usize),
+    #[error("Subgraph is overloaded, too many concurrent requests")]
+    Overloaded,
 }
 
 impl From<reqwest::Error> for FetchError {
     fn from(error: reqwest::Error) -> Self {
-        FetchError::Reqwest(format!("{:?}", error.without_url()))
+        let is_timeout = error.is_timeout();
+        let is_connection = error.is_connect() || error.is_request();
+        let message = format!("{:?}", error.without_url());
+
+        if is_timeout {
+            FetchError::Timeout(message)
+        } else if is_connection {
+            FetchError::Connection(message)
+        } else {
+            FetchError::Reqwest(message)
+        }
     }
 }
 
@@ -58,6 +76,8 @@ pub struct FetchRequest<'a> {
     pub headers: http::HeaderMap,
     pub body: Bytes,
     pub timeout: Duration,
+    /// Maximum size of the response body, the request fails if the subgraph sends more.
+    pub max_response_size: Option<usize>,
 }
 
 #[derive(Clone)]
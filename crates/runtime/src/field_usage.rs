@@ -0,0 +1,38 @@
+use std::collections::BTreeMap;
+
+/// Records the operations executed by the engine, so that the usage of fields, arguments and
+/// enum values can be aggregated over time and taken into account by operation checks.
+pub trait FieldUsageCollector: Send + Sync {
+    /// Whether usage is collected at all. The engine skips computing the [OperationFieldUsage]
+    /// otherwise.
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    /// Called once per prepared operation, with the usage computed from the bound operation and
+    /// its variables.
+    fn record(&self, usage: OperationFieldUsage);
+}
+
+impl FieldUsageCollector for () {
+    fn is_enabled(&self) -> bool {
+        false
+    }
+
+    fn record(&self, _usage: OperationFieldUsage) {}
+}
+
+/// Schema coordinates used by a single operation, with their number of occurrences.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OperationFieldUsage {
+    /// "Type.field", and "InputObject.field" for the input object fields of argument values
+    pub fields: BTreeMap<String, u64>,
+    /// "Type.field.argument"
+    pub arguments: BTreeMap<String, u64>,
+    /// "Enum.VALUE", whether provided inline or through variables
+    pub enum_values: BTreeMap<String, u64>,
+    /// "Type.field.argument" of required arguments with a default that were left out
+    pub arguments_with_defaults_left_out: BTreeMap<String, u64>,
+    /// "parent_type_name.implementer_type_name"
+    pub type_conditions: BTreeMap<String, u64>,
+}
@@ -3,6 +3,7 @@
 pub mod entity_cache;
 pub mod extension;
 pub mod fetch;
+pub mod field_usage;
 pub mod operation_cache;
 pub mod rate_limiting;
 pub mod trusted_documents_client;
@@ -12,6 +12,8 @@ use crate::{
 pub struct EngineMetrics {
     graph_version: Option<String>,
     operation_latency: Histogram<u64>,
+    operation_estimated_cost: Histogram<u64>,
+    operation_actual_cost: Histogram<u64>,
     subgraph_latency: Histogram<u64>,
     subgraph_retries: Counter<u64>,
     subgraph_request_body_size: Histogram<u64>,
@@ -19,6 +21,7 @@ pub struct EngineMetrics {
     subgraph_requests_inflight: UpDownCounter<i64>,
     subgraph_cache_hits: Counter<u64>,
     subgraph_cache_partial_hits: Counter<u64>,
+    subgraph_cache_stale_hits: Counter<u64>,
     subgraph_cache_misses: Counter<u64>,
     operation_cache_hits: Counter<u64>,
     operation_cache_misses: Counter<u64>,
@@ -35,6 +38,12 @@ pub struct GraphqlRequestMetricsAttributes {
     pub client: Option<Client>,
 }
 
+#[derive(Debug)]
+pub struct OperationCostAttributes {
+    pub operation: GraphqlOperationAttributes,
+    pub client: Option<Client>,
+}
+
 #[derive(Debug)]
 pub struct SubgraphRequestDurationAttributes {
     pub name: String,
@@ -94,6 +103,8 @@ impl EngineMetrics {
                 .u64_histogram("graphql.operation.duration")
                 .with_unit("ms")
                 .build(),
+            operation_estimated_cost: meter.u64_histogram("graphql.operation.cost.estimated").build(),
+            operation_actual_cost: meter.u64_histogram("graphql.operation.cost.actual").build(),
             subgraph_latency: meter
                 .u64_histogram("graphql.subgraph.request.duration")
                 .with_unit("ms")
@@ -104,6 +115,7 @@ impl EngineMetrics {
             subgraph_requests_inflight: meter.i64_up_down_counter("graphql.subgraph.request.inflight").build(),
             subgraph_cache_hits: meter.u64_counter("graphql.subgraph.request.cache.hit").build(),
             subgraph_cache_partial_hits: meter.u64_counter("graphql.subgraph.request.cache.partial_hit").build(),
+            subgraph_cache_stale_hits: meter.u64_counter("graphql.subgraph.request.cache.stale_hit").build(),
             subgraph_cache_misses: meter.u64_counter("graphql.subgraph.request.cache.miss").build(),
             operation_cache_hits: meter.u64_counter("graphql.operation.cache.hit").build(),
             operation_cache_misses: meter.u64_counter("graphql.operation.cache.miss").build(),
@@ -167,6 +179,30 @@ impl EngineMetrics {
         self.operation_latency.record(latency.as_millis() as u64, &attributes);
     }
 
+    pub fn record_operation_cost(
+        &self,
+        OperationCostAttributes { operation, client }: OperationCostAttributes,
+        estimated: usize,
+        actual: usize,
+    ) {
+        let mut attributes = self.create_operation_key_values(operation);
+
+        if let Some(version) = self.graph_version.clone() {
+            attributes.push(KeyValue::new("grafbase.graph.version", version))
+        }
+
+        if let Some(client) = client {
+            attributes.push(KeyValue::new("http.headers.x-grafbase-client-name", client.name));
+
+            if let Some(version) = client.version {
+                attributes.push(KeyValue::new("http.headers.x-grafbase-client-version", version));
+            }
+        }
+
+        self.operation_estimated_cost.record(estimated as u64, &attributes);
+        self.operation_actual_cost.record(actual as u64, &attributes);
+    }
+
     pub fn record_subgraph_request_duration(
         &self,
         SubgraphRequestDurationAttributes {
@@ -244,6 +280,11 @@ impl EngineMetrics {
         self.subgraph_cache_partial_hits.add(1, &attributes);
     }
 
+    pub fn record_subgraph_cache_stale_hit(&self, subgraph_name: String) {
+        let attributes = [KeyValue::new("graphql.subgraph.name", subgraph_name)];
+        self.subgraph_cache_stale_hits.add(1, &attributes);
+    }
+
     pub fn record_subgraph_cache_miss(&self, SubgraphCacheMissAttributes { name }: SubgraphCacheMissAttributes) {
         let attributes = [KeyValue::new("graphql.subgraph.name", name)];
         self.subgraph_cache_misses.add(1, &attributes);
@@ -39,6 +39,7 @@ impl SubgraphRequestSpanBuilder<'_> {
             "subgraph.name" = self.subgraph_name,
             "graphql.operation.type" = self.operation_type,
             "graphql.operation.document" = self.sanitized_query,
+            "subgraph.request.retry_count" = Empty,
             // "Describes a class of error the operation ended with."
             "error.type" = Empty,
             // Response
@@ -110,6 +111,12 @@ impl SubgraphHttpRequestSpan {
 }
 
 impl SubgraphGraphqlRequestSpan {
+    pub fn record_retry_count(&self, count: usize) {
+        if count > 0 {
+            self.record("subgraph.request.retry_count", count);
+        }
+    }
+
     pub fn record_graphql_response_status(&self, status: SubgraphResponseStatus) {
         match status {
             SubgraphResponseStatus::WellFormedGraphqlResponse(status) => {
@@ -0,0 +1,94 @@
+use std::fmt::Write as _;
+
+use grafbase_sdk::{
+    ResolverExtension,
+    host_io::{
+        grpc::GrpcClient,
+        http::{self, HttpRequest},
+        kafka::{self, KafkaProducerConfig},
+        nats,
+        postgres::{Pool, Query},
+    },
+    types::{
+        AuthorizedOperationContext, Configuration, Error, ResolvedField, Response, SubgraphHeaders, SubgraphSchema,
+        Variables,
+    },
+};
+
+/// Sends the `input` argument of the field through the protocol of the `@call` directive and
+/// resolves the field to what came back.
+#[derive(ResolverExtension)]
+struct HostIo;
+
+#[derive(serde::Deserialize)]
+struct Call {
+    protocol: String,
+}
+
+#[derive(serde::Deserialize)]
+struct Arguments {
+    input: String,
+}
+
+impl ResolverExtension for HostIo {
+    fn new(_: Vec<SubgraphSchema>, _: Configuration) -> Result<Self, Error> {
+        Ok(Self)
+    }
+
+    fn resolve(
+        &mut self,
+        _: &AuthorizedOperationContext,
+        prepared: &[u8],
+        _: SubgraphHeaders,
+        variables: Variables,
+    ) -> Result<Response, Error> {
+        let field = ResolvedField::try_from(prepared)?;
+        let Call { protocol } = field.directive().arguments()?;
+        let Arguments { input } = field.arguments(&variables)?;
+
+        let output = match protocol.as_str() {
+            "http" => {
+                let request = HttpRequest::post("http://localhost:8080/echo".parse().unwrap()).body(input.into_bytes());
+                let response = http::execute(request)?;
+                String::from_utf8_lossy(response.body()).into_owned()
+            }
+            "nats" => {
+                let client = nats::connect(["nats://localhost:4222"])?;
+                client.publish("events", &input)?;
+                let reply = client.request_bytes("echo", input.as_bytes(), None)?;
+                String::from_utf8_lossy(&reply).into_owned()
+            }
+            "kafka" => {
+                let producer = kafka::producer("events", ["localhost:9092"], "events", KafkaProducerConfig::default())?;
+                producer.produce(Some("input"), input.as_bytes())?;
+                String::from("produced")
+            }
+            "postgres" => {
+                let pool = Pool::connect("users", "postgres://localhost:5432/users")?;
+                let connection = pool.acquire()?;
+
+                let mut builder = Query::builder();
+                write!(builder, "SELECT name FROM users WHERE id = $1").unwrap();
+                builder.bind(input);
+
+                let mut names = Vec::new();
+                for columns in builder.finalize().fetch(&connection)? {
+                    for value in columns {
+                        names.push(value?.as_str()?.unwrap_or("NULL").to_owned());
+                    }
+                }
+                names.join(",")
+            }
+            "grpc" => {
+                let client = GrpcClient::new("http://localhost:50051")?;
+                let response = client
+                    .unary(input.as_bytes(), "echo.Echo", "Say", &[], None)
+                    .map_err(|status| Error::new(status.message()))?;
+                String::from_utf8_lossy(response.message()).into_owned()
+            }
+            protocol => return Err(Error::new(format!("Unknown protocol {protocol}"))),
+        };
+
+        Ok(Response::data(output))
+    }
+}
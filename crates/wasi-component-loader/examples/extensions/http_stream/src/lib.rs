@@ -0,0 +1,66 @@
+use grafbase_sdk::{
+    HooksExtension,
+    host_io::http::{self, HttpError, HttpRequest, Method, Url},
+    types::{Configuration, Error, ErrorResponse, GatewayHeaders},
+};
+
+/// Uploads a body in chunks to the URL of the `x-upload-url` header, or reads the body of the
+/// URL of the `x-download-url` header line by line. The outcome is reported in the `x-status` and
+/// `x-body` headers, or in the `x-error` header.
+#[derive(HooksExtension)]
+struct HttpStream;
+
+impl HooksExtension for HttpStream {
+    fn new(_: Configuration) -> Result<Self, Error> {
+        Ok(Self)
+    }
+
+    #[allow(refining_impl_trait)]
+    fn on_request(&mut self, _: &str, _: Method, headers: &mut GatewayHeaders) -> Result<(), ErrorResponse> {
+        let header_url = |name: &str| -> Option<Url> { Some(headers.get(name)?.to_str().ok()?.parse().unwrap()) };
+
+        let result = if let Some(url) = header_url("x-upload-url") {
+            upload(url)
+        } else if let Some(url) = header_url("x-download-url") {
+            download(url)
+        } else {
+            return Ok(());
+        };
+
+        match result {
+            Ok((status, body)) => {
+                headers.append("x-status", status.to_string().as_str());
+                headers.append("x-body", body.as_str());
+            }
+            Err(err) => headers.append("x-error", err.to_string().as_str()),
+        }
+
+        Ok(())
+    }
+}
+
+fn upload(url: Url) -> Result<(u16, String), HttpError> {
+    let writer = HttpRequest::post(url).send_streaming()?;
+
+    for chunk in ["first,", "second,", "third"] {
+        writer.write(chunk.as_bytes())?;
+    }
+
+    let response = writer.finish()?;
+    let status = response.status().as_u16();
+    let body = response.into_bytes()?;
+
+    Ok((status, String::from_utf8_lossy(&body).into_owned()))
+}
+
+fn download(url: Url) -> Result<(u16, String), HttpError> {
+    let mut response = http::execute_streaming(HttpRequest::get(url))?;
+    let status = response.status().as_u16();
+    let mut lines = Vec::new();
+
+    while let Some(line) = response.next_line()? {
+        lines.push(String::from_utf8_lossy(&line).into_owned());
+    }
+
+    Ok((status, lines.join(",")))
+}
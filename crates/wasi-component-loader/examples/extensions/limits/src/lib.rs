@@ -0,0 +1,33 @@
+use grafbase_sdk::{
+    HooksExtension,
+    host_io::http::Method,
+    types::{Configuration, Error, ErrorResponse, GatewayHeaders},
+};
+
+/// Goes over the limits of its instance on demand: `/loop` never returns and `/allocate` grows
+/// the memory by 64 MiB.
+#[derive(HooksExtension)]
+struct Limits;
+
+impl HooksExtension for Limits {
+    fn new(_: Configuration) -> Result<Self, Error> {
+        Ok(Self)
+    }
+
+    #[allow(refining_impl_trait)]
+    fn on_request(&mut self, url: &str, _: Method, _: &mut GatewayHeaders) -> Result<(), ErrorResponse> {
+        if url.ends_with("/loop") {
+            let mut i: u64 = 0;
+            loop {
+                i = std::hint::black_box(i.wrapping_add(1));
+            }
+        }
+
+        if url.ends_with("/allocate") {
+            let buffer = vec![1u8; 64 * 1024 * 1024];
+            std::hint::black_box(&buffer);
+        }
+
+        Ok(())
+    }
+}
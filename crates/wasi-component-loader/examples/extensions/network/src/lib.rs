@@ -0,0 +1,32 @@
+use grafbase_sdk::{
+    HooksExtension,
+    host_io::http::{self, HttpRequest, Method, Url},
+    types::{Configuration, Error, ErrorResponse, GatewayHeaders},
+};
+
+/// Sends a GET request to the URL of the `x-url` header, and reports the outcome in the
+/// `x-status` or `x-error` header.
+#[derive(HooksExtension)]
+struct Network;
+
+impl HooksExtension for Network {
+    fn new(_: Configuration) -> Result<Self, Error> {
+        Ok(Self)
+    }
+
+    #[allow(refining_impl_trait)]
+    fn on_request(&mut self, _: &str, _: Method, headers: &mut GatewayHeaders) -> Result<(), ErrorResponse> {
+        let Some(url) = headers.get("x-url") else {
+            return Ok(());
+        };
+
+        let url: Url = url.to_str().unwrap().parse().unwrap();
+
+        match http::execute(HttpRequest::get(url)) {
+            Ok(response) => headers.append("x-status", response.status().as_str()),
+            Err(err) => headers.append("x-error", err.to_string().as_str()),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use grafbase_sdk::{
+    HooksExtension, SdkError,
+    host_io::{
+        http::Method,
+        redis::{Pool, Value},
+    },
+    types::{Configuration, Error, ErrorResponse, GatewayHeaders},
+};
+
+/// Runs the Redis commands matching the request path, and reports the outcome in the `x-value`
+/// or `x-error` header.
+#[derive(HooksExtension)]
+struct Redis;
+
+impl HooksExtension for Redis {
+    fn new(_: Configuration) -> Result<Self, Error> {
+        Ok(Self)
+    }
+
+    #[allow(refining_impl_trait)]
+    fn on_request(&mut self, url: &str, _: Method, headers: &mut GatewayHeaders) -> Result<(), ErrorResponse> {
+        let path = url.rsplit_once('/').map(|(_, path)| path).unwrap_or_default();
+
+        match run(path) {
+            Ok(value) => headers.append("x-value", value.as_str()),
+            Err(err) => headers.append("x-error", err.to_string().as_str()),
+        }
+
+        Ok(())
+    }
+}
+
+fn run(path: &str) -> Result<String, SdkError> {
+    let pool = Pool::connect("cache", "redis://localhost:6379")?;
+
+    match path {
+        "set" => {
+            pool.set_bytes("greeting", b"hello", None)?;
+            Ok(String::from("OK"))
+        }
+        "get" => Ok(match pool.get_bytes("greeting")? {
+            Some(value) => String::from_utf8_lossy(&value).into_owned(),
+            None => String::from("nil"),
+        }),
+        "eval" => {
+            let value = pool.eval("return redis.call('INCRBY', KEYS[1], ARGV[1])", &["counter"], &[b"2"])?;
+            Ok(format_value(&value))
+        }
+        "subscribe" => {
+            let subscription = pool.subscribe(&["events"])?;
+            let mut messages = Vec::new();
+
+            while let Some(message) = subscription.next()? {
+                let payload = String::from_utf8_lossy(message.payload_bytes());
+                messages.push(format!("{}:{payload}", message.channel()));
+            }
+
+            Ok(messages.join(","))
+        }
+        _ => Ok(String::new()),
+    }
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::Nil => String::from("nil"),
+        Value::Integer(value) => value.to_string(),
+        Value::Bytes(value) => String::from_utf8_lossy(value).into_owned(),
+        Value::Array(values) => values.iter().map(format_value).collect::<Vec<_>>().join(","),
+    }
+}
@@ -0,0 +1,48 @@
+use grafbase_sdk::{
+    AuthorizationExtension, IntoAuthorizeQueryOutput,
+    types::{
+        AuthenticatedRequestContext, AuthorizationDecisions, Configuration, Error, ErrorResponse, QueryElements,
+        SubgraphHeaders,
+    },
+};
+
+/// Grants the elements decorated with `@requiresRole(role: String!)` only if the token is that
+/// role.
+#[derive(AuthorizationExtension)]
+struct Roles;
+
+#[derive(serde::Deserialize)]
+struct RequiresRole {
+    role: String,
+}
+
+impl AuthorizationExtension for Roles {
+    fn new(_: Configuration) -> Result<Self, Error> {
+        Ok(Self)
+    }
+
+    fn authorize_query(
+        &mut self,
+        ctx: &AuthenticatedRequestContext,
+        _: &SubgraphHeaders,
+        elements: QueryElements<'_>,
+    ) -> Result<impl IntoAuthorizeQueryOutput, ErrorResponse> {
+        let token = ctx.token();
+        let mut builder = AuthorizationDecisions::deny_some_builder();
+
+        for (name, elements) in elements.iter_grouped_by_directive_name() {
+            if name != "requiresRole" {
+                continue;
+            }
+
+            for element in elements {
+                let RequiresRole { role } = element.directive_arguments()?;
+                if token.as_bytes() != Some(role.as_bytes()) {
+                    builder.deny(element, format!("Missing role {role}"));
+                }
+            }
+        }
+
+        Ok(builder.build())
+    }
+}
@@ -10,6 +10,19 @@ pub(crate) fn build_context(config: &WasmConfig) -> WasiCtx {
         builder.allow_tcp(true);
         builder.allow_udp(true);
         builder.allow_ip_name_lookup(true);
+
+        // Sockets only know the resolved address, so only IP and network entries can match.
+        if let Some(allowed_hosts) = config.allowed_hosts.clone() {
+            builder.socket_addr_check(move |address, _| {
+                let allowed = allowed_hosts.iter().any(|allowed| allowed.allows_address(address));
+
+                if !allowed {
+                    tracing::warn!("Denied socket connection to {address}, not in the network allowlist");
+                }
+
+                Box::pin(async move { allowed })
+            });
+        }
     }
 
     if config.environment_variables {
@@ -22,7 +22,7 @@ impl HostHttpClient for InstanceState {
             Err(e) => return Ok(Err(e)),
         };
 
-        let response = match send_request(request, self.request_durations.clone()).await {
+        let response = match send_request(request, self.request_durations.clone(), self.host_io_mocks.clone()).await {
             Ok(resp) => resp,
             Err(e) => return Ok(Err(e.into())),
         };
@@ -48,9 +48,10 @@ impl HostHttpClient for InstanceState {
             .into_iter()
             .map(|request| {
                 let request_durations = self.request_durations.clone();
+                let host_io_mocks = self.host_io_mocks.clone();
                 let fut: BoxFuture<'_, Result<HttpResponse, HttpError>> = match request {
                     Ok(request) => Box::pin(async move {
-                        let response = send_request(request, request_durations).await?;
+                        let response = send_request(request, request_durations, host_io_mocks).await?;
                         convert_http_response(response).await
                     }),
                     Err(e) => Box::pin(async move { Err(e) }),
@@ -99,7 +100,10 @@ fn convert_http_request(
     }
 
     match req.build_split() {
-        (client, Ok(req)) => Ok((client, req)),
+        (client, Ok(req)) => match state.check_url_allowed(req.url().as_str(), None) {
+            Ok(()) => Ok((client, req)),
+            Err(err) => Err(HttpError::Connect(err)),
+        },
         (_, Err(e)) => Err(HttpError::Request(e.to_string())),
     }
 }
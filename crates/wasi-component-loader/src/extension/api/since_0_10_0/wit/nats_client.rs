@@ -27,6 +27,17 @@ impl HostNatsClient for InstanceState {
             return Ok(Err("Failed to parse server URLs".to_string()));
         };
 
+        if let Err(err) = addrs
+            .iter()
+            .try_for_each(|addr| self.check_host_allowed(addr.host(), Some(addr.port())))
+        {
+            return Ok(Err(err));
+        }
+
+        if let Some(mocks) = self.host_io_mocks.clone() {
+            return Ok(Ok(self.resources.push(NatsClient::Mocked(mocks))?));
+        }
+
         let opts = async_nats::ConnectOptions::new();
 
         let opts = match auth {
@@ -41,7 +52,7 @@ impl HostNatsClient for InstanceState {
 
         Ok(match async_nats::connect_with_options(addrs, opts).await {
             Ok(client) => {
-                let client = self.resources.push(client)?;
+                let client = self.resources.push(NatsClient::Connected(client))?;
 
                 Ok(client)
             }
@@ -55,7 +66,13 @@ impl HostNatsClient for InstanceState {
         subject: String,
         message: Vec<u8>,
     ) -> wasmtime::Result<Result<(), String>> {
-        let client = self.resources.get_mut(&self_)?;
+        let client = match self.resources.get_mut(&self_)? {
+            NatsClient::Connected(client) => client,
+            NatsClient::Mocked(mocks) => {
+                mocks.nats_publish(subject, message);
+                return Ok(Ok(()));
+            }
+        };
 
         let result = client
             .publish(subject, message.into())
@@ -71,7 +88,14 @@ impl HostNatsClient for InstanceState {
         subject: String,
         config: Option<NatsStreamConfig>,
     ) -> wasmtime::Result<Result<Resource<NatsSubscriber>, String>> {
-        let client = self.resources.get_mut(&self_)?;
+        let client = match self.resources.get_mut(&self_)? {
+            NatsClient::Connected(client) => client,
+            NatsClient::Mocked(mocks) => {
+                let payloads = mocks.nats_messages(&subject).into_iter();
+                let subscriber = self.resources.push(NatsSubscriber::Mocked { subject, payloads })?;
+                return Ok(Ok(subscriber));
+            }
+        };
 
         let Some(config) = config else {
             let result = match client.subscribe(subject).await {
@@ -132,7 +156,15 @@ impl HostNatsClient for InstanceState {
         message: Vec<u8>,
         timeout_ms: Option<u64>,
     ) -> wasmtime::Result<Result<NatsMessage, String>> {
-        let client = self.resources.get_mut(&self_)?;
+        let client = match self.resources.get_mut(&self_)? {
+            NatsClient::Connected(client) => client,
+            NatsClient::Mocked(mocks) => {
+                return Ok(mocks
+                    .nats_request(subject.clone(), message)
+                    .map(|payload| NatsMessage { subject, payload }));
+            }
+        };
+
         let request = client.request(subject, message.into());
 
         let result = match timeout_ms {
@@ -163,7 +195,15 @@ impl HostNatsClient for InstanceState {
         self_: Resource<NatsClient>,
         bucket: String,
     ) -> wasmtime::Result<Result<Resource<NatsKeyValue>, String>> {
-        let client = self.resources.get_mut(&self_)?;
+        let client = match self.resources.get_mut(&self_)? {
+            NatsClient::Connected(client) => client,
+            NatsClient::Mocked(_) => {
+                return Ok(Err(
+                    "NATS key-value stores are not supported by the host IO mocks".to_string()
+                ));
+            }
+        };
+
         let stream = async_nats::jetstream::new(client.clone());
 
         match stream.get_key_value(bucket).await {
@@ -186,10 +226,7 @@ impl HostNatsSubscriber for InstanceState {
         let subscriber = self.resources.get_mut(&self_)?;
 
         match subscriber.next().await {
-            Ok(Some(message)) => Ok(Ok(Some(NatsMessage {
-                subject: message.subject.to_string(),
-                payload: message.payload.into(),
-            }))),
+            Ok(Some((subject, payload))) => Ok(Ok(Some(NatsMessage { subject, payload }))),
             Ok(None) => Ok(Ok(None)),
             Err(err) => Ok(Err(err.to_string())),
         }
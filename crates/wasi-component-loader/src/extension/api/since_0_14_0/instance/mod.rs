@@ -70,7 +70,7 @@ impl SdkPre0_14_0 {
     }
 
     pub(crate) async fn instantiate(&self, state: InstanceState) -> wasmtime::Result<Box<dyn ExtensionInstance>> {
-        let mut store = Store::new(self.pre.engine(), state);
+        let mut store = crate::extension::new_store(self.pre.engine(), state);
 
         let inner = self.pre.instantiate_async(&mut store).await?;
         inner.call_register_extension(&mut store).await?;
@@ -95,6 +95,10 @@ impl ExtensionInstance for ExtensionInstanceSince0_14_0 {
     fn store(&self) -> &Store<InstanceState> {
         &self.store
     }
+
+    fn store_mut(&mut self) -> &mut Store<InstanceState> {
+        &mut self.store
+    }
 }
 
 impl ContractsExtensionInstance for ExtensionInstanceSince0_14_0 {}
@@ -15,6 +15,14 @@ impl HostGrpcClient for InstanceState {
     ) -> wasmtime::Result<Result<Resource<GrpcClient>, String>> {
         tracing::debug!("Creating new gRPC client for URI: {}", configuration.uri);
 
+        if let Err(err) = self.check_url_allowed(&configuration.uri, None) {
+            return Ok(Err(err));
+        }
+
+        if let Some(mocks) = self.host_io_mocks.clone() {
+            return Ok(Ok(self.resources.push(GrpcClient::Mocked(mocks))?));
+        }
+
         let client = match self.grpc_clients.entry(configuration.uri.clone()) {
             Entry::Occupied(entry) => entry.get().clone(),
             Entry::Vacant(entry) => {
@@ -28,7 +36,7 @@ impl HostGrpcClient for InstanceState {
                     Err(err) => return Ok(Err(err.to_string())),
                 };
 
-                let client = tonic::client::Grpc::new(transport);
+                let client = GrpcClient::Connected(tonic::client::Grpc::new(transport));
 
                 entry.insert(client.clone());
 
@@ -50,7 +58,18 @@ impl HostGrpcClient for InstanceState {
         metadata: MetadataMap,
         timeout: Option<u64>,
     ) -> wasmtime::Result<Result<GrpcUnaryResponse, GrpcStatus>> {
-        let client = self.resources.get_mut(&self_)?;
+        let client = match self.resources.get_mut(&self_)? {
+            GrpcClient::Connected(client) => client,
+            GrpcClient::Mocked(mocks) => {
+                return Ok(match mocks.grpc_unary(service, method, message) {
+                    Ok(message) => Ok(GrpcUnaryResponse {
+                        metadata: Vec::new(),
+                        message,
+                    }),
+                    Err(status) => Err(tonic_status_to_grpc_status(status)),
+                });
+            }
+        };
 
         client
             .ready()
@@ -91,7 +110,14 @@ impl HostGrpcClient for InstanceState {
         metadata: MetadataMap,
         timeout: Option<u64>,
     ) -> wasmtime::Result<Result<wasmtime::component::Resource<GrpcStreamingResponse>, GrpcStatus>> {
-        let client = self.resources.get_mut(&self_)?;
+        let client = match self.resources.get_mut(&self_)? {
+            GrpcClient::Connected(client) => client,
+            GrpcClient::Mocked(_) => {
+                return Ok(Err(tonic_status_to_grpc_status(tonic::Status::unimplemented(
+                    "Streaming gRPC calls are not supported by the host IO mocks",
+                ))));
+            }
+        };
 
         client
             .ready()
@@ -3,7 +3,7 @@ mod conversion;
 use super::{
     HostPgConnection, HostPgRow, HostPgTransaction, PgBoundValue, PgConnection, PgRow, PgTransaction, PgValueTree,
 };
-use crate::InstanceState;
+use crate::{InstanceState, host_io_mocks::MockPgRow};
 use sqlx::{Column, Row};
 use wasmtime::component::Resource;
 
@@ -14,7 +14,13 @@ impl HostPgConnection for InstanceState {
         query: String,
         (params, tree): (Vec<PgBoundValue>, PgValueTree),
     ) -> wasmtime::Result<Result<Vec<Resource<PgRow>>, String>> {
-        let connection = self.resources.get_mut(&self_)?;
+        let connection = match self.resources.get_mut(&self_)? {
+            PgConnection::Pooled(connection) => connection,
+            PgConnection::Mocked(mocks) => {
+                let rows = mocks.postgres_query(query);
+                return push_mocked_rows(self, rows);
+            }
+        };
         let mut query = sqlx::query(&query);
 
         for param in params.into_iter() {
@@ -26,7 +32,7 @@ impl HostPgConnection for InstanceState {
                 let mut result = Vec::with_capacity(rows.len());
 
                 for row in rows {
-                    result.push(self.resources.push(row)?);
+                    result.push(self.resources.push(PgRow::Row(row))?);
                 }
 
                 Ok(Ok(result))
@@ -41,7 +47,10 @@ impl HostPgConnection for InstanceState {
         query: String,
         (params, tree): (Vec<PgBoundValue>, PgValueTree),
     ) -> wasmtime::Result<Result<u64, String>> {
-        let connection = self.resources.get_mut(&self_)?;
+        let connection = match self.resources.get_mut(&self_)? {
+            PgConnection::Pooled(connection) => connection,
+            PgConnection::Mocked(mocks) => return Ok(Ok(mocks.postgres_execute(query))),
+        };
         let mut query = sqlx::query(&query);
 
         for param in params.into_iter() {
@@ -68,7 +77,13 @@ impl HostPgTransaction for InstanceState {
         query: String,
         (params, tree): (Vec<PgBoundValue>, PgValueTree),
     ) -> wasmtime::Result<Result<Vec<Resource<PgRow>>, String>> {
-        let tx = self.resources.get_mut(&self_)?;
+        let tx = match self.resources.get_mut(&self_)? {
+            PgTransaction::Transaction(tx) => tx,
+            PgTransaction::Mocked(mocks) => {
+                let rows = mocks.postgres_query(query);
+                return push_mocked_rows(self, rows);
+            }
+        };
         let mut query = sqlx::query(&query);
 
         for param in params.into_iter() {
@@ -80,7 +95,7 @@ impl HostPgTransaction for InstanceState {
                 let mut result = Vec::with_capacity(rows.len());
 
                 for row in rows {
-                    result.push(self.resources.push(row)?);
+                    result.push(self.resources.push(PgRow::Row(row))?);
                 }
 
                 Ok(Ok(result))
@@ -95,7 +110,10 @@ impl HostPgTransaction for InstanceState {
         query: String,
         (params, tree): (Vec<PgBoundValue>, PgValueTree),
     ) -> wasmtime::Result<Result<u64, String>> {
-        let tx = self.resources.get_mut(&self_)?;
+        let tx = match self.resources.get_mut(&self_)? {
+            PgTransaction::Transaction(tx) => tx,
+            PgTransaction::Mocked(mocks) => return Ok(Ok(mocks.postgres_execute(query))),
+        };
         let mut query = sqlx::query(&query);
 
         for param in params.into_iter() {
@@ -109,7 +127,9 @@ impl HostPgTransaction for InstanceState {
     }
 
     async fn commit(&mut self, self_: Resource<PgTransaction>) -> wasmtime::Result<Result<(), String>> {
-        let tx: PgTransaction = self.resources.delete(self_)?;
+        let PgTransaction::Transaction(tx) = self.resources.delete(self_)? else {
+            return Ok(Ok(()));
+        };
 
         match tx.commit().await {
             Ok(_) => Ok(Ok(())),
@@ -118,7 +138,9 @@ impl HostPgTransaction for InstanceState {
     }
 
     async fn rollback(&mut self, self_: Resource<PgTransaction>) -> wasmtime::Result<Result<(), String>> {
-        let tx: PgTransaction = self.resources.delete(self_)?;
+        let PgTransaction::Transaction(tx) = self.resources.delete(self_)? else {
+            return Ok(Ok(()));
+        };
 
         match tx.rollback().await {
             Ok(_) => Ok(Ok(())),
@@ -127,7 +149,9 @@ impl HostPgTransaction for InstanceState {
     }
 
     async fn drop(&mut self, rep: Resource<PgTransaction>) -> wasmtime::Result<()> {
-        let tx: PgTransaction = self.resources.delete(rep)?;
+        let PgTransaction::Transaction(tx) = self.resources.delete(rep)? else {
+            return Ok(());
+        };
 
         match tx.rollback().await {
             Ok(_) => Ok(()),
@@ -138,8 +162,10 @@ impl HostPgTransaction for InstanceState {
 
 impl HostPgRow for InstanceState {
     async fn columns(&mut self, self_: Resource<PgRow>) -> wasmtime::Result<Vec<String>> {
-        let row = self.resources.get(&self_)?;
-        Ok(row.columns().iter().map(|c| c.name().to_string()).collect())
+        Ok(match self.resources.get(&self_)? {
+            PgRow::Row(row) => row.columns().iter().map(|c| c.name().to_string()).collect(),
+            PgRow::Mocked(row) => row.columns.clone(),
+        })
     }
 
     async fn as_bytes(
@@ -147,7 +173,16 @@ impl HostPgRow for InstanceState {
         self_: Resource<PgRow>,
         index: u64,
     ) -> wasmtime::Result<Result<Option<Vec<u8>>, String>> {
-        let row = self.resources.get(&self_)?;
+        let row = match self.resources.get(&self_)? {
+            PgRow::Row(row) => row,
+            PgRow::Mocked(row) => {
+                return Ok(row
+                    .values
+                    .get(index as usize)
+                    .cloned()
+                    .ok_or_else(|| format!("Column index {index} is out of bounds")));
+            }
+        };
 
         match row.try_get_raw(index as usize) {
             Ok(data) => Ok(Ok(data.as_bytes().ok().map(|b| b.to_vec()))),
@@ -156,8 +191,10 @@ impl HostPgRow for InstanceState {
     }
 
     async fn len(&mut self, self_: Resource<PgRow>) -> wasmtime::Result<u64> {
-        let row = self.resources.get(&self_)?;
-        Ok(row.len() as u64)
+        Ok(match self.resources.get(&self_)? {
+            PgRow::Row(row) => row.len() as u64,
+            PgRow::Mocked(row) => row.values.len() as u64,
+        })
     }
 
     async fn drop(&mut self, rep: Resource<PgRow>) -> wasmtime::Result<()> {
@@ -165,3 +202,15 @@ impl HostPgRow for InstanceState {
         Ok(())
     }
 }
+
+fn push_mocked_rows(
+    state: &mut InstanceState,
+    rows: Vec<MockPgRow>,
+) -> wasmtime::Result<Result<Vec<Resource<PgRow>>, String>> {
+    let rows = rows
+        .into_iter()
+        .map(|row| state.resources.push(PgRow::Mocked(row)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Ok(rows))
+}
@@ -1,14 +1,13 @@
-use super::{HostPgPool, PgPool, PgPoolOptions};
+use super::{HostPgPool, PgConnection, PgPool, PgPoolOptions, PgTransaction};
 use crate::InstanceState;
 
 use dashmap::Entry;
-use sqlx::{
-    Postgres, Transaction,
-    pool::{PoolConnection, PoolOptions},
-};
+use sqlx::{Postgres, pool::PoolOptions};
 use std::time::Duration;
 use wasmtime::component::Resource;
 
+const DEFAULT_POSTGRES_PORT: u16 = 5432;
+
 impl HostPgPool for InstanceState {
     async fn connect(
         &mut self,
@@ -16,6 +15,18 @@ impl HostPgPool for InstanceState {
         url: String,
         options: PgPoolOptions,
     ) -> wasmtime::Result<Result<Resource<sqlx::Pool<Postgres>>, String>> {
+        if let Err(err) = self.check_url_allowed(&url, Some(DEFAULT_POSTGRES_PORT)) {
+            return Ok(Err(err));
+        }
+
+        // The mocks answer on the connections, the pool itself never connects.
+        if self.host_io_mocks.is_some() {
+            return Ok(match PoolOptions::<Postgres>::new().connect_lazy(&url) {
+                Ok(pool) => Ok(self.resources.push(pool)?),
+                Err(err) => Err(err.to_string()),
+            });
+        }
+
         let pool = match self.postgres_pools.entry(name) {
             Entry::Occupied(occupied_entry) => occupied_entry.get().clone(),
             Entry::Vacant(vacant_entry) => {
@@ -33,10 +44,11 @@ impl HostPgPool for InstanceState {
         Ok(Ok(self.resources.push(pool)?))
     }
 
-    async fn acquire(
-        &mut self,
-        self_: Resource<PgPool>,
-    ) -> wasmtime::Result<Result<Resource<PoolConnection<Postgres>>, String>> {
+    async fn acquire(&mut self, self_: Resource<PgPool>) -> wasmtime::Result<Result<Resource<PgConnection>, String>> {
+        if let Some(mocks) = self.host_io_mocks.clone() {
+            return Ok(Ok(self.resources.push(PgConnection::Mocked(mocks))?));
+        }
+
         let pool = self.resources.get_mut(&self_)?;
 
         let connection = match pool.acquire().await {
@@ -44,13 +56,17 @@ impl HostPgPool for InstanceState {
             Err(err) => return Ok(Err(err.to_string())),
         };
 
-        Ok(Ok(self.resources.push(connection)?))
+        Ok(Ok(self.resources.push(PgConnection::Pooled(connection))?))
     }
 
     async fn begin_transaction(
         &mut self,
         self_: Resource<PgPool>,
-    ) -> wasmtime::Result<Result<Resource<Transaction<'static, Postgres>>, String>> {
+    ) -> wasmtime::Result<Result<Resource<PgTransaction>, String>> {
+        if let Some(mocks) = self.host_io_mocks.clone() {
+            return Ok(Ok(self.resources.push(PgTransaction::Mocked(mocks))?));
+        }
+
         let pool = self.resources.get_mut(&self_)?;
 
         let transaction = match pool.begin().await {
@@ -58,7 +74,7 @@ impl HostPgPool for InstanceState {
             Err(err) => return Ok(Err(err.to_string())),
         };
 
-        Ok(Ok(self.resources.push(transaction)?))
+        Ok(Ok(self.resources.push(PgTransaction::Transaction(transaction))?))
     }
 
     async fn drop(&mut self, rep: Resource<PgPool>) -> wasmtime::Result<()> {
@@ -16,6 +16,8 @@ use rskafka::{
 };
 use wasmtime::component::Resource;
 
+const DEFAULT_KAFKA_PORT: u16 = 9092;
+
 impl Host for InstanceState {}
 
 impl HostKafkaProducer for InstanceState {
@@ -30,6 +32,15 @@ impl HostKafkaProducer for InstanceState {
             return Ok(Err("Network operations are disabled".to_string()));
         }
 
+        if let Err(err) = check_servers_allowed(self, &servers) {
+            return Ok(Err(err));
+        }
+
+        if let Some(mocks) = self.host_io_mocks.clone() {
+            let producer = KafkaProducer::new(vec![ProducerKind::Mocked(mocks, topic)]);
+            return Ok(Ok(self.resources.push(producer)?));
+        }
+
         let producer = match self.kafka_producers.entry(name) {
             Entry::Occupied(occupied_entry) => occupied_entry.get().clone(),
             Entry::Vacant(vacant_entry) => {
@@ -79,6 +90,15 @@ impl HostKafkaConsumer for InstanceState {
             return Ok(Err("Network operations are disabled".to_string()));
         }
 
+        if let Err(err) = check_servers_allowed(self, &servers) {
+            return Ok(Err(err));
+        }
+
+        if let Some(mocks) = self.host_io_mocks.clone() {
+            let consumer = crate::resources::KafkaConsumer::mocked(mocks.kafka_messages(&topic));
+            return Ok(Ok(self.resources.push(consumer)?));
+        }
+
         let client = match create_client(servers, &config.client_config).await {
             Ok(client) => client,
             Err(err) => return Ok(Err(err)),
@@ -359,3 +379,10 @@ async fn create_client(servers: Vec<String>, config: &KafkaClientConfig) -> Resu
 
     Ok(client)
 }
+
+/// Checks the bootstrap servers, given as `host:port`, against the network allowlist.
+fn check_servers_allowed(state: &InstanceState, servers: &[String]) -> Result<(), String> {
+    servers
+        .iter()
+        .try_for_each(|server| state.check_url_allowed(&format!("kafka://{server}"), Some(DEFAULT_KAFKA_PORT)))
+}
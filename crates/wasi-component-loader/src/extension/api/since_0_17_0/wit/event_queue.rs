@@ -99,6 +99,8 @@ impl From<event_queue::CacheStatus> for CacheStatus {
         match value {
             event_queue::CacheStatus::Hit => CacheStatus::Hit,
             event_queue::CacheStatus::PartialHit => CacheStatus::PartialHit,
+            // Stale data was still served from the cache.
+            event_queue::CacheStatus::Stale => CacheStatus::Hit,
             event_queue::CacheStatus::Miss => CacheStatus::Miss,
         }
     }
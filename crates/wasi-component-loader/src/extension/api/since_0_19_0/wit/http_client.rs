@@ -22,7 +22,7 @@ impl HostHttpClient for InstanceState {
             Err(e) => return Ok(Err(e)),
         };
 
-        let response = match send_request(request, self.request_durations.clone()).await {
+        let response = match send_request(request, self.request_durations.clone(), self.host_io_mocks.clone()).await {
             Ok(resp) => resp,
             Err(e) => return Ok(Err(e)),
         };
@@ -58,8 +58,11 @@ impl HostHttpClient for InstanceState {
             .into_iter()
             .map(|request| {
                 let request_durations = self.request_durations.clone();
+                let host_io_mocks = self.host_io_mocks.clone();
                 let fut: BoxFuture<'_, Result<http::Response<Bytes>, HttpError>> = match request {
-                    Ok(request) => Box::pin(send_request(request, request_durations).map_err(Into::into)),
+                    Ok(request) => {
+                        Box::pin(send_request(request, request_durations, host_io_mocks).map_err(Into::into))
+                    }
                     Err(e) => Box::pin(async move { Err(e) }),
                 };
                 fut
@@ -89,7 +92,7 @@ impl HostHttpClient for InstanceState {
     }
 }
 
-fn convert_http_request(
+pub(crate) fn convert_http_request(
     state: &mut InstanceState,
     request: HttpRequest,
 ) -> wasmtime::Result<Result<(reqwest::Client, reqwest::Request), HttpError>> {
@@ -118,7 +121,10 @@ fn convert_http_request(
     }
 
     Ok(match req.build_split() {
-        (client, Ok(req)) => Ok((client, req)),
+        (client, Ok(req)) => match state.check_url_allowed(req.url().as_str(), None) {
+            Ok(()) => Ok((client, req)),
+            Err(err) => Err(HttpError::Connect(err)),
+        },
         (_, Err(e)) => Err(HttpError::Request(e.to_string())),
     })
 }
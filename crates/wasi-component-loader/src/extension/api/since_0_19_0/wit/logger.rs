@@ -29,7 +29,7 @@ impl HostFileLogger for InstanceState {
                     None => RotateStrategy::never(),
                 };
 
-                match FileLogger::new(path, strategy) {
+                match FileLogger::new(path, strategy, self.config.file_logger_retention) {
                     Ok(logger) => {
                         vacant_entry.insert(logger.clone());
                         logger
@@ -24,8 +24,15 @@ pub fn add_to_linker_impl(linker: &mut wasmtime::component::Linker<InstanceState
                 let state = caller.data_mut();
                 let cache = state
                     .caches
-                    .entry(name)
-                    .or_insert_with(|| Cache::new(size as usize, ttl_ms.map(Duration::from_millis)))
+                    .entry(name.clone())
+                    .or_insert_with(|| {
+                        Cache::new(
+                            &state.config.cache,
+                            &name,
+                            size as usize,
+                            ttl_ms.map(Duration::from_millis),
+                        )
+                    })
                     .clone();
                 let cache = state.resources.push(cache)?;
                 Ok((cache,))
@@ -71,7 +78,7 @@ pub fn add_to_linker_impl(linker: &mut wasmtime::component::Linker<InstanceState
                 let cache = state.resources.get(&cache)?;
 
                 let key = key.to_str(&caller)?;
-                cache.remove(key.as_ref());
+                cache.remove(key.as_ref()).await;
 
                 Ok(())
             })
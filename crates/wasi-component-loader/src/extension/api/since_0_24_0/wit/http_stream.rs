@@ -0,0 +1,144 @@
+use tokio::sync::mpsc;
+use wasmtime::component::Resource;
+
+pub use super::grafbase::sdk::http_stream::*;
+use super::grafbase::sdk::http_types::{HttpError, HttpRequest};
+use crate::{
+    InstanceState,
+    extension::api::since_0_19_0::wit::http_client::convert_http_request,
+    http_client::send_streaming_request,
+    resources::{Headers, HttpBodyReader, HttpBodyWriter},
+};
+
+/// Number of chunks buffered before writing the request body waits for the server.
+const REQUEST_BODY_BUFFER: usize = 16;
+
+impl Host for InstanceState {}
+
+impl HostHttpStreamClient for InstanceState {
+    async fn execute(&mut self, request: HttpRequest) -> wasmtime::Result<Result<HttpStreamResponse, HttpError>> {
+        if !self.is_network_enabled() {
+            return Ok(Err(HttpError::Connect("Network is disabled".into())));
+        }
+
+        let request = match convert_http_request(self, request)? {
+            Ok(req) => req,
+            Err(e) => return Ok(Err(e)),
+        };
+
+        let response = match send_streaming_request(
+            request,
+            None,
+            self.request_durations.clone(),
+            self.host_io_mocks.clone(),
+        )
+        .await
+        {
+            Ok(resp) => resp,
+            Err(e) => return Ok(Err(e)),
+        };
+
+        Ok(Ok(push_response(self, response)?))
+    }
+
+    async fn start(
+        &mut self,
+        request: HttpStreamRequest,
+    ) -> wasmtime::Result<Result<Resource<HttpBodyWriter>, HttpError>> {
+        if !self.is_network_enabled() {
+            return Ok(Err(HttpError::Connect("Network is disabled".into())));
+        }
+
+        let HttpStreamRequest {
+            method,
+            url,
+            headers,
+            timeout_ms,
+        } = request;
+
+        let request = HttpRequest {
+            method,
+            url,
+            headers,
+            body: Vec::new(),
+            timeout_ms,
+        };
+
+        let request = match convert_http_request(self, request)? {
+            Ok(req) => req,
+            Err(e) => return Ok(Err(e)),
+        };
+
+        let (sender, receiver) = mpsc::channel(REQUEST_BODY_BUFFER);
+        let response = tokio::spawn(send_streaming_request(
+            request,
+            Some(receiver),
+            self.request_durations.clone(),
+            self.host_io_mocks.clone(),
+        ));
+
+        Ok(Ok(self.resources.push(HttpBodyWriter::new(sender, response))?))
+    }
+
+    async fn drop(&mut self, _: Resource<HttpStreamClient>) -> wasmtime::Result<()> {
+        // Singleton that is never allocated
+        Ok(())
+    }
+}
+
+impl HostHttpBodyWriter for InstanceState {
+    async fn write(
+        &mut self,
+        self_: Resource<HttpBodyWriter>,
+        chunk: Vec<u8>,
+    ) -> wasmtime::Result<Result<(), HttpError>> {
+        let writer = self.resources.get(&self_)?;
+
+        Ok(writer.write(chunk.into()).await)
+    }
+
+    async fn finish(
+        &mut self,
+        writer: Resource<HttpBodyWriter>,
+    ) -> wasmtime::Result<Result<HttpStreamResponse, HttpError>> {
+        let writer = self.resources.delete(writer)?;
+
+        let response = match writer.finish().await {
+            Ok(resp) => resp,
+            Err(e) => return Ok(Err(e)),
+        };
+
+        Ok(Ok(push_response(self, response)?))
+    }
+
+    async fn drop(&mut self, rep: Resource<HttpBodyWriter>) -> wasmtime::Result<()> {
+        self.resources.delete(rep)?;
+        Ok(())
+    }
+}
+
+impl HostHttpBodyReader for InstanceState {
+    async fn next(&mut self, self_: Resource<HttpBodyReader>) -> wasmtime::Result<Result<Option<Vec<u8>>, HttpError>> {
+        let reader = self.resources.get_mut(&self_)?;
+
+        Ok(reader.next().await.transpose().map(|chunk| chunk.map(Vec::from)))
+    }
+
+    async fn drop(&mut self, rep: Resource<HttpBodyReader>) -> wasmtime::Result<()> {
+        self.resources.delete(rep)?;
+        Ok(())
+    }
+}
+
+fn push_response(
+    state: &mut InstanceState,
+    response: http::Response<HttpBodyReader>,
+) -> wasmtime::Result<HttpStreamResponse> {
+    let (parts, body) = response.into_parts();
+
+    Ok(HttpStreamResponse {
+        status: parts.status.as_u16(),
+        headers: state.resources.push(Headers::from(parts.headers))?,
+        body: state.resources.push(body)?,
+    })
+}
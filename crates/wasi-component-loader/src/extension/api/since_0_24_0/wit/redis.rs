@@ -0,0 +1,427 @@
+use std::{path::Path, time::Duration};
+
+use dashmap::Entry;
+use deadpool::managed::Pool;
+use redis::FromRedisValue;
+use wasmtime::component::Resource;
+
+use crate::{
+    InstanceState,
+    host_io_mocks::{MockedMessage, MockedRedisEval},
+    resources::{RedisManager, RedisPool, RedisSubscriber, RedisTlsPaths, create_redis_pool},
+};
+
+pub use super::grafbase::sdk::redis::*;
+
+const DEFAULT_REDIS_PORT: u16 = 6379;
+
+impl Host for InstanceState {}
+
+impl HostRedisPool for InstanceState {
+    async fn connect(
+        &mut self,
+        name: String,
+        url: String,
+        options: RedisPoolOptions,
+    ) -> wasmtime::Result<Result<Resource<RedisPool>, String>> {
+        if !self.is_network_enabled() {
+            return Ok(Err("Network operations are disabled".to_string()));
+        }
+
+        if let Err(err) = self.check_url_allowed(&url, Some(DEFAULT_REDIS_PORT)) {
+            return Ok(Err(err));
+        }
+
+        if let Some(mocks) = self.host_io_mocks.clone() {
+            return Ok(Ok(self.resources.push(RedisPool::Mocked(mocks))?));
+        }
+
+        let pool = match self.redis_pools.entry(name) {
+            Entry::Occupied(occupied_entry) => occupied_entry.get().clone(),
+            Entry::Vacant(vacant_entry) => {
+                let pool = match create_new_pool(&url, options) {
+                    Ok(pool) => RedisPool::Connected(pool),
+                    Err(err) => return Ok(Err(err)),
+                };
+
+                vacant_entry.insert(pool.clone());
+
+                pool
+            }
+        };
+
+        Ok(Ok(self.resources.push(pool)?))
+    }
+
+    async fn get(
+        &mut self,
+        self_: Resource<RedisPool>,
+        key: String,
+    ) -> wasmtime::Result<Result<Option<Vec<u8>>, String>> {
+        let pool = match self.resources.get(&self_)? {
+            RedisPool::Connected(pool) => pool.clone(),
+            RedisPool::Mocked(mocks) => return Ok(Ok(mocks.redis(|redis| redis.values.get(&key).cloned()))),
+        };
+
+        Ok(query(&pool, redis::cmd("GET").arg(&key)).await)
+    }
+
+    async fn set(
+        &mut self,
+        self_: Resource<RedisPool>,
+        key: String,
+        value: Vec<u8>,
+        ttl_ms: Option<u64>,
+    ) -> wasmtime::Result<Result<(), String>> {
+        let pool = match self.resources.get(&self_)? {
+            RedisPool::Connected(pool) => pool.clone(),
+            RedisPool::Mocked(mocks) => {
+                mocks.redis(|redis| redis.values.insert(key, value));
+                return Ok(Ok(()));
+            }
+        };
+
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(&key).arg(value);
+
+        if let Some(ttl_ms) = ttl_ms {
+            cmd.arg("PX").arg(ttl_ms);
+        }
+
+        Ok(query(&pool, &cmd).await)
+    }
+
+    async fn delete(&mut self, self_: Resource<RedisPool>, key: String) -> wasmtime::Result<Result<bool, String>> {
+        let pool = match self.resources.get(&self_)? {
+            RedisPool::Connected(pool) => pool.clone(),
+            RedisPool::Mocked(mocks) => {
+                let existed = mocks.redis(|redis| {
+                    let value = redis.values.remove(&key).is_some();
+                    let hash = redis.hashes.remove(&key).is_some();
+                    value || hash
+                });
+
+                return Ok(Ok(existed));
+            }
+        };
+
+        Ok(query::<u64>(&pool, redis::cmd("DEL").arg(&key))
+            .await
+            .map(|deleted| deleted > 0))
+    }
+
+    async fn incr(
+        &mut self,
+        self_: Resource<RedisPool>,
+        key: String,
+        delta: i64,
+    ) -> wasmtime::Result<Result<i64, String>> {
+        let pool = match self.resources.get(&self_)? {
+            RedisPool::Connected(pool) => pool.clone(),
+            RedisPool::Mocked(mocks) => {
+                let result = mocks.redis(|redis| -> Result<i64, String> {
+                    let current = match redis.values.get(&key) {
+                        Some(value) => std::str::from_utf8(value)
+                            .ok()
+                            .and_then(|value| value.parse::<i64>().ok())
+                            .ok_or_else(|| "value is not an integer or out of range".to_string())?,
+                        None => 0,
+                    };
+
+                    let value = current.saturating_add(delta);
+                    redis.values.insert(key, value.to_string().into_bytes());
+
+                    Ok(value)
+                });
+
+                return Ok(result);
+            }
+        };
+
+        Ok(query(&pool, redis::cmd("INCRBY").arg(&key).arg(delta)).await)
+    }
+
+    async fn expire(
+        &mut self,
+        self_: Resource<RedisPool>,
+        key: String,
+        ttl_ms: u64,
+    ) -> wasmtime::Result<Result<bool, String>> {
+        let pool = match self.resources.get(&self_)? {
+            RedisPool::Connected(pool) => pool.clone(),
+            RedisPool::Mocked(mocks) => {
+                let exists = mocks.redis(|redis| redis.values.contains_key(&key) || redis.hashes.contains_key(&key));
+                return Ok(Ok(exists));
+            }
+        };
+
+        Ok(query(&pool, redis::cmd("PEXPIRE").arg(&key).arg(ttl_ms)).await)
+    }
+
+    async fn hget(
+        &mut self,
+        self_: Resource<RedisPool>,
+        key: String,
+        field: String,
+    ) -> wasmtime::Result<Result<Option<Vec<u8>>, String>> {
+        let pool = match self.resources.get(&self_)? {
+            RedisPool::Connected(pool) => pool.clone(),
+            RedisPool::Mocked(mocks) => {
+                let value = mocks.redis(|redis| redis.hashes.get(&key).and_then(|hash| hash.get(&field).cloned()));
+                return Ok(Ok(value));
+            }
+        };
+
+        Ok(query(&pool, redis::cmd("HGET").arg(&key).arg(&field)).await)
+    }
+
+    async fn hset(
+        &mut self,
+        self_: Resource<RedisPool>,
+        key: String,
+        field: String,
+        value: Vec<u8>,
+    ) -> wasmtime::Result<Result<(), String>> {
+        let pool = match self.resources.get(&self_)? {
+            RedisPool::Connected(pool) => pool.clone(),
+            RedisPool::Mocked(mocks) => {
+                mocks.redis(|redis| redis.hashes.entry(key).or_default().insert(field, value));
+                return Ok(Ok(()));
+            }
+        };
+
+        Ok(query(&pool, redis::cmd("HSET").arg(&key).arg(&field).arg(value)).await)
+    }
+
+    async fn hdel(
+        &mut self,
+        self_: Resource<RedisPool>,
+        key: String,
+        field: String,
+    ) -> wasmtime::Result<Result<bool, String>> {
+        let pool = match self.resources.get(&self_)? {
+            RedisPool::Connected(pool) => pool.clone(),
+            RedisPool::Mocked(mocks) => {
+                let existed = mocks.redis(|redis| {
+                    redis
+                        .hashes
+                        .get_mut(&key)
+                        .is_some_and(|hash| hash.remove(&field).is_some())
+                });
+
+                return Ok(Ok(existed));
+            }
+        };
+
+        Ok(query::<u64>(&pool, redis::cmd("HDEL").arg(&key).arg(&field))
+            .await
+            .map(|deleted| deleted > 0))
+    }
+
+    async fn hgetall(
+        &mut self,
+        self_: Resource<RedisPool>,
+        key: String,
+    ) -> wasmtime::Result<Result<Vec<(String, Vec<u8>)>, String>> {
+        let pool = match self.resources.get(&self_)? {
+            RedisPool::Connected(pool) => pool.clone(),
+            RedisPool::Mocked(mocks) => {
+                let fields = mocks.redis(|redis| redis.hashes.get(&key).cloned().unwrap_or_default());
+                return Ok(Ok(fields.into_iter().collect()));
+            }
+        };
+
+        Ok(
+            query::<std::collections::HashMap<String, Vec<u8>>>(&pool, redis::cmd("HGETALL").arg(&key))
+                .await
+                .map(|fields| fields.into_iter().collect()),
+        )
+    }
+
+    async fn eval(
+        &mut self,
+        self_: Resource<RedisPool>,
+        script: String,
+        keys: Vec<String>,
+        args: Vec<Vec<u8>>,
+    ) -> wasmtime::Result<Result<RedisValue, String>> {
+        let pool = match self.resources.get(&self_)? {
+            RedisPool::Connected(pool) => pool.clone(),
+            RedisPool::Mocked(mocks) => {
+                let result = mocks.redis(|redis| {
+                    let result = redis
+                        .scripts
+                        .get(&script)
+                        .cloned()
+                        .ok_or_else(|| String::from("No mocked result for the Lua script"));
+
+                    redis.evals.push(MockedRedisEval { script, keys, args });
+                    result
+                });
+
+                return Ok(result.and_then(into_wit_value));
+            }
+        };
+
+        let mut cmd = redis::cmd("EVAL");
+        cmd.arg(script).arg(keys.len());
+
+        for key in keys {
+            cmd.arg(key);
+        }
+
+        for arg in args {
+            cmd.arg(arg);
+        }
+
+        Ok(query::<redis::Value>(&pool, &cmd).await.and_then(into_wit_value))
+    }
+
+    async fn publish(
+        &mut self,
+        self_: Resource<RedisPool>,
+        channel: String,
+        message: Vec<u8>,
+    ) -> wasmtime::Result<Result<u64, String>> {
+        let pool = match self.resources.get(&self_)? {
+            RedisPool::Connected(pool) => pool.clone(),
+            RedisPool::Mocked(mocks) => {
+                mocks.redis(|redis| {
+                    redis.published.push(MockedMessage {
+                        subject: channel,
+                        payload: message,
+                    })
+                });
+
+                return Ok(Ok(0));
+            }
+        };
+
+        Ok(query(&pool, redis::cmd("PUBLISH").arg(&channel).arg(message)).await)
+    }
+
+    async fn subscribe(
+        &mut self,
+        self_: Resource<RedisPool>,
+        channels: Vec<String>,
+    ) -> wasmtime::Result<Result<Resource<RedisSubscriber>, String>> {
+        let pool = match self.resources.get(&self_)? {
+            RedisPool::Connected(pool) => pool.clone(),
+            RedisPool::Mocked(mocks) => {
+                let messages = mocks.redis(|redis| {
+                    channels
+                        .iter()
+                        .flat_map(|channel| {
+                            let payloads = redis.messages.get(channel).cloned().unwrap_or_default();
+                            payloads.into_iter().map(move |payload| (channel.clone(), payload))
+                        })
+                        .collect::<Vec<_>>()
+                });
+
+                let subscriber = self.resources.push(RedisSubscriber::Mocked(messages.into_iter()))?;
+                return Ok(Ok(subscriber));
+            }
+        };
+
+        // Subscriptions need a dedicated connection, multiplexed ones can't enter the pub/sub mode.
+        let mut pubsub = match pool.manager().client().get_async_pubsub().await {
+            Ok(pubsub) => pubsub,
+            Err(err) => return Ok(Err(err.to_string())),
+        };
+
+        for channel in channels {
+            if let Err(err) = pubsub.subscribe(channel).await {
+                return Ok(Err(err.to_string()));
+            }
+        }
+
+        let subscriber = RedisSubscriber::Connected(Box::pin(pubsub.into_on_message()));
+
+        Ok(Ok(self.resources.push(subscriber)?))
+    }
+
+    async fn drop(&mut self, rep: Resource<RedisPool>) -> wasmtime::Result<()> {
+        self.resources.delete(rep)?;
+        Ok(())
+    }
+}
+
+impl HostRedisSubscriber for InstanceState {
+    async fn next(
+        &mut self,
+        self_: Resource<RedisSubscriber>,
+    ) -> wasmtime::Result<Result<Option<RedisMessage>, String>> {
+        let subscriber = self.resources.get_mut(&self_)?;
+
+        let message = subscriber
+            .next()
+            .await
+            .map(|(channel, payload)| RedisMessage { channel, payload });
+
+        Ok(Ok(message))
+    }
+
+    async fn drop(&mut self, rep: Resource<RedisSubscriber>) -> wasmtime::Result<()> {
+        self.resources.delete(rep)?;
+        Ok(())
+    }
+}
+
+async fn query<T: FromRedisValue>(pool: &Pool<RedisManager>, cmd: &redis::Cmd) -> Result<T, String> {
+    let mut connection = pool
+        .get()
+        .await
+        .map_err(|err| format!("Failed to acquire a Redis connection: {err}"))?;
+
+    cmd.query_async(&mut *connection).await.map_err(|err| err.to_string())
+}
+
+fn create_new_pool(url: &str, options: RedisPoolOptions) -> Result<Pool<RedisManager>, String> {
+    let RedisPoolOptions {
+        max_connections,
+        acquisition_timeout_ms,
+        tls,
+    } = options;
+
+    let tls = tls.as_ref().map(|tls| RedisTlsPaths {
+        cert: tls.cert.as_deref().map(Path::new),
+        key: tls.key.as_deref().map(Path::new),
+        ca: tls.ca.as_deref().map(Path::new),
+    });
+
+    let wait_timeout = acquisition_timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(Duration::from_secs(5));
+
+    create_redis_pool(url, tls, max_connections.map(|max| max as usize), wait_timeout)
+}
+
+fn into_wit_value(value: redis::Value) -> Result<RedisValue, String> {
+    match value {
+        redis::Value::Array(values) | redis::Value::Set(values) => values
+            .into_iter()
+            .map(into_wit_scalar)
+            .collect::<Result<_, _>>()
+            .map(RedisValue::Array),
+        value => Ok(match into_wit_scalar(value)? {
+            RedisScalar::Nil => RedisValue::Nil,
+            RedisScalar::Integer(value) => RedisValue::Integer(value),
+            RedisScalar::Bytes(value) => RedisValue::Bytes(value),
+        }),
+    }
+}
+
+fn into_wit_scalar(value: redis::Value) -> Result<RedisScalar, String> {
+    match value {
+        redis::Value::Nil => Ok(RedisScalar::Nil),
+        redis::Value::Int(value) => Ok(RedisScalar::Integer(value)),
+        redis::Value::Boolean(value) => Ok(RedisScalar::Integer(value as i64)),
+        redis::Value::BulkString(value) => Ok(RedisScalar::Bytes(value)),
+        redis::Value::SimpleString(value) => Ok(RedisScalar::Bytes(value.into_bytes())),
+        redis::Value::Okay => Ok(RedisScalar::Bytes(b"OK".to_vec())),
+        redis::Value::Array(_) | redis::Value::Set(_) | redis::Value::Map(_) => {
+            Err("Nested arrays returned by Lua scripts are not supported".to_string())
+        }
+        value => Err(format!("Unsupported value returned by the Lua script: {value:?}")),
+    }
+}
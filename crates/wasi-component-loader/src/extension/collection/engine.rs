@@ -56,7 +56,7 @@ impl EngineWasmExtensions {
                     | TypeDiscriminants::Authorization
                     | TypeDiscriminants::Contracts
             )
-        });
+        })?;
 
         let contracts = extensions
             .iter()
@@ -75,12 +75,14 @@ impl EngineWasmExtensions {
             ));
         }
 
+        let engine = gateway_extensions.current().engine.clone();
+
         Ok(Self(Arc::new(EngineWasmExtensionsInner {
-            pools: create_pools(&gateway_extensions.engine, schema, extension_catalog, extensions).await?,
+            pools: create_pools(&engine, schema, extension_catalog, extensions).await?,
             contracts: match contracts {
                 Some(config) => Some(
                     Pool::new(
-                        &gateway_extensions.engine,
+                        &engine,
                         schema,
                         Arc::new(ExtensionState::new(extension_catalog, config)),
                     )
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError, RwLock};
 
 use engine_schema::Schema;
 use enumflags2::BitFlag;
@@ -11,16 +11,10 @@ use crate::{
 };
 
 /// Extensions tied to the gateway, rather than the engine. As such they won't reload if the schema
-/// changes.
+/// changes. All clones share the same extensions, which can be swapped with [Self::replace] when
+/// the extensions themselves change.
 #[derive(Default, Clone)]
-pub struct GatewayWasmExtensions(Arc<GatewayWasmExtensionsInner>);
-
-impl std::ops::Deref for GatewayWasmExtensions {
-    type Target = Arc<GatewayWasmExtensionsInner>;
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
+pub struct GatewayWasmExtensions(Arc<RwLock<Arc<GatewayWasmExtensionsInner>>>);
 
 pub struct GatewayWasmExtensionsInner {
     pub(crate) engine: wasmtime::Engine,
@@ -42,6 +36,18 @@ impl Default for GatewayWasmExtensionsInner {
 }
 
 impl GatewayWasmExtensions {
+    /// The extensions currently in use. Callers keep them alive until they're done, so in-flight
+    /// calls finish on the previous instances after a [Self::replace].
+    pub(crate) fn current(&self) -> Arc<GatewayWasmExtensionsInner> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Replaces the extensions of this instance and all its clones with the ones from `extensions`.
+    pub fn replace(&self, extensions: GatewayWasmExtensions) {
+        let extensions = extensions.current();
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = extensions;
+    }
+
     pub async fn new(
         extension_catalog: &Arc<ExtensionCatalog>,
         gateway_config: &Config,
@@ -51,7 +57,7 @@ impl GatewayWasmExtensions {
 
         let extension_configs = load_extensions_config(extension_catalog, gateway_config, logging_filter, |ty| {
             matches!(ty, TypeDiscriminants::Hooks | TypeDiscriminants::Authentication)
-        });
+        })?;
 
         let mut inner = GatewayWasmExtensionsInner {
             engine,
@@ -98,7 +104,7 @@ impl GatewayWasmExtensions {
             }
         }
 
-        Ok(Self(Arc::new(inner)))
+        Ok(Self(Arc::new(RwLock::new(Arc::new(inner)))))
     }
 }
 
@@ -1,9 +1,13 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
 use extension_catalog::{ExtensionCatalog, ExtensionId, HooksType};
-use gateway_config::Config;
+use gateway_config::{AccessLogsConfig, AllowedHost, Config, EntityCachingStorage, LogCompression};
+use rolling_logger::{Compression, RetentionPolicy};
 use semver::Version;
 
+use super::InstanceLimits;
+use crate::resources::{CacheStorage, RedisTlsPaths, create_redis_pool};
+
 pub(crate) struct ExtensionConfig<T = toml::Value> {
     pub id: ExtensionId,
     pub manifest_id: extension_catalog::Id,
@@ -14,6 +18,10 @@ pub(crate) struct ExtensionConfig<T = toml::Value> {
     pub guest_config: T,
     pub can_skip_sending_events: bool,
     pub logging_filter: String,
+    pub file_logger_retention: RetentionPolicy,
+    pub limits: InstanceLimits,
+    /// Storage of the named caches of the extension.
+    pub cache: CacheStorage,
 }
 
 #[derive(Default, Clone)]
@@ -25,6 +33,8 @@ pub(crate) struct PoolConfig {
 pub(crate) struct WasmConfig {
     pub location: PathBuf,
     pub networking: bool,
+    /// Restricts the hosts the extension can connect to, if set.
+    pub allowed_hosts: Option<Arc<[AllowedHost]>>,
     pub stdout: bool,
     pub stderr: bool,
     pub environment_variables: bool,
@@ -35,8 +45,9 @@ pub(super) fn load_extensions_config(
     config: &Config,
     logging_filter: String,
     filter: impl Fn(extension_catalog::TypeDiscriminants) -> bool,
-) -> Vec<ExtensionConfig> {
+) -> wasmtime::Result<Vec<ExtensionConfig>> {
     let mut wasm_extensions = Vec::with_capacity(extension_catalog.len());
+    let file_logger_retention = file_logger_retention(&config.gateway.access_logs);
 
     let can_skip_sending_events = extension_catalog.iter().all(|ext| match &ext.manifest.r#type {
         extension_catalog::Type::Hooks(HooksType { event_filter }) => event_filter
@@ -67,6 +78,7 @@ pub(super) fn load_extensions_config(
         let wasi_config = WasmConfig {
             location: extension.wasm_path.clone(),
             networking: extension_config.networking().unwrap_or(manifest.network_enabled()),
+            allowed_hosts: extension_config.allowed_hosts().map(Arc::from),
             stdout: extension_config.stdout().unwrap_or(manifest.stdout_enabled()),
             stderr: extension_config.stderr().unwrap_or(manifest.stderr_enabled()),
             environment_variables: extension_config
@@ -76,6 +88,38 @@ pub(super) fn load_extensions_config(
 
         let max_size = extension_config.max_pool_size();
 
+        let limits = InstanceLimits {
+            max_memory: extension_config
+                .max_memory()
+                .map(|size| usize::try_from(size.bytes()).unwrap_or(usize::MAX)),
+            max_execution_time: extension_config.max_execution_time(),
+            max_table_elements: extension_config.max_table_elements(),
+        };
+
+        let cache = match extension_config.cache() {
+            Some(cache) if cache.storage == EntityCachingStorage::Redis => {
+                let redis = cache.redis.as_ref().unwrap_or(&config.entity_caching.redis);
+                let tls = redis.tls.as_ref().map(|tls| RedisTlsPaths {
+                    cert: tls.cert.as_deref(),
+                    key: tls.key.as_deref(),
+                    ca: tls.ca.as_deref(),
+                });
+
+                let pool = create_redis_pool(redis.url.as_str(), tls, None, Duration::from_secs(5)).map_err(|err| {
+                    wasmtime::Error::msg(format!(
+                        "Could not create the Redis pool for the cache of extension {}: {err}",
+                        manifest.name()
+                    ))
+                })?;
+
+                CacheStorage::Redis {
+                    pool,
+                    key_prefix: format!("{}:extension-cache:{}", redis.key_prefix, manifest.name()),
+                }
+            }
+            _ => CacheStorage::Memory,
+        };
+
         wasm_extensions.push(ExtensionConfig {
             id,
             manifest_id: manifest.id.clone(),
@@ -89,8 +133,30 @@ pub(super) fn load_extensions_config(
             sdk_version: manifest.sdk_version.clone(),
             can_skip_sending_events,
             logging_filter: logging_filter.clone(),
+            file_logger_retention,
+            limits,
+            cache,
         });
     }
 
-    wasm_extensions
+    Ok(wasm_extensions)
+}
+
+/// The retention of the access log files, applied to the files written by the extension file loggers.
+fn file_logger_retention(config: &AccessLogsConfig) -> RetentionPolicy {
+    let mut retention = RetentionPolicy::default().with_compression(match config.compression {
+        LogCompression::None => Compression::None,
+        LogCompression::Gzip => Compression::Gzip,
+        LogCompression::Zstd => Compression::Zstd,
+    });
+
+    if let Some(max_files) = config.max_files {
+        retention = retention.with_max_files(max_files);
+    }
+
+    if let Some(max_age) = config.max_age {
+        retention = retention.with_max_age(max_age);
+    }
+
+    retention
 }
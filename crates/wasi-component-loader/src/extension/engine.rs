@@ -16,20 +16,26 @@ pub(crate) fn build_engine(config: WasmConfig) -> wasmtime::Result<Engine> {
         .unwrap_or_else(|| std::env::temp_dir().join("grafbase-wasm-cache"));
 
     tracing::debug!("Using Wasm cache dir: {}", cache_dir.display());
-    cfg.wasm_component_model(true).async_support(true).cache({
-        // Wasmtime seems to have a mechanism to re-use the cache.
-        // But it relies on a GIT_REV var which doesn't exist during compilation
-        // Furthermore the default behavior with debug assertions is to use last modified
-        // time of the current executable, which always changes for integration-tests...
-        let dir = cache_dir.join(crate::built_info::CARGO_LOCK_HASH);
-        if std::fs::create_dir_all(&dir).is_ok() || std::fs::read_dir(&dir).is_ok() {
-            let mut cfg = CacheConfig::new();
-            cfg.with_directory(dir);
-            wasmtime::Cache::new(cfg).ok()
-        } else {
-            None
-        }
-    });
+    cfg.wasm_component_model(true)
+        .async_support(true)
+        .epoch_interruption(true)
+        .cache({
+            // Wasmtime seems to have a mechanism to re-use the cache.
+            // But it relies on a GIT_REV var which doesn't exist during compilation
+            // Furthermore the default behavior with debug assertions is to use last modified
+            // time of the current executable, which always changes for integration-tests...
+            let dir = cache_dir.join(crate::built_info::CARGO_LOCK_HASH);
+            if std::fs::create_dir_all(&dir).is_ok() || std::fs::read_dir(&dir).is_ok() {
+                let mut cfg = CacheConfig::new();
+                cfg.with_directory(dir);
+                wasmtime::Cache::new(cfg).ok()
+            } else {
+                None
+            }
+        });
 
-    Engine::new(&cfg)
+    let engine = Engine::new(&cfg)?;
+    super::spawn_epoch_ticker(&engine)?;
+
+    Ok(engine)
 }
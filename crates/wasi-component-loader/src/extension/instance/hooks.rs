@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use engine::EngineOperationContext;
+use engine::{EngineOperationContext, EngineRequestContext};
 use engine_error::{ErrorResponse, GraphqlError};
 use engine_schema::{GraphqlSubgraph, VirtualSubgraph};
 use event_queue::EventQueue;
@@ -51,4 +51,13 @@ pub(crate) trait HooksExtensionInstance {
     ) -> BoxFuture<'a, wasmtime::Result<Result<http::HeaderMap, GraphqlError>>> {
         Box::pin(std::future::ready(Ok(Ok(headers))))
     }
+
+    /// Extensions built with an SDK predating policies deny all of them.
+    fn authorize_policies<'a>(
+        &'a mut self,
+        ctx: EngineRequestContext,
+        policies: &'a [&'a str],
+    ) -> BoxFuture<'a, wasmtime::Result<Result<Vec<bool>, GraphqlError>>> {
+        Box::pin(std::future::ready(Ok(Ok(vec![false; policies.len()]))))
+    }
 }
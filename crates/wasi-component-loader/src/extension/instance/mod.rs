@@ -29,4 +29,5 @@ pub trait ExtensionInstance:
     + 'static
 {
     fn store(&self) -> &Store<InstanceState>;
+    fn store_mut(&mut self) -> &mut Store<InstanceState>;
 }
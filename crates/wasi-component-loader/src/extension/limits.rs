@@ -0,0 +1,169 @@
+//! Resource limits of extension instances. Memory and tables are bounded by a [ResourceLimiter]
+//! installed in every store, and the execution time of a call by epoch interruption.
+
+use std::time::Duration;
+
+use grafbase_telemetry::otel::opentelemetry::KeyValue;
+use wasmtime::{Engine, ResourceLimiter, Store};
+
+use crate::InstanceState;
+
+/// How often the engine epoch is incremented. Execution time limits are rounded up to it.
+const EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// Deadline of instances without an execution time limit, far enough to never be reached.
+const NO_DEADLINE: u64 = u64::MAX / 2;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct InstanceLimits {
+    /// Maximum linear memory of an instance in bytes.
+    pub max_memory: Option<usize>,
+    /// Maximum duration of a single call into the instance, including the time spent in host
+    /// functions.
+    pub max_execution_time: Option<Duration>,
+    /// Maximum number of elements of a single table.
+    pub max_table_elements: Option<usize>,
+}
+
+impl InstanceLimits {
+    /// The number of epoch ticks a single call is allowed to last.
+    fn deadline_ticks(&self) -> u64 {
+        match self.max_execution_time {
+            Some(max_execution_time) => {
+                let ticks = max_execution_time.as_nanos().div_ceil(EPOCH_TICK.as_nanos()).max(1);
+                u64::try_from(ticks).unwrap_or(NO_DEADLINE)
+            }
+            None => NO_DEADLINE,
+        }
+    }
+}
+
+/// The limit an extension instance went over. Calls exceeding a limit trap, and the instance is
+/// not recycled afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub(crate) enum LimitExceeded {
+    #[error("memory limit exceeded")]
+    Memory,
+    #[error("table limit exceeded")]
+    Table,
+    #[error("execution time limit exceeded")]
+    ExecutionTime,
+}
+
+impl LimitExceeded {
+    /// Finds the limit, if any, behind the error of a call into an extension.
+    pub fn from_error(err: &wasmtime::Error) -> Option<Self> {
+        if let Some(limit) = err.downcast_ref::<LimitExceeded>() {
+            return Some(*limit);
+        }
+
+        match err.downcast_ref::<wasmtime::Trap>() {
+            Some(wasmtime::Trap::Interrupt) => Some(LimitExceeded::ExecutionTime),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            LimitExceeded::Memory => "memory",
+            LimitExceeded::Table => "table",
+            LimitExceeded::ExecutionTime => "execution_time",
+        }
+    }
+}
+
+/// Keeps the memories and tables of a store within the configured limits, tracking their growth
+/// along the way.
+pub(crate) struct Limiter {
+    limits: InstanceLimits,
+    memory: usize,
+}
+
+impl Limiter {
+    pub fn new(limits: InstanceLimits) -> Self {
+        Self { limits, memory: 0 }
+    }
+
+    /// Growth in bytes of the linear memories of the instance, summed over the lifetime of its
+    /// store. Wasm memories never shrink, so this is the high-water mark of the instance rather
+    /// than the memory it currently uses.
+    pub fn memory(&self) -> usize {
+        self.memory
+    }
+}
+
+impl ResourceLimiter for Limiter {
+    fn memory_growing(&mut self, current: usize, desired: usize, _maximum: Option<usize>) -> wasmtime::Result<bool> {
+        match self.limits.max_memory {
+            Some(max_memory) if desired > max_memory => Err(LimitExceeded::Memory.into()),
+            _ => {
+                self.memory += desired.saturating_sub(current);
+                Ok(true)
+            }
+        }
+    }
+
+    fn table_growing(&mut self, _current: usize, desired: usize, _maximum: Option<usize>) -> wasmtime::Result<bool> {
+        match self.limits.max_table_elements {
+            Some(max_table_elements) if desired > max_table_elements => Err(LimitExceeded::Table.into()),
+            _ => Ok(true),
+        }
+    }
+}
+
+/// Creates the store of a new extension instance, with its resource limits and the execution
+/// time deadline of the first call, which instantiates the extension.
+pub(crate) fn new_store(engine: &Engine, state: InstanceState) -> Store<InstanceState> {
+    let mut store = Store::new(engine, state);
+
+    store.limiter(|state| &mut state.limiter);
+    store.epoch_deadline_trap();
+    reset_deadline(&mut store);
+
+    store
+}
+
+/// Starts the execution time budget of a new call into the instance.
+pub(crate) fn reset_deadline(store: &mut Store<InstanceState>) {
+    let ticks = store.data().config.limits.deadline_ticks();
+    store.set_epoch_deadline(ticks);
+}
+
+/// Records a call which went over one of the instance limits.
+pub(crate) fn report_limit_exceeded(state: &InstanceState, err: &wasmtime::Error) {
+    let Some(limit) = LimitExceeded::from_error(err) else {
+        return;
+    };
+
+    tracing::error!("Extension {}: {limit}", state.config.manifest_id);
+
+    state.limit_exceeded.add(
+        1,
+        &[
+            KeyValue::new("grafbase.extension.name", state.extension_name().to_owned()),
+            KeyValue::new("grafbase.extension.limit", limit.as_str()),
+        ],
+    );
+}
+
+/// Increments the epoch of the engine at a fixed interval, so that calls exceeding their
+/// execution time are interrupted. The thread stops once the engine is dropped.
+pub(crate) fn spawn_epoch_ticker(engine: &Engine) -> std::io::Result<()> {
+    let engine = engine.weak();
+
+    std::thread::Builder::new()
+        .name(String::from("wasm-epoch-ticker"))
+        .spawn(move || {
+            loop {
+                std::thread::sleep(EPOCH_TICK);
+
+                let Some(engine) = engine.upgrade() else {
+                    break;
+                };
+
+                engine.increment_epoch();
+            }
+        })?;
+
+    Ok(())
+}
@@ -10,7 +10,7 @@ use wasmtime::{
 
 pub(crate) struct ExtensionLoader {
     pre: SdkPre,
-    state: Arc<ExtensionState>,
+    pub(super) state: Arc<ExtensionState>,
 }
 
 impl ExtensionLoader {
@@ -0,0 +1,146 @@
+//! Metrics and traces of extension calls and instances, all tagged with the extension name so a
+//! slow extension can be told apart from the others.
+
+use std::time::{Duration, Instant};
+
+use grafbase_telemetry::otel::opentelemetry::{
+    KeyValue,
+    metrics::{Counter, Histogram, Meter},
+};
+use tracing::{Span, field::Empty, info_span};
+
+use crate::InstanceState;
+
+pub(crate) struct ExtensionMetrics {
+    name: KeyValue,
+    /// Duration of each call into the guest per function. Its count is the number of calls and
+    /// the `grafbase.extension.call.status` attribute tells the errors apart.
+    call_duration: Histogram<f64>,
+    /// Time spent waiting for an instance from the pool.
+    pool_wait_duration: Histogram<f64>,
+    /// Instances created by the pool, including failed instantiations.
+    instances_created: Counter<u64>,
+    /// Instances returned to the pool, whether they're re-used or discarded.
+    instances_recycled: Counter<u64>,
+    /// Linear memory grown by the instance so far, recorded after each call. See [super::Limiter::memory].
+    memory: Histogram<u64>,
+}
+
+impl ExtensionMetrics {
+    pub fn new(meter: &Meter, extension_name: &str) -> Self {
+        Self {
+            name: KeyValue::new("grafbase.extension.name", extension_name.to_owned()),
+            call_duration: meter
+                .f64_histogram("grafbase.extension.call.duration")
+                .with_unit("ms")
+                .build(),
+            pool_wait_duration: meter
+                .f64_histogram("grafbase.extension.pool.wait.duration")
+                .with_unit("ms")
+                .build(),
+            instances_created: meter.u64_counter("grafbase.extension.instance.created").build(),
+            instances_recycled: meter.u64_counter("grafbase.extension.instance.recycled").build(),
+            memory: meter
+                .u64_histogram("grafbase.extension.instance.memory")
+                .with_unit("By")
+                .build(),
+        }
+    }
+
+    pub fn record_pool_wait(&self, duration: Duration) {
+        self.pool_wait_duration
+            .record(duration.as_secs_f64() * 1000.0, std::slice::from_ref(&self.name));
+    }
+
+    pub fn record_instance_created(&self, success: bool) {
+        self.instances_created.add(
+            1,
+            &[
+                self.name.clone(),
+                KeyValue::new("grafbase.extension.instance.success", success),
+            ],
+        );
+    }
+
+    /// `outcome` is either `reused` or the reason the instance was discarded.
+    pub fn record_instance_recycled(&self, outcome: &'static str) {
+        self.instances_recycled.add(
+            1,
+            &[
+                self.name.clone(),
+                KeyValue::new("grafbase.extension.instance.outcome", outcome),
+            ],
+        );
+    }
+}
+
+/// A call into the guest in progress, with its span.
+pub(crate) struct ExtensionCall {
+    function: &'static str,
+    span: Span,
+    start: Instant,
+}
+
+impl ExtensionCall {
+    pub fn start(state: &InstanceState, function: &'static str) -> Self {
+        let span = info_span!(
+            "extension call",
+            "grafbase.extension.name" = state.extension_name(),
+            "grafbase.extension.function" = function,
+            "otel.name" = format!("{} {function}", state.extension_name()),
+            "otel.status_code" = Empty,
+        );
+
+        Self {
+            function,
+            span,
+            start: Instant::now(),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span.clone()
+    }
+
+    /// Records the call, which failed if the guest returned an error or trapped.
+    pub fn end(self, state: &InstanceState, failed: bool) {
+        let metrics = &state.metrics;
+
+        if failed {
+            self.span.record("otel.status_code", "Error");
+        }
+
+        metrics.call_duration.record(
+            self.start.elapsed().as_secs_f64() * 1000.0,
+            &[
+                metrics.name.clone(),
+                KeyValue::new("grafbase.extension.function", self.function),
+                KeyValue::new(
+                    "grafbase.extension.call.status",
+                    if failed { "error" } else { "success" },
+                ),
+            ],
+        );
+
+        metrics
+            .memory
+            .record(state.limiter.memory() as u64, std::slice::from_ref(&metrics.name));
+    }
+}
+
+/// Whether the result of a call into the guest is an error.
+pub(crate) trait CallStatus {
+    fn is_error(&self) -> bool;
+}
+
+impl CallStatus for runtime::extension::Response {
+    fn is_error(&self) -> bool {
+        !self.errors.is_empty()
+    }
+}
+
+impl<T, E> CallStatus for Result<T, E> {
+    fn is_error(&self) -> bool {
+        self.is_err()
+    }
+}
@@ -3,14 +3,18 @@ mod collection;
 mod config;
 mod engine;
 mod instance;
+mod limits;
 mod loader;
+mod metrics;
 mod pool;
 mod runtime;
 
 pub use collection::*;
 pub(crate) use config::*;
-#[cfg(test)]
+#[cfg(any(test, feature = "test-utils"))]
 pub(crate) use engine::*;
 pub(crate) use instance::*;
+pub(crate) use limits::*;
 pub(crate) use loader::*;
+pub(crate) use metrics::*;
 pub(crate) use pool::*;
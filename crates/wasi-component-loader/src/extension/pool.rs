@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
 use deadpool::managed::{self, Manager};
 use engine_error::{ErrorResponse, GraphqlError};
@@ -10,7 +10,7 @@ use wasmtime::Engine;
 
 use crate::{ExtensionState, InstanceState};
 
-use super::{ExtensionInstance, ExtensionLoader};
+use super::{ExtensionCall, ExtensionInstance, ExtensionLoader};
 
 pub(crate) struct Pool {
     inner: managed::Pool<ExtensionLoader>,
@@ -46,8 +46,12 @@ impl Pool {
 
     pub(crate) async fn get(&self) -> wasmtime::Result<ExtensionGuard> {
         let span = info_span!("get extension from pool");
+        let start = Instant::now();
 
-        let instance = self.inner.get().instrument(span).await.map_err(|err| match err {
+        let instance = self.inner.get().instrument(span).await;
+        self.state.metrics.record_pool_wait(start.elapsed());
+
+        let instance = instance.map_err(|err| match err {
             managed::PoolError::Backend(err) => err,
             err => wasmtime::Error::msg(err),
         })?;
@@ -69,8 +73,11 @@ impl Manager for ExtensionLoader {
     type Error = wasmtime::Error;
 
     async fn create(&self) -> Result<Self::Type, Self::Error> {
+        let inner = self.instantiate().await;
+        self.state.metrics.record_instance_created(inner.is_ok());
+
         Ok(Instance {
-            inner: self.instantiate().await?,
+            inner: inner?,
             poisoned: false,
             recyclable: true,
         })
@@ -83,12 +90,16 @@ impl Manager for ExtensionLoader {
     ) -> managed::RecycleResult<Self::Error> {
         if instance.poisoned || !instance.recyclable {
             if instance.poisoned {
+                self.state.metrics.record_instance_recycled("poisoned");
                 return Err(managed::RecycleError::Message("Poisonned".into()));
             } else {
+                self.state.metrics.record_instance_recycled("not_recyclable");
                 return Err(managed::RecycleError::Message("Not recyclable".into()));
             }
         }
 
+        self.state.metrics.record_instance_recycled("reused");
+
         Ok(())
     }
 }
@@ -117,7 +128,7 @@ impl std::ops::DerefMut for ExtensionGuard {
 // Getting lifetime issues with a closure, so just doing a macro...
 #[macro_export]
 macro_rules! wasmsafe {
-    ($instance:ident . $($call:tt)*) => {{
+    ($instance:ident . $function:ident ( $($args:tt)* ) .await) => {{
         debug_assert!(
             !$instance.poisoned,
             "ExtensionGuard is poisoned, cannot call methods on it."
@@ -126,12 +137,17 @@ macro_rules! wasmsafe {
         // otherwise. If there is any wasmtime error we also assume the instance to be poisoned and
         // unrecoverable.
         $instance.poisoned = true;
-        match $instance.dont_use_me_without_wasmsafe().$($call)* {
+        let call = $instance.start_call(stringify!($function));
+        let future = $instance.dont_use_me_without_wasmsafe().$function($($args)*);
+        match ::tracing::Instrument::instrument(future, call.span()).await {
             Ok(result) => {
                 $instance.poisoned = false; // Reset poisoned state if the call was successful.
+                $instance.end_call(call, $crate::extension::CallStatus::is_error(&result));
                 result
             }
             Err(err) => {
+                $instance.end_call(call, true);
+                $instance.report_error(&err);
                 $crate::extension::pool::FromWasmtimeError::from_wasmtime_error(err)
             }
         }
@@ -143,6 +159,22 @@ impl ExtensionGuard {
         self.0.inner.store()
     }
 
+    /// Gives the next call its full execution time budget and starts its span.
+    pub fn start_call(&mut self, function: &'static str) -> ExtensionCall {
+        super::reset_deadline(self.0.inner.store_mut());
+        ExtensionCall::start(self.store().data(), function)
+    }
+
+    /// Records the metrics of a call.
+    pub fn end_call(&self, call: ExtensionCall, failed: bool) {
+        call.end(self.store().data(), failed);
+    }
+
+    /// Records the errors caused by the instance exceeding its limits.
+    pub fn report_error(&self, err: &wasmtime::Error) {
+        super::report_limit_exceeded(self.store().data(), err);
+    }
+
     pub fn dont_use_me_without_wasmsafe(&mut self) -> &mut dyn ExtensionInstance {
         self.0.inner.as_mut()
     }
@@ -16,8 +16,9 @@ impl AuthenticationExtension for GatewayWasmExtensions {
         ids: &[ExtensionId],
     ) -> (http::HeaderMap, Result<Token, ErrorResponse>) {
         let headers = Arc::new(gateway_headers);
+        let extensions = self.current();
 
-        let mut futures = self
+        let mut futures = extensions
             .authentication
             .iter()
             .filter(|pool| ids.contains(&pool.id()))
@@ -74,7 +75,8 @@ impl AuthenticationExtension for GatewayWasmExtensions {
     }
 
     async fn public_metadata_endpoints(&self) -> Result<Vec<PublicMetadataEndpoint>, String> {
-        let endpoints = self
+        let extensions = self.current();
+        let endpoints = extensions
             .authentication
             .iter()
             .map(|pool| async {
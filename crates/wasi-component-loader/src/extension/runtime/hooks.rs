@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use engine::EngineOperationContext;
+use engine::{EngineOperationContext, EngineRequestContext};
 use engine_error::{ErrorResponse, GraphqlError};
 use engine_schema::{GraphqlSubgraph, VirtualSubgraph};
 use event_queue::EventQueue;
@@ -14,8 +14,9 @@ use crate::{
 
 impl GatewayHooksExtension for GatewayWasmExtensions {
     async fn on_request(&self, parts: request::Parts) -> Result<OnRequest, ErrorResponse> {
-        let event_queue = EventQueue::new(self.hooks_event_filter);
-        let Some(pool) = self.hooks.as_ref() else {
+        let extensions = self.current();
+        let event_queue = EventQueue::new(extensions.hooks_event_filter);
+        let Some(pool) = extensions.hooks.as_ref() else {
             return Ok(OnRequest {
                 parts,
                 contract_key: None,
@@ -38,7 +39,8 @@ impl GatewayHooksExtension for GatewayWasmExtensions {
         hooks_context: Arc<[u8]>,
         parts: response::Parts,
     ) -> Result<response::Parts, String> {
-        let Some(pool) = self.hooks.as_ref() else {
+        let extensions = self.current();
+        let Some(pool) = extensions.hooks.as_ref() else {
             return Ok(parts);
         };
         let mut instance = pool.get().await.map_err(|e| e.to_string())?;
@@ -47,14 +49,15 @@ impl GatewayHooksExtension for GatewayWasmExtensions {
     }
 }
 
-impl EngineHooksExtension<EngineOperationContext> for EngineWasmExtensions {
+impl EngineHooksExtension<EngineRequestContext, EngineOperationContext> for EngineWasmExtensions {
     async fn on_graphql_subgraph_request<'r>(
         &self,
         context: EngineOperationContext,
         subgraph: GraphqlSubgraph<'_>,
         parts: ReqwestParts<'r>,
     ) -> Result<ReqwestParts<'r>, GraphqlError> {
-        let Some(pool) = self.gateway_extensions.hooks.as_ref() else {
+        let gateway_extensions = self.gateway_extensions.current();
+        let Some(pool) = gateway_extensions.hooks.as_ref() else {
             return Ok(parts);
         };
         let mut instance = pool.get().await.map_err(|e| {
@@ -71,7 +74,8 @@ impl EngineHooksExtension<EngineOperationContext> for EngineWasmExtensions {
         subgraph: VirtualSubgraph<'_>,
         headers: http::HeaderMap,
     ) -> Result<http::HeaderMap, GraphqlError> {
-        let Some(pool) = self.gateway_extensions.hooks.as_ref() else {
+        let gateway_extensions = self.gateway_extensions.current();
+        let Some(pool) = gateway_extensions.hooks.as_ref() else {
             return Ok(headers);
         };
         let mut instance = pool.get().await.map_err(|e| {
@@ -81,4 +85,21 @@ impl EngineHooksExtension<EngineOperationContext> for EngineWasmExtensions {
 
         wasmsafe!(instance.on_virtual_subgraph_request(context, subgraph, headers).await)
     }
+
+    async fn authorize_policies(
+        &self,
+        context: EngineRequestContext,
+        policies: &[&str],
+    ) -> Result<Vec<bool>, GraphqlError> {
+        let gateway_extensions = self.gateway_extensions.current();
+        let Some(pool) = gateway_extensions.hooks.as_ref() else {
+            return Ok(vec![false; policies.len()]);
+        };
+        let mut instance = pool.get().await.map_err(|e| {
+            tracing::error!("Failed to get instance from pool: {e}");
+            GraphqlError::internal_extension_error()
+        })?;
+
+        wasmsafe!(instance.authorize_policies(context, policies).await)
+    }
 }
@@ -0,0 +1,343 @@
+//! Fake host IO for extensions, used to test them in-process. When an extension state has mocks,
+//! the host implementations of the HTTP client, NATS, Kafka, Postgres, gRPC and Redis never touch
+//! the network. They answer with the registered responses instead and record every call.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+};
+
+use bytes::Bytes;
+
+use crate::extension::api::wit::HttpError;
+
+/// Registered responses and recorded calls of the host IO of an extension.
+#[derive(Debug, Default)]
+pub struct HostIoMocks {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    http_responses: Vec<(http::Method, String, http::Response<Vec<u8>>)>,
+    http_requests: Vec<MockedHttpRequest>,
+    nats_replies: HashMap<String, Vec<u8>>,
+    nats_messages: HashMap<String, Vec<Vec<u8>>>,
+    nats_published: Vec<MockedMessage>,
+    nats_requests: Vec<MockedMessage>,
+    kafka_messages: HashMap<String, Vec<MockedKafkaMessage>>,
+    kafka_produced: Vec<MockedKafkaMessage>,
+    postgres_rows: HashMap<String, Vec<MockPgRow>>,
+    postgres_rows_affected: HashMap<String, u64>,
+    postgres_queries: Vec<String>,
+    grpc_responses: HashMap<(String, String), Result<Vec<u8>, tonic::Status>>,
+    grpc_requests: Vec<MockedGrpcRequest>,
+    redis: MockRedis,
+}
+
+/// In-memory replacement of a Redis server. Expirations are ignored and Lua scripts only return
+/// their registered result.
+#[derive(Debug, Default)]
+pub(crate) struct MockRedis {
+    pub values: HashMap<String, Vec<u8>>,
+    pub hashes: HashMap<String, BTreeMap<String, Vec<u8>>>,
+    pub messages: HashMap<String, Vec<Vec<u8>>>,
+    pub published: Vec<MockedMessage>,
+    pub scripts: HashMap<String, redis::Value>,
+    pub evals: Vec<MockedRedisEval>,
+}
+
+/// An HTTP request sent by the extension.
+#[derive(Debug, Clone)]
+pub struct MockedHttpRequest {
+    pub method: http::Method,
+    pub url: String,
+    pub headers: http::HeaderMap,
+    pub body: Vec<u8>,
+}
+
+/// A NATS or Redis message published, or a NATS request sent, by the extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockedMessage {
+    pub subject: String,
+    pub payload: Vec<u8>,
+}
+
+/// A Lua script run by the extension on Redis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockedRedisEval {
+    pub script: String,
+    pub keys: Vec<String>,
+    pub args: Vec<Vec<u8>>,
+}
+
+/// A Kafka message, either produced by the extension or served to its consumers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockedKafkaMessage {
+    pub topic: String,
+    pub key: Option<String>,
+    pub value: Vec<u8>,
+}
+
+/// A gRPC request sent by the extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockedGrpcRequest {
+    pub service: String,
+    pub method: String,
+    pub message: Vec<u8>,
+}
+
+/// A row returned to a Postgres query. Values are in the binary format of Postgres, as the guest
+/// would receive them from a real database.
+#[derive(Debug, Clone, Default)]
+pub struct MockPgRow {
+    pub(crate) columns: Vec<String>,
+    pub(crate) values: Vec<Option<Vec<u8>>>,
+}
+
+impl MockPgRow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a column, `None` being `NULL`.
+    pub fn column(mut self, name: impl Into<String>, value: Option<impl Into<Vec<u8>>>) -> Self {
+        self.columns.push(name.into());
+        self.values.push(value.map(Into::into));
+        self
+    }
+}
+
+impl HostIoMocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Responds to any HTTP request with this method and URL. The latest registered response wins.
+    pub fn mock_http(&self, method: http::Method, url: impl Into<String>, response: http::Response<Vec<u8>>) -> &Self {
+        self.lock().http_responses.push((method, url.into(), response));
+        self
+    }
+
+    /// Replies to NATS requests on this subject.
+    pub fn mock_nats_reply(&self, subject: impl Into<String>, payload: impl Into<Vec<u8>>) -> &Self {
+        self.lock().nats_replies.insert(subject.into(), payload.into());
+        self
+    }
+
+    /// Messages received by subscribers of this subject, after which the subscription ends.
+    pub fn mock_nats_messages(&self, subject: impl Into<String>, payloads: Vec<Vec<u8>>) -> &Self {
+        self.lock().nats_messages.insert(subject.into(), payloads);
+        self
+    }
+
+    /// Messages received by consumers of this topic, after which the consumer ends.
+    pub fn mock_kafka_messages(&self, topic: impl Into<String>, messages: Vec<(Option<String>, Vec<u8>)>) -> &Self {
+        let topic = topic.into();
+        let messages = messages
+            .into_iter()
+            .map(|(key, value)| MockedKafkaMessage {
+                topic: topic.clone(),
+                key,
+                value,
+            })
+            .collect();
+
+        self.lock().kafka_messages.insert(topic, messages);
+        self
+    }
+
+    /// Rows returned by this exact Postgres query. Unknown queries return no rows.
+    pub fn mock_postgres_query(&self, query: impl Into<String>, rows: Vec<MockPgRow>) -> &Self {
+        self.lock().postgres_rows.insert(query.into(), rows);
+        self
+    }
+
+    /// Number of rows affected by this exact Postgres statement. Unknown statements affect no rows.
+    pub fn mock_postgres_execute(&self, query: impl Into<String>, rows_affected: u64) -> &Self {
+        self.lock().postgres_rows_affected.insert(query.into(), rows_affected);
+        self
+    }
+
+    /// Responds to unary gRPC calls of this service method.
+    pub fn mock_grpc_unary(
+        &self,
+        service: impl Into<String>,
+        method: impl Into<String>,
+        response: Result<Vec<u8>, tonic::Status>,
+    ) -> &Self {
+        self.lock()
+            .grpc_responses
+            .insert((service.into(), method.into()), response);
+        self
+    }
+
+    /// Sets the value of a Redis key.
+    pub fn mock_redis_value(&self, key: impl Into<String>, value: impl Into<Vec<u8>>) -> &Self {
+        self.lock().redis.values.insert(key.into(), value.into());
+        self
+    }
+
+    /// Result of this exact Lua script. Unknown scripts fail.
+    pub fn mock_redis_script(&self, script: impl Into<String>, result: redis::Value) -> &Self {
+        self.lock().redis.scripts.insert(script.into(), result);
+        self
+    }
+
+    /// Messages received by subscribers of this Redis channel, after which the subscription ends.
+    pub fn mock_redis_messages(&self, channel: impl Into<String>, payloads: Vec<Vec<u8>>) -> &Self {
+        self.lock().redis.messages.insert(channel.into(), payloads);
+        self
+    }
+
+    pub fn http_requests(&self) -> Vec<MockedHttpRequest> {
+        self.lock().http_requests.clone()
+    }
+
+    pub fn nats_published(&self) -> Vec<MockedMessage> {
+        self.lock().nats_published.clone()
+    }
+
+    pub fn nats_requests(&self) -> Vec<MockedMessage> {
+        self.lock().nats_requests.clone()
+    }
+
+    pub fn kafka_produced(&self) -> Vec<MockedKafkaMessage> {
+        self.lock().kafka_produced.clone()
+    }
+
+    /// Queries and statements sent to Postgres, in order.
+    pub fn postgres_queries(&self) -> Vec<String> {
+        self.lock().postgres_queries.clone()
+    }
+
+    pub fn grpc_requests(&self) -> Vec<MockedGrpcRequest> {
+        self.lock().grpc_requests.clone()
+    }
+
+    /// Current value of a Redis key.
+    pub fn redis_value(&self, key: &str) -> Option<Vec<u8>> {
+        self.lock().redis.values.get(key).cloned()
+    }
+
+    /// Messages published on Redis channels, in order.
+    pub fn redis_published(&self) -> Vec<MockedMessage> {
+        self.lock().redis.published.clone()
+    }
+
+    /// Lua scripts run on Redis, in order.
+    pub fn redis_evals(&self) -> Vec<MockedRedisEval> {
+        self.lock().redis.evals.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        // A panicking test shouldn't hide the calls recorded so far.
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn send_http_request(&self, request: reqwest::Request) -> Result<http::Response<Bytes>, HttpError> {
+        let mut inner = self.lock();
+
+        let response = inner
+            .http_responses
+            .iter()
+            .rev()
+            .find(|(method, url, _)| method == request.method() && url == request.url().as_str())
+            .map(|(_, _, response)| {
+                let mut builder = http::Response::builder().status(response.status());
+                if let Some(headers) = builder.headers_mut() {
+                    headers.extend(response.headers().clone());
+                }
+                builder.body(Bytes::from(response.body().clone())).unwrap()
+            });
+
+        inner.http_requests.push(MockedHttpRequest {
+            method: request.method().clone(),
+            url: request.url().to_string(),
+            headers: request.headers().clone(),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(<[u8]>::to_vec)
+                .unwrap_or_default(),
+        });
+
+        response
+            .ok_or_else(|| HttpError::Connect(format!("No mocked response for {} {}", request.method(), request.url())))
+    }
+
+    pub(crate) fn nats_publish(&self, subject: String, payload: Vec<u8>) {
+        self.lock().nats_published.push(MockedMessage { subject, payload });
+    }
+
+    pub(crate) fn nats_request(&self, subject: String, payload: Vec<u8>) -> Result<Vec<u8>, String> {
+        let mut inner = self.lock();
+        let reply = inner
+            .nats_replies
+            .get(&subject)
+            .cloned()
+            .ok_or_else(|| format!("No mocked reply for NATS subject {subject}"));
+
+        inner.nats_requests.push(MockedMessage { subject, payload });
+
+        reply
+    }
+
+    pub(crate) fn nats_messages(&self, subject: &str) -> Vec<Vec<u8>> {
+        self.lock().nats_messages.get(subject).cloned().unwrap_or_default()
+    }
+
+    pub(crate) fn kafka_produce(&self, topic: String, key: Option<String>, value: Vec<u8>) {
+        self.lock()
+            .kafka_produced
+            .push(MockedKafkaMessage { topic, key, value });
+    }
+
+    pub(crate) fn kafka_messages(&self, topic: &str) -> Vec<MockedKafkaMessage> {
+        self.lock().kafka_messages.get(topic).cloned().unwrap_or_default()
+    }
+
+    pub(crate) fn postgres_query(&self, query: String) -> Vec<MockPgRow> {
+        let mut inner = self.lock();
+        let rows = inner.postgres_rows.get(&query).cloned().unwrap_or_default();
+        inner.postgres_queries.push(query);
+        rows
+    }
+
+    pub(crate) fn postgres_execute(&self, query: String) -> u64 {
+        let mut inner = self.lock();
+        let rows_affected = inner.postgres_rows_affected.get(&query).copied().unwrap_or_default();
+        inner.postgres_queries.push(query);
+        rows_affected
+    }
+
+    pub(crate) fn grpc_unary(
+        &self,
+        service: String,
+        method: String,
+        message: Vec<u8>,
+    ) -> Result<Vec<u8>, tonic::Status> {
+        let mut inner = self.lock();
+        let response = inner
+            .grpc_responses
+            .get(&(service.clone(), method.clone()))
+            .cloned()
+            .unwrap_or_else(|| {
+                Err(tonic::Status::unimplemented(format!(
+                    "No mocked response for {service}/{method}"
+                )))
+            });
+
+        inner.grpc_requests.push(MockedGrpcRequest {
+            service,
+            method,
+            message,
+        });
+
+        response
+    }
+
+    pub(crate) fn redis<R>(&self, f: impl FnOnce(&mut MockRedis) -> R) -> R {
+        f(&mut self.lock().redis)
+    }
+}
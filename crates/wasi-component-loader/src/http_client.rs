@@ -1,35 +1,27 @@
-use crate::extension::api::wit::HttpError;
+use crate::{extension::api::wit::HttpError, host_io_mocks::HostIoMocks, resources::HttpBodyReader};
 use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
 use grafbase_telemetry::otel::opentelemetry::{KeyValue, metrics::Histogram};
-use http_body_util::BodyExt;
-use std::time::Instant;
-use tracing::{Instrument, field::Empty, info_span};
+use http_body_util::{BodyDataStream, BodyExt};
+use std::{convert::Infallible, sync::Arc, time::Instant};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{Instrument, Span, field::Empty, info_span};
 
 pub(crate) async fn send_request(
     (client, request): (reqwest::Client, reqwest::Request),
     request_durations: Histogram<u64>,
+    host_io_mocks: Option<Arc<HostIoMocks>>,
 ) -> Result<http::Response<Bytes>, HttpError> {
+    if let Some(mocks) = host_io_mocks {
+        return mocks.send_http_request(request);
+    }
+
     let start = Instant::now();
 
     let mut attributes = request_attributes(&request);
 
-    let span = info_span!(
-        "hook-http-request",
-        "http.request.body.size" = request
-            .body()
-            .and_then(|b| b.as_bytes())
-            .map(|b| b.len())
-            .unwrap_or_default(),
-        "http.request.method" = request.method().as_ref(),
-        "http.response.body.size" = Empty,
-        "http.response.status_code" = Empty,
-        "otel.name" = format!("{} {}", request.method().as_ref(), request.url().path()),
-        "server.address" = request.url().host_str(),
-        "server.port" = request.url().port(),
-        "url.path" = request.url().path(),
-        "otel.status_code" = Empty,
-        "error.message" = Empty,
-    );
+    let span = request_span(&request);
 
     let result = client.execute(request).instrument(span.clone()).await;
     let duration = start.elapsed().as_millis() as u64;
@@ -61,6 +53,83 @@ pub(crate) async fn send_request(
     }
 }
 
+/// Sends a request and returns as soon as the response headers are received, the response body
+/// being streamed. If `body` is provided, the request body is streamed from it.
+pub(crate) async fn send_streaming_request(
+    (client, mut request): (reqwest::Client, reqwest::Request),
+    body: Option<mpsc::Receiver<Bytes>>,
+    request_durations: Histogram<u64>,
+    host_io_mocks: Option<Arc<HostIoMocks>>,
+) -> Result<http::Response<HttpBodyReader>, HttpError> {
+    if let Some(mocks) = host_io_mocks {
+        if let Some(body) = body {
+            let chunks = ReceiverStream::new(body).collect::<Vec<_>>().await;
+            *request.body_mut() = Some(chunks.concat().into());
+        }
+
+        let response = mocks.send_http_request(request)?;
+        return Ok(response.map(|body| HttpBodyReader::new(futures::stream::once(async move { Ok(body) }))));
+    }
+
+    if let Some(body) = body {
+        let body = ReceiverStream::new(body).map(Ok::<_, Infallible>);
+        *request.body_mut() = Some(reqwest::Body::wrap_stream(body));
+    }
+
+    let start = Instant::now();
+
+    let mut attributes = request_attributes(&request);
+    let span = request_span(&request);
+
+    let result = client.execute(request).instrument(span.clone()).await;
+    let duration = start.elapsed().as_millis() as u64;
+
+    merge_response_attributes(&mut attributes, &result);
+    request_durations.record(duration, &attributes);
+
+    match result {
+        Ok(response) => {
+            let response: http::Response<reqwest::Body> = response.into();
+            let (parts, body) = response.into_parts();
+
+            span.record("http.response.status_code", parts.status.as_u16());
+
+            let body =
+                BodyDataStream::new(body).map_err(|err| HttpError::Connect(format!("Failed to receive body {err}")));
+
+            Ok(http::Response::from_parts(parts, HttpBodyReader::new(body)))
+        }
+        Err(error) => {
+            let error_message = error.to_string();
+
+            span.record("otel.status_code", "Error");
+            span.record("error.message", &error_message);
+
+            Err(HttpError::Connect(error_message))
+        }
+    }
+}
+
+fn request_span(request: &reqwest::Request) -> Span {
+    info_span!(
+        "hook-http-request",
+        "http.request.body.size" = request
+            .body()
+            .and_then(|b| b.as_bytes())
+            .map(|b| b.len())
+            .unwrap_or_default(),
+        "http.request.method" = request.method().as_ref(),
+        "http.response.body.size" = Empty,
+        "http.response.status_code" = Empty,
+        "otel.name" = format!("{} {}", request.method().as_ref(), request.url().path()),
+        "server.address" = request.url().host_str(),
+        "server.port" = request.url().port(),
+        "url.path" = request.url().path(),
+        "otel.status_code" = Empty,
+        "error.message" = Empty,
+    )
+}
+
 fn request_attributes(request: &reqwest::Request) -> Vec<KeyValue> {
     let mut attributes = Vec::new();
 
@@ -10,9 +10,12 @@ mod cache;
 mod cbor;
 mod config;
 pub mod extension;
+pub mod host_io_mocks;
 mod http_client;
 pub mod resources;
 mod state;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_harness;
 
 #[cfg(test)]
 mod tests;
@@ -1,44 +1,116 @@
 use std::{
-    sync::Arc,
-    time::{Duration, SystemTime},
+    sync::{Arc, LazyLock},
+    time::{Duration, Instant, SystemTime},
 };
 
 use dashmap::DashMap;
+use deadpool::managed;
 use futures::TryFutureExt;
 use tokio::sync::{mpsc, oneshot};
 use ulid::Ulid;
+
+use super::RedisManager;
+
 type WaitListReceiver = mpsc::Receiver<oneshot::Sender<Arc<[u8]>>>;
 type WaitListSender = mpsc::Sender<oneshot::Sender<Arc<[u8]>>>;
 
+/// How often an instance waiting for a value computed by another gateway checks Redis.
+const REDIS_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Returns the value if present. Otherwise reserves the entry with a lock expiring after the
+/// timeout, returning 0 if acquired and 2 if another instance holds it.
+static REDIS_GET_OR_RESERVE: LazyLock<redis::Script> = LazyLock::new(|| {
+    redis::Script::new(
+        r"
+        local value = redis.call('GET', KEYS[1])
+        if value then
+            return {1, value}
+        end
+        if redis.call('SET', KEYS[2], '1', 'NX', 'PX', ARGV[1]) then
+            return {0, ''}
+        end
+        return {2, ''}
+        ",
+    )
+});
+
+/// Where the named caches of an extension are stored.
+#[derive(Clone, Default)]
+pub enum CacheStorage {
+    #[default]
+    Memory,
+    /// Shared by all gateway instances, keys are prefixed with `key_prefix`.
+    Redis {
+        pool: managed::Pool<RedisManager>,
+        key_prefix: String,
+    },
+}
+
 #[derive(Clone)]
 pub struct Cache(Arc<CacheInner>);
 
-impl std::ops::Deref for Cache {
-    type Target = CacheInner;
-    fn deref(&self) -> &Self::Target {
-        &self.0
+enum CacheInner {
+    Memory(MemoryCache),
+    Redis(RedisCache),
+}
+
+impl Cache {
+    pub fn new(storage: &CacheStorage, name: &str, max_capacity: usize, ttl: Option<Duration>) -> Self {
+        let inner = match storage {
+            CacheStorage::Memory => CacheInner::Memory(MemoryCache::new(max_capacity, ttl)),
+            CacheStorage::Redis { pool, key_prefix } => CacheInner::Redis(RedisCache {
+                pool: pool.clone(),
+                key_prefix: format!("{key_prefix}:{name}"),
+                ttl,
+            }),
+        };
+
+        Self(Arc::new(inner))
+    }
+
+    /// Gets a value from the cache by key. If this function returns None, the caller must set a new one.
+    pub async fn get(&self, key: &str, timeout: Duration) -> Option<Arc<[u8]>> {
+        match self.0.as_ref() {
+            CacheInner::Memory(cache) => cache.get(key, timeout).await,
+            CacheInner::Redis(cache) => cache.get(key, timeout).await,
+        }
+    }
+
+    /// Sets a value in the cache, releasing the callers waiting for it.
+    pub async fn insert(&self, key: &str, value: Arc<[u8]>) {
+        match self.0.as_ref() {
+            CacheInner::Memory(cache) => cache.insert(key, value).await,
+            CacheInner::Redis(cache) => cache.insert(key, value).await,
+        }
+    }
+
+    pub async fn remove(&self, key: &str) {
+        match self.0.as_ref() {
+            CacheInner::Memory(cache) => cache.remove(key),
+            CacheInner::Redis(cache) => cache.remove(key).await,
+        }
     }
 }
 
-pub struct CacheInner {
+struct MemoryCache {
     cache: mini_moka::sync::Cache<String, Arc<[u8]>>,
     wait_list: DashMap<String, (Ulid, WaitListSender, WaitListReceiver)>,
 }
 
-impl Cache {
-    pub fn new(max_capacity: usize, ttl: Option<Duration>) -> Self {
+impl MemoryCache {
+    fn new(max_capacity: usize, ttl: Option<Duration>) -> Self {
         let mut builder = mini_moka::sync::Cache::builder().max_capacity(max_capacity as u64);
         if let Some(ttl) = ttl {
             builder = builder.time_to_live(ttl);
         }
-        Self(Arc::new(CacheInner {
+        Self {
             cache: builder.build(),
             wait_list: DashMap::new(),
-        }))
+        }
     }
 
     /// Gets a value from the cache by key. If this function returns None, the caller must set a new one.
-    pub async fn get(&self, key: &str, timeout: Duration) -> Option<Arc<[u8]>> {
+    async fn get(&self, key: &str, timeout: Duration) -> Option<Arc<[u8]>> {
         let key_string = key.to_owned();
         if let Some(value) = self.cache.get(&key_string) {
             return Some(value);
@@ -91,7 +163,7 @@ impl Cache {
     }
 
     /// Sets a value in the cache with an optional time-to-live duration in milliseconds.
-    pub async fn insert(&self, key: &str, value: Arc<[u8]>) {
+    async fn insert(&self, key: &str, value: Arc<[u8]>) {
         self.cache.insert(key.to_owned(), value.clone());
 
         // We remove the wait list so subsequent calls do not add themselves to the list. The value
@@ -104,7 +176,7 @@ impl Cache {
         }
     }
 
-    pub fn remove(&self, key: &str) {
+    fn remove(&self, key: &str) {
         self.wait_list.remove(key);
         self.cache.invalidate(&key.to_owned());
     }
@@ -146,3 +218,104 @@ impl Cache {
         }
     }
 }
+
+/// A cache shared by all gateway instances. The reservation of an entry is a lock in Redis, so
+/// a single instance across the cluster computes a missing value while the others wait for it.
+struct RedisCache {
+    pool: managed::Pool<RedisManager>,
+    key_prefix: String,
+    ttl: Option<Duration>,
+}
+
+impl RedisCache {
+    async fn get(&self, key: &str, timeout: Duration) -> Option<Arc<[u8]>> {
+        let value_key = self.value_key(key);
+        let lock_key = self.lock_key(key);
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let result = async {
+                let mut connection = self.pool.get().await.map_err(|err| err.to_string())?;
+
+                REDIS_GET_OR_RESERVE
+                    .key(&value_key)
+                    .key(&lock_key)
+                    .arg(redis_millis(timeout))
+                    .invoke_async::<(u8, Vec<u8>)>(&mut *connection)
+                    .await
+                    .map_err(|err| err.to_string())
+            }
+            .await;
+
+            match result {
+                Ok((1, value)) => return Some(value.into()),
+                // We hold the reservation, the caller must compute the value.
+                Ok((0, _)) => return None,
+                Ok(_) => (),
+                Err(err) => {
+                    tracing::error!("failed to read the extension cache from Redis: {err}");
+                    return None;
+                }
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                tracing::error!("timed out waiting for cached value in extension cache to be available");
+                return None;
+            }
+
+            tokio::time::sleep(REDIS_POLL_INTERVAL.min(deadline - now)).await;
+        }
+    }
+
+    async fn insert(&self, key: &str, value: Arc<[u8]>) {
+        let mut pipeline = redis::pipe();
+
+        let set = pipeline.cmd("SET").arg(self.value_key(key)).arg(value.as_ref());
+        if let Some(ttl) = self.ttl {
+            set.arg("PX").arg(redis_millis(ttl));
+        }
+        set.ignore().cmd("DEL").arg(self.lock_key(key)).ignore();
+
+        if let Err(err) = self.query(&pipeline).await {
+            tracing::error!("failed to write the extension cache to Redis: {err}");
+        }
+    }
+
+    async fn remove(&self, key: &str) {
+        let mut pipeline = redis::pipe();
+        pipeline
+            .cmd("DEL")
+            .arg(self.value_key(key))
+            .ignore()
+            .cmd("DEL")
+            .arg(self.lock_key(key))
+            .ignore();
+
+        if let Err(err) = self.query(&pipeline).await {
+            tracing::error!("failed to remove an entry of the extension cache from Redis: {err}");
+        }
+    }
+
+    async fn query(&self, pipeline: &redis::Pipeline) -> Result<(), String> {
+        let mut connection = self.pool.get().await.map_err(|err| err.to_string())?;
+
+        pipeline
+            .query_async::<()>(&mut *connection)
+            .await
+            .map_err(|err| err.to_string())
+    }
+
+    fn value_key(&self, key: &str) -> String {
+        format!("{}:value:{key}", self.key_prefix)
+    }
+
+    fn lock_key(&self, key: &str) -> String {
+        format!("{}:lock:{key}", self.key_prefix)
+    }
+}
+
+/// Redis rejects a `PX` of zero, so sub-millisecond durations are rounded up.
+fn redis_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX).max(1)
+}
@@ -1,7 +1,7 @@
 use std::{io::Write, path::Path, sync::Arc};
 
 use crossbeam::sync::WaitGroup;
-use rolling_logger::RotateStrategy;
+use rolling_logger::{RetentionPolicy, RotateStrategy};
 
 pub struct Inner {
     sender: crossbeam::channel::Sender<LogMessage>,
@@ -19,12 +19,13 @@ pub struct FileLogger {
 }
 
 impl FileLogger {
-    /// Creates a new file logger with the specified path and rotation strategy.
+    /// Creates a new file logger with the specified path, rotation strategy and retention policy.
     ///
     /// # Arguments
     ///
     /// * `path` - The file path where logs will be written
     /// * `rotate` - The rotation strategy to use for log files
+    /// * `retention` - What happens to the rotated files
     ///
     /// # Returns
     ///
@@ -34,15 +35,20 @@ impl FileLogger {
     /// # Examples
     ///
     /// ```ignore
-    /// use rolling_logger::RotateStrategy;
+    /// use rolling_logger::{RetentionPolicy, RotateStrategy};
     ///
-    /// let logger = FileLogger::new("app.log", RotateStrategy::Size(1024 * 1024))?;
+    /// let logger = FileLogger::new(
+    ///     "app.log",
+    ///     RotateStrategy::size(1024 * 1024),
+    ///     RetentionPolicy::default(),
+    /// )?;
     /// # Ok::<(), String>(())
     /// ```
-    pub fn new(path: impl AsRef<Path>, rotate: RotateStrategy) -> Result<Self, String> {
+    pub fn new(path: impl AsRef<Path>, rotate: RotateStrategy, retention: RetentionPolicy) -> Result<Self, String> {
         let (sender, receiver) = crossbeam::channel::unbounded();
 
-        let mut logger = rolling_logger::RollingLogger::new(path, rotate).map_err(|e| e.to_string())?;
+        let mut logger =
+            rolling_logger::RollingLogger::with_retention(path, rotate, retention).map_err(|e| e.to_string())?;
 
         let logger_task = tokio::task::spawn_blocking(move || {
             while let Ok(message) = receiver.recv() {
@@ -100,7 +106,11 @@ impl FileLogger {
     ///
     /// ```ignore
     /// # async fn example() -> Result<(), String> {
-    /// let logger = FileLogger::new("app.log", rolling_logger::RotateStrategy::Never)?;
+    /// let logger = FileLogger::new(
+    ///     "app.log",
+    ///     rolling_logger::RotateStrategy::never(),
+    ///     rolling_logger::RetentionPolicy::default(),
+    /// )?;
     /// logger.send(b"final log message".to_vec())?;
     /// logger.graceful_shutdown().await;
     /// # Ok(())
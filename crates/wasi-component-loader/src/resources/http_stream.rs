@@ -0,0 +1,63 @@
+use bytes::Bytes;
+use futures::{Stream, StreamExt, stream::BoxStream};
+use tokio::{sync::mpsc, task::JoinHandle};
+
+use crate::extension::api::wit::HttpError;
+
+/// The body of a response received in chunks.
+pub struct HttpBodyReader(BoxStream<'static, Result<Bytes, HttpError>>);
+
+impl HttpBodyReader {
+    pub fn new(stream: impl Stream<Item = Result<Bytes, HttpError>> + Send + 'static) -> Self {
+        Self(stream.boxed())
+    }
+
+    pub async fn next(&mut self) -> Option<Result<Bytes, HttpError>> {
+        self.0.next().await
+    }
+}
+
+/// The body of a request sent in chunks, while the request itself runs in a separate task. The
+/// request is aborted if the writer is dropped before being finished, so the server never
+/// receives a truncated body.
+pub struct HttpBodyWriter {
+    sender: Option<mpsc::Sender<Bytes>>,
+    response: JoinHandle<Result<http::Response<HttpBodyReader>, HttpError>>,
+}
+
+impl HttpBodyWriter {
+    pub fn new(
+        sender: mpsc::Sender<Bytes>,
+        response: JoinHandle<Result<http::Response<HttpBodyReader>, HttpError>>,
+    ) -> Self {
+        Self {
+            sender: Some(sender),
+            response,
+        }
+    }
+
+    /// Waits until the request is ready to accept the chunk.
+    pub async fn write(&self, chunk: Bytes) -> Result<(), HttpError> {
+        self.sender
+            .as_ref()
+            .expect("Only removed when finishing, which consumes the writer")
+            .send(chunk)
+            .await
+            .map_err(|_| HttpError::Connect("The request was closed before the body was fully sent".into()))
+    }
+
+    /// Ends the body and waits for the response headers.
+    pub async fn finish(mut self) -> Result<http::Response<HttpBodyReader>, HttpError> {
+        self.sender = None;
+
+        (&mut self.response)
+            .await
+            .map_err(|err| HttpError::Connect(format!("The request failed: {err}")))?
+    }
+}
+
+impl Drop for HttpBodyWriter {
+    fn drop(&mut self) {
+        self.response.abort();
+    }
+}
@@ -4,7 +4,13 @@ use std::{
 };
 
 use futures::{Stream, StreamExt, TryStreamExt};
-use rskafka::{client::consumer::StreamConsumer, record::RecordAndOffset};
+use rskafka::{
+    chrono,
+    client::consumer::StreamConsumer,
+    record::{Record, RecordAndOffset},
+};
+
+use crate::host_io_mocks::MockedKafkaMessage;
 
 type ConsumerStream = Pin<Box<dyn Stream<Item = Result<(RecordAndOffset, i64), String>> + Send + 'static>>;
 
@@ -19,6 +25,35 @@ impl KafkaConsumer {
 
         Self { inner }
     }
+
+    /// Serves the given messages, as if they were the whole topic, and ends.
+    pub fn mocked(messages: Vec<MockedKafkaMessage>) -> Self {
+        let high_watermark = messages.len() as i64;
+        let records = messages
+            .into_iter()
+            .enumerate()
+            .map(|(offset, message)| {
+                let record = Record {
+                    key: message.key.map(String::into_bytes),
+                    value: Some(message.value),
+                    headers: Default::default(),
+                    timestamp: chrono::Utc::now(),
+                };
+
+                Ok((
+                    RecordAndOffset {
+                        record,
+                        offset: offset as i64,
+                    },
+                    high_watermark,
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            inner: Box::pin(futures::stream::iter(records)),
+        }
+    }
 }
 
 impl Stream for KafkaConsumer {
@@ -17,10 +17,14 @@ use rskafka::{
     record::Record,
 };
 
+use crate::host_io_mocks::HostIoMocks;
+
 #[derive(Debug)]
 pub enum ProducerKind {
     Batch(BatchProducer<RecordAggregator>),
     Single(PartitionClient, Compression),
+    /// Records the messages in the host IO mocks instead of sending them to a broker.
+    Mocked(Arc<HostIoMocks>, String),
 }
 
 impl ProducerKind {
@@ -38,6 +42,10 @@ impl ProducerKind {
                     .await
                     .map_err(|e| format!("Failed to produce message: {e}"))?;
             }
+            ProducerKind::Mocked(mocks, topic) => {
+                let key = record.key.map(|key| String::from_utf8_lossy(&key).into_owned());
+                mocks.kafka_produce(topic.clone(), key, record.value.unwrap_or_default());
+            }
         }
 
         Ok(())
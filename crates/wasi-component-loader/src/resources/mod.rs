@@ -1,39 +1,47 @@
 mod cache;
 mod file_logger;
 mod headers;
+mod http_stream;
 mod kafka_consumer;
 mod kafka_producer;
 mod legacy_context;
 mod legacy_sdk18;
 mod nats;
+mod postgres;
+mod redis;
 
 use std::sync::Arc;
 
 use event_queue::EventQueue;
-use sqlx::Postgres;
 
+use crate::host_io_mocks::HostIoMocks;
+
+pub use self::redis::*;
 pub use cache::*;
 pub use headers::*;
+pub use http_stream::*;
 pub use kafka_consumer::*;
 pub use kafka_producer::*;
 pub use legacy_context::*;
 pub use legacy_sdk18::*;
 pub use nats::*;
+pub use postgres::*;
+
+#[derive(Clone)]
+pub enum GrpcClient {
+    Connected(tonic::client::Grpc<tonic::transport::Channel>),
+    /// Answered by the host IO mocks instead of a gRPC server.
+    Mocked(Arc<HostIoMocks>),
+}
 
-pub type GrpcClient = tonic::client::Grpc<tonic::transport::Channel>;
 pub type GrpcStreamingResponse = (
     tonic::metadata::MetadataMap,
     tonic::Streaming<Vec<u8>>,
     tonic::Extensions,
 );
 
-pub type NatsClient = async_nats::Client;
 pub type NatsKeyValue = async_nats::jetstream::kv::Store;
 
-pub type PgPool = sqlx::Pool<Postgres>;
-pub type PgConnection = sqlx::pool::PoolConnection<Postgres>;
-pub type PgTransaction = sqlx::Transaction<'static, Postgres>;
-pub type PgRow = sqlx::postgres::PgRow;
 pub type FileLogger = file_logger::FileLogger;
 
 pub type EventQueueResource = Arc<EventQueue>;
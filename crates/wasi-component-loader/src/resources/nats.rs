@@ -1,19 +1,40 @@
+use std::sync::Arc;
+
 use futures::StreamExt;
 
+use crate::host_io_mocks::HostIoMocks;
+
+pub enum NatsClient {
+    Connected(async_nats::Client),
+    /// Answered by the host IO mocks instead of a NATS server.
+    Mocked(Arc<HostIoMocks>),
+}
+
 pub enum NatsSubscriber {
     Stream(Box<async_nats::jetstream::consumer::pull::Stream>),
     Subject(async_nats::Subscriber),
+    Mocked {
+        subject: String,
+        payloads: std::vec::IntoIter<Vec<u8>>,
+    },
 }
 
 impl NatsSubscriber {
-    pub async fn next(&mut self) -> Result<Option<async_nats::Message>, String> {
+    /// Returns the subject and payload of the next message.
+    pub async fn next(&mut self) -> Result<Option<(String, Vec<u8>)>, String> {
         match self {
             NatsSubscriber::Stream(stream) => match stream.as_mut().next().await {
-                Some(Ok(message)) => Ok(Some(message.into())),
+                Some(Ok(message)) => Ok(Some((message.subject.to_string(), message.payload.to_vec()))),
                 Some(Err(err)) => Err(err.to_string()),
                 None => Ok(None),
             },
-            NatsSubscriber::Subject(subject) => Ok(subject.next().await),
+            NatsSubscriber::Subject(subject) => Ok(subject
+                .next()
+                .await
+                .map(|message| (message.subject.to_string(), message.payload.into()))),
+            NatsSubscriber::Mocked { subject, payloads } => {
+                Ok(payloads.next().map(|payload| (subject.clone(), payload)))
+            }
         }
     }
 }
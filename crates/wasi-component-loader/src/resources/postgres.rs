@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use sqlx::Postgres;
+
+use crate::host_io_mocks::{HostIoMocks, MockPgRow};
+
+pub type PgPool = sqlx::Pool<Postgres>;
+
+pub enum PgConnection {
+    Pooled(sqlx::pool::PoolConnection<Postgres>),
+    /// Answered by the host IO mocks instead of a database.
+    Mocked(Arc<HostIoMocks>),
+}
+
+pub enum PgTransaction {
+    Transaction(sqlx::Transaction<'static, Postgres>),
+    /// Answered by the host IO mocks instead of a database.
+    Mocked(Arc<HostIoMocks>),
+}
+
+pub enum PgRow {
+    Row(sqlx::postgres::PgRow),
+    Mocked(MockPgRow),
+}
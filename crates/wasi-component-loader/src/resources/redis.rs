@@ -0,0 +1,138 @@
+use std::{
+    path::Path,
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use deadpool::managed::{self, Metrics};
+use futures::{Stream, StreamExt};
+use redis::{Client, ClientTlsConfig, RedisError, TlsCertificates, aio::MultiplexedConnection};
+
+use crate::host_io_mocks::HostIoMocks;
+
+#[derive(Clone)]
+pub enum RedisPool {
+    Connected(managed::Pool<RedisManager>),
+    /// Answered by the in-memory store of the host IO mocks instead of a Redis server.
+    Mocked(Arc<HostIoMocks>),
+}
+
+pub enum RedisSubscriber {
+    Connected(Pin<Box<dyn Stream<Item = redis::Msg> + Send>>),
+    Mocked(std::vec::IntoIter<(String, Vec<u8>)>),
+}
+
+impl RedisSubscriber {
+    /// Returns the channel and payload of the next message.
+    pub async fn next(&mut self) -> Option<(String, Vec<u8>)> {
+        match self {
+            RedisSubscriber::Connected(stream) => stream.next().await.map(|message| {
+                (
+                    message.get_channel_name().to_string(),
+                    message.get_payload_bytes().to_vec(),
+                )
+            }),
+            RedisSubscriber::Mocked(messages) => messages.next(),
+        }
+    }
+}
+
+pub struct RedisManager {
+    client: Client,
+    ping_number: AtomicUsize,
+}
+
+impl RedisManager {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            ping_number: AtomicUsize::new(0),
+        }
+    }
+
+    /// The client of the pool, to open dedicated pub/sub connections.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+impl managed::Manager for RedisManager {
+    type Type = MultiplexedConnection;
+    type Error = RedisError;
+
+    async fn create(&self) -> Result<MultiplexedConnection, Self::Error> {
+        self.client.get_multiplexed_async_connection().await
+    }
+
+    async fn recycle(&self, conn: &mut MultiplexedConnection, _: &Metrics) -> managed::RecycleResult<Self::Error> {
+        let ping_number = self.ping_number.fetch_add(1, Ordering::Relaxed).to_string();
+
+        // Using pipeline to avoid roundtrip for UNWATCH
+        let (n,) = redis::Pipeline::with_capacity(2)
+            .cmd("UNWATCH")
+            .ignore()
+            .cmd("PING")
+            .arg(&ping_number)
+            .query_async::<(String,)>(conn)
+            .await?;
+
+        if n == ping_number {
+            Ok(())
+        } else {
+            Err(managed::RecycleError::message("Invalid PING response"))
+        }
+    }
+}
+
+/// Paths to the PEM files of a TLS connection to Redis.
+pub struct RedisTlsPaths<'a> {
+    pub cert: Option<&'a Path>,
+    pub key: Option<&'a Path>,
+    pub ca: Option<&'a Path>,
+}
+
+/// Creates a pool of Redis connections, which are only opened once needed.
+pub fn create_redis_pool(
+    url: &str,
+    tls: Option<RedisTlsPaths<'_>>,
+    max_connections: Option<usize>,
+    wait_timeout: Duration,
+) -> Result<managed::Pool<RedisManager>, String> {
+    let client = match tls {
+        Some(tls) => {
+            let read = |path: &Path, what: &str| {
+                std::fs::read(path)
+                    .map_err(|err| format!("Failed to load the Redis {what} at {}: {err}", path.display()))
+            };
+
+            let client_tls = match tls.cert.zip(tls.key) {
+                Some((cert, key)) => Some(ClientTlsConfig {
+                    client_cert: read(cert, "client certificate")?,
+                    client_key: read(key, "client key")?,
+                }),
+                None => None,
+            };
+
+            let root_cert = tls.ca.map(|ca| read(ca, "CA certificate")).transpose()?;
+
+            Client::build_with_tls(url, TlsCertificates { client_tls, root_cert })
+        }
+        None => Client::open(url),
+    }
+    .map_err(|err| err.to_string())?;
+
+    let mut builder = managed::Pool::builder(RedisManager::new(client))
+        .wait_timeout(Some(wait_timeout))
+        .create_timeout(Some(Duration::from_secs(10)))
+        .runtime(deadpool::Runtime::Tokio1);
+
+    if let Some(max_connections) = max_connections {
+        builder = builder.max_size(max_connections);
+    }
+
+    builder.build().map_err(|err| err.to_string())
+}
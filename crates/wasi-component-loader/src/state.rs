@@ -3,21 +3,33 @@ use std::{sync::Arc, time::Duration};
 use dashmap::DashMap;
 use engine_error::{ErrorCode, ErrorResponse};
 use extension_catalog::{ExtensionCatalog, ExtensionId};
-use grafbase_telemetry::{metrics::meter_from_global_provider, otel::opentelemetry::metrics::Histogram};
+use grafbase_telemetry::{
+    metrics::meter_from_global_provider,
+    otel::opentelemetry::metrics::{Counter, Histogram},
+};
 use sqlx::Postgres;
 use wasmtime::component::Resource;
 use wasmtime_wasi::{
     ResourceTable,
     p2::{IoView, WasiCtx, WasiView},
 };
-use wasmtime_wasi_http::{WasiHttpCtx, WasiHttpView};
+use wasmtime_wasi_http::{
+    HttpResult, WasiHttpCtx, WasiHttpView,
+    bindings::http::types::ErrorCode as WasiHttpErrorCode,
+    body::HyperOutgoingBody,
+    types::{HostFutureIncomingResponse, OutgoingRequestConfig, default_send_request},
+};
 
 use crate::{
     cache::LegacyCache,
-    extension::{ExtensionConfig, api::since_0_17_0::world as wit17, api::wit},
-    resources::{Cache, FileLogger, GrpcClient, KafkaProducer, OwnedOrShared, WasmOwnedOrLease},
+    extension::{ExtensionConfig, ExtensionMetrics, Limiter, api::since_0_17_0::world as wit17, api::wit},
+    host_io_mocks::HostIoMocks,
+    resources::{Cache, FileLogger, GrpcClient, KafkaProducer, OwnedOrShared, RedisPool, WasmOwnedOrLease},
 };
 
+/// The maximum number of redirects followed by the HTTP client, as with the default policy.
+const MAX_REDIRECTS: usize = 10;
+
 /// Represents the state of the WASI environment.
 ///
 /// This structure encapsulates the WASI context, HTTP context, and a resource table
@@ -33,6 +45,9 @@ pub(crate) struct InstanceState {
     /// The resource table that manages shared resources in memory.
     pub resources: ResourceTable,
 
+    /// Keeps the memories and tables of the instance within the configured limits.
+    pub limiter: Limiter,
+
     pub shared: Arc<ExtensionState>,
 }
 
@@ -50,6 +65,12 @@ pub(crate) struct ExtensionState {
     /// The histogram for request durations.
     pub request_durations: Histogram<u64>,
 
+    /// The counter of calls interrupted because the instance went over one of its limits.
+    pub limit_exceeded: Counter<u64>,
+
+    /// Calls, pool and instances metrics of the extension.
+    pub metrics: ExtensionMetrics,
+
     /// A client for making HTTP requests from the guest.
     pub http_client: reqwest::Client,
 
@@ -60,6 +81,9 @@ pub(crate) struct ExtensionState {
     /// A map of PostgreSQL connection pools per named connection.
     pub postgres_pools: DashMap<String, sqlx::Pool<Postgres>>,
 
+    /// A map of Redis connection pools per named connection.
+    pub redis_pools: DashMap<String, RedisPool>,
+
     /// A map of gRPC clients per named connection.
     pub grpc_clients: DashMap<String, GrpcClient>,
 
@@ -71,6 +95,9 @@ pub(crate) struct ExtensionState {
 
     /// The name of the extension.
     pub config: ExtensionConfig,
+
+    /// Fake host IO replacing the network, only set by the test harness.
+    pub host_io_mocks: Option<Arc<HostIoMocks>>,
 }
 
 impl ExtensionState {
@@ -78,6 +105,28 @@ impl ExtensionState {
         tracing::info!("Loading extension {}", config.manifest_id);
         let meter = meter_from_global_provider();
         let request_durations = meter.u64_histogram("grafbase.hook.http_request.duration").build();
+        let limit_exceeded = meter.u64_counter("grafbase.extension.limit_exceeded").build();
+        let metrics = ExtensionMetrics::new(&meter, &config.manifest_id.name);
+        let redirect_policy = match config.wasm.allowed_hosts.clone() {
+            // Redirects must not lead outside of the network allowlist.
+            Some(allowed_hosts) => reqwest::redirect::Policy::custom(move |attempt| {
+                let url = attempt.url();
+                let host = url.host_str().unwrap_or_default();
+
+                if attempt.previous().len() >= MAX_REDIRECTS {
+                    attempt.error("too many redirects")
+                } else if allowed_hosts
+                    .iter()
+                    .any(|allowed| allowed.allows(host, url.port_or_known_default()))
+                {
+                    attempt.follow()
+                } else {
+                    attempt.error(format!("redirect to {host} is not allowed by the network allowlist"))
+                }
+            }),
+            None => reqwest::redirect::Policy::default(),
+        };
+
         let http_client = reqwest::Client::builder()
             // Hyper connection pool only exposes two parameters max idle connections per host
             // and idle connection timeout. There is not TTL on the connections themselves to
@@ -89,6 +138,7 @@ impl ExtensionState {
             // router uses this same default value.
             .pool_idle_timeout(Some(Duration::from_secs(5)))
             .tcp_nodelay(true)
+            .redirect(redirect_policy)
             .tcp_keepalive(Some(std::time::Duration::from_secs(60)))
             .default_headers({
                 let mut headers = http::HeaderMap::new();
@@ -101,14 +151,18 @@ impl ExtensionState {
         Self {
             catalog: catalog.clone(),
             request_durations,
+            limit_exceeded,
+            metrics,
             http_client,
             legacy_cache: LegacyCache::new(),
             caches: DashMap::new(),
             postgres_pools: DashMap::new(),
+            redis_pools: DashMap::new(),
             grpc_clients: DashMap::new(),
             kafka_producers: DashMap::new(),
             file_loggers: DashMap::new(),
             config,
+            host_io_mocks: None,
         }
     }
 }
@@ -129,6 +183,7 @@ impl InstanceState {
             wasi_ctx: crate::config::build_context(&shared.config.wasm),
             wasi_http_ctx: WasiHttpCtx::new(),
             resources: ResourceTable::new(),
+            limiter: Limiter::new(shared.config.limits),
             shared,
         }
     }
@@ -181,6 +236,47 @@ impl InstanceState {
         self.config.wasm.networking
     }
 
+    /// Checks whether the network allowlist of the extension lets it connect to the given host and
+    /// port. Denied connections are logged.
+    pub fn check_host_allowed(&self, host: &str, port: Option<u16>) -> Result<(), String> {
+        let Some(allowed_hosts) = &self.config.wasm.allowed_hosts else {
+            return Ok(());
+        };
+
+        if allowed_hosts.iter().any(|allowed| allowed.allows(host, port)) {
+            return Ok(());
+        }
+
+        let target = match port {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_owned(),
+        };
+
+        tracing::warn!(
+            "Extension {} tried to connect to {target}, which is not in its network allowlist",
+            self.extension_name()
+        );
+
+        Err(format!(
+            "Connection to {target} is not allowed by the network allowlist"
+        ))
+    }
+
+    /// Checks the host of a URL against the network allowlist. The port defaults to the known
+    /// port of the scheme, or to the given one.
+    pub fn check_url_allowed(&self, url: &str, default_port: Option<u16>) -> Result<(), String> {
+        if self.config.wasm.allowed_hosts.is_none() {
+            return Ok(());
+        }
+
+        let url = url::Url::parse(url).map_err(|err| format!("Invalid URL: {err}"))?;
+
+        self.check_host_allowed(
+            url.host_str().unwrap_or_default(),
+            url.port_or_known_default().or(default_port),
+        )
+    }
+
     pub fn extension_name(&self) -> &str {
         &self.config.manifest_id.name
     }
@@ -206,4 +302,25 @@ impl WasiHttpView for InstanceState {
     fn ctx(&mut self) -> &mut WasiHttpCtx {
         &mut self.wasi_http_ctx
     }
+
+    fn send_request(
+        &mut self,
+        request: http::Request<HyperOutgoingBody>,
+        config: OutgoingRequestConfig,
+    ) -> HttpResult<HostFutureIncomingResponse> {
+        let uri = request.uri();
+        let default_port = if config.use_tls { 443 } else { 80 };
+
+        if self
+            .check_host_allowed(
+                uri.host().unwrap_or_default(),
+                Some(uri.port_u16().unwrap_or(default_port)),
+            )
+            .is_err()
+        {
+            return Err(WasiHttpErrorCode::HttpRequestDenied.into());
+        }
+
+        Ok(default_send_request(request, config))
+    }
 }
@@ -0,0 +1,298 @@
+//! Loads an extension built with `grafbase extension build` in-process, with all its host IO
+//! replaced by [HostIoMocks]. Extension authors can drive the extension directly from their tests
+//! and assert on the HTTP, NATS, Kafka, Postgres and gRPC calls it made.
+
+use std::{path::Path, sync::Arc};
+
+use anyhow::Context as _;
+use engine::{EngineOperationContext, EngineRequestContext};
+use engine_error::{ErrorResponse, GraphqlError};
+use engine_schema::Schema;
+use event_queue::EventQueue;
+use extension_catalog::{Extension, ExtensionCatalog, ExtensionId, VersionedManifest};
+use http::HeaderMap;
+use runtime::extension::{AuthorizationDecisions, OnRequest, Response, Token};
+
+pub use crate::host_io_mocks::*;
+use crate::{
+    ExtensionState, cbor,
+    extension::{AuthorizeQueryOutput, ExtensionConfig, ExtensionLoader, WasmConfig, api::wit, build_engine},
+};
+
+pub struct TestExtension {
+    loader: ExtensionLoader,
+    host_io: Arc<HostIoMocks>,
+}
+
+impl TestExtension {
+    /// Loads the `manifest.json` and `extension.wasm` from the build directory of the extension.
+    /// `config` is the TOML configuration of the extension as it would appear
+    /// in the gateway configuration.
+    pub async fn load(build_dir: impl AsRef<Path>, config: &str) -> anyhow::Result<Self> {
+        let build_dir = build_dir.as_ref();
+
+        let manifest_path = build_dir.join("manifest.json");
+        let manifest =
+            std::fs::read(&manifest_path).with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+        let manifest = serde_json::from_slice::<VersionedManifest>(&manifest)
+            .with_context(|| format!("Could not parse {}", manifest_path.display()))?
+            .into_latest();
+
+        let wasm = WasmConfig {
+            location: build_dir.join("extension.wasm"),
+            // Required for the HTTP client to be linked, the mocks ensure nothing reaches the network.
+            networking: true,
+            allowed_hosts: None,
+            stdout: true,
+            stderr: true,
+            environment_variables: false,
+        };
+
+        let config = ExtensionConfig {
+            id: ExtensionId::from(0usize),
+            manifest_id: manifest.id.clone(),
+            r#type: manifest.r#type.clone().into(),
+            sdk_version: manifest.sdk_version.clone(),
+            pool: Default::default(),
+            wasm,
+            guest_config: toml::from_str(config).context("Invalid extension configuration")?,
+            can_skip_sending_events: false,
+            logging_filter: String::from("info"),
+            file_logger_retention: Default::default(),
+            limits: Default::default(),
+            cache: Default::default(),
+        };
+
+        let mut catalog = ExtensionCatalog::default();
+        catalog.push(Extension {
+            config_key: manifest.name().to_string(),
+            manifest,
+            wasm_path: config.wasm.location.clone(),
+        });
+
+        let host_io = Arc::new(HostIoMocks::new());
+        let mut state = ExtensionState::new(&Arc::new(catalog), config);
+        state.host_io_mocks = Some(host_io.clone());
+
+        let engine = build_engine(Default::default())?;
+        let schema = Arc::new(Schema::from_sdl_or_panic("").await);
+        let loader = ExtensionLoader::new(&engine, schema, Arc::new(state))?;
+
+        Ok(Self { loader, host_io })
+    }
+
+    /// Registered responses and recorded calls of the extension host IO.
+    pub fn host_io(&self) -> &HostIoMocks {
+        &self.host_io
+    }
+
+    pub async fn authenticate(&self, headers: HeaderMap) -> anyhow::Result<Result<(HeaderMap, Token), ErrorResponse>> {
+        let result = self
+            .loader
+            .instantiate()
+            .await?
+            .authenticate(&Arc::new(EventQueue::default()), &Default::default(), headers.into())
+            .await?;
+
+        Ok(result.map(|(headers, token)| (headers.into_inner().unwrap_or_default(), token)))
+    }
+
+    pub async fn on_request(&self, parts: http::request::Parts) -> anyhow::Result<Result<OnRequest, ErrorResponse>> {
+        let result = self
+            .loader
+            .instantiate()
+            .await?
+            .on_request(EventQueue::default(), parts)
+            .await?;
+
+        Ok(result)
+    }
+
+    pub async fn on_response(
+        &self,
+        parts: http::response::Parts,
+    ) -> anyhow::Result<Result<http::response::Parts, String>> {
+        let result = self
+            .loader
+            .instantiate()
+            .await?
+            .on_response(Arc::new(EventQueue::default()), Default::default(), parts)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Resolves a single field decorated with the resolver directive `directive_name` in
+    /// `subgraph_name`. The field has no selection set and receives `field_arguments`.
+    pub async fn resolve_field(
+        &self,
+        subgraph_name: &str,
+        directive_name: &str,
+        directive_arguments: impl serde::Serialize,
+        field_arguments: impl serde::Serialize,
+        headers: HeaderMap,
+    ) -> anyhow::Result<Response> {
+        let mut instance = self.loader.instantiate().await?;
+
+        let directive = wit::Directive {
+            name: directive_name,
+            arguments: cbor::to_vec(directive_arguments).unwrap(),
+        };
+        let fields = [wit::Field {
+            alias: None,
+            definition_id: 0,
+            arguments: Some(0),
+            selection_set: None,
+        }];
+
+        let prepared = instance
+            .prepare(Arc::new(EventQueue::default()), subgraph_name, directive, 0, &fields)
+            .await?
+            .map_err(|err| anyhow::anyhow!("Failed to prepare the field: {}", err.message))?;
+
+        let ctx = EngineOperationContext::detached(EngineRequestContext::detached(
+            headers.clone(),
+            Token::Anonymous,
+            Default::default(),
+        ));
+        let arguments = cbor::to_vec(field_arguments).unwrap();
+        let response = instance
+            .resolve(ctx, headers, &prepared, &[(0, arguments.as_slice())])
+            .await?;
+
+        Ok(response)
+    }
+
+    /// Runs the query authorization of the extension over `elements` with the token bytes, if
+    /// any, returned by the authentication. The decisions are returned in the same order as the
+    /// elements, `Err` for the denied ones.
+    pub async fn authorize_query(
+        &self,
+        token: Option<&[u8]>,
+        headers: HeaderMap,
+        elements: &[QueryElement<'_>],
+    ) -> anyhow::Result<Result<(HeaderMap, Vec<Result<(), GraphqlError>>), ErrorResponse>> {
+        // The extension expects the elements to be grouped by directive.
+        let mut order = (0..elements.len()).collect::<Vec<_>>();
+        order.sort_by_key(|ix| elements[*ix].directive_name);
+
+        let mut directive_names = Vec::<(&str, u32, u32)>::new();
+        let mut query_elements = Vec::with_capacity(elements.len());
+        for (pos, ix) in order.iter().enumerate() {
+            let element = &elements[*ix];
+            match directive_names.last_mut() {
+                Some((name, _, end)) if *name == element.directive_name => *end += 1,
+                _ => directive_names.push((element.directive_name, pos as u32, pos as u32 + 1)),
+            }
+            query_elements.push(wit::QueryElement {
+                id: *ix as u32,
+                site: element.site.into(),
+                arguments: cbor::to_vec(&element.arguments).unwrap(),
+                subgraph_name: element.subgraph_name,
+            });
+        }
+
+        let token = token
+            .map(|bytes| Token::Bytes(bytes.into()))
+            .unwrap_or(Token::Anonymous);
+        let ctx = EngineRequestContext::detached(headers.clone(), token, Default::default());
+        let result = self
+            .loader
+            .instantiate()
+            .await?
+            .authorize_query(
+                ctx,
+                headers.into(),
+                wit::QueryElements {
+                    directive_names: &directive_names,
+                    elements: &query_elements,
+                },
+            )
+            .await?;
+
+        Ok(result.map(
+            |AuthorizeQueryOutput {
+                 subgraph_headers,
+                 additional_headers,
+                 decisions,
+                 ..
+             }| {
+                let mut headers = subgraph_headers.into_inner().unwrap_or_default();
+                headers.extend(additional_headers.unwrap_or_default());
+
+                let mut out = vec![Ok(()); elements.len()];
+                match decisions {
+                    AuthorizationDecisions::GrantAll => {}
+                    AuthorizationDecisions::DenyAll(error) => out.fill(Err(error)),
+                    AuthorizationDecisions::DenySome {
+                        element_to_error,
+                        errors,
+                    } => {
+                        for (pos, error_ix) in element_to_error {
+                            out[order[pos as usize]] = Err(errors[error_ix as usize].clone());
+                        }
+                    }
+                }
+
+                (headers, out)
+            },
+        ))
+    }
+}
+
+/// An element of the query decorated with an authorization directive.
+pub struct QueryElement<'a> {
+    pub directive_name: &'a str,
+    pub site: DirectiveSite<'a>,
+    pub arguments: serde_json::Value,
+    pub subgraph_name: Option<&'a str>,
+}
+
+/// Schema location of an authorization directive.
+#[derive(Clone, Copy, Debug)]
+pub enum DirectiveSite<'a> {
+    Scalar {
+        scalar_name: &'a str,
+    },
+    Object {
+        object_name: &'a str,
+    },
+    FieldDefinition {
+        parent_type_name: &'a str,
+        field_name: &'a str,
+    },
+    Interface {
+        interface_name: &'a str,
+    },
+    Union {
+        union_name: &'a str,
+    },
+    Enum {
+        enum_name: &'a str,
+    },
+}
+
+impl<'a> From<DirectiveSite<'a>> for wit::DirectiveSite<'a> {
+    fn from(site: DirectiveSite<'a>) -> Self {
+        match site {
+            DirectiveSite::Scalar { scalar_name } => {
+                wit::DirectiveSite::Scalar(wit::ScalarDirectiveSite { scalar_name })
+            }
+            DirectiveSite::Object { object_name } => {
+                wit::DirectiveSite::Object(wit::ObjectDirectiveSite { object_name })
+            }
+            DirectiveSite::FieldDefinition {
+                parent_type_name,
+                field_name,
+            } => wit::DirectiveSite::FieldDefinition(wit::FieldDefinitionDirectiveSite {
+                parent_type_name,
+                field_name,
+            }),
+            DirectiveSite::Interface { interface_name } => {
+                wit::DirectiveSite::Interface(wit::InterfaceDirectiveSite { interface_name })
+            }
+            DirectiveSite::Union { union_name } => wit::DirectiveSite::Union(wit::UnionDirectiveSite { union_name }),
+            DirectiveSite::Enum { enum_name } => wit::DirectiveSite::Enum(wit::EnumDirectiveSite { enum_name }),
+        }
+    }
+}
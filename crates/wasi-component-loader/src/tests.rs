@@ -1 +1,13 @@
+mod authorization;
+mod cache;
 mod extensions;
+mod gateway;
+mod harness;
+mod hooks;
+mod host_io;
+mod http_stream;
+mod limits;
+mod metrics;
+mod network;
+mod redis;
+mod telemetry;
@@ -0,0 +1,64 @@
+use extension_catalog::Type;
+use http::HeaderMap;
+use serde_json::json;
+
+use super::harness::load_example;
+use crate::test_harness::{DirectiveSite, QueryElement, TestExtension};
+
+/// Authorizes `elements`, returning the error message of the denied ones.
+async fn decisions(
+    extension: &TestExtension,
+    elements: &[QueryElement<'_>],
+    token: Option<&[u8]>,
+) -> Vec<Result<(), String>> {
+    let Ok(Ok((_, decisions))) = extension.authorize_query(token, HeaderMap::new(), elements).await else {
+        panic!("authorize_query failed");
+    };
+
+    decisions
+        .into_iter()
+        .map(|decision| decision.map_err(|err| err.message.to_string()))
+        .collect()
+}
+
+#[tokio::test]
+async fn authorize_query() {
+    let extension = load_example("roles", Type::Authorization(Default::default()), "").await;
+
+    let elements = [
+        QueryElement {
+            directive_name: "requiresRole",
+            site: DirectiveSite::FieldDefinition {
+                parent_type_name: "Query",
+                field_name: "users",
+            },
+            arguments: json!({"role": "admin"}),
+            subgraph_name: Some("accounts"),
+        },
+        QueryElement {
+            directive_name: "public",
+            site: DirectiveSite::Object { object_name: "Post" },
+            arguments: json!({}),
+            subgraph_name: None,
+        },
+        QueryElement {
+            directive_name: "requiresRole",
+            site: DirectiveSite::Object { object_name: "User" },
+            arguments: json!({"role": "reader"}),
+            subgraph_name: Some("accounts"),
+        },
+    ];
+
+    assert_eq!(
+        decisions(&extension, &elements, None).await,
+        vec![
+            Err(String::from("Missing role admin")),
+            Ok(()),
+            Err(String::from("Missing role reader")),
+        ]
+    );
+    assert_eq!(
+        decisions(&extension, &elements, Some(b"reader")).await,
+        vec![Err(String::from("Missing role admin")), Ok(()), Ok(())]
+    );
+}
@@ -0,0 +1,57 @@
+//! The Redis storage of extension caches, against the Redis instance of the local development
+//! environment.
+
+use std::{sync::Arc, time::Duration};
+
+use crate::resources::{Cache, CacheStorage, create_redis_pool};
+
+fn redis_storage() -> CacheStorage {
+    let pool = create_redis_pool("redis://localhost:6379", None, None, Duration::from_secs(5)).unwrap();
+
+    CacheStorage::Redis {
+        pool,
+        // Unique per test run, so that runs don't share entries.
+        key_prefix: format!("test-extension-cache-{}", ulid::Ulid::new()),
+    }
+}
+
+#[tokio::test]
+async fn get_and_insert() {
+    let cache = Cache::new(&redis_storage(), "test", 100, Some(Duration::from_secs(60)));
+
+    // A miss reserves the entry for the caller.
+    assert_eq!(cache.get("key", Duration::from_secs(1)).await, None);
+
+    cache.insert("key", Arc::from(&b"value"[..])).await;
+
+    assert_eq!(
+        cache.get("key", Duration::from_secs(1)).await.as_deref(),
+        Some(&b"value"[..])
+    );
+
+    cache.remove("key").await;
+
+    assert_eq!(cache.get("key", Duration::from_secs(1)).await, None);
+}
+
+#[tokio::test]
+async fn single_flight_across_instances() {
+    let storage = redis_storage();
+
+    // Two gateway instances sharing the same Redis.
+    let first = Cache::new(&storage, "test", 100, None);
+    let second = Cache::new(&storage, "test", 100, None);
+
+    // The first instance reserves the entry and computes the value.
+    assert_eq!(first.get("key", Duration::from_secs(5)).await, None);
+
+    // The second instance waits for it instead of computing it too.
+    let waiting = tokio::spawn(async move { second.get("key", Duration::from_secs(5)).await });
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(!waiting.is_finished());
+
+    first.insert("key", Arc::from(&b"value"[..])).await;
+
+    assert_eq!(waiting.await.unwrap().as_deref(), Some(&b"value"[..]));
+}
@@ -3,6 +3,7 @@ use std::{path::PathBuf, sync::Arc};
 use crate::{
     ExtensionState,
     extension::{ExtensionConfig, ExtensionLoader, WasmConfig, build_engine},
+    test_harness::TestExtension,
 };
 use engine_schema::Schema;
 use extension_catalog::{ExtensionId, TypeDiscriminants};
@@ -13,13 +14,14 @@ use futures::{
 use http::{HeaderMap, HeaderValue, Request, Response};
 use runtime::extension::Token;
 
-const LATEST_SDK: semver::Version = semver::Version::new(0, 23, 0);
+const LATEST_SDK: semver::Version = semver::Version::new(0, 24, 0);
 
 #[tokio::test]
 async fn single_call_caching_auth() {
     let config = WasmConfig {
         location: PathBuf::from("examples/target/wasm32-wasip2/debug/caching_auth.wasm"),
         networking: false,
+        allowed_hosts: None,
         stdout: false,
         stderr: false,
         environment_variables: false,
@@ -42,6 +44,9 @@ async fn single_call_caching_auth() {
         .unwrap(),
         can_skip_sending_events: false,
         logging_filter: String::from("info"),
+        file_logger_retention: Default::default(),
+        limits: Default::default(),
+        cache: Default::default(),
     })
     .await;
 
@@ -79,6 +84,7 @@ async fn single_call_caching_auth_invalid() {
     let config = WasmConfig {
         location: PathBuf::from("examples/target/wasm32-wasip2/debug/caching_auth.wasm"),
         networking: false,
+        allowed_hosts: None,
         stdout: false,
         stderr: false,
         environment_variables: false,
@@ -100,6 +106,9 @@ async fn single_call_caching_auth_invalid() {
         .unwrap(),
         can_skip_sending_events: false,
         logging_filter: String::from("info"),
+        file_logger_retention: Default::default(),
+        limits: Default::default(),
+        cache: Default::default(),
     })
     .await;
 
@@ -131,6 +140,7 @@ async fn multiple_cache_calls() {
     let config = WasmConfig {
         location: PathBuf::from("examples/target/wasm32-wasip2/debug/caching_auth.wasm"),
         networking: false,
+        allowed_hosts: None,
         stdout: false,
         stderr: false,
         environment_variables: false,
@@ -152,6 +162,9 @@ async fn multiple_cache_calls() {
         .unwrap(),
         can_skip_sending_events: false,
         logging_filter: String::from("info"),
+        file_logger_retention: Default::default(),
+        limits: Default::default(),
+        cache: Default::default(),
     })
     .await;
 
@@ -228,6 +241,7 @@ async fn on_request_hook() {
     let config = WasmConfig {
         location: PathBuf::from("examples/target/wasm32-wasip2/debug/simple_hooks.wasm"),
         networking: false,
+        allowed_hosts: None,
         stdout: true,
         stderr: true,
         environment_variables: false,
@@ -245,6 +259,9 @@ async fn on_request_hook() {
         guest_config: toml::Value::Table(Default::default()),
         can_skip_sending_events: false,
         logging_filter: String::from("info"),
+        file_logger_retention: Default::default(),
+        limits: Default::default(),
+        cache: Default::default(),
     })
     .await;
 
@@ -266,6 +283,7 @@ async fn on_response_hook() {
     let config = WasmConfig {
         location: PathBuf::from("examples/target/wasm32-wasip2/debug/simple_hooks.wasm"),
         networking: false,
+        allowed_hosts: None,
         stdout: true,
         stderr: true,
         environment_variables: false,
@@ -283,6 +301,9 @@ async fn on_response_hook() {
         guest_config: toml::Value::Table(Default::default()),
         can_skip_sending_events: false,
         logging_filter: String::from("info"),
+        file_logger_retention: Default::default(),
+        limits: Default::default(),
+        cache: Default::default(),
     })
     .await;
 
@@ -299,6 +320,61 @@ async fn on_response_hook() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn test_harness_with_mocked_host_io() {
+    let build_dir = tempfile::tempdir().unwrap();
+
+    std::fs::copy(
+        "examples/target/wasm32-wasip2/debug/caching_auth.wasm",
+        build_dir.path().join("extension.wasm"),
+    )
+    .unwrap();
+
+    let manifest = extension_catalog::VersionedManifest::V1(extension_catalog::Manifest {
+        id: "caching_auth-1.0.0".parse().unwrap(),
+        r#type: extension_catalog::Type::Authentication(Default::default()),
+        sdk_version: LATEST_SDK,
+        minimum_gateway_version: "0.1.0".parse().unwrap(),
+        description: String::new(),
+        associated_link_urls: Vec::new(),
+        sdl: None,
+        readme: None,
+        homepage_url: None,
+        repository_url: None,
+        license: None,
+        permissions: Default::default(),
+        legacy_event_filter: None,
+    });
+
+    std::fs::write(
+        build_dir.path().join("manifest.json"),
+        serde_json::to_vec(&manifest).unwrap(),
+    )
+    .unwrap();
+
+    let extension = TestExtension::load(build_dir.path(), r#"cache_config = "test""#)
+        .await
+        .unwrap();
+
+    let mut headers = HeaderMap::new();
+    headers.insert("Authorization", HeaderValue::from_static("valid"));
+
+    let (_, token) = extension.authenticate(headers).await.unwrap().unwrap();
+
+    let claims = match token {
+        Token::Anonymous => serde_json::Value::Null,
+        Token::Bytes(bytes) => serde_json::from_slice(&bytes).unwrap(),
+    };
+
+    insta::assert_json_snapshot!(claims, @r#"
+    {
+      "key": "default"
+    }
+    "#);
+
+    assert!(extension.host_io().http_requests().is_empty());
+}
+
 async fn load(config: ExtensionConfig) -> ExtensionLoader {
     let engine = build_engine(Default::default()).unwrap();
     ExtensionLoader::new(
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use crate::extension::GatewayWasmExtensions;
+
+#[test]
+fn replaced_extensions_are_shared_by_all_clones() {
+    let extensions = GatewayWasmExtensions::default();
+    let clone = extensions.clone();
+
+    extensions.replace(GatewayWasmExtensions::default());
+
+    assert!(Arc::ptr_eq(&extensions.current(), &clone.current()));
+}
+
+#[test]
+fn in_flight_calls_keep_the_previous_extensions() {
+    let extensions = GatewayWasmExtensions::default();
+
+    // What a call in progress holds on to until it completes.
+    let in_flight = extensions.current();
+
+    let new_extensions = GatewayWasmExtensions::default();
+    let new_current = new_extensions.current();
+    extensions.replace(new_extensions);
+
+    assert!(Arc::ptr_eq(&extensions.current(), &new_current));
+    assert!(!Arc::ptr_eq(&extensions.current(), &in_flight));
+
+    // The previous extensions are only kept alive by the in-flight call and dropped once it
+    // completes, along with their instance pools.
+    assert_eq!(Arc::strong_count(&in_flight), 1);
+    let previous = Arc::downgrade(&in_flight);
+    drop(in_flight);
+    assert!(previous.upgrade().is_none());
+}
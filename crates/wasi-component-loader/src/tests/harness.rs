@@ -0,0 +1,39 @@
+use extension_catalog::{Manifest, Type, VersionedManifest};
+
+use crate::test_harness::TestExtension;
+
+/// Loads the extension built from `example` in the test harness, as `grafbase extension build`
+/// would have laid it out, with all its host IO mocked.
+pub(super) async fn load_example(example: &str, r#type: Type, config: &str) -> TestExtension {
+    let build_dir = tempfile::tempdir().unwrap();
+
+    std::fs::copy(
+        format!("examples/target/wasm32-wasip2/debug/{example}.wasm"),
+        build_dir.path().join("extension.wasm"),
+    )
+    .unwrap();
+
+    let manifest = VersionedManifest::V1(Manifest {
+        id: format!("{example}-1.0.0").parse().unwrap(),
+        r#type,
+        sdk_version: "0.24.0".parse().unwrap(),
+        minimum_gateway_version: "0.1.0".parse().unwrap(),
+        description: String::new(),
+        associated_link_urls: Vec::new(),
+        sdl: None,
+        readme: None,
+        homepage_url: None,
+        repository_url: None,
+        license: None,
+        permissions: Default::default(),
+        legacy_event_filter: None,
+    });
+
+    std::fs::write(
+        build_dir.path().join("manifest.json"),
+        serde_json::to_vec(&manifest).unwrap(),
+    )
+    .unwrap();
+
+    TestExtension::load(build_dir.path(), config).await.unwrap()
+}
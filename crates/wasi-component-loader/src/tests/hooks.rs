@@ -0,0 +1,57 @@
+use std::{path::PathBuf, sync::Arc};
+
+use extension_catalog::{Extension, ExtensionCatalog, Manifest, Type};
+use gateway_config::Config;
+
+use crate::extension::GatewayWasmExtensions;
+
+/// Loads the hooks extension built from `example` as the extension `name`, with the given
+/// entries added to its configuration.
+pub(super) async fn load_hooks(example: &str, name: &str, extension_config: &str) -> GatewayWasmExtensions {
+    let wasm_path = PathBuf::from(format!("examples/target/wasm32-wasip2/debug/{example}.wasm"));
+    assert!(wasm_path.exists());
+
+    let mut catalog = ExtensionCatalog::default();
+    catalog.push(Extension {
+        config_key: name.to_owned(),
+        manifest: Manifest {
+            id: format!("{name}-1.0.0").parse().unwrap(),
+            r#type: Type::Hooks(Default::default()),
+            sdk_version: "0.24.0".parse().unwrap(),
+            minimum_gateway_version: "0.0.0".parse().unwrap(),
+            description: String::new(),
+            sdl: None,
+            readme: None,
+            homepage_url: None,
+            repository_url: None,
+            license: None,
+            permissions: Default::default(),
+            legacy_event_filter: Default::default(),
+            associated_link_urls: Default::default(),
+        },
+        wasm_path,
+    });
+
+    let config: Config = toml::from_str(&format!(
+        r#"
+        [extensions.{name}]
+        version = "1.0.0"
+        {extension_config}
+        "#
+    ))
+    .unwrap();
+
+    GatewayWasmExtensions::new(&Arc::new(catalog), &config, String::from("info"))
+        .await
+        .unwrap()
+}
+
+/// The parts of a request to the gateway at `path`.
+pub(super) fn request(path: &str) -> http::request::Parts {
+    http::Request::builder()
+        .uri(format!("http://127.0.0.1{path}"))
+        .body(())
+        .unwrap()
+        .into_parts()
+        .0
+}
@@ -0,0 +1,139 @@
+use extension_catalog::Type;
+use http::HeaderMap;
+use runtime::extension::Data;
+use serde_json::json;
+
+use super::harness::load_example;
+use crate::{
+    cbor,
+    test_harness::{MockPgRow, MockedGrpcRequest, MockedKafkaMessage, MockedMessage, TestExtension},
+};
+
+/// Resolves a field with `@call(protocol: $protocol)` and `input: "hello"`, returning its data or
+/// its first error.
+async fn call(extension: &TestExtension, protocol: &str) -> Result<serde_json::Value, String> {
+    let response = extension
+        .resolve_field(
+            "subgraph",
+            "call",
+            json!({"protocol": protocol}),
+            json!({"input": "hello"}),
+            HeaderMap::new(),
+        )
+        .await
+        .unwrap();
+
+    if let Some(error) = response.errors.first() {
+        return Err(error.message.to_string());
+    }
+
+    match response.data {
+        Some(Data::Json(bytes)) => Ok(serde_json::from_slice(&bytes).unwrap()),
+        Some(Data::Cbor(bytes)) => Ok(cbor::from_slice(&bytes).unwrap()),
+        None => Ok(serde_json::Value::Null),
+    }
+}
+
+async fn load() -> TestExtension {
+    load_example("host_io", Type::Resolver(Default::default()), "").await
+}
+
+#[tokio::test]
+async fn http() {
+    let extension = load().await;
+
+    assert!(call(&extension, "http").await.is_err());
+
+    extension.host_io().mock_http(
+        http::Method::POST,
+        "http://localhost:8080/echo",
+        http::Response::new(b"world".to_vec()),
+    );
+
+    assert_eq!(call(&extension, "http").await, Ok(json!("world")));
+
+    let requests = extension.host_io().http_requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].method, http::Method::POST);
+    assert_eq!(requests[1].url, "http://localhost:8080/echo");
+    assert_eq!(requests[1].body, b"hello");
+}
+
+#[tokio::test]
+async fn nats() {
+    let extension = load().await;
+    extension.host_io().mock_nats_reply("echo", b"world".to_vec());
+
+    assert_eq!(call(&extension, "nats").await, Ok(json!("world")));
+    assert_eq!(
+        extension.host_io().nats_published(),
+        vec![MockedMessage {
+            subject: String::from("events"),
+            payload: b"\"hello\"".to_vec(),
+        }]
+    );
+    assert_eq!(
+        extension.host_io().nats_requests(),
+        vec![MockedMessage {
+            subject: String::from("echo"),
+            payload: b"hello".to_vec(),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn kafka() {
+    let extension = load().await;
+
+    assert_eq!(call(&extension, "kafka").await, Ok(json!("produced")));
+    assert_eq!(
+        extension.host_io().kafka_produced(),
+        vec![MockedKafkaMessage {
+            topic: String::from("events"),
+            key: Some(String::from("input")),
+            value: b"hello".to_vec(),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn postgres() {
+    let extension = load().await;
+    let query = "SELECT name FROM users WHERE id = $1";
+
+    assert_eq!(call(&extension, "postgres").await, Ok(json!("")));
+
+    extension.host_io().mock_postgres_query(
+        query,
+        vec![
+            MockPgRow::new().column("name", Some("Alice")),
+            MockPgRow::new().column("name", None::<Vec<u8>>),
+        ],
+    );
+
+    assert_eq!(call(&extension, "postgres").await, Ok(json!("Alice,NULL")));
+    assert_eq!(extension.host_io().postgres_queries(), vec![query, query]);
+}
+
+#[tokio::test]
+async fn grpc() {
+    let extension = load().await;
+
+    assert_eq!(
+        call(&extension, "grpc").await,
+        Err(String::from("No mocked response for echo.Echo/Say"))
+    );
+
+    extension
+        .host_io()
+        .mock_grpc_unary("echo.Echo", "Say", Ok(b"world".to_vec()));
+
+    assert_eq!(call(&extension, "grpc").await, Ok(json!("world")));
+
+    let request = MockedGrpcRequest {
+        service: String::from("echo.Echo"),
+        method: String::from("Say"),
+        message: b"hello".to_vec(),
+    };
+    assert_eq!(extension.host_io().grpc_requests(), vec![request.clone(), request]);
+}
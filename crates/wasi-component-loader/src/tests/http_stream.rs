@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use runtime::extension::GatewayHooksExtension;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{any, body_string, method, path},
+};
+
+use super::hooks::load_hooks;
+use crate::extension::GatewayWasmExtensions;
+
+/// Loads the HTTP stream example allowed to connect to `allowed_host`.
+async fn load(name: &str, allowed_host: &str) -> GatewayWasmExtensions {
+    load_hooks(
+        "http_stream",
+        name,
+        &format!(
+            r#"
+            networking = true
+            allowed_hosts = ["{allowed_host}"]
+            "#
+        ),
+    )
+    .await
+}
+
+/// Has the extension stream a request to `url`, returning the `x-status` and `x-body` headers or
+/// the `x-error` header it reported.
+async fn stream(extensions: &GatewayWasmExtensions, header: &str, url: &str) -> Result<(String, String), String> {
+    let (parts, _) = http::Request::builder()
+        .uri("http://127.0.0.1/graphql")
+        .header(header, url)
+        .body(())
+        .unwrap()
+        .into_parts();
+
+    let Ok(on_request) = extensions.on_request(parts).await else {
+        panic!("on_request failed");
+    };
+
+    let header = |name: &str| {
+        on_request
+            .parts
+            .headers
+            .get(name)
+            .map(|value| value.to_str().unwrap().to_owned())
+    };
+
+    match (header("x-status"), header("x-body"), header("x-error")) {
+        (Some(status), Some(body), None) => Ok((status, body)),
+        (None, None, Some(error)) => Err(error),
+        headers => panic!("unexpected headers: {headers:?}"),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn chunked_request_upload() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/upload"))
+        .and(body_string("first,second,third"))
+        .respond_with(ResponseTemplate::new(201).set_body_string("received"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let extensions = load("http_stream_upload", &server.address().to_string()).await;
+    let response = stream(&extensions, "x-upload-url", &format!("{}/upload", server.uri())).await;
+
+    assert_eq!(response, Ok((String::from("201"), String::from("received"))));
+
+    // Without a known length, the body is sent with the chunked transfer encoding.
+    let requests = server.received_requests().await.unwrap();
+    let [request] = requests.as_slice() else {
+        panic!("expected a single request, got {}", requests.len());
+    };
+
+    assert_eq!(request.headers.get("transfer-encoding").unwrap(), "chunked");
+    assert!(request.headers.get("content-length").is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn chunked_response_read() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+
+    // Lines are split across chunks, sent one at a time.
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+
+        let mut request = Vec::new();
+        while !request.ends_with(b"\r\n\r\n") {
+            let mut buffer = [0; 1024];
+            let read = socket.read(&mut buffer).await.unwrap();
+            assert!(read > 0, "connection closed before the end of the request");
+            request.extend_from_slice(&buffer[..read]);
+        }
+
+        socket
+            .write_all(b"HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\n\r\n")
+            .await
+            .unwrap();
+
+        for chunk in ["fir", "st\nsec", "ond\r\n\nthi", "rd"] {
+            let chunk = format!("{:x}\r\n{chunk}\r\n", chunk.len());
+            socket.write_all(chunk.as_bytes()).await.unwrap();
+            socket.flush().await.unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        socket.write_all(b"0\r\n\r\n").await.unwrap();
+    });
+
+    let extensions = load("http_stream_download", &address.to_string()).await;
+    let response = stream(&extensions, "x-download-url", &format!("http://{address}/events")).await;
+
+    assert_eq!(response, Ok((String::from("200"), String::from("first,second,,third"))));
+    server.await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn allowlist_denial() {
+    let server = MockServer::start().await;
+
+    Mock::given(any())
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let extensions = load("http_stream_denied", "api.example.com").await;
+
+    for header in ["x-upload-url", "x-download-url"] {
+        let error = stream(&extensions, header, &server.uri()).await.unwrap_err();
+        assert!(error.contains("not allowed by the network allowlist"), "{error}");
+    }
+}
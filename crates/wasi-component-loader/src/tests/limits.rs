@@ -0,0 +1,97 @@
+use grafbase_telemetry::otel::opentelemetry::KeyValue;
+use runtime::extension::GatewayHooksExtension;
+
+use super::{
+    hooks::{load_hooks, request},
+    telemetry::{init_metrics, metric_total},
+};
+use crate::extension::GatewayWasmExtensions;
+
+async fn load(name: &str, limits: &str) -> GatewayWasmExtensions {
+    init_metrics();
+    load_hooks("limits", name, limits).await
+}
+
+/// Checks the interrupted instance was discarded and replaced by a fresh one. Instances are only
+/// recycled when taken out of the pool again, so this must follow another call.
+fn assert_replaced(name: &KeyValue) {
+    let poisoned = metric_total(
+        "grafbase.extension.instance.recycled",
+        &[
+            name.clone(),
+            KeyValue::new("grafbase.extension.instance.outcome", "poisoned"),
+        ],
+    );
+    assert_eq!(poisoned, 1);
+
+    // The first instance is created when loading the extension.
+    let created = metric_total(
+        "grafbase.extension.instance.created",
+        &[name.clone(), KeyValue::new("grafbase.extension.instance.success", true)],
+    );
+    assert_eq!(created, 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn execution_time_limit() {
+    let extensions = load("limits_execution_time", r#"max_execution_time = "100ms""#).await;
+    let name = KeyValue::new("grafbase.extension.name", "limits_execution_time");
+
+    assert!(extensions.on_request(request("/loop")).await.is_err());
+
+    let exceeded = metric_total(
+        "grafbase.extension.limit_exceeded",
+        &[
+            name.clone(),
+            KeyValue::new("grafbase.extension.limit", "execution_time"),
+        ],
+    );
+    assert_eq!(exceeded, 1);
+
+    assert!(extensions.on_request(request("/")).await.is_ok());
+    assert_replaced(&name);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn memory_limit() {
+    let extensions = load("limits_memory", r#"max_memory = "32MiB""#).await;
+    let name = KeyValue::new("grafbase.extension.name", "limits_memory");
+
+    assert!(extensions.on_request(request("/allocate")).await.is_err());
+
+    let exceeded = metric_total(
+        "grafbase.extension.limit_exceeded",
+        &[name.clone(), KeyValue::new("grafbase.extension.limit", "memory")],
+    );
+    assert_eq!(exceeded, 1);
+
+    assert!(extensions.on_request(request("/")).await.is_ok());
+    assert_replaced(&name);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn within_limits() {
+    let extensions = load(
+        "limits_within",
+        r#"
+        max_memory = "128MiB"
+        max_execution_time = "5s"
+        "#,
+    )
+    .await;
+    let name = KeyValue::new("grafbase.extension.name", "limits_within");
+
+    assert!(extensions.on_request(request("/allocate")).await.is_ok());
+    assert!(extensions.on_request(request("/allocate")).await.is_ok());
+
+    assert_eq!(metric_total("grafbase.extension.limit_exceeded", &[name.clone()]), 0);
+
+    let reused = metric_total(
+        "grafbase.extension.instance.recycled",
+        &[
+            name.clone(),
+            KeyValue::new("grafbase.extension.instance.outcome", "reused"),
+        ],
+    );
+    assert_eq!(reused, 2);
+}
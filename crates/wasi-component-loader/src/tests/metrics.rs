@@ -0,0 +1,153 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use grafbase_telemetry::otel::opentelemetry::KeyValue;
+use runtime::extension::GatewayHooksExtension;
+use tracing::{
+    Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+};
+use tracing_subscriber::{
+    Layer,
+    layer::{Context, SubscriberExt},
+};
+
+use super::{
+    hooks::{load_hooks, request},
+    telemetry::{histogram_max, init_metrics, metric_total},
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn call_and_instance_metrics() {
+    init_metrics();
+
+    let extensions = load_hooks("limits", "metrics_calls", r#"max_execution_time = "100ms""#).await;
+    let name = KeyValue::new("grafbase.extension.name", "metrics_calls");
+    let on_request = KeyValue::new("grafbase.extension.function", "on_request");
+
+    assert!(extensions.on_request(request("/")).await.is_ok());
+    assert!(extensions.on_request(request("/allocate")).await.is_ok());
+    assert!(extensions.on_request(request("/loop")).await.is_err());
+    assert!(extensions.on_request(request("/")).await.is_ok());
+
+    let calls = |status: &'static str| {
+        metric_total(
+            "grafbase.extension.call.duration",
+            &[
+                name.clone(),
+                on_request.clone(),
+                KeyValue::new("grafbase.extension.call.status", status),
+            ],
+        )
+    };
+    assert_eq!(calls("success"), 3);
+    assert_eq!(calls("error"), 1);
+
+    // One more instance is taken from the pool when loading the extension.
+    let pool_waits = metric_total("grafbase.extension.pool.wait.duration", std::slice::from_ref(&name));
+    assert_eq!(pool_waits, 5);
+
+    let created = |success: bool| {
+        metric_total(
+            "grafbase.extension.instance.created",
+            &[
+                name.clone(),
+                KeyValue::new("grafbase.extension.instance.success", success),
+            ],
+        )
+    };
+    assert_eq!(created(true), 2);
+    assert_eq!(created(false), 0);
+
+    let recycled = |outcome: &'static str| {
+        metric_total(
+            "grafbase.extension.instance.recycled",
+            &[
+                name.clone(),
+                KeyValue::new("grafbase.extension.instance.outcome", outcome),
+            ],
+        )
+    };
+    assert_eq!(recycled("reused"), 3);
+    assert_eq!(recycled("poisoned"), 1);
+    assert_eq!(recycled("not_recyclable"), 0);
+
+    let memory = std::slice::from_ref(&name);
+    assert_eq!(metric_total("grafbase.extension.instance.memory", memory), 4);
+
+    // The memory of an instance never shrinks, the 64 MiB allocated by the second call are kept
+    // by the first instance until it's discarded.
+    let max_memory = histogram_max("grafbase.extension.instance.memory", memory).unwrap();
+    assert!(max_memory >= 64 * 1024 * 1024, "{max_memory}");
+}
+
+/// Fields of the `extension call` spans, as recorded when created and updated afterwards. Span
+/// ids are re-used once closed, so updates go to the latest span with the id.
+#[derive(Clone, Default)]
+struct CallSpans(Arc<Mutex<Vec<(Id, HashMap<String, String>)>>>);
+
+struct Fields<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for Fields<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_owned(), value.to_owned());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_owned(), format!("{value:?}"));
+    }
+}
+
+impl<S: Subscriber> Layer<S> for CallSpans {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _: Context<'_, S>) {
+        if attrs.metadata().name() != "extension call" {
+            return;
+        }
+
+        let mut fields = HashMap::new();
+        attrs.record(&mut Fields(&mut fields));
+        self.0.lock().unwrap().push((id.clone(), fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _: Context<'_, S>) {
+        if let Some((_, fields)) = self.0.lock().unwrap().iter_mut().rev().find(|(span, _)| span == id) {
+            values.record(&mut Fields(fields));
+        }
+    }
+}
+
+#[tokio::test]
+async fn extension_call_span() {
+    let spans = CallSpans::default();
+    let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(spans.clone()));
+
+    let extensions = load_hooks("limits", "metrics_span", r#"max_execution_time = "100ms""#).await;
+
+    assert!(extensions.on_request(request("/")).await.is_ok());
+    assert!(extensions.on_request(request("/loop")).await.is_err());
+
+    let mut spans = spans
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(_, fields)| fields.clone())
+        .collect::<Vec<_>>();
+    spans.sort_by_key(|fields| fields.contains_key("otel.status_code"));
+
+    let [success, error] = spans.as_slice() else {
+        panic!("expected two extension calls, got {spans:?}");
+    };
+
+    for span in [success, error] {
+        assert_eq!(span["grafbase.extension.name"], "metrics_span");
+        assert_eq!(span["grafbase.extension.function"], "on_request");
+        assert_eq!(span["otel.name"], "metrics_span on_request");
+    }
+
+    assert!(!success.contains_key("otel.status_code"));
+    assert_eq!(error["otel.status_code"], "Error");
+}
@@ -0,0 +1,236 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use bytes::Bytes;
+use extension_catalog::{ExtensionId, TypeDiscriminants};
+use http_body_util::{BodyExt, Empty};
+use runtime::extension::GatewayHooksExtension;
+use wasmtime_wasi_http::{
+    WasiHttpView, bindings::http::types::ErrorCode as WasiHttpErrorCode, types::OutgoingRequestConfig,
+};
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+use super::hooks::load_hooks;
+use crate::{
+    ExtensionState, InstanceState,
+    extension::{ExtensionConfig, GatewayWasmExtensions, WasmConfig},
+};
+
+/// The state of an instance only allowed to connect to `allowed_hosts`, if any.
+fn instance_state(allowed_hosts: Option<&[&str]>) -> InstanceState {
+    let config = ExtensionConfig {
+        id: ExtensionId::from(0usize),
+        r#type: TypeDiscriminants::Hooks,
+        manifest_id: "network-1.0.0".parse().unwrap(),
+        sdk_version: semver::Version::new(0, 24, 0),
+        pool: Default::default(),
+        wasm: WasmConfig {
+            location: PathBuf::new(),
+            networking: true,
+            allowed_hosts: allowed_hosts.map(|hosts| hosts.iter().map(|host| host.parse().unwrap()).collect()),
+            stdout: false,
+            stderr: false,
+            environment_variables: false,
+        },
+        guest_config: toml::Value::Table(Default::default()),
+        can_skip_sending_events: false,
+        logging_filter: String::from("info"),
+        file_logger_retention: Default::default(),
+        limits: Default::default(),
+        cache: Default::default(),
+    };
+
+    InstanceState::new(Arc::new(ExtensionState::new(&Default::default(), config)))
+}
+
+/// Loads the network example allowed to connect to `allowed_hosts`.
+async fn load(name: &str, allowed_hosts: &[String]) -> GatewayWasmExtensions {
+    let allowed_hosts = allowed_hosts
+        .iter()
+        .map(|host| format!("{host:?}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    load_hooks(
+        "network",
+        name,
+        &format!(
+            r#"
+            networking = true
+            allowed_hosts = [{allowed_hosts}]
+            "#
+        ),
+    )
+    .await
+}
+
+/// Has the extension send a GET request to `url`, returning the `x-status` or `x-error` header it
+/// reported.
+async fn get(extensions: &GatewayWasmExtensions, url: &str) -> Result<String, String> {
+    let (parts, _) = http::Request::builder()
+        .uri("http://127.0.0.1/graphql")
+        .header("x-url", url)
+        .body(())
+        .unwrap()
+        .into_parts();
+
+    let Ok(on_request) = extensions.on_request(parts).await else {
+        panic!("on_request failed");
+    };
+
+    let header = |name: &str| {
+        on_request
+            .parts
+            .headers
+            .get(name)
+            .map(|value| value.to_str().unwrap().to_owned())
+    };
+
+    match (header("x-status"), header("x-error")) {
+        (Some(status), None) => Ok(status),
+        (None, Some(error)) => Err(error),
+        headers => panic!("unexpected headers: {headers:?}"),
+    }
+}
+
+#[test]
+fn hosts_and_urls() {
+    let state = instance_state(Some(&[
+        "api.example.com",
+        "*.internal.example.com:8443",
+        "10.0.0.0/8:5432",
+    ]));
+
+    assert!(state.check_host_allowed("api.example.com", Some(443)).is_ok());
+    assert!(state.check_host_allowed("other.example.com", Some(443)).is_err());
+    assert!(state.check_host_allowed("db.internal.example.com", Some(8443)).is_ok());
+    assert!(state.check_host_allowed("db.internal.example.com", Some(443)).is_err());
+    assert!(state.check_host_allowed("10.1.2.3", Some(5432)).is_ok());
+
+    assert!(state.check_url_allowed("https://api.example.com/graphql", None).is_ok());
+    assert!(state.check_url_allowed("postgres://10.1.2.3/db", Some(5432)).is_ok());
+    assert!(
+        state
+            .check_url_allowed("postgres://10.1.2.3:5433/db", Some(5432))
+            .is_err()
+    );
+    assert!(state.check_url_allowed("https://evil.example.org", None).is_err());
+
+    insta::assert_snapshot!(
+        state.check_url_allowed("redis://localhost", Some(6379)).unwrap_err(),
+        @"Connection to localhost:6379 is not allowed by the network allowlist"
+    );
+
+    // Postgres connection strings must be URLs.
+    insta::assert_snapshot!(
+        state.check_url_allowed("host=10.1.2.3 port=5432", Some(5432)).unwrap_err(),
+        @"Invalid URL: relative URL without a base"
+    );
+}
+
+#[test]
+fn no_allowlist() {
+    let state = instance_state(Some(&[]));
+    assert!(state.check_host_allowed("api.example.com", Some(443)).is_err());
+
+    let state = instance_state(None);
+    assert!(state.check_host_allowed("api.example.com", Some(443)).is_ok());
+    assert!(state.check_url_allowed("host=localhost", None).is_ok());
+}
+
+#[tokio::test]
+async fn wasi_http_request_denied() {
+    let mut state = instance_state(Some(&["api.example.com:443"]));
+
+    let request = http::Request::builder()
+        .uri("http://api.example.com/graphql")
+        .body(
+            Empty::<Bytes>::new()
+                .map_err(|never| -> WasiHttpErrorCode { match never {} })
+                .boxed(),
+        )
+        .unwrap();
+
+    let config = OutgoingRequestConfig {
+        use_tls: false,
+        connect_timeout: Duration::from_secs(1),
+        first_byte_timeout: Duration::from_secs(1),
+        between_bytes_timeout: Duration::from_secs(1),
+    };
+
+    let Err(err) = state.send_request(request, config) else {
+        panic!("request to a port outside of the allowlist was sent");
+    };
+
+    assert!(matches!(err.downcast(), Ok(WasiHttpErrorCode::HttpRequestDenied)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn http_requests() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let address = server.address();
+    let extensions = load("network_http", &[address.to_string()]).await;
+
+    assert_eq!(get(&extensions, &server.uri()).await.as_deref(), Ok("200"));
+
+    // Network entries only match IP addresses, never domains.
+    let error = get(&extensions, &format!("http://localhost:{}/", address.port()))
+        .await
+        .unwrap_err();
+    assert!(error.contains("not allowed by the network allowlist"), "{error}");
+
+    let error = get(&extensions, "http://127.0.0.2/").await.unwrap_err();
+    assert!(error.contains("not allowed by the network allowlist"), "{error}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn http_redirects() {
+    let allowed = MockServer::start().await;
+    let denied = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/inside"))
+        .respond_with(ResponseTemplate::new(302).insert_header("location", format!("{}/ok", allowed.uri())))
+        .expect(1)
+        .mount(&allowed)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/ok"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&allowed)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/outside"))
+        .respond_with(ResponseTemplate::new(302).insert_header("location", format!("{}/ok", denied.uri())))
+        .expect(1)
+        .mount(&allowed)
+        .await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&denied)
+        .await;
+
+    let extensions = load("network_redirects", &[allowed.address().to_string()]).await;
+
+    let inside = get(&extensions, &format!("{}/inside", allowed.uri())).await;
+    assert_eq!(inside.as_deref(), Ok("200"));
+
+    // The redirect is not followed, the server outside of the allowlist never gets the request.
+    let outside = get(&extensions, &format!("{}/outside", allowed.uri())).await;
+    assert!(outside.is_err(), "{outside:?}");
+}
@@ -0,0 +1,79 @@
+use extension_catalog::Type;
+
+use super::{harness::load_example, hooks::request};
+use crate::{host_io_mocks::MockedRedisEval, test_harness::TestExtension};
+
+/// Runs the Redis commands of `path`, returning the `x-value` or `x-error` header the extension
+/// reported.
+async fn run(extension: &TestExtension, path: &str) -> Result<String, String> {
+    let Ok(Ok(on_request)) = extension.on_request(request(path)).await else {
+        panic!("on_request failed");
+    };
+
+    let header = |name: &str| {
+        on_request
+            .parts
+            .headers
+            .get(name)
+            .map(|value| value.to_str().unwrap().to_owned())
+    };
+
+    match (header("x-value"), header("x-error")) {
+        (Some(value), None) => Ok(value),
+        (None, Some(error)) => Err(error),
+        headers => panic!("unexpected headers: {headers:?}"),
+    }
+}
+
+async fn load() -> TestExtension {
+    load_example("redis", Type::Hooks(Default::default()), "").await
+}
+
+#[tokio::test]
+async fn get_and_set() {
+    let extension = load().await;
+
+    assert_eq!(run(&extension, "/get").await.as_deref(), Ok("nil"));
+    assert_eq!(run(&extension, "/set").await.as_deref(), Ok("OK"));
+    assert_eq!(run(&extension, "/get").await.as_deref(), Ok("hello"));
+    assert_eq!(
+        extension.host_io().redis_value("greeting").as_deref(),
+        Some(&b"hello"[..])
+    );
+
+    extension.host_io().mock_redis_value("greeting", "bonjour");
+    assert_eq!(run(&extension, "/get").await.as_deref(), Ok("bonjour"));
+}
+
+#[tokio::test]
+async fn eval() {
+    let extension = load().await;
+    let script = "return redis.call('INCRBY', KEYS[1], ARGV[1])";
+
+    insta::assert_snapshot!(run(&extension, "/eval").await.unwrap_err(), @"No mocked result for the Lua script");
+
+    extension.host_io().mock_redis_script(script, ::redis::Value::Int(2));
+    assert_eq!(run(&extension, "/eval").await.as_deref(), Ok("2"));
+
+    let eval = MockedRedisEval {
+        script: script.to_owned(),
+        keys: vec![String::from("counter")],
+        args: vec![b"2".to_vec()],
+    };
+    assert_eq!(extension.host_io().redis_evals(), vec![eval.clone(), eval]);
+}
+
+#[tokio::test]
+async fn subscription() {
+    let extension = load().await;
+
+    extension
+        .host_io()
+        .mock_redis_messages("events", vec![b"first".to_vec(), b"second".to_vec()])
+        .mock_redis_messages("other", vec![b"ignored".to_vec()]);
+
+    assert_eq!(
+        run(&extension, "/subscribe").await.as_deref(),
+        Ok("events:first,events:second")
+    );
+}
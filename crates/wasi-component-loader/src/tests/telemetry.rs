@@ -0,0 +1,105 @@
+//! In-memory export of the metrics recorded by extensions. The meter provider is global, so tests
+//! share it and tell their metrics apart with a unique extension name.
+
+use std::sync::OnceLock;
+
+use grafbase_telemetry::otel::opentelemetry::{KeyValue, global};
+use opentelemetry_sdk::metrics::{
+    InMemoryMetricExporter, PeriodicReader, SdkMeterProvider,
+    data::{AggregatedMetrics, MetricData},
+};
+
+struct Metrics {
+    provider: SdkMeterProvider,
+    exporter: InMemoryMetricExporter,
+}
+
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+fn metrics() -> &'static Metrics {
+    METRICS.get_or_init(|| {
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+
+        global::set_meter_provider(provider.clone());
+
+        Metrics { provider, exporter }
+    })
+}
+
+/// Installs the in-memory meter provider. Must be called before loading the extensions, as their
+/// instruments are created from the global provider at that point.
+pub(super) fn init_metrics() {
+    metrics();
+}
+
+/// Calls `f` with the data of the metrics named `name` recorded so far.
+fn for_each_metric(name: &str, mut f: impl FnMut(&AggregatedMetrics)) {
+    let metrics = metrics();
+    metrics.provider.force_flush().unwrap();
+
+    // Temporality is cumulative, so the last export has everything recorded so far.
+    let exported = metrics.exporter.get_finished_metrics().unwrap();
+    let Some(last) = exported.last() else {
+        return;
+    };
+
+    last.scope_metrics()
+        .flat_map(|scope| scope.metrics())
+        .filter(|metric| metric.name() == name)
+        .for_each(|metric| f(metric.data()));
+}
+
+fn has_attributes<'a>(point_attributes: impl Iterator<Item = &'a KeyValue>, attributes: &[KeyValue]) -> bool {
+    let point_attributes = point_attributes.collect::<Vec<_>>();
+    attributes.iter().all(|attribute| point_attributes.contains(&attribute))
+}
+
+/// Total of the data points of `name` having all the `attributes`: the value of counters and the
+/// number of measurements of histograms.
+pub(super) fn metric_total(name: &str, attributes: &[KeyValue]) -> u64 {
+    let mut total = 0;
+
+    for_each_metric(name, |data| {
+        total += match data {
+            AggregatedMetrics::U64(MetricData::Sum(sum)) => sum
+                .data_points()
+                .filter(|point| has_attributes(point.attributes(), attributes))
+                .map(|point| point.value())
+                .sum(),
+            AggregatedMetrics::U64(MetricData::Histogram(histogram)) => histogram
+                .data_points()
+                .filter(|point| has_attributes(point.attributes(), attributes))
+                .map(|point| point.count())
+                .sum(),
+            AggregatedMetrics::F64(MetricData::Histogram(histogram)) => histogram
+                .data_points()
+                .filter(|point| has_attributes(point.attributes(), attributes))
+                .map(|point| point.count())
+                .sum(),
+            _ => 0,
+        }
+    });
+
+    total
+}
+
+/// Largest measurement of the integer histogram `name` with all the `attributes`.
+pub(super) fn histogram_max(name: &str, attributes: &[KeyValue]) -> Option<u64> {
+    let mut max = None;
+
+    for_each_metric(name, |data| {
+        if let AggregatedMetrics::U64(MetricData::Histogram(histogram)) = data {
+            let points = histogram
+                .data_points()
+                .filter(|point| has_attributes(point.attributes(), attributes))
+                .filter_map(|point| point.max());
+
+            max = max.into_iter().chain(points).max();
+        }
+    });
+
+    max
+}
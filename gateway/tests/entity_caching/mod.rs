@@ -73,6 +73,7 @@ where
         log_level: None,
         client_url_path: None,
         client_headers: None,
+        hot_reload: false,
     }
     .run(test)
 }
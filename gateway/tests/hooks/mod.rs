@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, path::Path, time::Duration};
 
 use handlebars::Handlebars;
 use serde_json::json;
@@ -7,7 +7,7 @@ use wiremock::{
     matchers::{header, method},
 };
 
-use crate::{load_schema, runtime, with_static_server};
+use crate::{GatewayBuilder, load_schema, runtime, with_static_server};
 
 #[test]
 fn extension_loads_and_passes_headers() {
@@ -85,3 +85,91 @@ fn extension_loads_and_passes_headers() {
         server.received_requests().await;
     });
 }
+
+/// Installs a test extension build under the name `hooks`, so that different builds can replace
+/// each other.
+fn install_hooks_extension(build: &str, target: &Path) {
+    let build_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../crates/integration-tests/data/extensions/crates")
+        .join(build)
+        .join("build");
+
+    let manifest = std::fs::read(build_dir.join("manifest.json")).unwrap();
+    let mut manifest: serde_json::Value = serde_json::from_slice(&manifest).unwrap();
+    manifest["id"]["name"] = json!("hooks");
+
+    std::fs::write(target.join("manifest.json"), serde_json::to_vec(&manifest).unwrap()).unwrap();
+    std::fs::copy(build_dir.join("extension.wasm"), target.join("extension.wasm")).unwrap();
+}
+
+#[test]
+fn extension_is_reloaded_when_its_wasm_module_changes() {
+    let extension_dir = tempfile::tempdir().unwrap();
+    // hooks-17 has no on_subgraph_request hook, hooks-19 adds the configured header.
+    install_hooks_extension("hooks-17", extension_dir.path());
+
+    let config = indoc::formatdoc! {r#"
+        [extensions.hooks]
+        path = "{}"
+
+        [extensions.hooks.config]
+        on_subgraph_request.header_name = "x-hooks"
+        on_subgraph_request.header_value = "reloaded"
+    "#, extension_dir.path().display()};
+
+    let server = runtime().block_on(async move {
+        let server = wiremock::MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": {
+                    "me": {
+                        "id": "1",
+                    }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        server
+    });
+
+    let mut hb = Handlebars::new();
+    hb.register_template_string("t1", load_schema("small")).unwrap();
+
+    let mut data = BTreeMap::new();
+    data.insert("subgraph_endpoint", format!("http://{}", server.address()));
+
+    let schema = hb.render("t1", &data).unwrap();
+
+    GatewayBuilder::new(&schema)
+        .with_toml_config(config)
+        .with_hot_reload()
+        .run(|client| async move {
+            let last_x_hooks_header = async || {
+                let requests = server.received_requests().await.unwrap();
+                let request = requests.last().unwrap();
+                request
+                    .headers
+                    .get("x-hooks")
+                    .map(|value| value.to_str().unwrap().to_owned())
+            };
+
+            let resp = client
+                .gql::<serde_json::Value>("query Simple { me { id } }")
+                .send()
+                .await;
+            assert_eq!(resp["data"]["me"]["id"], "1", "{resp:#?}");
+            assert_eq!(last_x_hooks_header().await, None);
+
+            install_hooks_extension("hooks-19", extension_dir.path());
+            tokio::time::sleep(Duration::from_secs(6)).await;
+
+            let resp = client
+                .gql::<serde_json::Value>("query Simple { me { id } }")
+                .send()
+                .await;
+            assert_eq!(resp["data"]["me"]["id"], "1", "{resp:#?}");
+            assert_eq!(last_x_hooks_header().await.as_deref(), Some("reloaded"));
+        });
+}
@@ -369,6 +369,7 @@ struct GatewayBuilder<'a> {
     log_level: Option<String>,
     client_url_path: Option<&'a str>,
     client_headers: Option<&'static [(&'static str, &'static str)]>,
+    hot_reload: bool,
 }
 
 impl<'a> GatewayBuilder<'a> {
@@ -379,6 +380,7 @@ impl<'a> GatewayBuilder<'a> {
             log_level: None,
             client_url_path: None,
             client_headers: None,
+            hot_reload: false,
         }
     }
 
@@ -387,6 +389,16 @@ impl<'a> GatewayBuilder<'a> {
         self
     }
 
+    fn with_toml_config(mut self, config: impl Into<ConfigContent<'a>>) -> Self {
+        self.toml_config = config.into();
+        self
+    }
+
+    fn with_hot_reload(mut self) -> Self {
+        self.hot_reload = true;
+        self
+    }
+
     fn run<F>(self, test: impl FnOnce(Arc<Client>) -> F)
     where
         F: Future<Output = ()>,
@@ -423,6 +435,10 @@ impl<'a> GatewayBuilder<'a> {
             args.push(level);
         }
 
+        if self.hot_reload {
+            args.push("--hot-reload".to_string());
+        }
+
         let command = cmd(cargo_bin("grafbase-gateway"), &args);
 
         let endpoint = match self.client_url_path {
@@ -587,6 +603,7 @@ fn with_static_server<'a, F, T>(
         log_level: None,
         client_url_path: path,
         client_headers: headers,
+        hot_reload: false,
     }
     .run(test)
 }
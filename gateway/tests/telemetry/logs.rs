@@ -229,6 +229,7 @@ fn logs_include_trace_id() {
         log_level: Some("debug".to_string()),
         client_url_path: None,
         client_headers: None,
+        hot_reload: false,
     }
     .run(|client| async move {
         // Make a request with a specific trace parent
@@ -327,6 +328,7 @@ fn logs_include_span_id() {
         log_level: Some("debug".to_string()),
         client_url_path: None,
         client_headers: None,
+        hot_reload: false,
     }
     .run(|client| async move {
         // Make a request with a specific trace parent
@@ -437,6 +439,7 @@ fn logs_include_semantic_attributes() {
         log_level: Some("debug".to_string()),
         client_url_path: None,
         client_headers: None,
+        hot_reload: false,
     }
     .run(|client| async move {
         let result: serde_json::Value = client
@@ -576,6 +576,7 @@ fn with_mock_subgraph<T, F>(
         log_level: None,
         client_url_path: None,
         client_headers: None,
+        hot_reload: false,
     }
     .run(|client| async move {
         const WAIT_SECONDS: u64 = 2;